@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use crate::aggregator::{
+        main::{SignalAggregator, VoteSource},
+        types::AggregatorError,
+    };
+    use crate::v1::rsi::types::{MarketCondition, RSIResult};
+    use crate::v1::types::TradingSignal;
+
+    /// Custom indicator voting by the sign of a spread
+    struct SpreadVote {
+        spread: f64,
+    }
+
+    impl VoteSource for SpreadVote {
+        fn vote(&self) -> f64 {
+            (self.spread / 10.0).clamp(-1.0, 1.0)
+        }
+    }
+
+    #[test]
+    fn test_custom_source_contributes_to_score() {
+        let rsi = RSIResult {
+            value: 25.0,
+            condition: MarketCondition::Oversold,
+            slope: None,
+            acceleration: None,
+        };
+
+        let mut aggregator = SignalAggregator::new();
+        aggregator.add(&rsi, 1.0).unwrap();
+        aggregator.add(&TradingSignal::Sell, 1.0).unwrap();
+        assert_eq!(aggregator.score(), Some(0.0));
+
+        aggregator.add(&SpreadVote { spread: -5.0 }, 2.0).unwrap();
+        assert_eq!(aggregator.len(), 3);
+        assert_eq!(aggregator.score(), Some(-0.25));
+
+        // Trait objects work too
+        let sources: Vec<Box<dyn VoteSource>> = vec![
+            Box::new(SpreadVote { spread: 20.0 }),
+            Box::new(None::<TradingSignal>),
+        ];
+        aggregator.clear();
+        for source in &sources {
+            aggregator.add(source.as_ref(), 1.0).unwrap();
+        }
+        assert_eq!(aggregator.score(), Some(0.5));
+    }
+
+    #[test]
+    fn test_invalid_votes_and_weights() {
+        let mut aggregator = SignalAggregator::new();
+        assert_eq!(aggregator.score(), None);
+        assert_eq!(
+            aggregator.add(&TradingSignal::Buy, -1.0),
+            Err(AggregatorError::InvalidWeight)
+        );
+        assert_eq!(
+            aggregator.add(&SpreadVote { spread: f64::NAN }, 1.0),
+            Err(AggregatorError::InvalidVote)
+        );
+        assert!(aggregator.is_empty());
+
+        // Zero total weight has no score; out-of-range votes are clamped
+        aggregator.add(&TradingSignal::Buy, 0.0).unwrap();
+        assert_eq!(aggregator.score(), None);
+        struct Loud;
+        impl VoteSource for Loud {
+            fn vote(&self) -> f64 {
+                5.0
+            }
+        }
+        aggregator.add(&Loud, 1.0).unwrap();
+        assert_eq!(aggregator.votes()[1].vote, 1.0);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::aggregator::types::{AggregatorError, WeightedVote};
+use crate::common::types::TrendDirection;
+use crate::v1::macd::types::MACDResult;
+use crate::v1::rsi::types::{MarketCondition, RSIResult};
+use crate::v1::types::TradingSignal;
+
+/// A reading that can be expressed as a normalized vote
+///
+/// Implemented for the common indicator outputs below; implement it for your
+/// own types to feed custom indicators into a [`SignalAggregator`].
+pub trait VoteSource {
+    /// Vote in [-1, 1]: -1 is a strong sell, 0 neutral, +1 a strong buy
+    fn vote(&self) -> f64;
+}
+
+impl VoteSource for MarketCondition {
+    /// Mean-reversion reading: oversold votes buy, overbought votes sell
+    fn vote(&self) -> f64 {
+        match self {
+            MarketCondition::Oversold => 1.0,
+            MarketCondition::Overbought => -1.0,
+            MarketCondition::Neutral => 0.0,
+        }
+    }
+}
+
+impl VoteSource for RSIResult {
+    fn vote(&self) -> f64 {
+        self.condition.vote()
+    }
+}
+
+impl VoteSource for TradingSignal {
+    fn vote(&self) -> f64 {
+        match self {
+            TradingSignal::Buy => 1.0,
+            TradingSignal::Sell => -1.0,
+            TradingSignal::Hold => 0.0,
+        }
+    }
+}
+
+impl VoteSource for MACDResult {
+    fn vote(&self) -> f64 {
+        self.signal.vote()
+    }
+}
+
+impl VoteSource for TrendDirection {
+    fn vote(&self) -> f64 {
+        match self {
+            TrendDirection::Up => 1.0,
+            TrendDirection::Down => -1.0,
+            TrendDirection::Sideways => 0.0,
+        }
+    }
+}
+
+impl<T: VoteSource> VoteSource for Option<T> {
+    /// A warming-up indicator (`None`) abstains with a neutral vote
+    fn vote(&self) -> f64 {
+        self.as_ref().map_or(0.0, VoteSource::vote)
+    }
+}
+
+/// Combines votes from several indicators into one score
+///
+/// Votes are collected per bar with [`add`](Self::add) and combined into their
+/// weighted mean with [`score`](Self::score), which is again in [-1, 1].
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::aggregator::main::{SignalAggregator, VoteSource};
+/// use indexes_rs::v1::types::TradingSignal;
+///
+/// struct AlwaysBullish;
+///
+/// impl VoteSource for AlwaysBullish {
+///     fn vote(&self) -> f64 {
+///         1.0
+///     }
+/// }
+///
+/// let mut aggregator = SignalAggregator::new();
+/// aggregator.add(&TradingSignal::Hold, 1.0).unwrap();
+/// aggregator.add(&AlwaysBullish, 1.0).unwrap();
+/// assert_eq!(aggregator.score(), Some(0.5));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SignalAggregator {
+    votes: Vec<WeightedVote>,
+}
+
+impl SignalAggregator {
+    /// Create an aggregator with no votes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect a source's vote with the given weight
+    ///
+    /// Votes outside [-1, 1] are clamped. Returns `AggregatorError::InvalidWeight`
+    /// for a negative or non-finite weight and `AggregatorError::InvalidVote` for a
+    /// non-finite vote.
+    pub fn add<S: VoteSource + ?Sized>(
+        &mut self,
+        source: &S,
+        weight: f64,
+    ) -> Result<(), AggregatorError> {
+        if !weight.is_finite() || weight < 0.0 {
+            return Err(AggregatorError::InvalidWeight);
+        }
+
+        let vote = source.vote();
+        if !vote.is_finite() {
+            return Err(AggregatorError::InvalidVote);
+        }
+
+        self.votes.push(WeightedVote {
+            vote: vote.clamp(-1.0, 1.0),
+            weight,
+        });
+        Ok(())
+    }
+
+    /// Weighted mean of the collected votes, or `None` if the total weight is zero
+    pub fn score(&self) -> Option<f64> {
+        let total_weight: f64 = self.votes.iter().map(|v| v.weight).sum();
+        if total_weight <= 0.0 {
+            return None;
+        }
+
+        let weighted: f64 = self.votes.iter().map(|v| v.vote * v.weight).sum();
+        Some(weighted / total_weight)
+    }
+
+    /// The votes collected so far
+    pub fn votes(&self) -> &[WeightedVote] {
+        &self.votes
+    }
+
+    /// Number of votes collected
+    pub fn len(&self) -> usize {
+        self.votes.len()
+    }
+
+    /// Whether no votes have been collected
+    pub fn is_empty(&self) -> bool {
+        self.votes.is_empty()
+    }
+
+    /// Drop all votes, e.g. before collecting the next bar's
+    pub fn clear(&mut self) {
+        self.votes.clear();
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// One vote collected by a [`SignalAggregator`](crate::aggregator::main::SignalAggregator)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeightedVote {
+    /// Normalized vote in [-1, 1] (-1 = strong sell, +1 = strong buy)
+    pub vote: f64,
+    /// Relative weight of the vote in the combined score
+    pub weight: f64,
+}
+
+/// Error types for collecting votes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AggregatorError {
+    /// Weight is negative, NaN, or infinite
+    InvalidWeight,
+    /// The source produced a NaN or infinite vote
+    InvalidVote,
+}
@@ -0,0 +1,146 @@
+/// Asserts that `calculate_batch_dense` keeps exactly the post-warmup outputs of
+/// `calculate_batch` on a 150-bar sine fixture
+///
+/// `$new` builds a fresh calculator; `$input` is its input type, built from a `Candle`.
+#[cfg(test)]
+macro_rules! assert_dense_batch {
+    ($new:expr, $input:ty) => {{
+        let inputs: Vec<$input> = (0..150)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.3).sin() * 5.0 + i as f64 * 0.1;
+                <$input>::from($crate::common::types::Candle::new(
+                    close - 0.5,
+                    close + 1.0,
+                    close - 1.0,
+                    close,
+                    1000.0 + i as f64,
+                ))
+            })
+            .collect();
+
+        let padded = $new.calculate_batch(&inputs).unwrap();
+        let mut checked = $new;
+        let warmup = inputs
+            .iter()
+            .take_while(|input| checked.calculate_checked(**input).unwrap().is_none())
+            .count();
+        let dense = $new.calculate_batch_dense(&inputs).unwrap();
+
+        assert!(dense.start_index > 0);
+        assert_eq!(dense.start_index, warmup);
+        assert_eq!(dense.len() + dense.start_index, inputs.len());
+        assert_eq!(dense.outputs, padded[dense.start_index..]);
+    }};
+}
+
+#[cfg(test)]
+pub(crate) use assert_dense_batch;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::precision::round_to;
+    use crate::common::types::{DenseBatch, GapAction, GapPolicy, Summation, TrendDirection};
+
+    #[test]
+    fn test_gap_policy_default_is_error() {
+        assert_eq!(GapPolicy::default(), GapPolicy::Error);
+    }
+
+    #[test]
+    fn test_gap_policy_passes_finite_bars_through() {
+        for policy in [GapPolicy::Error, GapPolicy::SkipBar, GapPolicy::ForwardFill] {
+            assert_eq!(policy.apply(2.0, true, Some(1.0)), GapAction::Process(2.0));
+        }
+    }
+
+    #[test]
+    fn test_gap_policy_non_finite_bar() {
+        assert_eq!(
+            GapPolicy::Error.apply(f64::NAN, false, Some(1.0)),
+            GapAction::Reject
+        );
+        assert_eq!(
+            GapPolicy::SkipBar.apply(f64::NAN, false, Some(1.0)),
+            GapAction::Skip
+        );
+        assert_eq!(
+            GapPolicy::ForwardFill.apply(f64::NAN, false, Some(1.0)),
+            GapAction::Process(1.0)
+        );
+        assert_eq!(
+            GapPolicy::ForwardFill.apply(f64::NAN, false, None::<f64>),
+            GapAction::Reject
+        );
+    }
+
+    #[test]
+    fn test_trend_direction_is_shared() {
+        // v1 and ADX expose the same type, so values move between them without conversion
+        let v1: crate::v1::types::TrendDirection = TrendDirection::Up;
+        let adx: crate::v2::adx::types::TrendDirection = v1;
+        assert_eq!(adx, TrendDirection::Up);
+    }
+
+    #[test]
+    fn test_round_to() {
+        assert_eq!(round_to(64.23871, 2), 64.24);
+        assert_eq!(round_to(64.23471, 2), 64.23);
+        assert_eq!(round_to(-12.5, 0), -13.0);
+        assert_eq!(round_to(3.0, 4), 3.0);
+        assert!(round_to(f64::NAN, 2).is_nan());
+        assert_eq!(round_to(f64::INFINITY, 2), f64::INFINITY);
+
+        // Scales beyond f64 precision leave the value as it is instead of yielding NaN
+        assert_eq!(round_to(1.5, u8::MAX), 1.5);
+        assert_eq!(round_to(0.0, u8::MAX), 0.0);
+        assert_eq!(round_to(0.1, 200), 0.1);
+        assert_eq!(round_to(1e300, 15), 1e300);
+        assert!((round_to(1.23456789e-20, 25) - 1.23457e-20).abs() < 1e-30);
+    }
+
+    #[test]
+    fn test_kahan_summation_beats_naive_over_long_series() {
+        // A million alternating large/small values: naive addition drops most of
+        // each 0.1 once the total is large, Kahan carries it forward
+        let values = (0..1_000_000).map(|i| if i % 2 == 0 { 1e9 } else { 0.1 });
+        let reference = 500_000.0 * 1e9 + 500_000.0 * 0.1;
+
+        let (mut naive, mut naive_compensation) = (0.0, 0.0);
+        let (mut kahan, mut kahan_compensation) = (0.0, 0.0);
+        for value in values {
+            Summation::Naive.add(&mut naive, &mut naive_compensation, value);
+            Summation::Kahan.add(&mut kahan, &mut kahan_compensation, value);
+        }
+
+        assert_eq!(naive_compensation, 0.0);
+        assert!((kahan - reference).abs() < 1.0);
+        assert!((naive - reference).abs() > 100.0 * (kahan - reference).abs().max(0.01));
+    }
+
+    #[test]
+    fn test_dense_batch_skips_leading_warmup() {
+        let results: [Result<Option<u8>, String>; 4] =
+            [Ok(None), Ok(None), Ok(Some(1)), Ok(Some(2))];
+        let batch = DenseBatch::try_collect(results, |message| message).unwrap();
+        assert_eq!(batch.start_index, 2);
+        assert_eq!(batch.outputs, vec![1, 2]);
+
+        let warmup_only = DenseBatch::<u8>::try_collect([Ok::<_, String>(None)], |message| message);
+        assert!(warmup_only.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dense_batch_rejects_warmup_after_ready_output() {
+        let results: [Result<Option<u8>, String>; 4] =
+            [Ok(None), Ok(Some(1)), Ok(None), Ok(Some(2))];
+        let error = DenseBatch::try_collect(results, |message| message).unwrap_err();
+        assert!(error.contains("input 2"));
+    }
+
+    #[test]
+    fn test_dense_batch_propagates_input_errors() {
+        let results = [Ok(Some(1u8)), Err("bad input".to_string()), Ok(None)];
+        let error = DenseBatch::try_collect(results, |message| message).unwrap_err();
+        assert_eq!(error, "bad input");
+    }
+}
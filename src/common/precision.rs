@@ -0,0 +1,28 @@
+//! Decimal rounding for indicator outputs.
+
+/// Round `value` to `decimals` decimal places (half away from zero)
+///
+/// Non-finite values are returned unchanged, as are values that have no
+/// fractional digits left at the requested scale (large `decimals` included).
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::round_to;
+///
+/// assert_eq!(round_to(64.23871, 2), 64.24);
+/// assert_eq!(round_to(-1.005, 0), -1.0);
+/// ```
+pub fn round_to(value: f64, decimals: u8) -> f64 {
+    if !value.is_finite() {
+        return value;
+    }
+    let factor = 10f64.powi(i32::from(decimals));
+    let scaled = value * factor;
+    // From 2^52 up every f64 is a whole number, so there is nothing to round; this
+    // also covers a factor that overflowed to infinity (`decimals` above 308)
+    if !factor.is_finite() || scaled.abs() >= 2f64.powi(52) {
+        return value;
+    }
+    scaled.round() / factor
+}
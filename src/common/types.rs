@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+
+/// A single OHLCV bar shared by transforms and candle-driven helpers
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    /// Bar open time (e.g. Unix milliseconds), if known
+    pub timestamp: Option<i64>,
+    /// Open price
+    pub open: f64,
+    /// High price
+    pub high: f64,
+    /// Low price
+    pub low: f64,
+    /// Close price
+    pub close: f64,
+    /// Volume
+    pub volume: f64,
+}
+
+impl Candle {
+    /// Create a candle without a timestamp
+    pub fn new(open: f64, high: f64, low: f64, close: f64, volume: f64) -> Self {
+        Self {
+            timestamp: None,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        }
+    }
+
+    /// Attach a timestamp to the candle
+    pub fn with_timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+}
+
+/// Direction of a trend, shared by v1 and v2 indicators
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TrendDirection {
+    /// Rising trend
+    Up,
+    /// Falling trend
+    Down,
+    /// No clear direction
+    Sideways,
+}
+
+/// How a stateful v2 indicator treats a bar containing non-finite (NaN or infinite) values
+///
+/// Every v2 indicator config carries a `gap_policy`. v1 indicators take no policy: feed
+/// them finite prices only, or use the `try_calculate` methods of RSI, MACD, Bollinger
+/// Bands, and Ichimoku, which reject a non-finite price with `V1Error::InvalidPrice`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GapPolicy {
+    /// Reject the bar with the indicator's invalid-price error (default)
+    #[default]
+    Error,
+    /// Ignore the bar: state is left untouched and the previous output is returned
+    SkipBar,
+    /// Replace the bar with the last valid input and process it as a new bar
+    ForwardFill,
+}
+
+/// When an input completes a bar, for indicators fed from a live feed
+///
+/// With `Close` every input is a new, closed bar. With `Tick` an indicator may be
+/// updated on every tick of the forming bar: an input carrying the same timestamp
+/// as the latest processed bar revises that bar in place (as `update_or_replace_last`
+/// does) instead of being counted again, so the final tick of a bar leaves the same
+/// state as feeding only the closed bar. Inputs without a timestamp are always new bars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BarMode {
+    /// Every input is a closed bar (default)
+    #[default]
+    Close,
+    /// Inputs sharing the latest bar's timestamp revise that bar
+    Tick,
+}
+
+/// How indicators maintain their long-running sums (OBV, MFI, CCI, standard deviation)
+///
+/// With `Naive` each update is a plain `+=`, which drops the low-order bits of a small
+/// value added to a large total; over millions of bars the error adds up. `Kahan`
+/// carries those bits in a compensation term (Kahan summation), at the cost of a few
+/// extra operations per update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Summation {
+    /// Plain floating-point addition (default)
+    #[default]
+    Naive,
+    /// Compensated (Kahan) summation
+    Kahan,
+}
+
+impl Summation {
+    /// Add `value` to `sum`, tracking the rounding error in `compensation`
+    ///
+    /// `compensation` starts at 0.0 and must always be passed alongside the same `sum`.
+    /// `Naive` leaves it untouched.
+    pub fn add(self, sum: &mut f64, compensation: &mut f64, value: f64) {
+        match self {
+            Summation::Naive => *sum += value,
+            Summation::Kahan => {
+                let adjusted = value - *compensation;
+                let total = *sum + adjusted;
+                *compensation = (total - *sum) - adjusted;
+                *sum = total;
+            }
+        }
+    }
+}
+
+/// Units for the "distance to level" fields of threshold oscillators (Williams %R, CCI)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DistanceUnits {
+    /// Distances in the oscillator's own points (default)
+    #[default]
+    Raw,
+    /// Distances as a percentage of the oscillator's overbought/oversold band,
+    /// comparable across indicators and configurations
+    Percent,
+}
+
+/// How trend-direction indicators (ADX, Parabolic SAR) resolve exact ties
+///
+/// A tie is +DI == -DI for ADX, and a bar that only touches the SAR (or an initial
+/// bar whose high equals the previous high) for Parabolic SAR. Both indicators share
+/// this policy so that equal-price bars are treated consistently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TiePolicy {
+    /// A tie has no direction (default): ADX reports `Sideways`, and Parabolic SAR
+    /// reverses when price touches the SAR and starts in a downtrend
+    #[default]
+    Neutral,
+    /// A tie keeps the previous direction: ADX repeats its last direction (`Sideways`
+    /// before any), and Parabolic SAR only reverses once price moves through the SAR
+    /// (an initial tie, with no direction to keep, still starts a downtrend)
+    CarryForward,
+}
+
+/// What an indicator should do with a bar after applying its `GapPolicy`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GapAction<T> {
+    /// Process this input (the original bar or the forward-filled one)
+    Process(T),
+    /// Leave state untouched and repeat the previous output
+    Skip,
+    /// Reject the bar as invalid
+    Reject,
+}
+
+impl GapPolicy {
+    /// Decide how to handle `input` given whether all of its values are finite
+    ///
+    /// Finite bars are always processed. Forward-filling needs a previous valid
+    /// input; without one the bar is rejected.
+    pub fn apply<T: Copy>(self, input: T, is_finite: bool, last_valid: Option<T>) -> GapAction<T> {
+        if is_finite {
+            return GapAction::Process(input);
+        }
+
+        match self {
+            GapPolicy::Error => GapAction::Reject,
+            GapPolicy::SkipBar => GapAction::Skip,
+            GapPolicy::ForwardFill => match last_valid {
+                Some(previous) => GapAction::Process(previous),
+                None => GapAction::Reject,
+            },
+        }
+    }
+}
+
+/// Post-warmup outputs of a batch, without the warmup padding
+///
+/// `outputs[i]` belongs to input `start_index + i`; the inputs before
+/// `start_index` only warmed the indicator up. Once an indicator is ready it
+/// stays ready, so `start_index + outputs.len()` equals the number of inputs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DenseBatch<T> {
+    /// Index of the input the first output belongs to
+    pub start_index: usize,
+    /// Outputs for the inputs from `start_index` on
+    pub outputs: Vec<T>,
+}
+
+impl<T> DenseBatch<T> {
+    /// Number of ready outputs
+    pub fn len(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// Whether no input produced a ready output
+    pub fn is_empty(&self) -> bool {
+        self.outputs.is_empty()
+    }
+}
+
+impl<T> Default for DenseBatch<T> {
+    fn default() -> Self {
+        Self {
+            start_index: 0,
+            outputs: Vec::new(),
+        }
+    }
+}
+
+impl<T> DenseBatch<T> {
+    /// Collect per-input results, where `Ok(None)` marks a warmup input
+    ///
+    /// A warmup input after the first ready output has no slot in the dense
+    /// layout, so it is reported through `invalid_input` instead of being
+    /// dropped and shifting every later output off its input.
+    pub fn try_collect<E>(
+        results: impl IntoIterator<Item = Result<Option<T>, E>>,
+        invalid_input: impl FnOnce(String) -> E,
+    ) -> Result<Self, E> {
+        let mut batch = Self::default();
+        for (index, result) in results.into_iter().enumerate() {
+            match result? {
+                Some(output) => batch.outputs.push(output),
+                None if batch.outputs.is_empty() => batch.start_index += 1,
+                None => {
+                    return Err(invalid_input(format!(
+                        "input {index} produced a warmup output after the first ready output"
+                    )))
+                }
+            }
+        }
+        Ok(batch)
+    }
+}
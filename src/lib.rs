@@ -18,6 +18,64 @@ The v2 indicators implemented in this library include:
 - **CCI (Commodity Channel Index):** Momentum oscillator for identifying cyclical trends and extreme conditions.
 - **Williams %R:** Momentum indicator for detecting extreme overbought/oversold conditions.
 - **Standard Deviation:** Mathematical foundation for volatility measurement and statistical analysis.
+- **Fisher Transform:** Gaussian-normalized price oscillator that sharpens turning points.
+- **Mass Index:** Range-expansion indicator that flags reversal bulges.
+- **Choppiness Index:** Distinguishes trending from ranging markets.
+- **Volume Profile:** Price-by-volume histogram exposing the point of control and value area.
+- **Schaff Trend Cycle:** Double-stochastic of the MACD line for a fast 0-100 trend oscillator.
+- **Percent Rank:** Ranks the close within its recent window as a mean-reversion gauge.
+- **Rolling Beta:** Rolling beta of an asset's returns against a benchmark for portfolio risk.
+- **Drawdown:** Running peak, current drawdown and max drawdown for equity curves.
+- **True Strength Index:** Double-smoothed momentum oscillator with a signal line.
+- **Balance of Power:** Close-minus-open over the bar range, with optional SMA smoothing.
+- **Chande Kroll Stop:** ATR-based long and short trailing stops from rolling extremes.
+- **VWAP Bands:** Anchored VWAP with volume-weighted standard deviation bands.
+- **Relative Vigor Index:** Close-minus-open over the bar range, weighted and smoothed, with a signal line.
+- **Williams Fractals:** Confirmed 5-bar (configurable) swing highs and lows, reported after the right-side bars close.
+
+## Shared building blocks
+- **prelude:** `use indexes_rs::prelude::*;` brings the calculators and their input/output types into scope.
+- **common:** Crate-wide shared types such as the OHLCV `Candle`, `TrendDirection`, and the `GapPolicy` v2 indicators apply to NaN/missing bars.
+- **transform:** Input transforms applied before indicators, such as multi-timeframe resampling, inside/outside bar detection, and derived price sources.
+- **util:** Reusable primitives such as rolling min/max tracking, percent rank, True Range, and signal debouncing.
+- **suite:** `IndicatorSuite` drives RSI, MACD, Bollinger Bands, ADX, and ATR from one candle stream.
+- **snapshot:** Versioned `StateSnapshot` for saving and restoring running indicators across restarts.
+- **aggregator:** `SignalAggregator` combines normalized votes from any `VoteSource` into one score.
+- **risk:** ATR-based position sizing, as a pure function or with a built-in ATR.
+- **testutil:** Seeded OHLCV series generator for fixtures (behind the `test-util` feature).
+
+## Zero and negative inputs
+Some instruments (spreads, funding rates) trade at or below zero. Indicators built on differences,
+ranges, or averages are defined for any finite input: the moving averages, RSI, MACD, Bollinger Bands,
+Standard Deviation, CCI, Williams %R, Stochastic, ATR, ADX, Fisher Transform, and Percent Rank.
+Where a value is divided by a past price or a mean, the divisor's magnitude is used so signs keep
+their meaning, and a (near) zero divisor yields no value instead of an infinity:
+
+- **ROC / Momentum:** measured against `|past price|`; `None` when the past price is zero.
+- **Envelope:** the band offset is a fraction of `|MA|`, so the upper band stays on top.
+- **Standard Deviation:** `coefficient_of_variation` is `None` (volatility level `Undefined`) for a zero or negative mean.
+
+MFI and OBV's typical-price mode weight money flow by price and assume positive prices.
+
+## Revising the live bar
+Live feeds often revise the latest (unclosed) candle before it finalizes. Feeding each revision
+to `calculate` would count the bar several times. The windowed indicators below can instead
+replace their latest input with `update_or_replace_last`, giving the same result as if only the
+revised bar had been fed: call `calculate` (or `add_value`) when a new bar opens, then
+`update_or_replace_last` for each revision.
+
+- **Supported:** SMA, Stochastic Oscillator, Williams %R, CCI.
+- **Not supported:** recursive indicators (EMA, RSI, MACD, ADX, ...) whose state cannot be cheaply
+  rolled back; feed those closed bars only.
+
+Williams %R and CCI can also detect revisions themselves: with `bar_mode: BarMode::Tick` in their
+config, `calculate` treats an input with the same timestamp as the latest bar as a revision of it,
+so every tick can go through `calculate`. The default `BarMode::Close` counts every input as a bar.
+
+## Long series
+OBV, MFI, CCI and Standard Deviation keep running sums that are updated bar by bar. On very long
+streams plain addition slowly accumulates rounding error; set `summation: Summation::Kahan` in their
+config to use compensated summation instead, at a small per-bar cost.
 
 Each module contains its own implementation (typically in a `main.rs` file) and associated tests (in a `__tests__.rs` or `_tests__` directory). For more details on each indicator, please refer to the documentation within the corresponding module.
 
@@ -32,6 +90,7 @@ pub mod v1 {
     //! - **atr:** Implements the Average True Range (ATR) indicator.
     //! - **bollinger:** Implements Bollinger Bands.
     //! - **ema:** Implements the Exponential Moving Average (EMA) indicator.
+    //! - **zlema:** Implements the Zero-Lag Exponential Moving Average (ZLEMA) indicator.
     //! - **ma:** Provides a unified interface for multiple moving averages (SMA, EMA, MACD).
     //! - **macd:** Implements the MACD (Moving Average Convergence Divergence) indicator.
     //! - **rsi:** Implements the Relative Strength Index (RSI) indicator.
@@ -40,7 +99,15 @@ pub mod v1 {
     //! - **momentum:** Implements the Momentum indicator.
     //! - **stochastic:** Implements the Stochastic Oscillator indicator.
     //! - **support_resistance:** Implements Support & Resistance indicators.
-    //! - **types:** Contains shared types (structs, enums) used throughout the library.
+    //! - **kst:** Implements the KST (Know Sure Thing) oscillator.
+    //! - **wma:** Implements the Weighted Moving Average.
+    //! - **coppock:** Implements the Coppock Curve.
+    //! - **smma:** Implements the Smoothed Moving Average (SMMA).
+    //! - **alligator:** Implements Bill Williams' Alligator (three displaced SMMAs).
+    //! - **envelope:** Implements fixed-percent moving average envelopes.
+    //! - **ichimoku:** Implements the Ichimoku Cloud (Tenkan, Kijun, Senkou spans and Chikou).
+    //! - **types:** Contains shared types (structs, enums) used throughout the library, including
+    //!   `V1Error`, the error returned by the `try_new` constructors and `try_calculate` methods.
 
     pub mod atr {
         //! **ATR Module**
@@ -63,6 +130,14 @@ pub mod v1 {
         //! Implements the Exponential Moving Average (EMA) indicator.
         mod __tests__;
         pub mod main;
+        pub mod types;
+    }
+    pub mod zlema {
+        //! **ZLEMA Module**
+        //!
+        //! Implements the Zero-Lag Exponential Moving Average (ZLEMA) indicator.
+        mod __tests__;
+        pub mod main;
     }
     pub mod ma {
         //! **Moving Averages Module**
@@ -127,6 +202,63 @@ pub mod v1 {
         pub mod main;
         pub mod types;
     }
+    pub mod kst {
+        //! **KST Module**
+        //!
+        //! Implements the KST (Know Sure Thing) oscillator from four smoothed ROC series.
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+    pub mod wma {
+        //! **Weighted Moving Average Module**
+        //!
+        //! Implements a linearly Weighted Moving Average (WMA).
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+    pub mod coppock {
+        //! **Coppock Curve Module**
+        //!
+        //! Implements the Coppock Curve, a WMA of summed long and short ROCs.
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+    pub mod smma {
+        //! **Smoothed Moving Average Module**
+        //!
+        //! Implements the Smoothed Moving Average (SMMA), also known as Wilder's moving average.
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+    pub mod alligator {
+        //! **Alligator Module**
+        //!
+        //! Implements Bill Williams' Alligator: jaw, teeth and lips SMMAs displaced forward.
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+    pub mod envelope {
+        //! **Envelope Module**
+        //!
+        //! Implements moving average envelopes with bands a fixed percent above and below an SMA or EMA.
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+    pub mod ichimoku {
+        //! **Ichimoku Module**
+        //!
+        //! Implements the Ichimoku Cloud: high/low midpoint lines with forward- and
+        //! backward-displaced spans.
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
 
     pub mod types;
 }
@@ -150,12 +282,30 @@ pub mod v2 {
     //!
     //! ## Tier 3 Indicators (Supplementary)
     //! - **williams_r:** Williams %R - Extreme condition detection for overbought/oversold analysis
+    //! - **fisher:** Fisher Transform - Sharp turning-point detection via Gaussian normalization
+    //! - **mass_index:** Mass Index - Reversal bulge detection from high-low range expansion
+    //! - **choppiness:** Choppiness Index - Trending vs ranging market classification
+    //! - **volume_profile:** Volume Profile - Price-by-volume histogram with point of control and value area
+    //! - **stc:** Schaff Trend Cycle - MACD line run through two smoothed stochastic passes
+    //! - **percent_rank:** Percent Rank - Close ranked within its recent window, with overbought/oversold percentiles
+    //! - **beta:** Rolling Beta - Covariance of asset and benchmark returns over benchmark variance
+    //! - **drawdown:** Drawdown - Running peak with current and maximum drawdown of an equity curve
+    //! - **tsi:** True Strength Index - Momentum double-smoothed by EMAs and normalized by absolute momentum
+    //! - **bop:** Balance of Power - (Close - Open) / (High - Low) per bar, optionally SMA-smoothed
+    //! - **chande_kroll:** Chande Kroll Stop - ATR offsets from rolling extremes, trailed over a second window
+    //! - **vwap:** VWAP Bands - Anchored volume-weighted average price with standard deviation bands
+    //! - **rvi:** Relative Vigor Index - Closing vigor against the bar range with a signal line
+    //! - **fractals:** Williams Fractals - Confirmed swing highs/lows with a configurable wing size
     //!
     //! ## Mathematical Foundation
     //! - **std_dev:** Standard Deviation - Essential statistical foundation for volatility analysis
     //!
     //! All indicators are designed to work together in signal aggregation systems and include
     //! comprehensive error handling, state management, and batch processing capabilities.
+    //!
+    //! Configs are validated once, when a calculator is built or its state is restored. An
+    //! invalid config is kept as an error and returned by every `calculate` call instead of
+    //! being re-checked on each bar.
 
     /// **OBV Module**
     ///
@@ -226,4 +376,537 @@ pub mod v2 {
         pub mod main;
         pub mod types;
     }
+
+    /// **Fisher Transform Module**
+    ///
+    /// The Fisher Transform maps the price position within a lookback window onto a
+    /// Gaussian-like distribution, turning reversals into sharp, easy-to-spot peaks.
+    pub mod fisher {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Mass Index Module**
+    ///
+    /// The Mass Index sums the ratio of single and double EMAs of the high-low range
+    /// to catch the range expansion and contraction that often precedes reversals.
+    pub mod mass_index {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Choppiness Index Module**
+    ///
+    /// The Choppiness Index compares the summed true range with the net high-low range
+    /// of the window to tell trending markets (low values) from choppy ones (high values).
+    pub mod choppiness {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Volume Profile Module**
+    ///
+    /// Volume Profile buckets a rolling window's volume by price level to locate
+    /// high-volume nodes, the point of control, and the value area.
+    pub mod volume_profile {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Schaff Trend Cycle Module**
+    ///
+    /// Schaff Trend Cycle (STC) applies a smoothed stochastic to the MACD line twice,
+    /// producing a fast 0-100 oscillator that turns ahead of MACD.
+    pub mod stc {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Percent Rank Module**
+    ///
+    /// Percent Rank reports where the close ranks within the last N closes (0-100),
+    /// a simple mean-reversion gauge with configurable overbought/oversold percentiles.
+    pub mod percent_rank {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Rolling Beta Module**
+    ///
+    /// Rolling beta reports cov(asset, benchmark) / var(benchmark) over a window of
+    /// return pairs, taken in two passes over the window.
+    pub mod beta {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Drawdown Module**
+    ///
+    /// Drawdown tracks the running peak of an equity curve and reports the current
+    /// and maximum drawdown in absolute and percentage terms.
+    pub mod drawdown {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **True Strength Index Module**
+    ///
+    /// True Strength Index: 100 x EMA(EMA(momentum)) / EMA(EMA(|momentum|)), bounded in
+    /// [-100, 100], with an EMA signal line.
+    pub mod tsi {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Balance of Power Module**
+    ///
+    /// Balance of Power: (close - open) / (high - low) per OHLC bar, 0 for zero-range
+    /// bars, with optional SMA smoothing.
+    pub mod bop {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Chande Kroll Stop Module**
+    ///
+    /// Chande Kroll Stop: long/short trailing stops from the highest high/lowest low
+    /// offset by an ATR multiple, trailed over a second window.
+    pub mod chande_kroll {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **VWAP Bands Module**
+    ///
+    /// Anchored VWAP with bands at a multiple of the volume-weighted standard deviation,
+    /// computed from running weighted sums; `reset` re-anchors.
+    pub mod vwap {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Relative Vigor Index Module**
+    ///
+    /// Compares each bar's close-minus-open to its range with a 4-bar weighted smoothing.
+    pub mod rvi {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Williams Fractals Module**
+    ///
+    /// Center bars beyond their neighbours' highs or lows, for support/resistance levels.
+    pub mod fractals {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+}
+
+pub mod prelude {
+    //! # Prelude
+    //!
+    //! Re-exports the calculators, their input/output types, and the shared signal types so that
+    //! `use indexes_rs::prelude::*;` is enough for most programs.
+    //!
+    //! `TrendDirection` is the shared enum used by both v1 and v2; Parabolic SAR's two-state
+    //! direction is exported as `SARDirection`.
+    //!
+    //! ```rust
+    //! use indexes_rs::prelude::*;
+    //!
+    //! let mut rsi = RSI::new(14, None, None);
+    //! let mut adx = ADX::new();
+    //! let _ = rsi.calculate(100.0);
+    //! let _ = adx.calculate(ADXInput { timestamp: None, high: 101.0, low: 99.0, close: 100.0 });
+    //! ```
+
+    mod __tests__;
+
+    pub use crate::common::precision::round_to;
+    pub use crate::common::types::{
+        BarMode, Candle, DenseBatch, DistanceUnits, GapPolicy, Summation, TiePolicy, TrendDirection,
+    };
+    pub use crate::v1::types::{Cross, HistColor, TradingSignal, V1Error};
+
+    pub use crate::v1::alligator::{main::Alligator, types::AlligatorResult};
+    pub use crate::v1::atr::main::ATR;
+    pub use crate::v1::bollinger::{
+        main::BollingerBands,
+        types::{BBResult, BollingerError},
+    };
+    pub use crate::v1::coppock::{main::Coppock, types::CoppockResult};
+    pub use crate::v1::ema::main::ExponentialMovingAverage;
+    pub use crate::v1::envelope::{
+        main::Envelope,
+        types::{EnvelopeMAType, EnvelopeResult},
+    };
+    pub use crate::v1::ichimoku::{main::Ichimoku, types::IchimokuResult};
+    pub use crate::v1::kst::{main::KST, types::KstResult};
+    pub use crate::v1::ma::main::{MovingAverageParams, MovingAverageResults, MovingAverages};
+    pub use crate::v1::macd::{main::MACD, types::MACDResult};
+    pub use crate::v1::momentum::{main::Momentum, types::MomentumResult};
+    pub use crate::v1::roc::{main::ROC, types::ROCResult};
+    pub use crate::v1::rsi::{main::RSI, types::RSIResult};
+    pub use crate::v1::sma::main::{SMADeadband, SMAError, SMAResult, SimpleMovingAverage};
+    pub use crate::v1::smma::main::SmoothedMovingAverage;
+    pub use crate::v1::stochastic::{main::StochasticOscillator, types::StochResult};
+    pub use crate::v1::support_resistance::{
+        main::SupportResistance,
+        types::{BreakoutEvent, SRResult},
+    };
+    pub use crate::v1::wma::main::WeightedMovingAverage;
+    pub use crate::v1::zlema::main::ZeroLagExponentialMovingAverage;
+
+    pub use crate::v2::adx::{
+        main::ADX,
+        types::{ADXConfig, ADXInput, ADXOutput, ADXRaw, AdxSmoothing},
+    };
+    pub use crate::v2::beta::{main::RollingBeta, types::BetaConfig};
+    pub use crate::v2::bop::{
+        main::BOP,
+        types::{BOPConfig, BOPInput, BOPOutput},
+    };
+    pub use crate::v2::cci::{
+        main::CCI,
+        types::{CCIConfig, CCIInput, CCIOutput},
+    };
+    pub use crate::v2::chande_kroll::{
+        main::ChandeKrollStop,
+        types::{ChandeKrollConfig, ChandeKrollInput, ChandeKrollOutput},
+    };
+    pub use crate::v2::choppiness::{
+        main::ChoppinessIndex,
+        types::{ChoppinessConfig, ChoppinessInput, ChoppinessOutput},
+    };
+    pub use crate::v2::drawdown::{main::Drawdown, types::DrawdownOutput};
+    pub use crate::v2::fisher::{
+        main::FisherTransform,
+        types::{FisherConfig, FisherInput, FisherOutput},
+    };
+    pub use crate::v2::fractals::{
+        main::Fractals,
+        types::{FractalType, FractalsConfig, FractalsInput, FractalsOutput},
+    };
+    pub use crate::v2::mass_index::{
+        main::MassIndex,
+        types::{MassIndexConfig, MassIndexInput, MassIndexOutput},
+    };
+    pub use crate::v2::mfi::{
+        main::MFI,
+        types::{MFIConfig, MFIInput, MFIOutput},
+    };
+    pub use crate::v2::obv::{
+        main::OBV,
+        types::{OBVConfig, OBVInput, OBVNormalization, OBVOutput, OBVPriceSource},
+    };
+    pub use crate::v2::parabolic_sar::{
+        main::ParabolicSAR,
+        types::{
+            AfResetPolicy, ParabolicSARConfig, ParabolicSARInput, ParabolicSAROutput, SARDirection,
+        },
+    };
+    pub use crate::v2::percent_rank::{
+        main::PercentRank,
+        types::{PercentRankConfig, PercentRankInput, PercentRankOutput},
+    };
+    pub use crate::v2::rvi::{
+        main::RVI,
+        types::{RVIConfig, RVIInput, RVIOutput},
+    };
+    pub use crate::v2::std_dev::{
+        main::StandardDeviation,
+        types::{
+            RunningStats, StandardDeviationConfig, StandardDeviationInput, StandardDeviationOutput,
+            StdDevMode,
+        },
+    };
+    pub use crate::v2::tsi::{
+        main::TSI,
+        types::{TSIConfig, TSIInput, TSIOutput},
+    };
+    pub use crate::v2::volume_profile::{
+        main::VolumeProfile,
+        types::{VolumeProfileConfig, VolumeProfileInput, VolumeProfileOutput},
+    };
+    pub use crate::v2::vwap::{
+        main::VwapBands,
+        types::{VwapBandsConfig, VwapInput, VwapOutput, VwapZone},
+    };
+    pub use crate::v2::williams_r::{
+        main::WilliamsR,
+        types::{WilliamsRConfig, WilliamsRInput, WilliamsROutput},
+    };
+
+    pub use crate::transform::bar_pattern::{main::BarPattern, types::BarType};
+    pub use crate::transform::input_filter::{
+        main::{Filtered, InputFilter},
+        types::InputFilterKind,
+    };
+    pub use crate::transform::price_source::types::PriceSource;
+    pub use crate::transform::resampler::main::Resampler;
+
+    pub use crate::util::confirm::main::Confirm;
+    pub use crate::util::pivot::main::{Pivot, PivotDetector, PivotKind};
+    pub use crate::util::signal_debouncer::main::SignalDebouncer;
+
+    pub use crate::aggregator::{
+        main::{SignalAggregator, VoteSource},
+        types::WeightedVote,
+    };
+    pub use crate::risk::{
+        main::{position_size, AtrPositionSizer},
+        types::PositionSizerConfig,
+    };
+    pub use crate::suite::{
+        main::IndicatorSuite,
+        types::{SuiteConfig, SuiteSnapshot},
+    };
+}
+
+pub mod util {
+    //! # Utilities
+    //!
+    //! Reusable building blocks shared by indicator implementations.
+    //!
+    //! - **rolling_extremes:** Amortized O(1) rolling minimum/maximum over a fixed window.
+    //! - **true_range:** True Range of a bar, as a free function and a stateful tracker.
+    //! - **percent_rank:** Rolling percent rank of the latest value within a fixed window.
+    //! - **signal_debouncer:** Cooldown filter suppressing repeated trading signals.
+    //! - **slope_trend:** Regression slope of a moving average classified as up, flat, or down.
+    //! - **float_cmp:** Tolerance-based float comparisons for checking indicator outputs.
+    //! - **derivative:** First and second differences (slope and acceleration) of any series.
+    //! - **confirm:** Confirmation filter emitting a signal only after N consecutive agreeing bars.
+    //! - **pivot:** Pivot high/low detection with left/right confirmation bars, for divergence checks.
+    //! - **ema_chain:** Stacked EMAs of one period (EMA of EMA ...) exposing every level.
+    //! - **rolling_sum:** O(1) running sum and mean over a fixed window, with one-step undo.
+
+    /// **Rolling Extremes Module**
+    pub mod rolling_extremes {
+        mod __tests__;
+        pub mod main;
+    }
+
+    /// **True Range Module**
+    pub mod true_range {
+        mod __tests__;
+        pub mod main;
+    }
+
+    /// **Percent Rank Module**
+    pub mod percent_rank {
+        mod __tests__;
+        pub mod main;
+    }
+
+    /// **Signal Debouncer Module**
+    pub mod signal_debouncer {
+        mod __tests__;
+        pub mod main;
+    }
+
+    /// **Slope Trend Module**
+    pub mod slope_trend {
+        mod __tests__;
+        pub mod main;
+    }
+
+    /// **Float Comparison Module**
+    pub mod float_cmp {
+        mod __tests__;
+        pub mod main;
+    }
+
+    /// **Derivative Module**
+    pub mod derivative {
+        mod __tests__;
+        pub mod main;
+    }
+
+    /// **Confirm Module**
+    pub mod confirm {
+        mod __tests__;
+        pub mod main;
+    }
+
+    /// **Pivot Module**
+    pub mod pivot {
+        mod __tests__;
+        pub mod main;
+    }
+
+    /// **EMA Chain Module**
+    pub mod ema_chain {
+        mod __tests__;
+        pub mod main;
+    }
+
+    /// **Rolling Sum Module**
+    pub mod rolling_sum {
+        mod __tests__;
+        pub mod main;
+    }
+
+    pub use confirm::main::Confirm;
+    pub use derivative::main::{Derivative, DerivativeOutput};
+    pub use ema_chain::main::EmaChain;
+    pub use float_cmp::main::{approx_eq, relative_eq, Tolerance};
+    pub use pivot::main::{Pivot, PivotDetector, PivotKind};
+    pub use rolling_sum::main::RollingSum;
+    pub use slope_trend::main::{slope_trend, MaSlope, SlopeTrend};
+    pub use true_range::main::{true_range, TrueRange};
+}
+
+pub mod common {
+    //! # Common Types
+    //!
+    //! Crate-wide types shared by indicators and transforms of both versions, including the
+    //! `GapPolicy` that v2 indicators consult when a bar contains NaN or infinite values, plus
+    //! the `round_to` helper behind the optional `precision` setting of indicator configs.
+
+    mod __tests__;
+    pub mod precision;
+    pub mod types;
+
+    #[cfg(test)]
+    pub(crate) use __tests__::assert_dense_batch;
+}
+
+pub use common::precision::round_to;
+
+pub mod transform {
+    //! # Transforms
+    //!
+    //! Input transforms that reshape a candle stream before it is fed to an indicator.
+    //!
+    //! - **resampler:** Aggregates N candles (or timestamp buckets) into a higher-timeframe candle.
+    //! - **bar_pattern:** Classifies each bar as inside, outside, up, or down against the previous bar.
+    //! - **price_source:** Derived prices (median, typical, weighted close, average) of a candle.
+    //! - **input_filter:** Median-of-3 or EMA pre-filter placed in front of an indicator's update.
+
+    /// **Resampler Module**
+    ///
+    /// Multi-timeframe helper that aggregates lower-timeframe candles, e.g. 1h into 4h.
+    pub mod resampler {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Bar Pattern Module**
+    ///
+    /// Stateful inside/outside bar detector for pattern-based strategies.
+    pub mod bar_pattern {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Price Source Module**
+    ///
+    /// Pure functions and a `PriceSource` selector for the derived prices indicators consume.
+    pub mod price_source {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+
+    /// **Input Filter Module**
+    ///
+    /// Smooths noisy raw inputs (e.g. ticks) before an indicator sees them.
+    pub mod input_filter {
+        mod __tests__;
+        pub mod main;
+        pub mod types;
+    }
+}
+
+pub mod suite {
+    //! # Indicator Suite
+    //!
+    //! Feeds one candle to a bundle of common indicators and returns all of their readings
+    //! as a single serializable snapshot, e.g. for dashboards.
+
+    mod __tests__;
+    pub mod main;
+    pub mod types;
+}
+
+pub mod snapshot {
+    //! # State Snapshots
+    //!
+    //! Persists the state of a heterogeneous set of running indicators in one serializable
+    //! blob so a service can resume streaming after a restart. Each indicator's state is
+    //! wrapped in a versioned `StateSnapshot`; `save` and `restore` convert to and from it.
+    //!
+    //! Resumed indicators match uninterrupted ones bit for bit only if the serializer
+    //! round-trips `f64` exactly (for `serde_json`, enable its `float_roundtrip` feature).
+
+    mod __tests__;
+    pub mod main;
+    pub mod types;
+}
+
+pub mod aggregator {
+    //! # Signal Aggregation
+    //!
+    //! Combines readings of several indicators into one weighted score. Anything that
+    //! implements `VoteSource` (a normalized vote in [-1, 1]) can take part: RSI and MACD
+    //! results, trading signals, and trend directions out of the box, plus user types.
+
+    mod __tests__;
+    pub mod main;
+    pub mod types;
+}
+
+pub mod risk {
+    //! # Risk Management
+    //!
+    //! Position sizing from volatility: `position_size` turns a risk budget and an ATR-based
+    //! stop distance into a number of units, and `AtrPositionSizer` does the same from a close
+    //! stream with its own ATR.
+
+    mod __tests__;
+    pub mod main;
+    pub mod types;
+}
+
+#[cfg(any(test, feature = "test-util"))]
+pub mod testutil {
+    //! # Test Utilities
+    //!
+    //! Deterministic fixtures for indicator tests and examples, compiled for the crate's own
+    //! tests and, for downstream users, behind the `test-util` feature.
+    //!
+    //! - **ohlcv_series:** Seeded geometric-Brownian-motion OHLCV candles.
+
+    mod __tests__;
+    pub mod main;
+    pub mod types;
+}
+
+#[cfg(feature = "wasm")]
+pub mod wasm {
+    //! # WebAssembly Bindings
+    //!
+    //! `wasm-bindgen` wrappers (enabled by the `wasm` feature) that run RSI, MACD, and Bollinger
+    //! Bands over a whole price array: a `Float64Array` goes in and typed arrays come out, with
+    //! `NaN` for warmup bars. Build with `wasm-pack` from a crate that sets
+    //! `crate-type = ["cdylib"]`, or via `cargo rustc --features wasm --crate-type cdylib`.
+
+    mod __tests__;
+    pub mod main;
 }
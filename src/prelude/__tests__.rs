@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_prelude_v1_calculators() {
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        sma.add_value(1.0);
+        sma.add_value(3.0);
+        let result: SMAResult = sma.calculate().unwrap();
+        assert_eq!(result.value, 2.0);
+        assert_eq!(result.trend, TrendDirection::Sideways);
+
+        let mut rsi = RSI::new(2, None, None);
+        let mut last: Option<RSIResult> = None;
+        for price in [1.0, 2.0, 3.0] {
+            last = rsi.calculate(price);
+        }
+        assert!(last.unwrap().value > 50.0);
+    }
+
+    #[test]
+    fn test_prelude_v2_calculators() {
+        let mut adx = ADX::new();
+        let output: ADXOutput = adx
+            .calculate(ADXInput {
+                timestamp: None,
+                high: 101.0,
+                low: 99.0,
+                close: 100.0,
+            })
+            .unwrap();
+        assert!(output.adx >= 0.0);
+
+        let mut obv = OBV::with_config(OBVConfig {
+            gap_policy: GapPolicy::SkipBar,
+            ..Default::default()
+        });
+        let output: OBVOutput = obv
+            .calculate(OBVInput {
+                timestamp: None,
+                close: 10.0,
+                volume: 500.0,
+                high: None,
+                low: None,
+            })
+            .unwrap();
+        assert_eq!(output.obv, 500.0);
+
+        let _: Option<TrendDirection> = adx.trend_direction();
+        let candle = Candle::new(1.0, 2.0, 0.5, 1.5, 10.0);
+        assert_eq!(candle.close, 1.5);
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use crate::risk::{
+        main::{position_size, AtrPositionSizer},
+        types::{PositionSizerConfig, RiskError},
+    };
+
+    #[test]
+    fn test_position_size_math() {
+        // 2% of 50,000 = 1,000 at risk; stop 1.5 × 4.0 = 6.0 away
+        let size = position_size(50_000.0, 0.02, 4.0, 1.5);
+        assert!((size - 1_000.0 / 6.0).abs() < 1e-9);
+
+        // Doubling the ATR halves the size
+        assert!((position_size(50_000.0, 0.02, 8.0, 1.5) - size / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_zero_atr_gives_zero_size() {
+        assert_eq!(position_size(50_000.0, 0.02, 0.0, 2.0), 0.0);
+        assert_eq!(position_size(50_000.0, 0.02, 4.0, 0.0), 0.0);
+        assert_eq!(position_size(50_000.0, 0.02, f64::NAN, 2.0), 0.0);
+        assert_eq!(position_size(-50_000.0, 0.02, 4.0, 2.0), 0.0);
+
+        // A flat market has zero ATR: size is zero, not infinite
+        let mut sizer = AtrPositionSizer::try_with_config(PositionSizerConfig {
+            atr_period: 3,
+            ..PositionSizerConfig::default()
+        })
+        .unwrap();
+        let sizes: Vec<_> = (0..5).map(|_| sizer.update(100.0, 10_000.0)).collect();
+        assert_eq!(sizes, [None, None, Some(0.0), Some(0.0), Some(0.0)]);
+    }
+
+    #[test]
+    fn test_sizer_uses_internal_atr() {
+        let mut sizer = AtrPositionSizer::try_with_config(PositionSizerConfig {
+            atr_period: 3,
+            atr_multiple: 2.0,
+            risk_fraction: 0.01,
+        })
+        .unwrap();
+
+        // True ranges 0, 2, 1, 3 (close to close)
+        assert_eq!(sizer.update(100.0, 20_000.0), None);
+        assert_eq!(sizer.update(102.0, 20_000.0), None);
+        // ATR (0 + 2 + 1) / 3 = 1: 200 at risk over a 2.0 stop
+        assert_eq!(sizer.update(101.0, 20_000.0), Some(100.0));
+        assert_eq!(sizer.atr(), Some(1.0));
+        // ATR (2 + 1 + 3) / 3 = 2
+        assert_eq!(sizer.update(104.0, 20_000.0), Some(50.0));
+        assert_eq!(sizer.size_for(40_000.0), 100.0);
+
+        sizer.reset();
+        assert_eq!(sizer.atr(), None);
+        assert_eq!(sizer.size_for(40_000.0), 0.0);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let invalid = [
+            (
+                PositionSizerConfig {
+                    atr_period: 0,
+                    ..PositionSizerConfig::default()
+                },
+                RiskError::InvalidPeriod,
+            ),
+            (
+                PositionSizerConfig {
+                    atr_multiple: 0.0,
+                    ..PositionSizerConfig::default()
+                },
+                RiskError::InvalidAtrMultiple,
+            ),
+            (
+                PositionSizerConfig {
+                    risk_fraction: 1.5,
+                    ..PositionSizerConfig::default()
+                },
+                RiskError::InvalidRiskFraction,
+            ),
+        ];
+        for (config, error) in invalid {
+            assert_eq!(AtrPositionSizer::try_with_config(config).err(), Some(error));
+        }
+    }
+}
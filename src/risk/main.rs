@@ -0,0 +1,128 @@
+use crate::risk::types::{PositionSizerConfig, RiskError};
+use crate::v1::atr::main::ATR;
+
+/// Position size (in units of the instrument) risking `risk_fraction` of `capital`
+/// on a stop placed `atr_multiple` ATRs away
+///
+/// Size = (capital × risk_fraction) / (atr_multiple × atr)
+///
+/// A zero stop distance (zero ATR or multiple) returns 0.0 rather than an infinite
+/// size, as do negative or non-finite inputs.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::risk::main::position_size;
+///
+/// // Risk 1% of 100,000 with a stop 2 ATRs away at ATR 2.5: 1,000 / 5 = 200 units
+/// assert_eq!(position_size(100_000.0, 0.01, 2.5, 2.0), 200.0);
+/// assert_eq!(position_size(100_000.0, 0.01, 0.0, 2.0), 0.0);
+/// ```
+pub fn position_size(capital: f64, risk_fraction: f64, atr: f64, atr_multiple: f64) -> f64 {
+    let risk_capital = capital * risk_fraction;
+    let stop_distance = atr_multiple * atr;
+
+    if !risk_capital.is_finite() || risk_capital <= 0.0 {
+        return 0.0;
+    }
+    if !stop_distance.is_finite() || stop_distance <= 0.0 {
+        return 0.0;
+    }
+
+    risk_capital / stop_distance
+}
+
+/// Streaming position sizer that keeps its own ATR
+///
+/// Feed each close with the current capital; once the internal ATR has warmed up,
+/// every update returns the [`position_size`] for the configured risk fraction and
+/// ATR multiple.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::risk::main::AtrPositionSizer;
+/// use indexes_rs::risk::types::PositionSizerConfig;
+///
+/// let mut sizer = AtrPositionSizer::try_with_config(PositionSizerConfig {
+///     atr_period: 2,
+///     ..PositionSizerConfig::default()
+/// })
+/// .unwrap();
+///
+/// assert_eq!(sizer.update(100.0, 10_000.0), None);
+/// assert_eq!(sizer.update(101.0, 10_000.0), Some(100.0 / (2.0 * 0.5)));
+/// ```
+pub struct AtrPositionSizer {
+    config: PositionSizerConfig,
+    atr: ATR,
+    last_atr: Option<f64>,
+}
+
+impl AtrPositionSizer {
+    /// Create a sizer with the default configuration (ATR 14, 2 ATR stop, 1% risk)
+    pub fn new() -> Self {
+        Self::with_config(PositionSizerConfig::default())
+    }
+
+    /// Create a sizer with a custom configuration
+    ///
+    /// The configuration is not checked; use `try_with_config` to reject invalid values.
+    pub fn with_config(config: PositionSizerConfig) -> Self {
+        Self {
+            config,
+            atr: ATR::new(config.atr_period),
+            last_atr: None,
+        }
+    }
+
+    /// Create a sizer, rejecting an invalid configuration up front
+    pub fn try_with_config(config: PositionSizerConfig) -> Result<Self, RiskError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Feed a close and return the position size for `capital`
+    ///
+    /// Returns `None` until the ATR has `atr_period` values.
+    pub fn update(&mut self, close: f64, capital: f64) -> Option<f64> {
+        let atr = self.atr.calculate(close)?;
+        self.last_atr = Some(atr);
+        Some(self.size_for(capital))
+    }
+
+    /// Position size for `capital` at the latest ATR, without feeding a bar
+    ///
+    /// Returns 0.0 before the ATR has warmed up.
+    pub fn size_for(&self, capital: f64) -> f64 {
+        self.last_atr.map_or(0.0, |atr| {
+            position_size(
+                capital,
+                self.config.risk_fraction,
+                atr,
+                self.config.atr_multiple,
+            )
+        })
+    }
+
+    /// Latest ATR value, `None` during warmup
+    pub fn atr(&self) -> Option<f64> {
+        self.last_atr
+    }
+
+    /// Current configuration
+    pub fn config(&self) -> &PositionSizerConfig {
+        &self.config
+    }
+
+    /// Reset the internal ATR
+    pub fn reset(&mut self) {
+        *self = Self::with_config(self.config);
+    }
+}
+
+impl Default for AtrPositionSizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for [`AtrPositionSizer`](crate::risk::main::AtrPositionSizer)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PositionSizerConfig {
+    /// Period of the internal ATR (default: 14)
+    pub atr_period: usize,
+    /// Stop distance in ATRs (default: 2.0)
+    pub atr_multiple: f64,
+    /// Fraction of capital risked per position, e.g. 0.01 for 1% (default: 0.01)
+    pub risk_fraction: f64,
+}
+
+impl Default for PositionSizerConfig {
+    fn default() -> Self {
+        Self {
+            atr_period: 14,
+            atr_multiple: 2.0,
+            risk_fraction: 0.01,
+        }
+    }
+}
+
+impl PositionSizerConfig {
+    /// Check the invariants the position sizer relies on
+    pub fn validate(&self) -> Result<(), RiskError> {
+        if self.atr_period == 0 {
+            return Err(RiskError::InvalidPeriod);
+        }
+
+        if !self.atr_multiple.is_finite() || self.atr_multiple <= 0.0 {
+            return Err(RiskError::InvalidAtrMultiple);
+        }
+
+        if !(0.0..=1.0).contains(&self.risk_fraction) {
+            return Err(RiskError::InvalidRiskFraction);
+        }
+
+        Ok(())
+    }
+}
+
+/// Error types for position sizing configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RiskError {
+    /// ATR period of zero
+    InvalidPeriod,
+    /// ATR multiple is not a positive finite number
+    InvalidAtrMultiple,
+    /// Risk fraction outside [0, 1] (or NaN)
+    InvalidRiskFraction,
+}
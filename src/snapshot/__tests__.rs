@@ -0,0 +1,271 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::types::Candle;
+    use crate::snapshot::{
+        main::{restore, save, Snapshottable},
+        types::{Snapshot, SnapshotError, StateSnapshot, SNAPSHOT_VERSION},
+    };
+    use crate::transform::bar_pattern::main::BarPattern;
+    use crate::transform::input_filter::{main::InputFilter, types::InputFilterKind};
+    use crate::transform::resampler::main::Resampler;
+    use crate::v1::rsi::main::RSI;
+    use crate::v2::adx::{main::ADX, types::ADXInput};
+    use crate::v2::beta::main::RollingBeta;
+    use crate::v2::bop::main::BOP;
+    use crate::v2::cci::{main::CCI, types::CCIInput};
+    use crate::v2::chande_kroll::main::ChandeKrollStop;
+    use crate::v2::drawdown::main::Drawdown;
+    use crate::v2::fractals::main::Fractals;
+    use crate::v2::obv::{main::OBV, types::OBVInput};
+    use crate::v2::rvi::main::RVI;
+    use crate::v2::std_dev::{main::StandardDeviation, types::StandardDeviationInput};
+    use crate::v2::tsi::main::TSI;
+    use crate::v2::vwap::main::VwapBands;
+    use approx::assert_relative_eq;
+    use serde_json::{json, Value};
+
+    fn bars() -> Vec<(f64, f64, f64, f64)> {
+        (0..40)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.4).sin() * 5.0 + i as f64 * 0.2;
+                (
+                    close + 1.5,
+                    close - 1.5,
+                    close,
+                    1000.0 + (i % 7) as f64 * 150.0,
+                )
+            })
+            .collect()
+    }
+
+    fn adx_input(bar: (f64, f64, f64, f64)) -> ADXInput {
+        ADXInput {
+            timestamp: None,
+            high: bar.0,
+            low: bar.1,
+            close: bar.2,
+        }
+    }
+
+    fn cci_input(bar: (f64, f64, f64, f64)) -> CCIInput {
+        CCIInput {
+            timestamp: None,
+            high: bar.0,
+            low: bar.1,
+            close: bar.2,
+        }
+    }
+
+    fn std_dev_input(bar: (f64, f64, f64, f64)) -> StandardDeviationInput {
+        StandardDeviationInput {
+            timestamp: None,
+            value: bar.2,
+        }
+    }
+
+    fn obv_input(bar: (f64, f64, f64, f64)) -> OBVInput {
+        OBVInput {
+            timestamp: None,
+            close: bar.2,
+            volume: bar.3,
+            high: None,
+            low: None,
+        }
+    }
+
+    fn candle(bar: (f64, f64, f64, f64)) -> Candle {
+        Candle::new(bar.2, bar.0, bar.1, bar.2, bar.3)
+    }
+
+    /// Feed the first bars to `indicator`, restore its JSON snapshot into `resumed`,
+    /// and check that both produce the same outputs for the remaining bars
+    fn assert_resumes<T: Snapshottable, R: PartialEq + std::fmt::Debug>(
+        mut indicator: T,
+        mut resumed: T,
+        mut step: impl FnMut(&mut T, (f64, f64, f64, f64)) -> R,
+    ) {
+        let bars = bars();
+        let (head, tail) = bars.split_at(25);
+        for &bar in head {
+            step(&mut indicator, bar);
+        }
+
+        let json = serde_json::to_string(&save(&indicator)).unwrap();
+        restore(&mut resumed, serde_json::from_str(&json).unwrap()).unwrap();
+        for &bar in tail {
+            assert_eq!(step(&mut resumed, bar), step(&mut indicator, bar));
+        }
+    }
+
+    #[test]
+    fn test_round_trip_through_one_json_array() {
+        let bars = bars();
+        let (head, tail) = bars.split_at(25);
+
+        let mut rsi = RSI::new(14, None, None);
+        let mut adx = ADX::with_period(5).unwrap();
+        let mut obv = OBV::new();
+        for &bar in head {
+            rsi.calculate(bar.2);
+            adx.calculate(adx_input(bar)).unwrap();
+            obv.calculate(obv_input(bar)).unwrap();
+        }
+
+        let blob = serde_json::to_string(&vec![save(&rsi), save(&adx), save(&obv)]).unwrap();
+        let snapshots: Vec<Snapshot> = serde_json::from_str(&blob).unwrap();
+        assert_eq!(snapshots.len(), 3);
+        assert!(snapshots.iter().all(|s| s.version == SNAPSHOT_VERSION));
+
+        let mut snapshots = snapshots.into_iter();
+        let mut resumed_rsi = RSI::new(14, None, None);
+        let mut resumed_adx = ADX::with_period(5).unwrap();
+        let mut resumed_obv = OBV::new();
+        restore(&mut resumed_rsi, snapshots.next().unwrap()).unwrap();
+        restore(&mut resumed_adx, snapshots.next().unwrap()).unwrap();
+        restore(&mut resumed_obv, snapshots.next().unwrap()).unwrap();
+
+        for &bar in tail {
+            assert_eq!(resumed_rsi.calculate(bar.2), rsi.calculate(bar.2));
+            assert_eq!(
+                resumed_adx.calculate(adx_input(bar)),
+                adx.calculate(adx_input(bar))
+            );
+            assert_eq!(
+                resumed_obv.calculate(obv_input(bar)),
+                obv.calculate(obv_input(bar))
+            );
+        }
+    }
+
+    #[test]
+    fn test_json_shape_is_tagged() {
+        let value = serde_json::to_value(save(&OBV::new())).unwrap();
+        assert_eq!(value["version"], SNAPSHOT_VERSION);
+        assert_eq!(value["indicator"], "Obv");
+        assert!(value["state"].is_object());
+    }
+
+    #[test]
+    fn test_restore_rejects_other_indicator() {
+        let mut rsi = RSI::new(14, None, None);
+        assert_eq!(
+            restore(&mut rsi, save(&OBV::new())),
+            Err(SnapshotError::IndicatorMismatch {
+                expected: "Rsi",
+                found: "Obv",
+            })
+        );
+    }
+
+    #[test]
+    fn test_restore_rejects_newer_version() {
+        let mut obv = OBV::new();
+        let snapshot = Snapshot {
+            version: SNAPSHOT_VERSION + 1,
+            state: obv.snapshot(),
+        };
+        assert_eq!(
+            restore(&mut obv, snapshot),
+            Err(SnapshotError::UnsupportedVersion(SNAPSHOT_VERSION + 1))
+        );
+        assert!(matches!(obv.snapshot(), StateSnapshot::Obv(_)));
+    }
+
+    #[test]
+    fn test_restores_states_saved_before_rolling_sum() {
+        // States saved by indexes-rs 1.0.1 (period 3), which kept the typical prices and
+        // values as plain lists next to their running sums
+        let cci_state = r#"{"config":{"period":3,"overbought":100.0,"oversold":-100.0,"extreme_overbought":200.0,"extreme_oversold":-200.0},"typical_prices":[11.333333333333334,12.166666666666666,12.133333333333333],"tp_sum":35.63333333333333,"has_sufficient_data":true}"#;
+        let std_dev_state = r#"{"config":{"period":3,"use_sample":true},"values":[11.0,12.5,12.0],"sum":35.5,"sum_squared":421.25,"has_sufficient_data":true,"current_mean":11.833333333333334}"#;
+        let snapshot = |indicator: &str, state: &str| -> Snapshot {
+            serde_json::from_value(json!({
+                "version": SNAPSHOT_VERSION,
+                "indicator": indicator,
+                "state": serde_json::from_str::<Value>(state).unwrap(),
+            }))
+            .unwrap()
+        };
+
+        let mut resumed_cci = CCI::new();
+        let mut resumed_std_dev = StandardDeviation::new();
+        restore(&mut resumed_cci, snapshot("Cci", cci_state)).unwrap();
+        restore(&mut resumed_std_dev, snapshot("StdDev", std_dev_state)).unwrap();
+
+        let saved_bars = [
+            (11.0, 9.0, 10.0, 1000.0),
+            (12.0, 10.0, 11.5, 1500.0),
+            (12.5, 10.5, 11.0, 1200.0),
+            (13.0, 11.0, 12.5, 1800.0),
+            (12.8, 11.6, 12.0, 900.0),
+        ];
+        let mut cci = CCI::with_period(3).unwrap();
+        let mut std_dev = StandardDeviation::with_period(3).unwrap();
+        for &bar in &saved_bars {
+            cci.calculate(cci_input(bar)).unwrap();
+            std_dev.calculate(std_dev_input(bar)).unwrap();
+        }
+
+        // Enough bars to evict every restored value
+        for &bar in &bars()[..4] {
+            assert_relative_eq!(
+                resumed_cci.calculate(cci_input(bar)).unwrap().cci,
+                cci.calculate(cci_input(bar)).unwrap().cci,
+                max_relative = 1e-9
+            );
+            assert_relative_eq!(
+                resumed_std_dev
+                    .calculate(std_dev_input(bar))
+                    .unwrap()
+                    .std_dev,
+                std_dev.calculate(std_dev_input(bar)).unwrap().std_dev,
+                max_relative = 1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trip_of_every_snapshottable_state() {
+        assert_resumes(
+            RollingBeta::new(5).unwrap(),
+            RollingBeta::new(5).unwrap(),
+            |beta, bar| beta.add_values(bar.2 / 100.0 - 1.0, bar.0 / 100.0 - 1.0),
+        );
+        assert_resumes(Drawdown::new(), Drawdown::new(), |drawdown, bar| {
+            drawdown.update(bar.2)
+        });
+        assert_resumes(TSI::new(), TSI::new(), |tsi, bar| {
+            tsi.calculate(candle(bar).into())
+        });
+        assert_resumes(BOP::new(), BOP::new(), |bop, bar| {
+            bop.calculate(candle(bar).into())
+        });
+        assert_resumes(
+            ChandeKrollStop::new(),
+            ChandeKrollStop::new(),
+            |stop, bar| stop.calculate(candle(bar).into()),
+        );
+        assert_resumes(VwapBands::new(), VwapBands::new(), |vwap, bar| {
+            vwap.calculate(candle(bar).into())
+        });
+        assert_resumes(RVI::new(), RVI::new(), |rvi, bar| {
+            rvi.calculate(candle(bar).into())
+        });
+        assert_resumes(Fractals::new(), Fractals::new(), |fractals, bar| {
+            fractals.calculate(candle(bar).into())
+        });
+        assert_resumes(
+            Resampler::new(3).unwrap(),
+            Resampler::new(3).unwrap(),
+            |resampler, bar| resampler.update(candle(bar)),
+        );
+        assert_resumes(BarPattern::new(), BarPattern::new(), |pattern, bar| {
+            pattern.update(candle(bar).into())
+        });
+        let ema = InputFilterKind::Ema { period: 5 };
+        assert_resumes(
+            InputFilter::new(ema).unwrap(),
+            InputFilter::new(ema).unwrap(),
+            |filter, bar| filter.apply(bar.2),
+        );
+    }
+}
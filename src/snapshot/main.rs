@@ -0,0 +1,132 @@
+use crate::snapshot::types::{Snapshot, SnapshotError, StateSnapshot, SNAPSHOT_VERSION};
+use crate::transform::bar_pattern::main::BarPattern;
+use crate::transform::input_filter::main::InputFilter;
+use crate::transform::resampler::main::Resampler;
+use crate::v1::rsi::main::RSI;
+use crate::v2::adx::main::ADX;
+use crate::v2::beta::main::RollingBeta;
+use crate::v2::bop::main::BOP;
+use crate::v2::cci::main::CCI;
+use crate::v2::chande_kroll::main::ChandeKrollStop;
+use crate::v2::choppiness::main::ChoppinessIndex;
+use crate::v2::drawdown::main::Drawdown;
+use crate::v2::fisher::main::FisherTransform;
+use crate::v2::fractals::main::Fractals;
+use crate::v2::mass_index::main::MassIndex;
+use crate::v2::mfi::main::MFI;
+use crate::v2::obv::main::OBV;
+use crate::v2::parabolic_sar::main::ParabolicSAR;
+use crate::v2::percent_rank::main::PercentRank;
+use crate::v2::rvi::main::RVI;
+use crate::v2::stc::main::STC;
+use crate::v2::std_dev::main::StandardDeviation;
+use crate::v2::tsi::main::TSI;
+use crate::v2::volume_profile::main::VolumeProfile;
+use crate::v2::vwap::main::VwapBands;
+use crate::v2::williams_r::main::WilliamsR;
+
+/// An indicator whose state can be captured in a [`StateSnapshot`]
+pub trait Snapshottable {
+    /// Capture the current state
+    fn snapshot(&self) -> StateSnapshot;
+
+    /// Replace the current state, failing if the snapshot belongs to another indicator
+    fn restore_snapshot(&mut self, state: StateSnapshot) -> Result<(), SnapshotError>;
+}
+
+/// Capture an indicator's state tagged with the current format version
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::snapshot::main::{restore, save};
+/// use indexes_rs::v2::obv::{main::OBV, types::OBVInput};
+///
+/// let mut obv = OBV::new();
+/// let input = OBVInput {
+///     timestamp: None,
+///     close: 10.0,
+///     volume: 1000.0,
+///     high: None,
+///     low: None,
+/// };
+/// obv.calculate(input).unwrap();
+/// let snapshot = save(&obv);
+///
+/// // After a restart
+/// let mut resumed = OBV::new();
+/// restore(&mut resumed, snapshot).unwrap();
+/// assert_eq!(resumed.current(), obv.current());
+/// ```
+pub fn save<T: Snapshottable>(indicator: &T) -> Snapshot {
+    Snapshot {
+        version: SNAPSHOT_VERSION,
+        state: indicator.snapshot(),
+    }
+}
+
+/// Restore an indicator from a snapshot written by [`save`]
+///
+/// Snapshots from newer format versions are rejected rather than misread.
+pub fn restore<T: Snapshottable>(
+    indicator: &mut T,
+    snapshot: Snapshot,
+) -> Result<(), SnapshotError> {
+    if snapshot.version > SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(snapshot.version));
+    }
+    indicator.restore_snapshot(snapshot.state)
+}
+
+/// Implements [`Snapshottable`] for indicators with `get_state` / `set_state`, storing
+/// each state in the [`StateSnapshot`] variant named after it
+macro_rules! impl_snapshottable {
+    ($($indicator:ty => $variant:ident),+ $(,)?) => {$(
+        impl Snapshottable for $indicator {
+            fn snapshot(&self) -> StateSnapshot {
+                StateSnapshot::$variant(Box::new(self.get_state().clone()))
+            }
+
+            fn restore_snapshot(&mut self, state: StateSnapshot) -> Result<(), SnapshotError> {
+                match state {
+                    StateSnapshot::$variant(state) => {
+                        self.set_state(*state);
+                        Ok(())
+                    }
+                    other => Err(SnapshotError::IndicatorMismatch {
+                        expected: stringify!($variant),
+                        found: other.indicator(),
+                    }),
+                }
+            }
+        }
+    )+};
+}
+
+impl_snapshottable! {
+    RSI => Rsi,
+    ADX => Adx,
+    RollingBeta => Beta,
+    BOP => Bop,
+    CCI => Cci,
+    ChandeKrollStop => ChandeKroll,
+    ChoppinessIndex => Choppiness,
+    Drawdown => Drawdown,
+    FisherTransform => Fisher,
+    Fractals => Fractals,
+    MassIndex => MassIndex,
+    MFI => Mfi,
+    OBV => Obv,
+    ParabolicSAR => ParabolicSar,
+    PercentRank => PercentRank,
+    RVI => Rvi,
+    STC => Stc,
+    StandardDeviation => StdDev,
+    TSI => Tsi,
+    VolumeProfile => VolumeProfile,
+    VwapBands => Vwap,
+    WilliamsR => WilliamsR,
+    Resampler => Resampler,
+    BarPattern => BarPattern,
+    InputFilter => InputFilter,
+}
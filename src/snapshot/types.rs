@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+
+use crate::transform::bar_pattern::types::BarPatternState;
+use crate::transform::input_filter::types::InputFilterState;
+use crate::transform::resampler::types::ResamplerState;
+use crate::v1::rsi::types::RSIState;
+use crate::v2::adx::types::ADXState;
+use crate::v2::beta::types::RollingBetaState;
+use crate::v2::bop::types::BOPState;
+use crate::v2::cci::types::CCIState;
+use crate::v2::chande_kroll::types::ChandeKrollState;
+use crate::v2::choppiness::types::ChoppinessState;
+use crate::v2::drawdown::types::DrawdownState;
+use crate::v2::fisher::types::FisherState;
+use crate::v2::fractals::types::FractalsState;
+use crate::v2::mass_index::types::MassIndexState;
+use crate::v2::mfi::types::MFIState;
+use crate::v2::obv::types::OBVState;
+use crate::v2::parabolic_sar::types::ParabolicSARState;
+use crate::v2::percent_rank::types::PercentRankState;
+use crate::v2::rvi::types::RVIState;
+use crate::v2::stc::types::STCState;
+use crate::v2::std_dev::types::StandardDeviationState;
+use crate::v2::tsi::types::TSIState;
+use crate::v2::volume_profile::types::VolumeProfileState;
+use crate::v2::vwap::types::VwapState;
+use crate::v2::williams_r::types::WilliamsRState;
+
+/// Current snapshot format version, written by `save` and checked by `restore`
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Serializable state of one running indicator
+///
+/// Each variant boxes the indicator's own state struct. Serialized as
+/// `{"indicator": "<variant>", "state": {...}}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "indicator", content = "state")]
+pub enum StateSnapshot {
+    /// RSI state
+    Rsi(Box<RSIState>),
+    /// ADX state
+    Adx(Box<ADXState>),
+    /// RollingBeta state
+    Beta(Box<RollingBetaState>),
+    /// BOP state
+    Bop(Box<BOPState>),
+    /// CCI state
+    Cci(Box<CCIState>),
+    /// ChandeKrollStop state
+    ChandeKroll(Box<ChandeKrollState>),
+    /// ChoppinessIndex state
+    Choppiness(Box<ChoppinessState>),
+    /// Drawdown state
+    Drawdown(Box<DrawdownState>),
+    /// FisherTransform state
+    Fisher(Box<FisherState>),
+    /// Fractals state
+    Fractals(Box<FractalsState>),
+    /// MassIndex state
+    MassIndex(Box<MassIndexState>),
+    /// MFI state
+    Mfi(Box<MFIState>),
+    /// OBV state
+    Obv(Box<OBVState>),
+    /// ParabolicSAR state
+    ParabolicSar(Box<ParabolicSARState>),
+    /// PercentRank state
+    PercentRank(Box<PercentRankState>),
+    /// RVI state
+    Rvi(Box<RVIState>),
+    /// STC state
+    Stc(Box<STCState>),
+    /// StandardDeviation state
+    StdDev(Box<StandardDeviationState>),
+    /// TSI state
+    Tsi(Box<TSIState>),
+    /// VolumeProfile state
+    VolumeProfile(Box<VolumeProfileState>),
+    /// VwapBands state
+    Vwap(Box<VwapState>),
+    /// WilliamsR state
+    WilliamsR(Box<WilliamsRState>),
+    /// Resampler state
+    Resampler(Box<ResamplerState>),
+    /// BarPattern state
+    BarPattern(Box<BarPatternState>),
+    /// InputFilter state
+    InputFilter(Box<InputFilterState>),
+}
+
+impl StateSnapshot {
+    /// Name of the indicator the state belongs to
+    pub fn indicator(&self) -> &'static str {
+        match self {
+            StateSnapshot::Rsi(_) => "Rsi",
+            StateSnapshot::Adx(_) => "Adx",
+            StateSnapshot::Beta(_) => "Beta",
+            StateSnapshot::Bop(_) => "Bop",
+            StateSnapshot::Cci(_) => "Cci",
+            StateSnapshot::ChandeKroll(_) => "ChandeKroll",
+            StateSnapshot::Choppiness(_) => "Choppiness",
+            StateSnapshot::Drawdown(_) => "Drawdown",
+            StateSnapshot::Fisher(_) => "Fisher",
+            StateSnapshot::Fractals(_) => "Fractals",
+            StateSnapshot::MassIndex(_) => "MassIndex",
+            StateSnapshot::Mfi(_) => "Mfi",
+            StateSnapshot::Obv(_) => "Obv",
+            StateSnapshot::ParabolicSar(_) => "ParabolicSar",
+            StateSnapshot::PercentRank(_) => "PercentRank",
+            StateSnapshot::Rvi(_) => "Rvi",
+            StateSnapshot::Stc(_) => "Stc",
+            StateSnapshot::StdDev(_) => "StdDev",
+            StateSnapshot::Tsi(_) => "Tsi",
+            StateSnapshot::VolumeProfile(_) => "VolumeProfile",
+            StateSnapshot::Vwap(_) => "Vwap",
+            StateSnapshot::WilliamsR(_) => "WilliamsR",
+            StateSnapshot::Resampler(_) => "Resampler",
+            StateSnapshot::BarPattern(_) => "BarPattern",
+            StateSnapshot::InputFilter(_) => "InputFilter",
+        }
+    }
+}
+
+/// A [`StateSnapshot`] tagged with the format version it was written with
+///
+/// Serialized flat: `{"version": 1, "indicator": "<variant>", "state": {...}}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Snapshot format version
+    pub version: u32,
+    /// The indicator state
+    #[serde(flatten)]
+    pub state: StateSnapshot,
+}
+
+/// Error types for restoring a snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotError {
+    /// The snapshot was written by a newer, unknown format version
+    UnsupportedVersion(u32),
+    /// The snapshot holds the state of a different indicator
+    IndicatorMismatch {
+        /// Indicator being restored
+        expected: &'static str,
+        /// Indicator the snapshot belongs to
+        found: &'static str,
+    },
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "Unsupported snapshot version {version} (latest is {SNAPSHOT_VERSION})"
+            ),
+            SnapshotError::IndicatorMismatch { expected, found } => {
+                write!(f, "Snapshot holds {found} state, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::types::Candle;
+    use crate::suite::main::IndicatorSuite;
+    use crate::suite::types::{SuiteConfig, SuiteError};
+    use crate::v1::atr::main::ATR;
+    use crate::v1::bollinger::main::BollingerBands;
+    use crate::v1::macd::main::MACD;
+    use crate::v1::rsi::main::RSI;
+    use crate::v2::adx::main::ADX;
+    use serde::Serialize;
+
+    fn assert_serialize<T: Serialize>(_: &T) {}
+
+    fn candles() -> Vec<Candle> {
+        (0..60)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.4).sin() * 5.0 + i as f64 * 0.2;
+                Candle::new(close - 0.5, close + 1.0, close - 1.0, close, 1000.0).with_timestamp(i)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_suite_matches_standalone_calculators() {
+        let mut suite = IndicatorSuite::default();
+        let mut rsi = RSI::new(14, None, None);
+        let mut macd = MACD::new(12, 26, 9);
+        let mut bollinger = BollingerBands::new(20, 2.0).unwrap();
+        let mut adx = ADX::new();
+        let mut atr = ATR::new(14);
+
+        for candle in candles() {
+            let snapshot = suite.update(candle);
+            assert_eq!(snapshot.timestamp, candle.timestamp);
+            assert_eq!(snapshot.rsi, rsi.calculate(candle.close));
+            assert_eq!(
+                snapshot
+                    .macd
+                    .map(|m| (m.macd_line, m.signal_line, m.histogram)),
+                macd.calculate(candle.close)
+                    .map(|m| (m.macd_line, m.signal_line, m.histogram))
+            );
+            assert_eq!(snapshot.bollinger, bollinger.calculate(candle.close));
+            assert_eq!(snapshot.adx, adx.calculate_checked(candle.into()).unwrap());
+            assert_eq!(snapshot.atr, atr.calculate(candle.close));
+        }
+    }
+
+    #[test]
+    fn test_suite_snapshot_warmup_and_serialization() {
+        let mut suite = IndicatorSuite::default();
+        let snapshots: Vec<_> = candles()
+            .into_iter()
+            .map(|candle| suite.update(candle))
+            .collect();
+
+        let first = &snapshots[0];
+        assert!(first.rsi.is_none() && first.bollinger.is_none());
+        assert!(first.adx.is_none() && first.atr.is_none());
+
+        let last = snapshots.last().unwrap();
+        assert!(last.rsi.is_some() && last.macd.is_some() && last.bollinger.is_some());
+        assert!(last.adx.is_some() && last.atr.is_some());
+
+        assert_eq!(last.timestamp, Some(59));
+        assert_serialize(last);
+    }
+
+    #[test]
+    fn test_suite_rejects_invalid_config() {
+        let config = SuiteConfig {
+            rsi_period: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            IndicatorSuite::new(config).err(),
+            Some(SuiteError::InvalidPeriod)
+        );
+
+        let config = SuiteConfig {
+            bollinger_multiplier: -1.0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            IndicatorSuite::new(config),
+            Err(SuiteError::Bollinger(_))
+        ));
+    }
+}
@@ -0,0 +1,69 @@
+use crate::common::types::Candle;
+use crate::suite::types::{SuiteConfig, SuiteError, SuiteSnapshot};
+use crate::v1::atr::main::ATR;
+use crate::v1::bollinger::main::BollingerBands;
+use crate::v1::macd::main::MACD;
+use crate::v1::rsi::main::RSI;
+use crate::v2::adx::main::ADX;
+
+/// A bundle of commonly used indicators driven by a single candle stream
+///
+/// The suite owns configured RSI, MACD, Bollinger Bands, ADX, and ATR
+/// calculators. Each call to [`update`](IndicatorSuite::update) feeds the
+/// candle to all of them and returns one serializable [`SuiteSnapshot`], so
+/// dashboards don't have to wire each indicator by hand.
+///
+/// RSI, MACD, Bollinger Bands, and ATR are fed the close; ADX uses high, low,
+/// and close.
+pub struct IndicatorSuite {
+    rsi: RSI,
+    macd: MACD,
+    bollinger: BollingerBands,
+    adx: ADX,
+    atr: ATR,
+}
+
+impl IndicatorSuite {
+    /// Create a suite with the given configuration
+    pub fn new(config: SuiteConfig) -> Result<Self, SuiteError> {
+        let periods = [
+            config.rsi_period,
+            config.macd_fast,
+            config.macd_slow,
+            config.macd_signal,
+            config.atr_period,
+        ];
+        if periods.contains(&0) {
+            return Err(SuiteError::InvalidPeriod);
+        }
+        config.adx.validate()?;
+
+        Ok(Self {
+            rsi: RSI::new(config.rsi_period, None, None),
+            macd: MACD::new(config.macd_fast, config.macd_slow, config.macd_signal),
+            bollinger: BollingerBands::new(config.bollinger_period, config.bollinger_multiplier)?,
+            adx: ADX::with_config(config.adx),
+            atr: ATR::new(config.atr_period),
+        })
+    }
+
+    /// Feed one candle to every indicator and return their readings
+    pub fn update(&mut self, candle: Candle) -> SuiteSnapshot {
+        let close = candle.close;
+
+        SuiteSnapshot {
+            timestamp: candle.timestamp,
+            rsi: self.rsi.calculate(close),
+            macd: self.macd.calculate(close),
+            bollinger: self.bollinger.calculate(close),
+            adx: self.adx.calculate_checked(candle.into()).ok().flatten(),
+            atr: self.atr.calculate(close),
+        }
+    }
+}
+
+impl Default for IndicatorSuite {
+    fn default() -> Self {
+        Self::new(SuiteConfig::default()).expect("default suite configuration is valid")
+    }
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+use crate::v1::bollinger::types::{BBResult, BollingerError};
+use crate::v1::macd::types::MACDResult;
+use crate::v1::rsi::types::RSIResult;
+use crate::v2::adx::types::{ADXConfig, ADXError, ADXOutput};
+
+/// Configuration for the indicators driven by an [`IndicatorSuite`](crate::suite::main::IndicatorSuite)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SuiteConfig {
+    /// RSI period (default: 14)
+    pub rsi_period: usize,
+    /// MACD fast EMA period (default: 12)
+    pub macd_fast: usize,
+    /// MACD slow EMA period (default: 26)
+    pub macd_slow: usize,
+    /// MACD signal EMA period (default: 9)
+    pub macd_signal: usize,
+    /// Bollinger Bands period (default: 20)
+    pub bollinger_period: usize,
+    /// Bollinger Bands standard deviation multiplier (default: 2.0)
+    pub bollinger_multiplier: f64,
+    /// ADX configuration (default: `ADXConfig::default()`)
+    pub adx: ADXConfig,
+    /// ATR period (default: 14)
+    pub atr_period: usize,
+}
+
+impl Default for SuiteConfig {
+    fn default() -> Self {
+        Self {
+            rsi_period: 14,
+            macd_fast: 12,
+            macd_slow: 26,
+            macd_signal: 9,
+            bollinger_period: 20,
+            bollinger_multiplier: 2.0,
+            adx: ADXConfig::default(),
+            atr_period: 14,
+        }
+    }
+}
+
+/// Readings of every suite indicator for a single candle
+///
+/// Each field is `None` while the corresponding indicator is warming up.
+#[derive(Debug, Clone, Serialize)]
+pub struct SuiteSnapshot {
+    /// Timestamp of the input candle, if it carried one
+    pub timestamp: Option<i64>,
+    /// Relative Strength Index
+    pub rsi: Option<RSIResult>,
+    /// MACD line, signal line, and histogram
+    pub macd: Option<MACDResult>,
+    /// Bollinger Bands
+    pub bollinger: Option<BBResult>,
+    /// Average Directional Index (also `None` if ADX rejected the candle)
+    pub adx: Option<ADXOutput>,
+    /// Average True Range
+    pub atr: Option<f64>,
+}
+
+/// Error types for building an indicator suite
+#[derive(Debug, PartialEq)]
+pub enum SuiteError {
+    /// An RSI, MACD, or ATR period is zero
+    InvalidPeriod,
+    /// The Bollinger Bands settings were rejected
+    Bollinger(BollingerError),
+    /// The ADX configuration was rejected
+    Adx(ADXError),
+}
+
+impl From<BollingerError> for SuiteError {
+    fn from(error: BollingerError) -> Self {
+        SuiteError::Bollinger(error)
+    }
+}
+
+impl From<ADXError> for SuiteError {
+    fn from(error: ADXError) -> Self {
+        SuiteError::Adx(error)
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use crate::testutil::{main::ohlcv_series, types::OhlcvParams};
+
+    #[test]
+    fn test_same_seed_is_deterministic() {
+        let params = OhlcvParams {
+            drift: 0.001,
+            volatility: 0.02,
+            ..OhlcvParams::default()
+        };
+        let first = ohlcv_series(7, 500, params);
+        let second = ohlcv_series(7, 500, params);
+        assert_eq!(first, second);
+
+        // A different seed gives a different path
+        assert_ne!(first, ohlcv_series(8, 500, params));
+        // A shorter series is a prefix of a longer one
+        assert_eq!(ohlcv_series(7, 100, params), first[..100]);
+    }
+
+    #[test]
+    fn test_bars_are_well_formed() {
+        let candles = ohlcv_series(1, 1000, OhlcvParams::default());
+        assert_eq!(candles[0].open, 100.0);
+
+        for pair in candles.windows(2) {
+            assert_eq!(pair[1].open, pair[0].close);
+        }
+        for candle in &candles {
+            assert!(candle.high >= candle.open.max(candle.close));
+            assert!(candle.low <= candle.open.min(candle.close));
+            assert!(candle.low > 0.0);
+            assert!((500.0..1500.0).contains(&candle.volume));
+        }
+
+        assert!(ohlcv_series(1, 0, OhlcvParams::default()).is_empty());
+    }
+}
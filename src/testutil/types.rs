@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Parameters of the geometric Brownian motion behind [`ohlcv_series`](super::main::ohlcv_series)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OhlcvParams {
+    /// Open of the first bar (default: 100.0, must be positive)
+    pub start_price: f64,
+    /// Expected log return per bar (default: 0.0)
+    pub drift: f64,
+    /// Standard deviation of the log return per bar (default: 0.01, should be well below 1)
+    pub volatility: f64,
+    /// Average volume per bar (default: 1000.0)
+    pub base_volume: f64,
+}
+
+impl Default for OhlcvParams {
+    fn default() -> Self {
+        Self {
+            start_price: 100.0,
+            drift: 0.0,
+            volatility: 0.01,
+            base_volume: 1000.0,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::types::Candle;
+    use crate::transform::bar_pattern::{
+        main::BarPattern,
+        types::{BarPatternError, BarPatternInput, BarType},
+    };
+
+    fn bar(high: f64, low: f64, close: f64) -> BarPatternInput {
+        BarPatternInput { high, low, close }
+    }
+
+    #[test]
+    fn test_bar_types_on_constructed_sequence() {
+        let mut pattern = BarPattern::new();
+        let bars = [
+            bar(110.0, 100.0, 105.0), // First bar
+            bar(108.0, 102.0, 104.0), // Inside
+            bar(112.0, 98.0, 111.0),  // Outside
+            bar(115.0, 99.0, 114.0),  // Higher high, higher low: Up
+            bar(113.0, 95.0, 96.0),   // Lower high, lower low: Down
+            bar(113.0, 97.0, 100.0),  // Equal high, higher low, higher close: Up
+            bar(113.0, 98.0, 99.0),   // Equal high, higher low, lower close: Down
+        ];
+
+        let types = pattern.update_batch(&bars).unwrap();
+        assert_eq!(
+            types,
+            vec![
+                None,
+                Some(BarType::Inside),
+                Some(BarType::Outside),
+                Some(BarType::Up),
+                Some(BarType::Down),
+                Some(BarType::Up),
+                Some(BarType::Down),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bar_pattern_from_candles_and_reset() {
+        let mut pattern = BarPattern::new();
+        let first = Candle::new(100.0, 110.0, 100.0, 105.0, 1000.0);
+        let inside = Candle::new(105.0, 109.0, 101.0, 103.0, 800.0);
+
+        assert_eq!(pattern.update(first.into()).unwrap(), None);
+        assert_eq!(
+            pattern.update(inside.into()).unwrap(),
+            Some(BarType::Inside)
+        );
+
+        pattern.reset();
+        assert_eq!(pattern.get_state().previous, None);
+        assert_eq!(pattern.update(inside.into()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_bar_pattern_rejects_invalid_bars() {
+        let mut pattern = BarPattern::new();
+        assert_eq!(
+            pattern.update(bar(f64::NAN, 1.0, 1.0)),
+            Err(BarPatternError::InvalidPrice)
+        );
+        assert_eq!(
+            pattern.update(bar(1.0, 2.0, 1.5)),
+            Err(BarPatternError::InvalidHLC)
+        );
+        // Rejected bars do not become the reference bar
+        assert_eq!(pattern.get_state().previous, None);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::transform::bar_pattern::types::{
+    BarPatternError, BarPatternInput, BarPatternState, BarType,
+};
+
+/// Inside/outside bar detector
+///
+/// Classifies each bar against the previous one:
+/// - Inside: High < previous high and Low > previous low
+/// - Outside: High > previous high and Low < previous low
+/// - Up: a higher high without a lower low
+/// - Down: a lower low without a higher high
+///
+/// Bars that exceed neither extreme but are not strictly inside (equal highs or
+/// lows) are classified as Up or Down by comparing the close to the previous close.
+/// The first bar has nothing to compare against and yields `None`.
+#[derive(Default)]
+pub struct BarPattern {
+    state: BarPatternState,
+}
+
+impl BarPattern {
+    /// Create a new bar pattern detector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one bar and return its classification (None for the first bar)
+    pub fn update(&mut self, input: BarPatternInput) -> Result<Option<BarType>, BarPatternError> {
+        self.validate_input(&input)?;
+
+        let bar_type = self
+            .state
+            .previous
+            .map(|previous| Self::classify(&previous, &input));
+        self.state.previous = Some(input);
+
+        Ok(bar_type)
+    }
+
+    /// Feed a batch of bars and return the classification of each
+    pub fn update_batch(
+        &mut self,
+        inputs: &[BarPatternInput],
+    ) -> Result<Vec<Option<BarType>>, BarPatternError> {
+        inputs.iter().map(|input| self.update(*input)).collect()
+    }
+
+    /// Reset the detector state
+    pub fn reset(&mut self) {
+        self.state = BarPatternState::default();
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &BarPatternState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: BarPatternState) {
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn validate_input(&self, input: &BarPatternInput) -> Result<(), BarPatternError> {
+        if !input.high.is_finite() || !input.low.is_finite() || !input.close.is_finite() {
+            return Err(BarPatternError::InvalidPrice);
+        }
+
+        if input.high < input.low || input.close < input.low || input.close > input.high {
+            return Err(BarPatternError::InvalidHLC);
+        }
+
+        Ok(())
+    }
+
+    fn classify(previous: &BarPatternInput, current: &BarPatternInput) -> BarType {
+        let higher_high = current.high > previous.high;
+        let lower_low = current.low < previous.low;
+
+        match (higher_high, lower_low) {
+            (true, true) => BarType::Outside,
+            (true, false) => BarType::Up,
+            (false, true) => BarType::Down,
+            _ if current.high < previous.high && current.low > previous.low => BarType::Inside,
+            _ if current.close >= previous.close => BarType::Up,
+            _ => BarType::Down,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::types::Candle;
+
+/// Input data for bar pattern detection
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BarPatternInput {
+    /// High price
+    pub high: f64,
+    /// Low price
+    pub low: f64,
+    /// Close price
+    pub close: f64,
+}
+
+impl From<Candle> for BarPatternInput {
+    fn from(candle: Candle) -> Self {
+        Self {
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+        }
+    }
+}
+
+/// Classification of a bar relative to the previous bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BarType {
+    /// High below the previous high and low above the previous low
+    Inside,
+    /// High above the previous high and low below the previous low
+    Outside,
+    /// Directional bar extending upwards (or closing higher when neither extreme is exceeded)
+    Up,
+    /// Directional bar extending downwards (or closing lower when neither extreme is exceeded)
+    Down,
+}
+
+/// Bar pattern detector state
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BarPatternState {
+    /// Previous bar, once one has been seen
+    pub previous: Option<BarPatternInput>,
+}
+
+/// Error types for bar pattern detection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BarPatternError {
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid HLC relationship (e.g., high < low)
+    InvalidHLC,
+}
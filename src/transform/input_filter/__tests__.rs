@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use crate::transform::input_filter::{
+        main::{Filtered, InputFilter},
+        types::{InputFilterError, InputFilterKind},
+    };
+    use crate::v1::rsi::main::RSI;
+    use crate::v1::sma::main::SimpleMovingAverage;
+
+    #[test]
+    fn test_median3_removes_single_spike() {
+        let mut filter = InputFilter::median3();
+        let filtered: Vec<f64> = [10.0, 10.5, 11.0, 40.0, 11.5, 12.0]
+            .iter()
+            .map(|&v| filter.apply(v))
+            .collect();
+        assert_eq!(filtered, vec![10.0, 10.5, 10.5, 11.0, 11.5, 12.0]);
+    }
+
+    #[test]
+    fn test_spike_attenuated_before_indicator() {
+        let prices = [100.0, 100.5, 101.0, 101.5, 102.0, 150.0, 102.5, 103.0];
+
+        let mut raw = SimpleMovingAverage::new(3).unwrap();
+        let mut filtered =
+            Filtered::new(SimpleMovingAverage::new(3).unwrap(), InputFilter::median3());
+        let mut raw_max: f64 = 0.0;
+        let mut filtered_max: f64 = 0.0;
+        for price in prices {
+            raw.add_value(price);
+            if let Some(result) = raw.calculate() {
+                raw_max = raw_max.max(result.value);
+            }
+            if let Some(result) = filtered.update(price, |sma, value| {
+                sma.add_value(value);
+                sma.calculate()
+            }) {
+                filtered_max = filtered_max.max(result.value);
+            }
+        }
+
+        assert!(raw_max > 115.0);
+        assert!(filtered_max < 103.0);
+
+        // Same wrapper works for an indicator with a different update signature
+        let mut rsi = Filtered::new(RSI::new(3, None, None), InputFilter::median3());
+        let last = prices
+            .iter()
+            .filter_map(|&p| rsi.update(p, |rsi, value| rsi.calculate(value)))
+            .last();
+        assert!(last.is_some());
+    }
+
+    #[test]
+    fn test_ema_damps_spike() {
+        let mut filter = InputFilter::ema(4).unwrap();
+        assert_eq!(filter.apply(10.0), 10.0);
+        // alpha = 0.4
+        assert!((filter.apply(20.0) - 14.0).abs() < 1e-12);
+
+        // Non-finite values pass through without touching the history
+        assert!(filter.apply(f64::NAN).is_nan());
+        assert!((filter.apply(14.0) - 14.0).abs() < 1e-12);
+
+        assert_eq!(
+            InputFilter::ema(0).err(),
+            Some(InputFilterError::InvalidPeriod)
+        );
+        assert_eq!(filter.kind(), InputFilterKind::Ema { period: 4 });
+    }
+}
@@ -0,0 +1,154 @@
+use crate::transform::input_filter::types::{InputFilterError, InputFilterKind, InputFilterState};
+
+/// Pre-filter for noisy inputs
+///
+/// Smooths raw values (e.g. tick prices) before they are handed to an
+/// indicator. This is separate from any smoothing the indicator does itself:
+/// the indicator simply sees the filtered series.
+///
+/// Filters:
+/// - Median3: median of the last three values. A lone spike is discarded
+///   outright; until three values have arrived, values pass through unchanged.
+/// - Ema: exponential moving average seeded with the first value. Spikes are
+///   damped rather than removed, at the cost of some lag.
+///
+/// Non-finite values pass through unchanged and are not added to the filter
+/// history, so the indicator's own gap handling still applies to them.
+#[derive(Debug, Clone)]
+pub struct InputFilter {
+    state: InputFilterState,
+}
+
+impl InputFilter {
+    /// Create a new input filter
+    pub fn new(kind: InputFilterKind) -> Result<Self, InputFilterError> {
+        if let InputFilterKind::Ema { period: 0 } = kind {
+            return Err(InputFilterError::InvalidPeriod);
+        }
+
+        Ok(Self {
+            state: InputFilterState::new(kind),
+        })
+    }
+
+    /// Create a median-of-3 filter
+    pub fn median3() -> Self {
+        Self {
+            state: InputFilterState::new(InputFilterKind::Median3),
+        }
+    }
+
+    /// Create an EMA filter over `period` values
+    pub fn ema(period: usize) -> Result<Self, InputFilterError> {
+        Self::new(InputFilterKind::Ema { period })
+    }
+
+    /// Filter the next raw value
+    pub fn apply(&mut self, value: f64) -> f64 {
+        if !value.is_finite() {
+            return value;
+        }
+
+        match self.state.kind {
+            InputFilterKind::Median3 => {
+                if self.state.recent.len() == 3 {
+                    self.state.recent.pop_front();
+                }
+                self.state.recent.push_back(value);
+
+                if self.state.recent.len() < 3 {
+                    return value;
+                }
+                let (a, b, c) = (
+                    self.state.recent[0],
+                    self.state.recent[1],
+                    self.state.recent[2],
+                );
+                a.max(b).min(a.min(b).max(c))
+            }
+            InputFilterKind::Ema { period } => {
+                let alpha = 2.0 / (period as f64 + 1.0);
+                let ema = self
+                    .state
+                    .ema
+                    .map_or(value, |prev| prev + alpha * (value - prev));
+                self.state.ema = Some(ema);
+                ema
+            }
+        }
+    }
+
+    /// Filter in use
+    pub fn kind(&self) -> InputFilterKind {
+        self.state.kind
+    }
+
+    /// Reset the filter history
+    pub fn reset(&mut self) {
+        self.state = InputFilterState::new(self.state.kind);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &InputFilterState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: InputFilterState) {
+        self.state = state;
+    }
+}
+
+/// An indicator with its own input filter in front of it
+///
+/// Indicators do not share an update signature, so `update` takes a closure
+/// that feeds the filtered value to the wrapped calculator:
+///
+/// ```rust
+/// use indexes_rs::transform::input_filter::main::{Filtered, InputFilter};
+/// use indexes_rs::v1::sma::main::SimpleMovingAverage;
+///
+/// let mut sma = Filtered::new(SimpleMovingAverage::new(3).unwrap(), InputFilter::median3());
+/// for price in [10.0, 10.0, 10.0, 50.0, 10.0] {
+///     sma.update(price, |sma, value| sma.add_value(value));
+/// }
+/// assert_eq!(sma.inner_mut().calculate().unwrap().value, 10.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Filtered<I> {
+    indicator: I,
+    filter: InputFilter,
+}
+
+impl<I> Filtered<I> {
+    /// Wrap an indicator with the given filter
+    pub fn new(indicator: I, filter: InputFilter) -> Self {
+        Self { indicator, filter }
+    }
+
+    /// Filter `value` and pass it to the indicator through `update`
+    pub fn update<R>(&mut self, value: f64, update: impl FnOnce(&mut I, f64) -> R) -> R {
+        let filtered = self.filter.apply(value);
+        update(&mut self.indicator, filtered)
+    }
+
+    /// The wrapped indicator
+    pub fn inner(&self) -> &I {
+        &self.indicator
+    }
+
+    /// The wrapped indicator, mutably
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.indicator
+    }
+
+    /// The filter in front of the indicator
+    pub fn filter(&self) -> &InputFilter {
+        &self.filter
+    }
+
+    /// Unwrap the indicator, dropping the filter
+    pub fn into_inner(self) -> I {
+        self.indicator
+    }
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Smoothing applied to raw inputs before they reach an indicator
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum InputFilterKind {
+    /// Median of the last three values; removes single-tick spikes entirely
+    #[default]
+    Median3,
+    /// Exponential moving average over `period` values (alpha = 2 / (period + 1))
+    Ema { period: usize },
+}
+
+/// Input filter state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputFilterState {
+    /// Filter in use
+    pub kind: InputFilterKind,
+    /// Last three raw values (Median3)
+    pub recent: VecDeque<f64>,
+    /// Running average (Ema)
+    pub ema: Option<f64>,
+}
+
+impl InputFilterState {
+    pub fn new(kind: InputFilterKind) -> Self {
+        Self {
+            kind,
+            recent: VecDeque::with_capacity(3),
+            ema: None,
+        }
+    }
+}
+
+/// Error types for input filtering
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InputFilterError {
+    /// Invalid EMA period (must be > 0)
+    InvalidPeriod,
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::types::Candle;
+    use crate::transform::price_source::{
+        main::{average_price, median_price, typical_price, weighted_close},
+        types::PriceSource,
+    };
+
+    fn candle() -> Candle {
+        Candle::new(10.0, 16.0, 7.0, 13.0, 500.0)
+    }
+
+    #[test]
+    fn test_formulas() {
+        assert_eq!(median_price(16.0, 7.0), 11.5);
+        assert_eq!(typical_price(16.0, 7.0, 13.0), 12.0);
+        assert_eq!(weighted_close(16.0, 7.0, 13.0), 12.25);
+        assert_eq!(average_price(10.0, 16.0, 7.0, 13.0), 11.5);
+    }
+
+    #[test]
+    fn test_apply_to_candle() {
+        let candle = candle();
+        assert_eq!(PriceSource::Open.apply(&candle), 10.0);
+        assert_eq!(PriceSource::High.apply(&candle), 16.0);
+        assert_eq!(PriceSource::Low.apply(&candle), 7.0);
+        assert_eq!(PriceSource::Close.apply(&candle), 13.0);
+        assert_eq!(PriceSource::Median.apply(&candle), 11.5);
+        assert_eq!(PriceSource::Typical.apply(&candle), 12.0);
+        assert_eq!(PriceSource::WeightedClose.apply(&candle), 12.25);
+        assert_eq!(PriceSource::Average.apply(&candle), 11.5);
+    }
+
+    #[test]
+    fn test_default_is_close() {
+        assert_eq!(PriceSource::default(), PriceSource::Close);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::common::types::Candle;
+use crate::transform::price_source::types::PriceSource;
+
+/// Median price: (High + Low) / 2
+pub fn median_price(high: f64, low: f64) -> f64 {
+    (high + low) / 2.0
+}
+
+/// Typical price: (High + Low + Close) / 3
+pub fn typical_price(high: f64, low: f64, close: f64) -> f64 {
+    (high + low + close) / 3.0
+}
+
+/// Weighted close: (High + Low + 2 × Close) / 4
+pub fn weighted_close(high: f64, low: f64, close: f64) -> f64 {
+    (high + low + 2.0 * close) / 4.0
+}
+
+/// Average price: (Open + High + Low + Close) / 4
+pub fn average_price(open: f64, high: f64, low: f64, close: f64) -> f64 {
+    (open + high + low + close) / 4.0
+}
+
+impl PriceSource {
+    /// Derive this price from a candle
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use indexes_rs::common::types::Candle;
+    /// use indexes_rs::transform::price_source::types::PriceSource;
+    ///
+    /// let candle = Candle::new(10.0, 14.0, 8.0, 12.0, 1000.0);
+    /// assert_eq!(PriceSource::Median.apply(&candle), 11.0);
+    /// assert_eq!(PriceSource::WeightedClose.apply(&candle), 11.5);
+    /// ```
+    pub fn apply(&self, candle: &Candle) -> f64 {
+        match self {
+            PriceSource::Open => candle.open,
+            PriceSource::High => candle.high,
+            PriceSource::Low => candle.low,
+            PriceSource::Close => candle.close,
+            PriceSource::Median => median_price(candle.high, candle.low),
+            PriceSource::Typical => typical_price(candle.high, candle.low, candle.close),
+            PriceSource::WeightedClose => weighted_close(candle.high, candle.low, candle.close),
+            PriceSource::Average => {
+                average_price(candle.open, candle.high, candle.low, candle.close)
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Which price of a candle an indicator consumes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PriceSource {
+    /// Open price
+    Open,
+    /// High price
+    High,
+    /// Low price
+    Low,
+    /// Close price (default)
+    #[default]
+    Close,
+    /// Median price: (High + Low) / 2
+    Median,
+    /// Typical price: (High + Low + Close) / 3
+    Typical,
+    /// Weighted close: (High + Low + 2 × Close) / 4
+    WeightedClose,
+    /// Average price: (Open + High + Low + Close) / 4
+    Average,
+}
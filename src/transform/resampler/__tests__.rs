@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::types::Candle;
+    use crate::transform::resampler::{main::Resampler, types::ResamplerError};
+
+    const HOUR: i64 = 3_600_000;
+
+    fn hourly_candles() -> Vec<Candle> {
+        vec![
+            Candle::new(100.0, 105.0, 99.0, 104.0, 1000.0),
+            Candle::new(104.0, 108.0, 103.0, 107.0, 1500.0),
+            Candle::new(107.0, 107.5, 96.0, 98.0, 2000.0),
+            Candle::new(98.0, 101.0, 97.0, 100.5, 500.0),
+        ]
+    }
+
+    #[test]
+    fn test_resampler_aggregates_four_hourly_candles() {
+        let mut resampler = Resampler::new(4).unwrap();
+        let candles = hourly_candles();
+
+        // Nothing is emitted until the bucket is complete
+        for candle in &candles[..3] {
+            assert_eq!(resampler.update(*candle).unwrap(), None);
+        }
+
+        let aggregated = resampler.update(candles[3]).unwrap().unwrap();
+        assert_eq!(aggregated.open, 100.0); // First open
+        assert_eq!(aggregated.high, 108.0); // Max high
+        assert_eq!(aggregated.low, 96.0); // Min low
+        assert_eq!(aggregated.close, 100.5); // Last close
+        assert_eq!(aggregated.volume, 5000.0); // Sum of volumes
+    }
+
+    #[test]
+    fn test_resampler_batch_and_flush() {
+        let mut resampler = Resampler::new(4).unwrap();
+        let mut candles = hourly_candles();
+        candles.extend(hourly_candles());
+        candles.push(Candle::new(100.0, 102.0, 99.5, 101.0, 300.0));
+
+        let aggregated = resampler.update_batch(&candles).unwrap();
+        assert_eq!(aggregated.len(), 2);
+        assert_eq!(aggregated[0], aggregated[1]);
+
+        // The trailing partial bucket is only emitted on flush
+        let partial = resampler.flush().unwrap();
+        assert_eq!(partial.open, 100.0);
+        assert_eq!(partial.volume, 300.0);
+        assert_eq!(resampler.flush(), None);
+    }
+
+    #[test]
+    fn test_resampler_timestamp_boundaries() {
+        let mut resampler = Resampler::with_interval(4 * HOUR).unwrap();
+
+        // Start mid-bucket: the first bucket only holds two candles
+        let candles: Vec<Candle> = hourly_candles()
+            .into_iter()
+            .chain(hourly_candles())
+            .enumerate()
+            .map(|(i, candle)| candle.with_timestamp((i as i64 + 2) * HOUR))
+            .collect();
+
+        let aggregated = resampler.update_batch(&candles).unwrap();
+        assert_eq!(aggregated.len(), 2);
+
+        // First bucket [0h, 4h) holds the first two candles
+        assert_eq!(aggregated[0].timestamp, Some(0));
+        assert_eq!(aggregated[0].open, 100.0);
+        assert_eq!(aggregated[0].close, 107.0);
+        assert_eq!(aggregated[0].volume, 2500.0);
+
+        // Second bucket [4h, 8h) holds the next four
+        assert_eq!(aggregated[1].timestamp, Some(4 * HOUR));
+        assert_eq!(aggregated[1].open, 107.0);
+        assert_eq!(aggregated[1].high, 108.0);
+        assert_eq!(aggregated[1].low, 96.0);
+        assert_eq!(aggregated[1].close, 107.0);
+        assert_eq!(aggregated[1].volume, 5000.0);
+
+        // The last bucket is still open
+        let open_bucket = resampler.flush().unwrap();
+        assert_eq!(open_bucket.timestamp, Some(8 * HOUR));
+        assert_eq!(open_bucket.volume, 2500.0);
+    }
+
+    #[test]
+    fn test_resampler_error_handling() {
+        assert!(matches!(
+            Resampler::new(0),
+            Err(ResamplerError::InvalidFactor)
+        ));
+        assert!(matches!(
+            Resampler::with_interval(0),
+            Err(ResamplerError::InvalidInterval)
+        ));
+
+        let mut resampler = Resampler::new(2).unwrap();
+        let inverted = Candle::new(100.0, 99.0, 101.0, 100.0, 10.0);
+        assert!(matches!(
+            resampler.update(inverted),
+            Err(ResamplerError::InvalidOHLC)
+        ));
+
+        let nan = Candle::new(f64::NAN, 101.0, 99.0, 100.0, 10.0);
+        assert!(matches!(
+            resampler.update(nan),
+            Err(ResamplerError::InvalidPrice)
+        ));
+
+        let nan_volume = Candle::new(100.0, 101.0, 99.0, 100.0, f64::NAN);
+        assert!(matches!(
+            resampler.update(nan_volume),
+            Err(ResamplerError::InvalidVolume)
+        ));
+        assert!(resampler.flush().is_none());
+    }
+}
@@ -0,0 +1,194 @@
+use crate::common::types::Candle;
+use crate::transform::resampler::types::{ResamplerConfig, ResamplerError, ResamplerState};
+
+/// Multi-timeframe candle resampler
+///
+/// Aggregates consecutive input candles into a single higher-timeframe candle,
+/// e.g. four 1h candles into one 4h candle, so any indicator can be driven on
+/// the higher timeframe.
+///
+/// Aggregation rules:
+/// - Open = first open
+/// - High = max high
+/// - Low = min low
+/// - Close = last close
+/// - Volume = sum of volumes
+///
+/// By default a candle is emitted every `factor` inputs. When an `interval` is
+/// configured and the input carries a timestamp, candles are grouped by
+/// `timestamp / interval` instead and a bucket is emitted as soon as a candle
+/// from the next bucket arrives (use `flush` to emit the final open bucket).
+pub struct Resampler {
+    state: ResamplerState,
+}
+
+impl Resampler {
+    /// Create a resampler emitting one candle every `factor` inputs
+    pub fn new(factor: usize) -> Result<Self, ResamplerError> {
+        if factor == 0 {
+            return Err(ResamplerError::InvalidFactor);
+        }
+
+        let config = ResamplerConfig {
+            factor,
+            interval: None,
+        };
+        Ok(Self::with_config(config))
+    }
+
+    /// Create a resampler grouping candles by timestamp buckets of `interval` length
+    ///
+    /// Candles without a timestamp fall back to count-based grouping using the
+    /// default factor.
+    pub fn with_interval(interval: i64) -> Result<Self, ResamplerError> {
+        if interval <= 0 {
+            return Err(ResamplerError::InvalidInterval);
+        }
+
+        let config = ResamplerConfig {
+            interval: Some(interval),
+            ..Default::default()
+        };
+        Ok(Self::with_config(config))
+    }
+
+    /// Create a resampler with custom configuration
+    pub fn with_config(config: ResamplerConfig) -> Self {
+        Self {
+            state: ResamplerState::new(config),
+        }
+    }
+
+    /// Feed one candle and return an aggregated candle when a bucket completes
+    pub fn update(&mut self, candle: Candle) -> Result<Option<Candle>, ResamplerError> {
+        self.validate_input(&candle)?;
+        self.validate_config()?;
+
+        match (self.state.config.interval, candle.timestamp) {
+            (Some(interval), Some(timestamp)) => {
+                Ok(self.update_by_time(candle, timestamp, interval))
+            }
+            _ => Ok(self.update_by_count(candle)),
+        }
+    }
+
+    /// Feed a batch of candles and return every completed aggregate
+    pub fn update_batch(&mut self, candles: &[Candle]) -> Result<Vec<Candle>, ResamplerError> {
+        let mut results = Vec::with_capacity(candles.len() / self.state.config.factor.max(1) + 1);
+        for candle in candles {
+            if let Some(aggregated) = self.update(*candle)? {
+                results.push(aggregated);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Emit the partially filled bucket (if any) and start a fresh one
+    pub fn flush(&mut self) -> Option<Candle> {
+        self.state.count = 0;
+        self.state.bucket = None;
+        self.state.pending.take()
+    }
+
+    /// Reset the resampler state
+    pub fn reset(&mut self) {
+        self.state = ResamplerState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &ResamplerState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: ResamplerState) {
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn validate_input(&self, candle: &Candle) -> Result<(), ResamplerError> {
+        if !candle.open.is_finite()
+            || !candle.high.is_finite()
+            || !candle.low.is_finite()
+            || !candle.close.is_finite()
+        {
+            return Err(ResamplerError::InvalidPrice);
+        }
+
+        if candle.high < candle.low {
+            return Err(ResamplerError::InvalidOHLC);
+        }
+
+        if !candle.volume.is_finite() {
+            return Err(ResamplerError::InvalidVolume);
+        }
+
+        if candle.volume < 0.0 {
+            return Err(ResamplerError::NegativeVolume);
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), ResamplerError> {
+        if self.state.config.factor == 0 {
+            return Err(ResamplerError::InvalidFactor);
+        }
+
+        if matches!(self.state.config.interval, Some(interval) if interval <= 0) {
+            return Err(ResamplerError::InvalidInterval);
+        }
+
+        Ok(())
+    }
+
+    fn update_by_count(&mut self, candle: Candle) -> Option<Candle> {
+        self.merge(candle);
+
+        if self.state.count >= self.state.config.factor {
+            self.flush()
+        } else {
+            None
+        }
+    }
+
+    fn update_by_time(&mut self, candle: Candle, timestamp: i64, interval: i64) -> Option<Candle> {
+        let bucket = timestamp.div_euclid(interval);
+
+        // A candle from a new bucket closes the previous one
+        let completed = match self.state.bucket {
+            Some(current) if current != bucket => self.flush(),
+            _ => None,
+        };
+
+        self.merge(candle);
+        self.state.bucket = Some(bucket);
+        if let Some(pending) = self.state.pending.as_mut() {
+            pending.timestamp = Some(bucket * interval);
+        }
+
+        completed
+    }
+
+    fn merge(&mut self, candle: Candle) {
+        self.state.pending = Some(match self.state.pending {
+            Some(pending) => Candle {
+                timestamp: pending.timestamp,
+                open: pending.open,
+                high: pending.high.max(candle.high),
+                low: pending.low.min(candle.low),
+                close: candle.close,
+                volume: pending.volume + candle.volume,
+            },
+            None => candle,
+        });
+        self.state.count += 1;
+    }
+}
+
+impl Default for Resampler {
+    fn default() -> Self {
+        Self::with_config(ResamplerConfig::default())
+    }
+}
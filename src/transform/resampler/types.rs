@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::types::Candle;
+
+/// Configuration for candle resampling
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResamplerConfig {
+    /// Number of input candles aggregated into one output candle (default: 4)
+    pub factor: usize,
+    /// Optional bucket length in timestamp units. When set, candles carrying a
+    /// timestamp are grouped by `timestamp / interval` instead of by count.
+    pub interval: Option<i64>,
+}
+
+impl Default for ResamplerConfig {
+    fn default() -> Self {
+        Self {
+            factor: 4,
+            interval: None,
+        }
+    }
+}
+
+/// Resampler state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResamplerState {
+    /// Configuration
+    pub config: ResamplerConfig,
+    /// Aggregate of the candles seen in the current bucket
+    pub pending: Option<Candle>,
+    /// Number of candles merged into `pending`
+    pub count: usize,
+    /// Bucket index of `pending` when grouping by timestamp
+    pub bucket: Option<i64>,
+}
+
+impl ResamplerState {
+    pub fn new(config: ResamplerConfig) -> Self {
+        Self {
+            config,
+            pending: None,
+            count: 0,
+            bucket: None,
+        }
+    }
+}
+
+/// Error types for resampling
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ResamplerError {
+    /// Invalid aggregation factor (must be > 0)
+    InvalidFactor,
+    /// Invalid timestamp interval (must be > 0)
+    InvalidInterval,
+    /// Invalid OHLC relationship (e.g., high < low)
+    InvalidOHLC,
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid volume (NaN or infinite)
+    InvalidVolume,
+    /// Negative volume
+    NegativeVolume,
+}
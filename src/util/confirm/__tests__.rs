@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use crate::util::confirm::main::Confirm;
+    use crate::v1::types::TradingSignal::{self, Buy, Hold, Sell};
+
+    fn run(confirm: &mut Confirm, signals: &[TradingSignal]) -> Vec<TradingSignal> {
+        signals
+            .iter()
+            .map(|signal| confirm.update(signal.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_two_bar_flicker_is_suppressed() {
+        let mut confirm = Confirm::new(3);
+        let signals = [Buy, Buy, Hold, Sell, Sell, Buy];
+        assert_eq!(run(&mut confirm, &signals), vec![Hold; 6]);
+        assert_eq!(confirm.run_length(), 1);
+    }
+
+    #[test]
+    fn test_three_bar_run_confirms() {
+        let mut confirm = Confirm::new(3);
+        let signals = [Buy, Buy, Buy, Buy, Sell];
+        assert_eq!(
+            run(&mut confirm, &signals),
+            vec![Hold, Hold, Buy, Buy, Hold]
+        );
+    }
+
+    #[test]
+    fn test_disagreement_resets_the_run() {
+        let mut confirm = Confirm::new(2);
+        let signals = [Sell, Buy, Sell, Sell, Hold, Sell];
+        assert_eq!(
+            run(&mut confirm, &signals),
+            vec![Hold, Hold, Hold, Sell, Hold, Hold]
+        );
+
+        confirm.reset();
+        assert_eq!(confirm.run_length(), 0);
+        assert_eq!(confirm.bars(), 2);
+    }
+
+    #[test]
+    fn test_single_bar_passes_everything() {
+        let mut confirm = Confirm::new(1);
+        assert_eq!(run(&mut confirm, &[Buy, Sell, Hold]), vec![Buy, Sell, Hold]);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::v1::types::TradingSignal;
+
+/// Passes a trading signal through only after it has held for N consecutive bars
+///
+/// Feed one signal per bar. A `Buy` or `Sell` starts (or extends) a run; the
+/// signal is emitted on every bar once the run length reaches `bars`, and
+/// replaced by `Hold` before that. A different signal or a `Hold` ends the run,
+/// so a flicker shorter than `bars` never gets through. Pair with
+/// [`SignalDebouncer`](crate::util::signal_debouncer::main::SignalDebouncer) to
+/// emit a confirmed run only once.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::Confirm;
+/// use indexes_rs::v1::types::TradingSignal;
+///
+/// let mut confirm = Confirm::new(2);
+/// assert_eq!(confirm.update(TradingSignal::Buy), TradingSignal::Hold);
+/// assert_eq!(confirm.update(TradingSignal::Buy), TradingSignal::Buy);
+/// // Disagreement restarts the count
+/// assert_eq!(confirm.update(TradingSignal::Sell), TradingSignal::Hold);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Confirm {
+    /// Consecutive agreeing bars required before a signal is emitted
+    bars: usize,
+    /// Signal of the current run and its length
+    run: Option<(TradingSignal, usize)>,
+}
+
+impl Confirm {
+    /// Create a filter requiring `bars` consecutive agreeing bars (0 and 1 pass every signal)
+    pub fn new(bars: usize) -> Self {
+        Self { bars, run: None }
+    }
+
+    /// Process the signal for the next bar and return the confirmed signal
+    pub fn update(&mut self, signal: TradingSignal) -> TradingSignal {
+        if signal == TradingSignal::Hold {
+            self.run = None;
+            return signal;
+        }
+
+        let length = match self.run.as_mut() {
+            Some((current, length)) if *current == signal => {
+                *length += 1;
+                *length
+            }
+            _ => {
+                self.run = Some((signal.clone(), 1));
+                1
+            }
+        };
+
+        if length >= self.bars {
+            signal
+        } else {
+            TradingSignal::Hold
+        }
+    }
+
+    /// Required run length in bars
+    pub fn bars(&self) -> usize {
+        self.bars
+    }
+
+    /// Length of the current run of agreeing `Buy`/`Sell` bars (0 after a `Hold`)
+    pub fn run_length(&self) -> usize {
+        self.run.as_ref().map_or(0, |(_, length)| *length)
+    }
+
+    /// Forget the current run
+    pub fn reset(&mut self) {
+        self.run = None;
+    }
+}
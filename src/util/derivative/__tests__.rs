@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use crate::util::derivative::main::{Derivative, DerivativeOutput};
+
+    #[test]
+    fn test_quadratic_has_constant_second_difference() {
+        let mut derivative = Derivative::new();
+        let outputs: Vec<DerivativeOutput> = (0..10)
+            .map(|i| {
+                let x = i as f64;
+                derivative.update(Some(3.0 * x * x - 2.0 * x + 1.0))
+            })
+            .collect();
+
+        assert_eq!(outputs[0], DerivativeOutput::default());
+        assert_eq!(outputs[1].slope, Some(1.0));
+        assert_eq!(outputs[1].acceleration, None);
+        for (i, output) in outputs.iter().enumerate().skip(2) {
+            // Δ(3x² - 2x + 1) = 6x - 5, Δ² = 6
+            assert_eq!(output.slope, Some(6.0 * i as f64 - 5.0));
+            assert_eq!(output.acceleration, Some(6.0));
+        }
+    }
+
+    #[test]
+    fn test_missing_value_breaks_the_chain() {
+        let mut derivative = Derivative::new();
+        derivative.update(Some(1.0));
+        derivative.update(Some(2.0));
+
+        assert_eq!(derivative.update(None), DerivativeOutput::default());
+        assert_eq!(derivative.update(Some(10.0)), DerivativeOutput::default());
+        assert_eq!(
+            derivative.update(Some(f64::NAN)),
+            DerivativeOutput::default()
+        );
+        assert_eq!(derivative.update(Some(3.0)).slope, None);
+        assert_eq!(derivative.update(Some(5.0)).slope, Some(2.0));
+    }
+}
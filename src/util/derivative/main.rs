@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+/// First and second differences of a series at one step
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DerivativeOutput {
+    /// Change from the previous value (`None` until two consecutive values are seen)
+    pub slope: Option<f64>,
+    /// Change in slope from the previous step (`None` until three consecutive values are seen)
+    pub acceleration: Option<f64>,
+}
+
+/// Tracks the rate of change (slope) and its change (acceleration) of a series
+///
+/// Feed one value per bar, e.g. an oscillator reading. Differences are taken
+/// between consecutive bars, so `slope` is the first difference and `acceleration`
+/// the second. A `None` (or non-finite) value, such as a warmup bar, yields no
+/// differences and breaks the chain: the next values start over.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::derivative::main::Derivative;
+///
+/// let mut derivative = Derivative::new();
+/// derivative.update(Some(1.0));
+/// derivative.update(Some(4.0));
+/// let output = derivative.update(Some(9.0));
+/// assert_eq!(output.slope, Some(5.0));
+/// assert_eq!(output.acceleration, Some(2.0));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Derivative {
+    /// Previous value
+    prev_value: Option<f64>,
+    /// Previous first difference
+    prev_slope: Option<f64>,
+}
+
+impl Derivative {
+    /// Create a tracker with no history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process the next value and return its first and second differences
+    pub fn update(&mut self, value: Option<f64>) -> DerivativeOutput {
+        let Some(value) = value.filter(|v| v.is_finite()) else {
+            self.reset();
+            return DerivativeOutput::default();
+        };
+
+        let slope = self.prev_value.map(|prev| value - prev);
+        let acceleration = match (slope, self.prev_slope) {
+            (Some(slope), Some(prev_slope)) => Some(slope - prev_slope),
+            _ => None,
+        };
+
+        self.prev_value = Some(value);
+        self.prev_slope = slope;
+
+        DerivativeOutput {
+            slope,
+            acceleration,
+        }
+    }
+
+    /// Forget all history
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
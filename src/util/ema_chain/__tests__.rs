@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use crate::util::ema_chain::main::EmaChain;
+    use crate::v1::ema::main::ExponentialMovingAverage;
+
+    const PRICES: [f64; 12] = [
+        100.0, 102.0, 101.0, 105.0, 107.0, 104.0, 103.0, 108.0, 110.0, 109.0, 111.0, 115.0,
+    ];
+
+    #[test]
+    fn test_depth_two_matches_manual_ema_of_ema() {
+        let mut chain = EmaChain::new(5, 2);
+        let mut inner = ExponentialMovingAverage::new(5);
+        let mut outer = ExponentialMovingAverage::new(5);
+
+        for price in PRICES {
+            let first = inner.add_value(price).unwrap();
+            let second = outer.add_value(first).unwrap();
+            let value = chain.update(price);
+
+            assert!((value - second).abs() < 1e-12);
+            assert_eq!(chain.value(), Some(value));
+            // The intermediate level is the plain EMA
+            assert!((chain.level(0).unwrap() - first).abs() < 1e-12);
+            assert_eq!(chain.level(1), chain.value());
+        }
+        assert_eq!(chain.level(2), None);
+    }
+
+    #[test]
+    fn test_levels_and_reset() {
+        let mut chain = EmaChain::new(4, 3);
+        assert_eq!(chain.value(), None);
+        assert!(chain.levels().is_empty());
+
+        chain.update(10.0);
+        assert_eq!(chain.levels(), vec![10.0; 3]);
+        for price in PRICES {
+            chain.update(price);
+        }
+        // On a rising series each deeper level lags further behind
+        let levels = chain.levels();
+        assert_eq!(levels.len(), 3);
+        assert!(levels[0] > levels[1] && levels[1] > levels[2]);
+
+        chain.reset();
+        assert_eq!(chain.value(), None);
+        assert_eq!(chain.depth(), 3);
+        assert_eq!(EmaChain::new(4, 0).depth(), 1);
+        assert_eq!(EmaChain::with_periods(&[]).depth(), 1);
+    }
+
+    #[test]
+    fn test_with_periods_smooths_each_level_with_its_own_period() {
+        let mut chain = EmaChain::with_periods(&[6, 3]);
+        let mut inner = ExponentialMovingAverage::new(6);
+        let mut outer = ExponentialMovingAverage::new(3);
+
+        for price in PRICES {
+            let expected = outer.add_value(inner.add_value(price).unwrap()).unwrap();
+            assert!((chain.update(price) - expected).abs() < 1e-12);
+        }
+        assert_eq!(chain.depth(), 2);
+        assert_eq!(chain.alpha(0), Some(inner.alpha()));
+        assert_eq!(chain.alpha(1), Some(outer.alpha()));
+        assert_eq!(chain.alpha(2), None);
+    }
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+use crate::v1::ema::main::ExponentialMovingAverage;
+
+/// A stack of EMAs, each smoothing the one below it
+///
+/// Level 0 is `EMA(x)`, level 1 is `EMA(EMA(x))`, and so on; the final value is
+/// the top level. Each level is an [`ExponentialMovingAverage`] with its own period,
+/// seeded with its first input. [`EmaChain::new`] gives every level the same period,
+/// the shared core of DEMA, TEMA, and TRIX style indicators; [`EmaChain::with_periods`]
+/// builds mixed stacks such as TSI's `EMA(EMA(x, long), short)`.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::EmaChain;
+///
+/// let mut chain = EmaChain::new(3, 2);
+/// assert_eq!(chain.update(10.0), 10.0);
+/// // EMA(x) moves halfway to 20, EMA(EMA(x)) halfway to that
+/// assert_eq!(chain.update(20.0), 12.5);
+/// assert_eq!(chain.levels(), vec![15.0, 12.5]);
+///
+/// // EMA(EMA(x, 3), 1): a period-1 EMA follows its input exactly
+/// let mut mixed = EmaChain::with_periods(&[3, 1]);
+/// mixed.update(10.0);
+/// assert_eq!(mixed.update(20.0), 15.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmaChain {
+    /// One EMA per level, innermost first
+    emas: Vec<ExponentialMovingAverage>,
+}
+
+impl EmaChain {
+    /// Create a chain of `depth` EMAs over `period` bars (a depth of 0 is treated as 1)
+    pub fn new(period: usize, depth: usize) -> Self {
+        Self::with_periods(&vec![period; depth.max(1)])
+    }
+
+    /// Create a chain with one EMA per period, innermost first
+    ///
+    /// An empty list is treated as a single period-1 EMA, which passes values through.
+    pub fn with_periods(periods: &[usize]) -> Self {
+        let periods = if periods.is_empty() { &[1] } else { periods };
+        Self {
+            emas: periods
+                .iter()
+                .map(|&period| ExponentialMovingAverage::new(period))
+                .collect(),
+        }
+    }
+
+    /// Feed the next value through every level and return the top level
+    pub fn update(&mut self, value: f64) -> f64 {
+        self.emas
+            .iter_mut()
+            .fold(value, |input, ema| ema.add_value(input).unwrap_or(input))
+    }
+
+    /// The top level, `None` before the first update
+    pub fn value(&self) -> Option<f64> {
+        self.emas
+            .last()
+            .and_then(ExponentialMovingAverage::get_current_value)
+    }
+
+    /// The value of one level (0 = `EMA(x)`), `None` before the first update or past the depth
+    pub fn level(&self, index: usize) -> Option<f64> {
+        self.emas
+            .get(index)
+            .and_then(ExponentialMovingAverage::get_current_value)
+    }
+
+    /// All levels, innermost first (empty before the first update)
+    pub fn levels(&self) -> Vec<f64> {
+        self.emas
+            .iter()
+            .filter_map(ExponentialMovingAverage::get_current_value)
+            .collect()
+    }
+
+    /// Number of stacked EMAs
+    pub fn depth(&self) -> usize {
+        self.emas.len()
+    }
+
+    /// Smoothing factor of one level, `None` past the depth
+    pub fn alpha(&self, index: usize) -> Option<f64> {
+        self.emas.get(index).map(ExponentialMovingAverage::alpha)
+    }
+
+    /// Forget all values
+    pub fn reset(&mut self) {
+        for ema in &mut self.emas {
+            ema.current_ema = None;
+        }
+    }
+}
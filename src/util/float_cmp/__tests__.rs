@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use crate::util::float_cmp::main::{approx_eq, relative_eq, Tolerance};
+
+    #[test]
+    fn test_approx_eq() {
+        assert!(approx_eq(1.0, 1.0 + 1e-10, 1e-9));
+        assert!(!approx_eq(1.0, 1.0 + 1e-8, 1e-9));
+        assert!(approx_eq(0.0, -0.0, 0.0));
+        assert!(approx_eq(0.0, 1e-12, 1e-9));
+    }
+
+    #[test]
+    fn test_approx_eq_non_finite() {
+        assert!(!approx_eq(f64::NAN, f64::NAN, f64::INFINITY));
+        assert!(!approx_eq(f64::NAN, 1.0, f64::INFINITY));
+        assert!(approx_eq(f64::INFINITY, f64::INFINITY, 0.0));
+        assert!(!approx_eq(f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY));
+        assert!(!approx_eq(f64::INFINITY, f64::MAX, f64::INFINITY));
+    }
+
+    #[test]
+    fn test_relative_eq() {
+        assert!(relative_eq(1e9, 1e9 + 1.0, 1e-8));
+        assert!(!relative_eq(1e9, 1e9 + 100.0, 1e-8));
+        assert!(relative_eq(-2.0, -2.0000001, 1e-6));
+        assert!(!relative_eq(-2.0, 2.0, 0.5));
+    }
+
+    #[test]
+    fn test_relative_eq_edge_cases() {
+        // Only exact zeros compare equal around zero
+        assert!(relative_eq(0.0, -0.0, 1e-6));
+        assert!(!relative_eq(0.0, 1e-300, 1e-6));
+        assert!(!relative_eq(f64::NAN, f64::NAN, 1.0));
+        assert!(relative_eq(f64::NEG_INFINITY, f64::NEG_INFINITY, 0.0));
+        assert!(!relative_eq(f64::INFINITY, 1e308, 1.0));
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let tol = Tolerance::new(1e-6).with_relative(1e-9);
+        assert!(tol.matches(0.0, 5e-7));
+        assert!(tol.matches(1e12, 1e12 + 500.0));
+        assert!(!tol.matches(1.0, 1.001));
+        assert!(!tol.matches(f64::NAN, f64::NAN));
+
+        let default = Tolerance::default();
+        assert_eq!(default.absolute, 1e-9);
+        assert_eq!(default.relative, 0.0);
+        assert!(default.matches(0.1 + 0.2, 0.3));
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether `a` and `b` differ by at most `eps`
+///
+/// NaN is never equal to anything, including NaN. Infinities are equal only to
+/// the same infinity. `eps` is an absolute bound, so it is the right choice for
+/// values near zero.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::approx_eq;
+///
+/// assert!(approx_eq(0.1 + 0.2, 0.3, 1e-12));
+/// assert!(!approx_eq(1.0, 1.1, 1e-3));
+/// assert!(!approx_eq(f64::NAN, f64::NAN, 1.0));
+/// ```
+pub fn approx_eq(a: f64, b: f64, eps: f64) -> bool {
+    if a == b {
+        return true;
+    }
+    if !a.is_finite() || !b.is_finite() {
+        return false;
+    }
+    (a - b).abs() <= eps
+}
+
+/// Whether `a` and `b` differ by at most `rel` times the larger magnitude
+///
+/// Suited to values whose scale is unknown (e.g. OBV or volume sums). Near zero the
+/// bound shrinks towards zero as well, so only exact zeros compare equal there;
+/// use [`approx_eq`] or [`Tolerance`] when values can straddle zero.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::relative_eq;
+///
+/// assert!(relative_eq(1_000_000.0, 1_000_000.5, 1e-6));
+/// assert!(!relative_eq(1.0, 1.5, 1e-6));
+/// ```
+pub fn relative_eq(a: f64, b: f64, rel: f64) -> bool {
+    if a == b {
+        return true;
+    }
+    if !a.is_finite() || !b.is_finite() {
+        return false;
+    }
+    (a - b).abs() <= rel * a.abs().max(b.abs())
+}
+
+/// A comparison tolerance with an absolute and a relative bound
+///
+/// Two values match when they are within either bound, which covers both values
+/// near zero (absolute) and large values (relative).
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::Tolerance;
+///
+/// let tol = Tolerance::new(1e-9).with_relative(1e-9);
+/// assert!(tol.matches(0.0, 1e-12));
+/// assert!(tol.matches(1e12, 1e12 + 1.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Tolerance {
+    /// Absolute bound
+    pub absolute: f64,
+    /// Relative bound, as a fraction of the larger magnitude
+    pub relative: f64,
+}
+
+impl Tolerance {
+    /// Create a tolerance with an absolute bound and no relative bound
+    pub fn new(absolute: f64) -> Self {
+        Self {
+            absolute,
+            relative: 0.0,
+        }
+    }
+
+    /// Add a relative bound
+    pub fn with_relative(mut self, relative: f64) -> Self {
+        self.relative = relative;
+        self
+    }
+
+    /// Whether `a` and `b` are within the absolute or the relative bound
+    pub fn matches(&self, a: f64, b: f64) -> bool {
+        approx_eq(a, b, self.absolute) || relative_eq(a, b, self.relative)
+    }
+}
+
+impl Default for Tolerance {
+    /// An absolute bound of 1e-9
+    fn default() -> Self {
+        Self::new(1e-9)
+    }
+}
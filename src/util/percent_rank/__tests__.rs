@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use crate::util::percent_rank::main::RollingPercentRank;
+
+    #[test]
+    fn test_extremes_rank_0_and_100() {
+        let mut rank = RollingPercentRank::new(5);
+        for value in [3.0, 1.0, 4.0, 2.0] {
+            assert_eq!(rank.push(value), None);
+        }
+        assert_eq!(rank.push(5.0), Some(100.0));
+        assert_eq!(rank.push(0.5), Some(0.0));
+        // 2.5 is above 0.5 and 2.0 but below 4.0 and 5.0
+        assert_eq!(rank.push(2.5), Some(50.0));
+    }
+
+    #[test]
+    fn test_ties_count_as_at_or_below() {
+        let mut rank = RollingPercentRank::new(3);
+        rank.push(1.0);
+        rank.push(1.0);
+        assert_eq!(rank.push(1.0), Some(100.0));
+    }
+
+    #[test]
+    fn test_window_slides_and_resets() {
+        let mut rank = RollingPercentRank::new(1);
+        assert_eq!(rank.period(), 2);
+        assert_eq!(rank.push(10.0), None);
+        assert_eq!(rank.push(5.0), Some(0.0));
+        // 10.0 left the window
+        assert_eq!(rank.push(6.0), Some(100.0));
+        assert_eq!(rank.len(), 2);
+
+        rank.reset();
+        assert!(rank.is_empty());
+        assert_eq!(rank.push(6.0), None);
+    }
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Rolling percent rank of the latest value within a fixed-size window
+///
+/// The rank is the share of the other values in the window that are less than
+/// or equal to the latest value, scaled to 0-100. The window maximum ranks 100
+/// and a unique window minimum ranks 0. Each `push` is O(period).
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::percent_rank::main::RollingPercentRank;
+///
+/// let mut rank = RollingPercentRank::new(3);
+/// assert_eq!(rank.push(2.0), None);
+/// assert_eq!(rank.push(1.0), None);
+/// // 3.0 is above both other values
+/// assert_eq!(rank.push(3.0), Some(100.0));
+/// // 2.0 leaves the window; 1.5 is above 1.0 but below 3.0
+/// assert_eq!(rank.push(1.5), Some(50.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RollingPercentRank {
+    /// Window size
+    period: usize,
+    /// Values in the window, oldest first
+    values: VecDeque<f64>,
+}
+
+impl RollingPercentRank {
+    /// Create a ranker over the last `period` values (periods below 2 are treated as 2)
+    pub fn new(period: usize) -> Self {
+        let period = period.max(2);
+        Self {
+            period,
+            values: VecDeque::with_capacity(period),
+        }
+    }
+
+    /// Add a value and return its percent rank once the window is full
+    pub fn push(&mut self, value: f64) -> Option<f64> {
+        if self.values.len() == self.period {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+
+        self.is_full().then(|| self.rank_latest())
+    }
+
+    /// Number of values currently in the window
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether no value has been pushed yet
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Whether the window holds `period` values
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.period
+    }
+
+    /// Window size
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Clear the window
+    pub fn reset(&mut self) {
+        self.values.clear();
+    }
+
+    /// Rank of the newest value against the others; only called on a full window
+    fn rank_latest(&self) -> f64 {
+        let others = self.values.len() - 1;
+        let latest = self.values[others];
+        let at_or_below = self
+            .values
+            .iter()
+            .take(others)
+            .filter(|&&value| value <= latest)
+            .count();
+        at_or_below as f64 / others as f64 * 100.0
+    }
+}
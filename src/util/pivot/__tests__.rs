@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use crate::util::pivot::main::{Pivot, PivotDetector, PivotKind};
+
+    fn run(detector: &mut PivotDetector, values: &[f64]) -> Vec<Option<Pivot>> {
+        values.iter().map(|&value| detector.update(value)).collect()
+    }
+
+    #[test]
+    fn test_pivot_high_in_hump() {
+        let mut detector = PivotDetector::new(2, 2);
+        let pivots = run(&mut detector, &[1.0, 2.0, 3.0, 5.0, 3.0, 2.0, 1.0]);
+
+        // The top of the hump (bar 3) is confirmed two bars later
+        assert!(pivots[..5].iter().all(Option::is_none));
+        assert_eq!(
+            pivots[5],
+            Some(Pivot {
+                kind: PivotKind::High,
+                value: 5.0,
+                offset: 2,
+                index: 3,
+            })
+        );
+        assert_eq!(pivots[6], None);
+    }
+
+    #[test]
+    fn test_pivot_low_and_plateau() {
+        let mut detector = PivotDetector::new(2, 2);
+        let pivots = run(&mut detector, &[5.0, 4.0, 2.0, 4.0, 5.0]);
+        assert_eq!(
+            pivots[4].map(|p| (p.kind, p.value, p.index)),
+            Some((PivotKind::Low, 2.0, 2))
+        );
+
+        // Equal neighbours do not make a pivot
+        detector.reset();
+        let plateau = run(&mut detector, &[1.0, 2.0, 5.0, 5.0, 2.0, 1.0]);
+        assert!(plateau.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_asymmetric_sides() {
+        // One bar of confirmation after a three-bar rise
+        let mut detector = PivotDetector::new(3, 1);
+        let pivots = run(&mut detector, &[1.0, 2.0, 3.0, 4.0, 3.5, 6.0]);
+        let pivot = pivots[4].unwrap();
+        assert_eq!(
+            (pivot.kind, pivot.value, pivot.offset, pivot.index),
+            (PivotKind::High, 4.0, 1, 3)
+        );
+        assert_eq!(pivots[5], None);
+        assert_eq!((detector.left(), detector.right()), (3, 1));
+    }
+}
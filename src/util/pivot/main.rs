@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Direction of a confirmed pivot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PivotKind {
+    /// Strictly above the `left` values before it and the `right` values after it
+    High,
+    /// Strictly below the `left` values before it and the `right` values after it
+    Low,
+}
+
+/// A pivot confirmed by [`PivotDetector::update`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Pivot {
+    /// Pivot high or low
+    pub kind: PivotKind,
+    /// Value at the pivot bar
+    pub value: f64,
+    /// Bars between the pivot and the value that confirmed it (always `right`)
+    pub offset: usize,
+    /// Position of the pivot bar in the stream, counting from 0 (since creation or `reset`)
+    pub index: usize,
+}
+
+/// Detects pivot highs and lows in a value stream
+///
+/// A bar is a pivot high when its value is strictly greater than the `left`
+/// values before it and the `right` values after it (a pivot low: strictly
+/// smaller). A pivot can only be confirmed once its `right` bars have been
+/// seen, so it is reported `right` bars late. Each `update` is O(left + right).
+/// Comparisons with NaN fail, so a window containing NaN never confirms a pivot.
+///
+/// This is the building block for divergence checks, which compare successive
+/// pivots of price against those of an oscillator.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::pivot::main::{PivotDetector, PivotKind};
+///
+/// let mut pivots = PivotDetector::new(1, 1);
+/// assert_eq!(pivots.update(1.0), None);
+/// assert_eq!(pivots.update(3.0), None);
+/// // 3.0 is above both neighbours, confirmed one bar later
+/// let pivot = pivots.update(2.0).unwrap();
+/// assert_eq!((pivot.kind, pivot.value, pivot.offset), (PivotKind::High, 3.0, 1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PivotDetector {
+    /// Values required before the pivot bar
+    left: usize,
+    /// Values required after the pivot bar
+    right: usize,
+    /// The last `left + right + 1` values, oldest first
+    window: VecDeque<f64>,
+    /// Number of values seen
+    count: usize,
+}
+
+impl PivotDetector {
+    /// Create a detector for pivots dominating `left` bars before and `right` bars after them
+    ///
+    /// With both sides 0 every value would be a pivot; at least one side should be positive.
+    pub fn new(left: usize, right: usize) -> Self {
+        Self {
+            left,
+            right,
+            window: VecDeque::with_capacity(left + right + 1),
+            count: 0,
+        }
+    }
+
+    /// Add the next value and return the pivot it confirms, if any
+    ///
+    /// A pivot high is reported in preference to a pivot low when both hold,
+    /// which can only happen with `left` and `right` both 0.
+    pub fn update(&mut self, value: f64) -> Option<Pivot> {
+        let len = self.left + self.right + 1;
+        if self.window.len() == len {
+            self.window.pop_front();
+        }
+        self.window.push_back(value);
+        self.count += 1;
+
+        if self.window.len() < len {
+            return None;
+        }
+
+        let candidate = self.window[self.left];
+        let others = || {
+            self.window
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != self.left)
+                .map(|(_, &other)| other)
+        };
+        let kind = if others().all(|other| other < candidate) {
+            PivotKind::High
+        } else if others().all(|other| other > candidate) {
+            PivotKind::Low
+        } else {
+            return None;
+        };
+
+        Some(Pivot {
+            kind,
+            value: candidate,
+            offset: self.right,
+            index: self.count - 1 - self.right,
+        })
+    }
+
+    /// Bars required before a pivot
+    pub fn left(&self) -> usize {
+        self.left
+    }
+
+    /// Bars required after a pivot (the confirmation delay)
+    pub fn right(&self) -> usize {
+        self.right
+    }
+
+    /// Whether enough values have been seen to confirm a pivot (`left + right + 1`)
+    pub fn is_full(&self) -> bool {
+        self.window.len() == self.left + self.right + 1
+    }
+
+    /// Forget all values
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.count = 0;
+    }
+}
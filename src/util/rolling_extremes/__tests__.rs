@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod tests {
+    use crate::util::rolling_extremes::main::RollingExtremes;
+
+    /// Deterministic pseudo-random series (LCG) so the tests need no extra dependencies
+    fn pseudo_random_series(len: usize, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 * 200.0 - 100.0
+            })
+            .collect()
+    }
+
+    fn naive_extremes(window: &[f64]) -> (f64, f64) {
+        let min = window.iter().fold(f64::INFINITY, |acc, &x| acc.min(x));
+        let max = window.iter().fold(f64::NEG_INFINITY, |acc, &x| acc.max(x));
+        (min, max)
+    }
+
+    #[test]
+    fn test_matches_naive_fold_on_random_data() {
+        for (period, seed) in [(1, 1), (3, 7), (14, 42), (50, 1234)] {
+            let series = pseudo_random_series(500, seed);
+            let mut extremes = RollingExtremes::new(period);
+
+            for (i, &value) in series.iter().enumerate() {
+                let start = (i + 1).saturating_sub(period);
+                assert_eq!(
+                    extremes.push(value),
+                    naive_extremes(&series[start..=i]),
+                    "period {} index {}",
+                    period,
+                    i
+                );
+                assert_eq!(extremes.len(), i + 1 - start);
+            }
+        }
+    }
+
+    #[test]
+    fn test_monotonic_and_repeated_values() {
+        let mut extremes = RollingExtremes::new(3);
+        assert!(extremes.is_empty());
+        assert_eq!(extremes.min(), None);
+
+        for value in [1.0, 2.0, 3.0, 4.0, 5.0] {
+            extremes.push(value);
+        }
+        assert_eq!((extremes.min(), extremes.max()), (Some(3.0), Some(5.0)));
+        assert_eq!(
+            (extremes.bars_since_min(), extremes.bars_since_max()),
+            (Some(2), Some(0))
+        );
+        assert!(extremes.is_full());
+
+        for value in [2.0, 2.0, 2.0] {
+            extremes.push(value);
+        }
+        assert_eq!((extremes.min(), extremes.max()), (Some(2.0), Some(2.0)));
+        assert_eq!(
+            (extremes.bars_since_min(), extremes.bars_since_max()),
+            (Some(0), Some(0))
+        );
+
+        extremes.reset();
+        assert!(extremes.is_empty());
+        assert_eq!(extremes.period(), 3);
+    }
+
+    #[test]
+    fn test_large_series() {
+        // Benchmark-style check: a long series with a wide window stays linear overall
+        let period = 1_000;
+        let series = pseudo_random_series(500_000, 99);
+        let mut extremes = RollingExtremes::new(period);
+
+        let mut last = (0.0, 0.0);
+        for &value in &series {
+            last = extremes.push(value);
+        }
+
+        assert_eq!(last, naive_extremes(&series[series.len() - period..]));
+    }
+
+    #[test]
+    fn test_replace_last_matches_feeding_revised_value() {
+        for (period, seed) in [(1, 3), (3, 11), (14, 77)] {
+            let series = pseudo_random_series(300, seed);
+            let revisions = pseudo_random_series(300, seed + 1);
+            let mut extremes = RollingExtremes::new(period);
+            let mut fed = Vec::new();
+
+            for (i, (&value, &revised)) in series.iter().zip(&revisions).enumerate() {
+                extremes.push(value);
+                // Revise the live value twice; only the final revision counts
+                extremes.replace_last(value * 0.5);
+                let result = extremes.replace_last(revised);
+                fed.push(revised);
+
+                let start = (i + 1).saturating_sub(period);
+                assert_eq!(
+                    result,
+                    naive_extremes(&fed[start..]),
+                    "period {} index {}",
+                    period,
+                    i
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_undo_last_only_once() {
+        let mut extremes = RollingExtremes::new(3);
+        assert!(!extremes.undo_last());
+
+        for value in [5.0, 1.0, 3.0, 4.0] {
+            extremes.push(value);
+        }
+        assert_eq!((extremes.min(), extremes.max()), (Some(1.0), Some(4.0)));
+
+        // Undoing 4.0 brings back the expired 5.0 and the dominated 3.0
+        assert!(extremes.undo_last());
+        assert_eq!((extremes.min(), extremes.max()), (Some(1.0), Some(5.0)));
+        assert_eq!(extremes.len(), 3);
+        assert!(!extremes.undo_last());
+
+        assert_eq!(extremes.push(0.5), (0.5, 3.0));
+    }
+}
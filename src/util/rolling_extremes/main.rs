@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Rolling minimum and maximum over a fixed-size window
+///
+/// Uses two monotonic deques of `(index, value)` pairs, so each `push` is amortized
+/// O(1) instead of the O(period) of folding over the whole window every bar.
+///
+/// - The min deque keeps values in increasing order; its front is the window minimum
+/// - The max deque keeps values in decreasing order; its front is the window maximum
+/// - Entries whose index has left the window are dropped from the front
+///
+/// The entries removed by the latest `push` are kept, so that push can be undone
+/// with `undo_last` or revised in place with `replace_last` (e.g. for a live,
+/// still-forming bar).
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::rolling_extremes::main::RollingExtremes;
+///
+/// let mut extremes = RollingExtremes::new(3);
+/// assert_eq!(extremes.push(5.0), (5.0, 5.0));
+/// assert_eq!(extremes.push(2.0), (2.0, 5.0));
+/// assert_eq!(extremes.push(4.0), (2.0, 5.0));
+/// // 5.0 leaves the window
+/// assert_eq!(extremes.push(3.0), (2.0, 4.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RollingExtremes {
+    /// Window size
+    period: usize,
+    /// Number of values pushed so far (the index of the next value)
+    count: u64,
+    /// Candidates for the minimum, increasing by value
+    mins: VecDeque<(u64, f64)>,
+    /// Candidates for the maximum, decreasing by value
+    maxs: VecDeque<(u64, f64)>,
+    /// Entries removed by the latest push
+    #[serde(default)]
+    undo: ExtremesUndo,
+}
+
+/// Entries removed by the latest push, restored by `undo_last`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct ExtremesUndo {
+    /// Whether the latest push can still be undone
+    available: bool,
+    /// Min candidates popped from the back, in pop order
+    dominated_mins: Vec<(u64, f64)>,
+    /// Max candidates popped from the back, in pop order
+    dominated_maxs: Vec<(u64, f64)>,
+    /// Min candidate expired from the front
+    expired_min: Option<(u64, f64)>,
+    /// Max candidate expired from the front
+    expired_max: Option<(u64, f64)>,
+}
+
+impl RollingExtremes {
+    /// Create a tracker over the last `period` values (a period of 0 is treated as 1)
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            count: 0,
+            mins: VecDeque::new(),
+            maxs: VecDeque::new(),
+            undo: ExtremesUndo::default(),
+        }
+    }
+
+    /// Add a value and return the `(min, max)` of the current window
+    pub fn push(&mut self, value: f64) -> (f64, f64) {
+        let index = self.count;
+        self.count += 1;
+        self.undo.dominated_mins.clear();
+        self.undo.dominated_maxs.clear();
+
+        // Drop candidates the new value dominates
+        while self.mins.back().is_some_and(|&(_, v)| v >= value) {
+            self.undo.dominated_mins.extend(self.mins.pop_back());
+        }
+        self.mins.push_back((index, value));
+
+        while self.maxs.back().is_some_and(|&(_, v)| v <= value) {
+            self.undo.dominated_maxs.extend(self.maxs.pop_back());
+        }
+        self.maxs.push_back((index, value));
+
+        // Expire entries that have left the window (at most one per push)
+        let period = self.period as u64;
+        self.undo.expired_min = None;
+        self.undo.expired_max = None;
+        if self.mins.front().is_some_and(|&(i, _)| i + period <= index) {
+            self.undo.expired_min = self.mins.pop_front();
+        }
+        if self.maxs.front().is_some_and(|&(i, _)| i + period <= index) {
+            self.undo.expired_max = self.maxs.pop_front();
+        }
+        self.undo.available = true;
+
+        (self.mins[0].1, self.maxs[0].1)
+    }
+
+    /// Undo the latest push, restoring the window to its previous contents
+    ///
+    /// Only one push can be undone; returns `false` (and does nothing) if there
+    /// is no push to undo.
+    pub fn undo_last(&mut self) -> bool {
+        if !self.undo.available {
+            return false;
+        }
+        self.undo.available = false;
+        self.count -= 1;
+
+        // The latest value is always the last candidate of both deques
+        self.mins.pop_back();
+        self.maxs.pop_back();
+        self.mins.extend(self.undo.dominated_mins.drain(..).rev());
+        self.maxs.extend(self.undo.dominated_maxs.drain(..).rev());
+        if let Some(entry) = self.undo.expired_min.take() {
+            self.mins.push_front(entry);
+        }
+        if let Some(entry) = self.undo.expired_max.take() {
+            self.maxs.push_front(entry);
+        }
+
+        true
+    }
+
+    /// Replace the latest value (or add it, if nothing can be undone) and return the new `(min, max)`
+    pub fn replace_last(&mut self, value: f64) -> (f64, f64) {
+        self.undo_last();
+        self.push(value)
+    }
+
+    /// Minimum of the current window, if any value has been pushed
+    pub fn min(&self) -> Option<f64> {
+        self.mins.front().map(|&(_, v)| v)
+    }
+
+    /// Maximum of the current window, if any value has been pushed
+    pub fn max(&self) -> Option<f64> {
+        self.maxs.front().map(|&(_, v)| v)
+    }
+
+    /// Bars elapsed since the window maximum (0 = the latest value)
+    ///
+    /// With repeated values the most recent occurrence is reported.
+    pub fn bars_since_max(&self) -> Option<usize> {
+        self.maxs
+            .front()
+            .map(|&(i, _)| (self.count - 1 - i) as usize)
+    }
+
+    /// Bars elapsed since the window minimum (0 = the latest value)
+    ///
+    /// With repeated values the most recent occurrence is reported.
+    pub fn bars_since_min(&self) -> Option<usize> {
+        self.mins
+            .front()
+            .map(|&(i, _)| (self.count - 1 - i) as usize)
+    }
+
+    /// Number of values currently in the window
+    pub fn len(&self) -> usize {
+        (self.count as usize).min(self.period)
+    }
+
+    /// Whether no value has been pushed yet
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Whether the window holds `period` values
+    pub fn is_full(&self) -> bool {
+        self.len() == self.period
+    }
+
+    /// Window size
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Clear all values, keeping the period
+    pub fn reset(&mut self) {
+        *self = Self::new(self.period);
+    }
+}
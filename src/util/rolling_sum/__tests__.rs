@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::types::Summation;
+    use crate::testutil::{main::ohlcv_series, types::OhlcvParams};
+    use crate::util::rolling_sum::main::RollingSum;
+
+    #[test]
+    fn test_matches_naive_windowed_sum() {
+        let values: Vec<f64> = ohlcv_series(11, 500, OhlcvParams::default())
+            .iter()
+            .map(|candle| candle.close - 100.0)
+            .collect();
+
+        for period in [1, 2, 7, 50] {
+            let mut sum = RollingSum::new(period);
+            for (i, &value) in values.iter().enumerate() {
+                let window = &values[(i + 1).saturating_sub(period)..=i];
+                let naive: f64 = window.iter().sum();
+                assert!((sum.push(value) - naive).abs() < 1e-9);
+                assert_eq!(sum.len(), window.len());
+                assert!(sum.iter().eq(window.iter()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_undo_last_restores_window_and_sum() {
+        let mut sum = RollingSum::new(2);
+        sum.push(0.1);
+        sum.push(0.2);
+        let before = sum.clone();
+
+        sum.push(0.7);
+        assert_eq!(sum.evicted(), Some(0.1));
+        assert!(sum.undo_last());
+        assert_eq!(sum.sum(), before.sum());
+        assert!(sum.iter().eq(before.iter()));
+        // Only one push can be undone
+        assert!(!sum.undo_last());
+
+        sum.reset();
+        assert!(sum.is_empty());
+        assert_eq!(sum.mean(), None);
+        assert_eq!(RollingSum::new(0).period(), 1);
+    }
+
+    #[test]
+    fn test_kahan_summation_survives_undo_and_reset() {
+        let mut sum = RollingSum::new(3).with_summation(Summation::Kahan);
+        for value in [1e16, 1.0, 1.0] {
+            sum.push(value);
+        }
+        // Naive addition would lose both 1.0s against 1e16
+        assert_eq!(sum.sum() - 1e16, 2.0);
+
+        // The compensation is restored with the sum, so replaying matches
+        let mut before = sum.clone();
+        sum.push(5.0);
+        assert!(sum.undo_last());
+        assert_eq!(sum.push(1.0), before.push(1.0));
+
+        sum.reset();
+        assert_eq!(sum.summation(), Summation::Kahan);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::common::types::Summation;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Running sum over a fixed-size window
+///
+/// Each `push` adds the new value and subtracts the one leaving the window, so
+/// the sum is O(1) per bar instead of re-adding the whole window. The window's
+/// values stay available for indicators that also need them (e.g. CCI's mean
+/// deviation). The latest push can be undone with `undo_last` (e.g. for a live,
+/// still-forming bar), which restores the previous sum exactly. `with_summation`
+/// switches the sum to compensated (Kahan) summation for very long series.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::RollingSum;
+///
+/// let mut sum = RollingSum::new(3);
+/// assert_eq!(sum.push(1.0), 1.0);
+/// assert_eq!(sum.push(2.0), 3.0);
+/// assert_eq!(sum.push(3.0), 6.0);
+/// // 1.0 leaves the window
+/// assert_eq!(sum.push(4.0), 9.0);
+/// assert_eq!(sum.evicted(), Some(1.0));
+/// assert_eq!(sum.mean(), Some(3.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RollingSum {
+    /// Window size
+    period: usize,
+    /// Values in the window, oldest first
+    values: VecDeque<f64>,
+    /// Sum of the values in the window
+    sum: f64,
+    /// How the sum is accumulated
+    #[serde(default)]
+    summation: Summation,
+    /// Rounding error carried by `Summation::Kahan`
+    #[serde(default)]
+    compensation: f64,
+    /// What the latest push changed, for `undo_last`
+    #[serde(default)]
+    undo: Option<SumUndo>,
+}
+
+/// State overwritten by the latest push
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct SumUndo {
+    /// Value that left the window
+    evicted: Option<f64>,
+    /// Sum before the push
+    previous_sum: f64,
+    /// Compensation before the push
+    #[serde(default)]
+    previous_compensation: f64,
+}
+
+impl RollingSum {
+    /// Create a sum over the last `period` values (a period of 0 is treated as 1)
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            values: VecDeque::new(),
+            sum: 0.0,
+            summation: Summation::Naive,
+            compensation: 0.0,
+            undo: None,
+        }
+    }
+
+    /// Accumulate the sum with the given summation method
+    pub fn with_summation(mut self, summation: Summation) -> Self {
+        self.summation = summation;
+        self
+    }
+
+    /// Add a value, evicting the oldest once the window is full, and return the sum
+    pub fn push(&mut self, value: f64) -> f64 {
+        let previous_sum = self.sum;
+        let previous_compensation = self.compensation;
+        let evicted = if self.values.len() >= self.period {
+            self.values.pop_front()
+        } else {
+            None
+        };
+        if let Some(oldest) = evicted {
+            self.summation
+                .add(&mut self.sum, &mut self.compensation, -oldest);
+        }
+
+        self.values.push_back(value);
+        self.summation
+            .add(&mut self.sum, &mut self.compensation, value);
+        self.undo = Some(SumUndo {
+            evicted,
+            previous_sum,
+            previous_compensation,
+        });
+
+        self.sum
+    }
+
+    /// Undo the latest push, restoring the previous window and sum
+    ///
+    /// Only one push can be undone; returns `false` (and does nothing) if there
+    /// is no push to undo.
+    pub fn undo_last(&mut self) -> bool {
+        let Some(undo) = self.undo.take() else {
+            return false;
+        };
+
+        self.values.pop_back();
+        if let Some(evicted) = undo.evicted {
+            self.values.push_front(evicted);
+        }
+        self.sum = undo.previous_sum;
+        self.compensation = undo.previous_compensation;
+
+        true
+    }
+
+    /// Sum of the values in the window
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Mean of the values in the window, `None` when empty
+    pub fn mean(&self) -> Option<f64> {
+        (!self.values.is_empty()).then(|| self.sum / self.values.len() as f64)
+    }
+
+    /// Value the latest push evicted from the window, if any
+    pub fn evicted(&self) -> Option<f64> {
+        self.undo.and_then(|undo| undo.evicted)
+    }
+
+    /// Values in the window, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &f64> + '_ {
+        self.values.iter()
+    }
+
+    /// Number of values currently in the window
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether no value has been pushed yet
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Whether the window holds `period` values
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.period
+    }
+
+    /// Window size
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// How the sum is accumulated
+    pub fn summation(&self) -> Summation {
+        self.summation
+    }
+
+    /// Clear all values, keeping the period and summation method
+    pub fn reset(&mut self) {
+        *self = Self::new(self.period).with_summation(self.summation);
+    }
+}
+
+/// A window as stored in serialized indicator state
+///
+/// States saved before an indicator moved to `RollingSum` hold the window as a plain
+/// list of values; both forms are accepted.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum StoredWindow {
+    /// A serialized `RollingSum`
+    Current(RollingSum),
+    /// The values alone, oldest first
+    Legacy(VecDeque<f64>),
+}
+
+impl StoredWindow {
+    /// The stored window, rebuilt over `period` values if it is a legacy list
+    ///
+    /// A rebuilt window has no push to undo.
+    pub(crate) fn into_rolling_sum(self, period: usize, summation: Summation) -> RollingSum {
+        match self {
+            StoredWindow::Current(window) => window,
+            StoredWindow::Legacy(values) => {
+                let mut window = RollingSum::new(period).with_summation(summation);
+                for value in values {
+                    window.push(value);
+                }
+                window.undo = None;
+                window
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use crate::util::signal_debouncer::main::SignalDebouncer;
+    use crate::v1::types::TradingSignal::{self, Buy, Hold, Sell};
+
+    fn run(debouncer: &mut SignalDebouncer, signals: &[TradingSignal]) -> Vec<TradingSignal> {
+        signals
+            .iter()
+            .map(|signal| debouncer.update(signal.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_consecutive_buys_collapse_to_one() {
+        let mut debouncer = SignalDebouncer::new(3);
+        assert_eq!(run(&mut debouncer, &[Buy, Buy, Buy]), vec![Buy, Hold, Hold]);
+    }
+
+    #[test]
+    fn test_signal_passes_after_cooldown() {
+        let mut debouncer = SignalDebouncer::new(3);
+        let signals = [Buy, Buy, Hold, Buy, Buy];
+        // The fourth bar after the first Buy is outside the cooldown
+        assert_eq!(
+            run(&mut debouncer, &signals),
+            vec![Buy, Hold, Hold, Hold, Buy]
+        );
+    }
+
+    #[test]
+    fn test_opposite_direction_passes() {
+        let mut debouncer = SignalDebouncer::new(5);
+        let signals = [Buy, Sell, Buy, Sell, Sell];
+        assert_eq!(
+            run(&mut debouncer, &signals),
+            vec![Buy, Sell, Buy, Sell, Hold]
+        );
+
+        debouncer.reset();
+        assert_eq!(debouncer.update(Sell), Sell);
+        assert_eq!(debouncer.cooldown_bars(), 5);
+    }
+
+    #[test]
+    fn test_zero_cooldown_passes_everything() {
+        let mut debouncer = SignalDebouncer::new(0);
+        assert_eq!(run(&mut debouncer, &[Buy, Buy, Buy]), vec![Buy, Buy, Buy]);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::v1::types::TradingSignal;
+
+/// Suppresses repeated trading signals of the same direction within a cooldown
+///
+/// Feed one signal per bar. A `Buy` or `Sell` passes through when it differs
+/// from the last emitted signal or when more than `cooldown_bars` bars have
+/// passed since that signal; otherwise it is replaced by `Hold`. Suppressed
+/// signals do not restart the cooldown, and `Hold` always passes through.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::signal_debouncer::main::SignalDebouncer;
+/// use indexes_rs::v1::types::TradingSignal;
+///
+/// let mut debouncer = SignalDebouncer::new(2);
+/// assert_eq!(debouncer.update(TradingSignal::Buy), TradingSignal::Buy);
+/// assert_eq!(debouncer.update(TradingSignal::Buy), TradingSignal::Hold);
+/// // A signal in the other direction is not suppressed
+/// assert_eq!(debouncer.update(TradingSignal::Sell), TradingSignal::Sell);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalDebouncer {
+    /// Bars after an emitted signal during which repeats are suppressed
+    cooldown_bars: usize,
+    /// Last emitted `Buy`/`Sell` and the number of bars since it
+    last: Option<(TradingSignal, usize)>,
+}
+
+impl SignalDebouncer {
+    /// Create a debouncer suppressing repeats for `cooldown_bars` bars after each emitted signal
+    pub fn new(cooldown_bars: usize) -> Self {
+        Self {
+            cooldown_bars,
+            last: None,
+        }
+    }
+
+    /// Process the signal for the next bar and return the debounced signal
+    pub fn update(&mut self, signal: TradingSignal) -> TradingSignal {
+        if let Some((_, bars_since)) = self.last.as_mut() {
+            *bars_since += 1;
+        }
+
+        if signal == TradingSignal::Hold {
+            return signal;
+        }
+
+        let suppressed = matches!(
+            &self.last,
+            Some((last, bars_since)) if *last == signal && *bars_since <= self.cooldown_bars
+        );
+        if suppressed {
+            return TradingSignal::Hold;
+        }
+
+        self.last = Some((signal.clone(), 0));
+        signal
+    }
+
+    /// Cooldown length in bars
+    pub fn cooldown_bars(&self) -> usize {
+        self.cooldown_bars
+    }
+
+    /// Forget the last emitted signal
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::types::TrendDirection;
+    use crate::util::slope_trend::main::{slope_trend, MaSlope};
+    use crate::v1::sma::main::SimpleMovingAverage;
+
+    #[test]
+    fn test_rising_flat_and_falling_sequences() {
+        let rising = slope_trend(&[1.0, 2.0, 3.0, 4.0, 5.0], 0.1).unwrap();
+        assert_eq!(rising.slope, 1.0);
+        assert_eq!(rising.direction, TrendDirection::Up);
+
+        let flat = slope_trend(&[5.0, 5.02, 4.98, 5.01, 5.0], 0.1).unwrap();
+        assert!(flat.slope.abs() <= 0.1);
+        assert_eq!(flat.direction, TrendDirection::Sideways);
+
+        let falling = slope_trend(&[10.0, 9.0, 8.5, 7.0, 6.0], 0.1).unwrap();
+        assert!(falling.slope < -0.1);
+        assert_eq!(falling.direction, TrendDirection::Down);
+
+        assert_eq!(slope_trend(&[1.0], 0.1), None);
+    }
+
+    #[test]
+    fn test_ma_slope_follows_sma() {
+        let mut sma = SimpleMovingAverage::new(3).unwrap();
+        let mut slope = MaSlope::new(4, 0.05);
+
+        // Rise, stall, then fall
+        let prices: Vec<f64> = (0..10)
+            .map(|i| 100.0 + i as f64)
+            .chain(std::iter::repeat_n(110.0, 10))
+            .chain((0..10).map(|i| 110.0 - i as f64))
+            .collect();
+
+        let mut directions = Vec::new();
+        for price in prices {
+            sma.add_value(price);
+            if let Some(ma) = sma.calculate() {
+                if let Some(trend) = slope.update(ma.value) {
+                    directions.push(trend.direction);
+                }
+            }
+        }
+
+        assert_eq!(directions.first(), Some(&TrendDirection::Up));
+        assert!(directions.contains(&TrendDirection::Sideways));
+        assert_eq!(directions.last(), Some(&TrendDirection::Down));
+    }
+
+    #[test]
+    fn test_ma_slope_window_and_reset() {
+        let mut slope = MaSlope::new(2, 0.0);
+        assert_eq!(slope.update(1.0), None);
+        assert_eq!(slope.update(3.0).unwrap().slope, 2.0);
+        // Only the last two values count
+        assert_eq!(slope.update(2.0).unwrap().direction, TrendDirection::Down);
+
+        slope.reset();
+        assert_eq!(slope.update(5.0), None);
+    }
+}
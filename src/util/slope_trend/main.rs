@@ -0,0 +1,112 @@
+use crate::common::types::TrendDirection;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Slope of a series and its classified direction
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SlopeTrend {
+    /// Least-squares slope in value units per bar
+    pub slope: f64,
+    /// `Up`/`Down` when the slope exceeds the flat threshold, otherwise `Sideways`
+    pub direction: TrendDirection,
+}
+
+/// Classify the trend of a series (e.g. the last K moving average values)
+///
+/// The slope is the least-squares regression slope over the window, so a single
+/// noisy value moves it less than a first-to-last difference would. A slope whose
+/// magnitude is at most `flat_threshold` is classified as `Sideways`.
+///
+/// Returns `None` for fewer than two values.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::common::types::TrendDirection;
+/// use indexes_rs::util::slope_trend;
+///
+/// let trend = slope_trend(&[10.0, 10.5, 11.0, 11.5], 0.1).unwrap();
+/// assert_eq!(trend.slope, 0.5);
+/// assert_eq!(trend.direction, TrendDirection::Up);
+/// ```
+pub fn slope_trend(values: &[f64], flat_threshold: f64) -> Option<SlopeTrend> {
+    if values.len() < 2 {
+        return None;
+    }
+
+    let n = values.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let (covariance, variance) =
+        values
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(cov, var), (i, &y)| {
+                let dx = i as f64 - mean_x;
+                (cov + dx * (y - mean_y), var + dx * dx)
+            });
+    let slope = covariance / variance;
+
+    let direction = if slope > flat_threshold {
+        TrendDirection::Up
+    } else if slope < -flat_threshold {
+        TrendDirection::Down
+    } else {
+        TrendDirection::Sideways
+    };
+
+    Some(SlopeTrend { slope, direction })
+}
+
+/// Stateful moving average slope classifier over the last `lookback` values
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::common::types::TrendDirection;
+/// use indexes_rs::util::MaSlope;
+///
+/// let mut slope = MaSlope::new(3, 0.1);
+/// assert_eq!(slope.update(100.0), None);
+/// assert_eq!(slope.update(100.0), None);
+/// assert_eq!(slope.update(100.05).unwrap().direction, TrendDirection::Sideways);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaSlope {
+    /// Number of MA values in the regression window
+    lookback: usize,
+    /// Largest absolute slope still classified as flat
+    flat_threshold: f64,
+    /// Last `lookback` MA values, oldest first
+    values: VecDeque<f64>,
+}
+
+impl MaSlope {
+    /// Create a classifier over the last `lookback` values (lookbacks below 2 are treated as 2)
+    pub fn new(lookback: usize, flat_threshold: f64) -> Self {
+        let lookback = lookback.max(2);
+        Self {
+            lookback,
+            flat_threshold: flat_threshold.abs(),
+            values: VecDeque::with_capacity(lookback),
+        }
+    }
+
+    /// Add the latest MA value and return the slope once `lookback` values are available
+    pub fn update(&mut self, ma_value: f64) -> Option<SlopeTrend> {
+        if self.values.len() == self.lookback {
+            self.values.pop_front();
+        }
+        self.values.push_back(ma_value);
+
+        if self.values.len() < self.lookback {
+            return None;
+        }
+        slope_trend(self.values.make_contiguous(), self.flat_threshold)
+    }
+
+    /// Forget all values
+    pub fn reset(&mut self) {
+        self.values.clear();
+    }
+}
@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use crate::util::true_range::main::{true_range, TrueRange};
+
+    #[test]
+    fn test_true_range_matches_adx_gap_case() {
+        // Same bars as the ADX true range test: max(15-12, |15-9|, |12-9|) = 6
+        assert_eq!(true_range(15.0, 12.0, Some(9.0)), 6.0);
+        assert_eq!(true_range(10.0, 8.0, None), 2.0);
+
+        // Gap down: previous close above the high
+        assert_eq!(true_range(10.0, 8.0, Some(13.0)), 5.0);
+        // Previous close inside the bar
+        assert_eq!(true_range(10.0, 8.0, Some(9.0)), 2.0);
+    }
+
+    #[test]
+    fn test_stateful_true_range() {
+        let mut tr = TrueRange::new();
+        assert_eq!(tr.prev_close(), None);
+        assert_eq!(tr.update(10.0, 8.0, 9.0), 2.0);
+        assert_eq!(tr.update(15.0, 12.0, 14.0), 6.0);
+        assert_eq!(tr.prev_close(), Some(14.0));
+
+        tr.reset();
+        assert_eq!(tr.update(15.0, 12.0, 14.0), 3.0);
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// True Range of a bar
+///
+/// The greatest of:
+/// - high - low
+/// - |high - previous close|
+/// - |low - previous close|
+///
+/// Without a previous close (the first bar) the true range is simply high - low.
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::true_range;
+///
+/// assert_eq!(true_range(15.0, 12.0, None), 3.0);
+/// // Gap up from a previous close of 9.0
+/// assert_eq!(true_range(15.0, 12.0, Some(9.0)), 6.0);
+/// ```
+pub fn true_range(high: f64, low: f64, prev_close: Option<f64>) -> f64 {
+    let hl = high - low;
+    match prev_close {
+        Some(prev_close) => {
+            let hc = (high - prev_close).abs();
+            let lc = (low - prev_close).abs();
+            hl.max(hc).max(lc)
+        }
+        None => hl,
+    }
+}
+
+/// Stateful True Range that remembers the previous close between bars
+///
+/// # Example
+///
+/// ```rust
+/// use indexes_rs::util::TrueRange;
+///
+/// let mut tr = TrueRange::new();
+/// assert_eq!(tr.update(10.0, 8.0, 9.0), 2.0);
+/// assert_eq!(tr.update(15.0, 12.0, 14.0), 6.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrueRange {
+    /// Close of the previous bar
+    prev_close: Option<f64>,
+}
+
+impl TrueRange {
+    /// Create a tracker that has not seen any bar yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the true range of the bar and remember its close
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
+        let value = true_range(high, low, self.prev_close);
+        self.prev_close = Some(close);
+        value
+    }
+
+    /// Close of the previous bar, if any
+    pub fn prev_close(&self) -> Option<f64> {
+        self.prev_close
+    }
+
+    /// Forget the previous close
+    pub fn reset(&mut self) {
+        self.prev_close = None;
+    }
+}
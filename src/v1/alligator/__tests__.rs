@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use crate::v1::{
+        alligator::main::Alligator,
+        smma::main::{SMMAError, SmoothedMovingAverage},
+    };
+
+    #[test]
+    fn test_invalid_period() {
+        assert!(matches!(
+            Alligator::new(13, 8, 0, 5, 5, 3),
+            Err(SMMAError::InvalidPeriod)
+        ));
+    }
+
+    #[test]
+    fn test_warmup_includes_displacement() {
+        let mut alligator = Alligator::default();
+        // The jaw needs 13 values to seed and 8 more bars of displacement.
+        for i in 0..20 {
+            assert!(alligator.calculate(100.0 + i as f64).is_none(), "bar {}", i);
+        }
+        assert!(alligator.calculate(120.0).is_some());
+    }
+
+    #[test]
+    fn test_lines_are_displaced_smmas() {
+        let prices: Vec<f64> = (0..30)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0)
+            .collect();
+        let mut alligator = Alligator::new(5, 2, 3, 1, 2, 0).unwrap();
+        let mut jaw = SmoothedMovingAverage::new(5).unwrap();
+        let jaw_values: Vec<Option<f64>> = prices.iter().map(|&p| jaw.add_value(p)).collect();
+
+        for (i, &price) in prices.iter().enumerate() {
+            if let Some(result) = alligator.calculate(price) {
+                // The jaw on bar i is the SMMA from two bars earlier
+                assert_eq!(Some(result.jaw), jaw_values[i - 2]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_line_order_in_strong_trends() {
+        let mut up = Alligator::default();
+        let mut down = Alligator::default();
+        let mut up_result = None;
+        let mut down_result = None;
+        for i in 0..60 {
+            up_result = up.calculate(100.0 + i as f64 * 2.0);
+            down_result = down.calculate(300.0 - i as f64 * 2.0);
+        }
+
+        let up_lines = up_result.unwrap();
+        assert!(up_lines.lips > up_lines.teeth && up_lines.teeth > up_lines.jaw);
+
+        let down_lines = down_result.unwrap();
+        assert!(down_lines.lips < down_lines.teeth && down_lines.teeth < down_lines.jaw);
+    }
+}
@@ -0,0 +1,142 @@
+//! # Alligator Module
+//!
+//! This module implements Bill Williams' Alligator: three Smoothed Moving Averages (jaw, teeth
+//! and lips) that are each displaced forward by a number of bars. A line displaced by `shift`
+//! reports, on the current bar, the SMMA value computed `shift` bars ago.
+//!
+//! The classic settings are jaw 13/8, teeth 8/5 and lips 5/3 (period/shift), applied to the
+//! median price `(high + low) / 2`. In a strong uptrend the lines fan out as lips > teeth > jaw;
+//! in a downtrend the order reverses.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use indexes_rs::v1::alligator::main::Alligator;
+//!
+//! let mut alligator = Alligator::default();
+//! let mut result = None;
+//! for i in 0..40 {
+//!     let (high, low) = (101.0 + i as f64, 99.0 + i as f64);
+//!     result = alligator.calculate((high + low) / 2.0);
+//! }
+//!
+//! let lines = result.unwrap();
+//! assert!(lines.lips > lines.teeth && lines.teeth > lines.jaw);
+//! ```
+
+use super::types::AlligatorResult;
+use crate::v1::smma::main::{SMMAError, SmoothedMovingAverage};
+use std::collections::VecDeque;
+
+/// An SMMA whose output is delayed by `shift` bars.
+struct DisplacedLine {
+    smma: SmoothedMovingAverage,
+    shift: usize,
+    buffer: VecDeque<f64>,
+}
+
+impl DisplacedLine {
+    fn new(period: usize, shift: usize) -> Result<Self, SMMAError> {
+        Ok(DisplacedLine {
+            smma: SmoothedMovingAverage::new(period)?,
+            shift,
+            buffer: VecDeque::with_capacity(shift + 1),
+        })
+    }
+
+    /// Feeds a value and returns the SMMA value from `shift` bars ago, once available.
+    fn update(&mut self, value: f64) -> Option<f64> {
+        self.buffer.push_back(self.smma.add_value(value)?);
+        if self.buffer.len() > self.shift {
+            self.buffer.pop_front()
+        } else {
+            None
+        }
+    }
+}
+
+/// A Bill Williams Alligator indicator.
+pub struct Alligator {
+    jaw: DisplacedLine,
+    teeth: DisplacedLine,
+    lips: DisplacedLine,
+}
+
+impl Alligator {
+    /// Default jaw SMMA period.
+    pub const DEFAULT_JAW_PERIOD: usize = 13;
+    /// Default jaw displacement.
+    pub const DEFAULT_JAW_SHIFT: usize = 8;
+    /// Default teeth SMMA period.
+    pub const DEFAULT_TEETH_PERIOD: usize = 8;
+    /// Default teeth displacement.
+    pub const DEFAULT_TEETH_SHIFT: usize = 5;
+    /// Default lips SMMA period.
+    pub const DEFAULT_LIPS_PERIOD: usize = 5;
+    /// Default lips displacement.
+    pub const DEFAULT_LIPS_SHIFT: usize = 3;
+
+    /// Creates a new Alligator indicator.
+    ///
+    /// # Arguments
+    ///
+    /// * `jaw_period` / `jaw_shift` - The SMMA period and forward displacement of the jaw.
+    /// * `teeth_period` / `teeth_shift` - The SMMA period and forward displacement of the teeth.
+    /// * `lips_period` / `lips_shift` - The SMMA period and forward displacement of the lips.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Alligator)` on success, or `Err(SMMAError)` if any period is zero.
+    pub fn new(
+        jaw_period: usize,
+        jaw_shift: usize,
+        teeth_period: usize,
+        teeth_shift: usize,
+        lips_period: usize,
+        lips_shift: usize,
+    ) -> Result<Self, SMMAError> {
+        Ok(Alligator {
+            jaw: DisplacedLine::new(jaw_period, jaw_shift)?,
+            teeth: DisplacedLine::new(teeth_period, teeth_shift)?,
+            lips: DisplacedLine::new(lips_period, lips_shift)?,
+        })
+    }
+
+    /// Updates the Alligator with a new price and returns the three displaced lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The latest price, classically the median price `(high + low) / 2`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(AlligatorResult)` once every line has warmed up and filled its displacement buffer.
+    /// * `None` otherwise.
+    pub fn calculate(&mut self, price: f64) -> Option<AlligatorResult> {
+        // Update every line so each keeps its own history even while the others are warming up.
+        let jaw = self.jaw.update(price);
+        let teeth = self.teeth.update(price);
+        let lips = self.lips.update(price);
+
+        Some(AlligatorResult {
+            jaw: jaw?,
+            teeth: teeth?,
+            lips: lips?,
+        })
+    }
+}
+
+impl Default for Alligator {
+    /// Creates an Alligator with the classic 13/8, 8/5, 5/3 settings.
+    fn default() -> Self {
+        Self::new(
+            Self::DEFAULT_JAW_PERIOD,
+            Self::DEFAULT_JAW_SHIFT,
+            Self::DEFAULT_TEETH_PERIOD,
+            Self::DEFAULT_TEETH_SHIFT,
+            Self::DEFAULT_LIPS_PERIOD,
+            Self::DEFAULT_LIPS_SHIFT,
+        )
+        .expect("default Alligator periods are non-zero")
+    }
+}
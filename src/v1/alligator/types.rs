@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+/// The result of an Alligator calculation.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AlligatorResult {
+    /// The jaw line (slowest SMMA, displaced furthest).
+    pub jaw: f64,
+    /// The teeth line (middle SMMA).
+    pub teeth: f64,
+    /// The lips line (fastest SMMA, displaced least).
+    pub lips: f64,
+}
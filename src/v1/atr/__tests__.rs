@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::v1::atr::main::ATR;
+    use crate::v1::types::V1Error;
 
     #[test]
     fn test_insufficient_data() {
@@ -46,4 +47,10 @@ mod tests {
         // However, for this test we simply check that an ATR is produced.
         assert!(last_atr.is_some());
     }
+
+    #[test]
+    fn test_try_new_rejects_zero_period() {
+        assert_eq!(ATR::try_new(0).err(), Some(V1Error::InvalidPeriod));
+        assert!(ATR::try_new(14).is_ok());
+    }
 }
@@ -27,6 +27,8 @@
 //! }
 //! ```
 
+use crate::util::TrueRange;
+use crate::v1::types::V1Error;
 use std::collections::VecDeque;
 
 /// A simplified Average True Range (ATR) indicator.
@@ -38,8 +40,8 @@ pub struct ATR {
     period: usize,
     /// A sliding window of true range values.
     values: VecDeque<f64>,
-    /// The previous closing price.
-    prev_close: Option<f64>,
+    /// True range tracker holding the previous closing price.
+    true_range: TrueRange,
 }
 
 impl ATR {
@@ -60,10 +62,28 @@ impl ATR {
         ATR {
             period,
             values: VecDeque::with_capacity(period),
-            prev_close: None,
+            true_range: TrueRange::new(),
         }
     }
 
+    /// Creates a new `ATR` indicator, rejecting a zero period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of periods for the ATR.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ATR)` for a non-zero period.
+    /// * `Err(V1Error::InvalidPeriod)` if `period` is zero.
+    pub fn try_new(period: usize) -> Result<Self, V1Error> {
+        if period == 0 {
+            return Err(V1Error::InvalidPeriod);
+        }
+
+        Ok(Self::new(period))
+    }
+
     /// Calculates the current ATR value using the latest closing price.
     ///
     /// The true range is computed as the absolute difference between the current closing price and the previous closing price.
@@ -78,16 +98,14 @@ impl ATR {
     /// * `Some(f64)` containing the ATR value if enough data is available.
     /// * `None` if there aren't enough values yet.
     pub fn calculate(&mut self, close: f64) -> Option<f64> {
-        // Compute the true range based on the previous close, or 0.0 if none exists.
-        let true_range = self.prev_close.map_or(0.0, |prev| (close - prev).abs());
+        // With only closes, high = low = close, so the true range is |close - previous close| (0.0 on the first bar).
+        let true_range = self.true_range.update(close, close, close);
 
         self.values.push_back(true_range);
         if self.values.len() > self.period {
             self.values.pop_front();
         }
 
-        self.prev_close = Some(close);
-
         if self.values.len() == self.period {
             Some(self.values.iter().sum::<f64>() / self.period as f64)
         } else {
@@ -1,6 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use crate::v1::bollinger::{main::BollingerBands, types::BBResult};
+    use crate::common::types::Candle;
+    use crate::transform::price_source::types::PriceSource;
+    use crate::v1::bollinger::{
+        main::BollingerBands,
+        types::{BBColumns, BBResult, BollingerError},
+    };
+    use crate::v1::types::V1Error;
 
     #[test]
     fn test_insufficient_data() {
@@ -30,4 +36,295 @@ mod tests {
         assert!((res.upper - 105.266).abs() < 0.01);
         assert!((res.lower - 98.734).abs() < 0.01);
     }
+
+    /// Reference two-pass population standard deviation (the pre-refactor implementation).
+    fn reference_bands(window: &[f64], multiplier: f64) -> BBResult {
+        let mean = window.iter().sum::<f64>() / window.len() as f64;
+        let variance =
+            window.iter().map(|v| (mean - v) * (mean - v)).sum::<f64>() / window.len() as f64;
+        let band_width = variance.sqrt() * multiplier;
+        BBResult {
+            upper: mean + band_width,
+            middle: mean,
+            lower: mean - band_width,
+        }
+    }
+
+    #[test]
+    fn test_matches_reference_implementation() {
+        let prices = [
+            100.0, 101.0, 102.0, 101.5, 100.5, 102.0, 103.0, 102.5, 104.0, 105.0, 104.5, 105.5,
+            106.0, 107.0, 106.5, 108.0, 107.5, 108.5, 109.0, 110.0, 108.0, 106.5, 107.0, 109.5,
+            111.0,
+        ];
+        let period = 5;
+        let mut bb = BollingerBands::new(period, 2.0).unwrap();
+
+        for (i, &price) in prices.iter().enumerate() {
+            let result = bb.calculate(price);
+            if i + 1 < period {
+                assert!(result.is_none());
+                continue;
+            }
+            let result = result.unwrap();
+            let expected = reference_bands(&prices[i + 1 - period..=i], 2.0);
+            assert!(
+                (result.middle - expected.middle).abs() < 1e-9,
+                "middle at {}",
+                i
+            );
+            assert!(
+                (result.upper - expected.upper).abs() < 1e-9,
+                "upper at {}",
+                i
+            );
+            assert!(
+                (result.lower - expected.lower).abs() < 1e-9,
+                "lower at {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches_reference_at_high_price_level() {
+        // Cent-sized noise on a 60000 level: Σx²/n - mean² would cancel away the spread
+        let prices: Vec<f64> = (0..500)
+            .map(|i| 60_000.0 + ((i * 7919) % 13) as f64 * 0.01)
+            .collect();
+        let period = 20;
+        let mut bb = BollingerBands::new(period, 2.0).unwrap();
+
+        for (i, &price) in prices.iter().enumerate() {
+            let Some(result) = bb.calculate(price) else {
+                continue;
+            };
+            let expected = reference_bands(&prices[i + 1 - period..=i], 2.0);
+            let (width, expected_width) =
+                (result.upper - result.lower, expected.upper - expected.lower);
+            assert!(
+                (width - expected_width).abs() <= 1e-6 * expected_width,
+                "width at {}: {} vs {}",
+                i,
+                width,
+                expected_width
+            );
+        }
+    }
+
+    #[test]
+    fn test_sample_std_dev_widens_bands() {
+        let mut population = BollingerBands::new(3, 2.0).unwrap();
+        let mut sample = BollingerBands::with_sample_std_dev(3, 2.0).unwrap();
+        let (mut pop, mut smp) = (None, None);
+        for price in [100.0, 102.0, 104.0] {
+            pop = population.calculate(price);
+            smp = sample.calculate(price);
+        }
+        let (pop, smp) = (pop.unwrap(), smp.unwrap());
+        // Sample std dev of [100, 102, 104] is exactly 2.0
+        assert!((smp.upper - 106.0).abs() < 1e-9);
+        assert!(smp.upper > pop.upper);
+        assert!(BollingerBands::with_sample_std_dev(1, 2.0).is_err());
+    }
+
+    #[test]
+    fn test_invalid_multiplier_rejected() {
+        for multiplier in [-2.0, 0.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                BollingerBands::new(20, multiplier).err(),
+                Some(BollingerError::InvalidMultiplier)
+            );
+            assert_eq!(
+                BollingerBands::with_sample_std_dev(20, multiplier).err(),
+                Some(BollingerError::InvalidMultiplier)
+            );
+        }
+        assert_eq!(
+            BollingerBands::new(0, 2.0).err(),
+            Some(BollingerError::InvalidPeriod)
+        );
+
+        // The valid path is unaffected
+        let mut bb = BollingerBands::new(3, 1.5).unwrap();
+        bb.calculate(100.0);
+        bb.calculate(102.0);
+        let result = bb.calculate(104.0).unwrap();
+        assert!((result.middle - 102.0).abs() < 1e-9);
+        assert!(result.upper > result.middle && result.lower < result.middle);
+    }
+
+    #[test]
+    fn test_columns_match_row_batch() {
+        let candles: Vec<Candle> = (0..20)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 3.0)
+            .map(|c| Candle::new(c, c + 1.0, c - 1.0, c, 1000.0))
+            .collect();
+
+        let rows = BollingerBands::new(5, 2.0)
+            .unwrap()
+            .calculate_batch(&candles);
+        let columns: BBColumns = BollingerBands::new(5, 2.0)
+            .unwrap()
+            .calculate_columns(&candles);
+
+        assert_eq!(columns.len(), candles.len());
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(columns.upper[i], row.as_ref().map(|r| r.upper));
+            assert_eq!(columns.middle[i], row.as_ref().map(|r| r.middle));
+            assert_eq!(columns.lower[i], row.as_ref().map(|r| r.lower));
+        }
+        assert!(rows.iter().any(|row| row.is_some()));
+    }
+
+    #[cfg(feature = "polars")]
+    #[test]
+    fn test_columns_to_dataframe() {
+        let candles: Vec<Candle> = [100.0, 101.0, 102.0, 101.5, 103.0, 102.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Candle::new(c, c, c, c, 0.0).with_timestamp(i as i64 * 60_000))
+            .collect();
+
+        let columns = BollingerBands::new(3, 2.0)
+            .unwrap()
+            .calculate_columns(&candles);
+        let df = columns.to_dataframe().unwrap();
+
+        assert_eq!(
+            df.get_column_names_str(),
+            vec!["timestamp", "upper", "middle", "lower"]
+        );
+        assert_eq!(df.height(), candles.len());
+        assert_eq!(df.column("timestamp").unwrap().null_count(), 0);
+        for name in ["upper", "middle", "lower"] {
+            // Two warmup bars before the 3-bar window fills
+            assert_eq!(df.column(name).unwrap().null_count(), 2);
+        }
+    }
+
+    #[test]
+    fn test_v1_error_paths() {
+        let error: V1Error = BollingerBands::new(5, -1.0).err().unwrap().into();
+        assert_eq!(error, V1Error::InvalidMultiplier);
+        let error: V1Error = BollingerBands::new(0, 2.0).err().unwrap().into();
+        assert_eq!(error, V1Error::InvalidPeriod);
+
+        let mut bb = BollingerBands::new(3, 2.0).unwrap();
+        assert_eq!(bb.try_calculate(f64::NAN), Err(V1Error::InvalidPrice));
+        for price in [100.0, 101.0] {
+            bb.try_calculate(price).unwrap();
+        }
+        let bands = bb.try_calculate(102.0).unwrap().unwrap();
+        assert!((bands.middle - 101.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_typical_price_source_differs_on_wide_bars() {
+        // Wide bars that close near one extreme, so the typical price sits far from the close
+        let candles: Vec<Candle> = [
+            (110.0, 90.0, 109.0),
+            (112.0, 92.0, 93.0),
+            (115.0, 95.0, 114.0),
+            (111.0, 89.0, 90.0),
+            (116.0, 94.0, 115.0),
+        ]
+        .iter()
+        .map(|&(h, l, c)| Candle::new(c, h, l, c, 0.0))
+        .collect();
+
+        let close_bands = BollingerBands::new(3, 2.0)
+            .unwrap()
+            .calculate_batch(&candles);
+        let typical_bands = BollingerBands::new(3, 2.0)
+            .unwrap()
+            .with_price_source(PriceSource::Typical)
+            .calculate_batch(&candles);
+
+        for (close, typical) in close_bands[2..].iter().zip(&typical_bands[2..]) {
+            let (close, typical) = (close.as_ref().unwrap(), typical.as_ref().unwrap());
+            assert_ne!(close.middle, typical.middle);
+            // The typical price swings less than the close, so its bands are narrower
+            assert!(typical.upper - typical.lower < close.upper - close.lower);
+        }
+
+        // The typical-price bands match plain bands fed the typical price directly
+        let mut reference = BollingerBands::new(3, 2.0).unwrap();
+        let expected: Vec<_> = candles
+            .iter()
+            .map(|c| reference.calculate((c.high + c.low + c.close) / 3.0))
+            .collect();
+        assert_eq!(typical_bands, expected);
+    }
+
+    #[test]
+    fn test_hlc_input_defaults_to_close() {
+        let bars = [
+            (110.0, 90.0, 109.0),
+            (112.0, 92.0, 93.0),
+            (115.0, 95.0, 114.0),
+        ];
+        let mut hlc = BollingerBands::new(3, 2.0).unwrap();
+        let mut close = BollingerBands::new(3, 2.0).unwrap();
+        assert_eq!(hlc.price_source(), PriceSource::Close);
+
+        for &(h, l, c) in &bars {
+            assert_eq!(hlc.calculate_hlc(h, l, c), close.calculate(c));
+        }
+
+        let mut typical = BollingerBands::new(3, 2.0)
+            .unwrap()
+            .with_price_source(PriceSource::Typical);
+        let last = bars
+            .iter()
+            .map(|&(h, l, c)| typical.calculate_hlc(h, l, c))
+            .last()
+            .unwrap()
+            .unwrap();
+        assert!((last.middle - (309.0 + 297.0 + 324.0) / 9.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bandwidth_percentile_flags_squeeze() {
+        let mut bb = BollingerBands::new(5, 2.0)
+            .unwrap()
+            .with_bandwidth_lookback(10);
+        // Swings that narrow steadily, so each new bandwidth is the tightest so far
+        let mut percentiles = Vec::new();
+        for i in 0..20 {
+            let amplitude = 20.0 - i as f64;
+            let price = if i % 2 == 0 {
+                100.0 + amplitude
+            } else {
+                100.0 - amplitude
+            };
+            bb.calculate(price);
+            percentiles.push(bb.bandwidth_percentile());
+        }
+
+        // 4 warmup bars, then 9 more bandwidths before the lookback is full
+        assert!(percentiles[..13].iter().all(Option::is_none));
+        for percentile in &percentiles[13..] {
+            assert!(percentile.unwrap() < 1e-9);
+        }
+        assert!(bb.bandwidth().unwrap() > 0.0);
+
+        // A burst of volatility ranks as the widest bandwidth of the lookback
+        bb.calculate(200.0);
+        assert_eq!(bb.bandwidth_percentile(), Some(100.0));
+    }
+
+    #[test]
+    fn test_non_finite_price_keeps_windows_in_step() {
+        let prices = [100.0, 102.0, 101.0, 104.0, 103.0, 105.0];
+        let mut clean = BollingerBands::new(3, 2.0).unwrap();
+        let mut gapped = BollingerBands::new(3, 2.0).unwrap();
+
+        for (i, &price) in prices.iter().enumerate() {
+            if i == 3 {
+                assert!(gapped.calculate(f64::NAN).is_none());
+            }
+            assert_eq!(gapped.calculate(price), clean.calculate(price));
+        }
+    }
 }
@@ -5,6 +5,17 @@
 //! band plus a specified multiplier times the standard deviation, and the lower band is defined as the
 //! middle band minus that value.
 //!
+//! The standard deviation is delegated to the v2 [`StandardDeviation`] calculator. By default it
+//! uses the population formula (the classic Bollinger definition); [`BollingerBands::with_sample_std_dev`]
+//! switches to the sample (n-1) formula.
+//!
+//! Bands are computed on the close by default. [`BollingerBands::with_price_source`] selects another
+//! [`PriceSource`], e.g. the typical price `(H+L+C)/3`, for candle and HLC input.
+//!
+//! Each reading's bandwidth `(upper - lower) / middle` is kept in a rolling history, and
+//! [`BollingerBands::bandwidth_percentile`] ranks the latest one within it: a reading near 0
+//! is the tightest band of the lookback, the classic squeeze signal.
+//!
 //! # Examples
 //!
 //! ```rust
@@ -27,18 +38,28 @@
 //! }
 //! ```
 
-use super::types::BBResult;
-use crate::v1::sma::main::{SMAError, SimpleMovingAverage};
-use std::collections::VecDeque;
+use super::types::{BBColumns, BBResult, BollingerError};
+use crate::common::types::Candle;
+use crate::transform::price_source::types::PriceSource;
+use crate::util::percent_rank::main::RollingPercentRank;
+use crate::v1::sma::main::SimpleMovingAverage;
+use crate::v1::types::V1Error;
+use crate::v2::std_dev::{main::StandardDeviation, types::StandardDeviationInput};
 
 /// Bollinger Bands indicator.
 pub struct BollingerBands {
     sma: SimpleMovingAverage,
-    period: usize,
+    std_dev: StandardDeviation,
     multiplier: f64,
-    values: VecDeque<f64>,
+    price_source: PriceSource,
+    bandwidth_history: RollingPercentRank,
+    bandwidth: Option<f64>,
+    bandwidth_percentile: Option<f64>,
 }
 
+/// Bandwidth readings ranked by [`BollingerBands::bandwidth_percentile`] unless overridden.
+pub const DEFAULT_BANDWIDTH_LOOKBACK: usize = 125;
+
 impl BollingerBands {
     /// Creates a new BollingerBands indicator.
     ///
@@ -49,7 +70,9 @@ impl BollingerBands {
     ///
     /// # Returns
     ///
-    /// * `Ok(BollingerBands)` on success, or `Err(SMAError)` if the underlying SMA creation fails.
+    /// * `Ok(BollingerBands)` on success.
+    /// * `Err(BollingerError::InvalidPeriod)` if the period is zero.
+    /// * `Err(BollingerError::InvalidMultiplier)` if the multiplier is not positive and finite.
     ///
     /// # Examples
     ///
@@ -59,19 +82,112 @@ impl BollingerBands {
     /// let bb = BollingerBands::new(20, 2.0);
     /// assert!(bb.is_ok());
     /// ```
-    pub fn new(period: usize, multiplier: f64) -> Result<Self, SMAError> {
+    pub fn new(period: usize, multiplier: f64) -> Result<Self, BollingerError> {
+        Self::validate_multiplier(multiplier)?;
+        Ok(BollingerBands {
+            sma: SimpleMovingAverage::new(period)?,
+            std_dev: StandardDeviation::population(period)
+                .map_err(|_| BollingerError::InvalidPeriod)?,
+            multiplier,
+            price_source: PriceSource::Close,
+            bandwidth_history: RollingPercentRank::new(DEFAULT_BANDWIDTH_LOOKBACK),
+            bandwidth: None,
+            bandwidth_percentile: None,
+        })
+    }
+
+    /// Creates a new BollingerBands indicator using the sample (n-1) standard deviation.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of values for the moving average and standard deviation (must be > 1).
+    /// * `multiplier` - The multiplier applied to the standard deviation to determine band width.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BollingerBands)` on success.
+    /// * `Err(BollingerError::InvalidPeriod)` if the period is less than 2.
+    /// * `Err(BollingerError::InvalidMultiplier)` if the multiplier is not positive and finite.
+    pub fn with_sample_std_dev(period: usize, multiplier: f64) -> Result<Self, BollingerError> {
+        Self::validate_multiplier(multiplier)?;
         Ok(BollingerBands {
             sma: SimpleMovingAverage::new(period)?,
-            period,
+            std_dev: StandardDeviation::sample(period)
+                .map_err(|_| BollingerError::InvalidPeriod)?,
             multiplier,
-            values: VecDeque::with_capacity(period),
+            price_source: PriceSource::Close,
+            bandwidth_history: RollingPercentRank::new(DEFAULT_BANDWIDTH_LOOKBACK),
+            bandwidth: None,
+            bandwidth_percentile: None,
         })
     }
 
+    /// Sets the price a candle or HLC bar is reduced to before it enters the bands.
+    ///
+    /// Only [`BollingerBands::calculate_candle`], [`BollingerBands::calculate_hlc`] and the batch
+    /// methods consult the source; [`BollingerBands::calculate`] always uses the price it is given.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use indexes_rs::transform::price_source::types::PriceSource;
+    /// use indexes_rs::v1::bollinger::main::BollingerBands;
+    ///
+    /// let bb = BollingerBands::new(20, 2.0).unwrap().with_price_source(PriceSource::Typical);
+    /// assert_eq!(bb.price_source(), PriceSource::Typical);
+    /// ```
+    pub fn with_price_source(mut self, price_source: PriceSource) -> Self {
+        self.price_source = price_source;
+        self
+    }
+
+    /// Returns the configured price source (close by default).
+    pub fn price_source(&self) -> PriceSource {
+        self.price_source
+    }
+
+    /// Sets how many bandwidth readings the percentile is ranked against (default 125).
+    ///
+    /// Lookbacks below 2 are treated as 2. Any history collected so far is discarded.
+    pub fn with_bandwidth_lookback(mut self, lookback: usize) -> Self {
+        self.bandwidth_history = RollingPercentRank::new(lookback);
+        self.bandwidth_percentile = None;
+        self
+    }
+
+    /// Returns the bandwidth `(upper - lower) / middle` of the latest reading.
+    ///
+    /// `None` during warmup or when the middle band is zero.
+    pub fn bandwidth(&self) -> Option<f64> {
+        self.bandwidth
+    }
+
+    /// Returns the percentile (0-100) of the latest bandwidth within the lookback.
+    ///
+    /// Near 0 means the bands are the tightest they have been over the lookback (a squeeze);
+    /// 100 means the widest. `None` until the lookback holds a full window of bandwidths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use indexes_rs::v1::bollinger::main::BollingerBands;
+    ///
+    /// let mut bb = BollingerBands::new(3, 2.0).unwrap().with_bandwidth_lookback(3);
+    /// for price in [100.0, 110.0, 95.0, 105.0, 104.0] {
+    ///     bb.calculate(price);
+    /// }
+    /// // The calm last window has the narrowest bands of the three readings
+    /// assert_eq!(bb.bandwidth_percentile(), Some(0.0));
+    /// ```
+    pub fn bandwidth_percentile(&self) -> Option<f64> {
+        self.bandwidth_percentile
+    }
+
     /// Calculates the Bollinger Bands for the given price.
     ///
-    /// The method updates the internal SMA and sliding window of prices.
+    /// The method updates the internal SMA and standard deviation windows.
     /// It returns a `BBResult` containing the upper, middle, and lower bands when enough data is available.
+    /// A non-finite price is skipped, leaving both windows untouched.
     ///
     /// # Arguments
     ///
@@ -82,44 +198,148 @@ impl BollingerBands {
     /// * `Some(BBResult)` if enough data is available.
     /// * `None` if not enough data has been collected.
     pub fn calculate(&mut self, price: f64) -> Option<BBResult> {
+        // The standard deviation rejects a non-finite price without touching its window,
+        // so feed it first and keep the SMA window in step by skipping the price as well
+        let std_dev = self
+            .std_dev
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: price,
+            })
+            .ok()?;
         self.sma.add_value(price);
-        self.values.push_back(price);
-        if self.values.len() > self.period {
-            self.values.pop_front();
-        }
         let middle = self.sma.calculate()?;
-        let std_dev = self.calculate_std_dev(middle.value)?;
-        let band_width = std_dev * self.multiplier;
-        Some(BBResult {
+        let band_width = std_dev.std_dev * self.multiplier;
+        let result = BBResult {
             upper: middle.value + band_width,
             middle: middle.value,
             lower: middle.value - band_width,
-        })
+        };
+        self.update_bandwidth(&result);
+        Some(result)
     }
 
-    /// Calculates the standard deviation of the prices in the current window.
+    /// Calculates the bands for a new price, rejecting non-finite prices.
+    ///
+    /// Unlike [`BollingerBands::calculate`], a NaN or infinite price is reported as an error and leaves
+    /// the state untouched instead of poisoning later readings.
     ///
     /// # Arguments
     ///
-    /// * `mean` - The mean (middle band) value.
+    /// * `price` - The latest price.
     ///
     /// # Returns
     ///
-    /// * `Some(f64)` containing the standard deviation if the window is full.
-    /// * `None` if the window does not yet contain enough values.
-    fn calculate_std_dev(&self, mean: f64) -> Option<f64> {
-        if self.values.len() < self.period {
-            return None;
+    /// * `Ok(Some(BBResult))` once enough data is available.
+    /// * `Ok(None)` while warming up.
+    /// * `Err(V1Error::InvalidPrice)` if `price` is NaN or infinite.
+    pub fn try_calculate(&mut self, price: f64) -> Result<Option<BBResult>, V1Error> {
+        if !price.is_finite() {
+            return Err(V1Error::InvalidPrice);
         }
-        let variance = self
-            .values
+
+        Ok(self.calculate(price))
+    }
+
+    /// Calculates the bands for a candle, reduced to a single price by the configured source.
+    ///
+    /// # Arguments
+    ///
+    /// * `candle` - The latest bar.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(BBResult)` if enough data is available.
+    /// * `None` if not enough data has been collected.
+    pub fn calculate_candle(&mut self, candle: &Candle) -> Option<BBResult> {
+        self.calculate(self.price_source.apply(candle))
+    }
+
+    /// Calculates the bands for a high/low/close bar, reduced by the configured source.
+    ///
+    /// HLC bars carry no open, so the open-based sources (`Open`, `Average`) treat the close as the open.
+    ///
+    /// # Arguments
+    ///
+    /// * `high` - The bar's high.
+    /// * `low` - The bar's low.
+    /// * `close` - The bar's close.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(BBResult)` if enough data is available.
+    /// * `None` if not enough data has been collected.
+    pub fn calculate_hlc(&mut self, high: f64, low: f64, close: f64) -> Option<BBResult> {
+        self.calculate_candle(&Candle::new(close, high, low, close, 0.0))
+    }
+
+    /// Feeds every candle through the configured price source and returns one result per bar.
+    ///
+    /// # Arguments
+    ///
+    /// * `candles` - The bars to process, oldest first.
+    ///
+    /// # Returns
+    ///
+    /// * A vector with one entry per candle; warmup bars are `None`.
+    pub fn calculate_batch(&mut self, candles: &[Candle]) -> Vec<Option<BBResult>> {
+        candles
             .iter()
-            .map(|&value| {
-                let diff = mean - value;
-                diff * diff
-            })
-            .sum::<f64>()
-            / self.period as f64;
-        Some(variance.sqrt())
+            .map(|candle| self.calculate_candle(candle))
+            .collect()
+    }
+
+    /// Feeds every candle through the configured price source and returns the bands as columns.
+    ///
+    /// Produces the same values as [`BollingerBands::calculate_batch`], laid out as one vector per band,
+    /// alongside each candle's timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `candles` - The bars to process, oldest first.
+    ///
+    /// # Returns
+    ///
+    /// * A `BBColumns` whose columns each have one entry per candle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use indexes_rs::common::types::Candle;
+    /// use indexes_rs::v1::bollinger::main::BollingerBands;
+    ///
+    /// let candles: Vec<Candle> = [100.0, 101.0, 102.0, 101.5].iter().map(|&c| Candle::new(c, c, c, c, 0.0)).collect();
+    /// let mut bb = BollingerBands::new(3, 2.0).unwrap();
+    /// let columns = bb.calculate_columns(&candles);
+    /// assert_eq!(columns.len(), 4);
+    /// assert!(columns.upper[1].is_none());
+    /// assert!(columns.upper[2].is_some());
+    /// ```
+    pub fn calculate_columns(&mut self, candles: &[Candle]) -> BBColumns {
+        candles
+            .iter()
+            .map(|candle| (candle.timestamp, self.calculate_candle(candle)))
+            .collect()
+    }
+
+    /// Records the bandwidth of a new reading and ranks it within the lookback.
+    fn update_bandwidth(&mut self, result: &BBResult) {
+        let bandwidth = (result.upper - result.lower) / result.middle;
+        if bandwidth.is_finite() {
+            self.bandwidth = Some(bandwidth);
+            self.bandwidth_percentile = self.bandwidth_history.push(bandwidth);
+        } else {
+            self.bandwidth = None;
+            self.bandwidth_percentile = None;
+        }
+    }
+
+    /// Rejects negative, zero, and non-finite multipliers, which would produce inverted or NaN bands.
+    fn validate_multiplier(multiplier: f64) -> Result<(), BollingerError> {
+        if multiplier.is_finite() && multiplier > 0.0 {
+            Ok(())
+        } else {
+            Err(BollingerError::InvalidMultiplier)
+        }
     }
 }
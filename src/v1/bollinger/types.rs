@@ -1,3 +1,4 @@
+use crate::v1::sma::main::SMAError;
 use serde::Serialize;
 
 /// The result of a Bollinger Bands calculation.
@@ -10,3 +11,90 @@ pub struct BBResult {
     /// The lower Bollinger Band.
     pub lower: f64,
 }
+
+/// Bollinger Bands output in columnar (struct-of-arrays) form.
+///
+/// Each column has one entry per input bar; warmup bars are `None`, as are
+/// timestamps of bars that did not carry one.
+#[derive(Debug, Default, PartialEq, Serialize, Clone)]
+pub struct BBColumns {
+    /// The timestamp of each bar.
+    pub timestamp: Vec<Option<i64>>,
+    /// The upper Bollinger Band per bar.
+    pub upper: Vec<Option<f64>>,
+    /// The middle Bollinger Band (SMA) per bar.
+    pub middle: Vec<Option<f64>>,
+    /// The lower Bollinger Band per bar.
+    pub lower: Vec<Option<f64>>,
+}
+
+impl BBColumns {
+    /// Appends one bar's result to every column.
+    pub fn push(&mut self, timestamp: Option<i64>, result: Option<&BBResult>) {
+        self.timestamp.push(timestamp);
+        self.upper.push(result.map(|r| r.upper));
+        self.middle.push(result.map(|r| r.middle));
+        self.lower.push(result.map(|r| r.lower));
+    }
+
+    /// Returns the number of bars in the columns.
+    pub fn len(&self) -> usize {
+        self.middle.len()
+    }
+
+    /// Returns `true` if the columns hold no bars.
+    pub fn is_empty(&self) -> bool {
+        self.middle.is_empty()
+    }
+
+    /// Converts the columns into a Polars `DataFrame`.
+    ///
+    /// The frame has a `timestamp` column followed by one column per series, named after
+    /// the struct fields. `None` entries (warmup bars, missing timestamps) become nulls.
+    #[cfg(feature = "polars")]
+    pub fn to_dataframe(&self) -> polars::prelude::PolarsResult<polars::prelude::DataFrame> {
+        use polars::prelude::{Column, DataFrame};
+
+        DataFrame::new(vec![
+            Column::new("timestamp".into(), &self.timestamp),
+            Column::new("upper".into(), &self.upper),
+            Column::new("middle".into(), &self.middle),
+            Column::new("lower".into(), &self.lower),
+        ])
+    }
+}
+
+impl FromIterator<Option<BBResult>> for BBColumns {
+    fn from_iter<I: IntoIterator<Item = Option<BBResult>>>(iter: I) -> Self {
+        let mut columns = BBColumns::default();
+        for result in iter {
+            columns.push(None, result.as_ref());
+        }
+        columns
+    }
+}
+
+/// An error type for BollingerBands.
+#[derive(Debug, PartialEq)]
+pub enum BollingerError {
+    /// Indicates that the period is invalid (e.g., zero, or 1 with the sample standard deviation).
+    InvalidPeriod,
+    /// Indicates that the multiplier is not a positive, finite number.
+    InvalidMultiplier,
+}
+
+impl From<SMAError> for BollingerError {
+    fn from(_: SMAError) -> Self {
+        BollingerError::InvalidPeriod
+    }
+}
+
+impl FromIterator<(Option<i64>, Option<BBResult>)> for BBColumns {
+    fn from_iter<I: IntoIterator<Item = (Option<i64>, Option<BBResult>)>>(iter: I) -> Self {
+        let mut columns = BBColumns::default();
+        for (timestamp, result) in iter {
+            columns.push(timestamp, result.as_ref());
+        }
+        columns
+    }
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use crate::v1::{coppock::main::Coppock, types::Cross};
+
+    #[test]
+    fn test_insufficient_data() {
+        let mut coppock = Coppock::new(4, 3, 3).unwrap();
+        // ROC(4) needs 5 prices, then the WMA needs 3 sums.
+        for price in [100.0, 101.0, 102.0, 103.0, 104.0, 105.0] {
+            assert!(coppock.calculate(price).is_none());
+        }
+        assert!(coppock.calculate(106.0).is_some());
+    }
+
+    #[test]
+    fn test_turns_up_from_below_zero_on_recovery() {
+        let mut coppock = Coppock::new(4, 3, 3).unwrap();
+
+        // Sell-off: the curve goes negative.
+        let decline: Vec<f64> = (0..10).map(|i| 100.0 - i as f64 * 2.0).collect();
+        let mut values = Vec::new();
+        for price in decline {
+            if let Some(result) = coppock.calculate(price) {
+                values.push(result.value);
+            }
+        }
+        assert!(values.last().unwrap() < &0.0);
+
+        // Recovery: the curve turns up while still below zero, then crosses above it.
+        let mut turned_up_below_zero = false;
+        let mut crosses = Vec::new();
+        let mut prev = *values.last().unwrap();
+        for i in 1..=12 {
+            let result = coppock.calculate(82.0 + i as f64 * 2.0).unwrap();
+            if result.value > prev && prev < 0.0 {
+                turned_up_below_zero = true;
+            }
+            if let Some(cross) = result.zero_cross {
+                crosses.push(cross);
+            }
+            prev = result.value;
+        }
+        assert!(turned_up_below_zero);
+        assert_eq!(crosses, vec![Cross::Bullish]);
+        assert!(prev > 0.0);
+    }
+
+    #[test]
+    fn test_default_parameters() {
+        let mut coppock = Coppock::default();
+        let mut result = None;
+        for i in 0..30 {
+            result = coppock.calculate(100.0 + i as f64);
+        }
+        // A steady rise keeps the curve positive.
+        assert!(result.unwrap().value > 0.0);
+    }
+}
@@ -0,0 +1,104 @@
+//! # Coppock Curve Module
+//!
+//! This module implements the Coppock Curve, a long-term momentum indicator originally designed
+//! to spot major market bottoms. It is the Weighted Moving Average of the sum of a long and a
+//! short Rate of Change:
+//!
+//! \[\text{Coppock} = \text{WMA}_{10}(\text{ROC}_{14} + \text{ROC}_{11})\]
+//!
+//! The classic buy signal is the curve turning up from below zero; a zero-line cross is
+//! reported on the bar where the curve changes sign.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use indexes_rs::v1::coppock::main::Coppock;
+//!
+//! let mut coppock = Coppock::new(4, 3, 3).unwrap();
+//! for price in [100.0, 98.0, 95.0, 93.0, 92.0, 93.0, 95.0, 98.0, 102.0, 106.0] {
+//!     if let Some(result) = coppock.calculate(price) {
+//!         println!("Coppock: {:.2}, Zero cross: {:?}", result.value, result.zero_cross);
+//!     }
+//! }
+//! ```
+
+use super::types::CoppockResult;
+use crate::v1::{
+    roc::main::ROC,
+    types::Cross,
+    wma::main::{WMAError, WeightedMovingAverage},
+};
+
+/// A Coppock Curve indicator.
+pub struct Coppock {
+    long_roc: ROC,
+    short_roc: ROC,
+    wma: WeightedMovingAverage,
+    prev_value: Option<f64>,
+}
+
+impl Coppock {
+    /// Default long ROC period.
+    pub const DEFAULT_LONG_ROC: usize = 14;
+    /// Default short ROC period.
+    pub const DEFAULT_SHORT_ROC: usize = 11;
+    /// Default WMA period.
+    pub const DEFAULT_WMA_PERIOD: usize = 10;
+
+    /// Creates a new Coppock Curve indicator.
+    ///
+    /// # Arguments
+    ///
+    /// * `long_roc` - The period of the long ROC.
+    /// * `short_roc` - The period of the short ROC.
+    /// * `wma_period` - The period of the WMA applied to the ROC sum.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Coppock)` on success, or `Err(WMAError)` if the WMA period is zero.
+    pub fn new(long_roc: usize, short_roc: usize, wma_period: usize) -> Result<Self, WMAError> {
+        Ok(Coppock {
+            long_roc: ROC::new(long_roc),
+            short_roc: ROC::new(short_roc),
+            wma: WeightedMovingAverage::new(wma_period)?,
+            prev_value: None,
+        })
+    }
+
+    /// Updates the curve with a new price and returns the current result.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The latest price.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(CoppockResult)` once both ROCs and the WMA are warmed up.
+    /// * `None` otherwise.
+    pub fn calculate(&mut self, price: f64) -> Option<CoppockResult> {
+        let long = self.long_roc.calculate(price);
+        let short = self.short_roc.calculate(price);
+        let value = self.wma.add_value(long?.value + short?.value)?;
+
+        let zero_cross = match self.prev_value {
+            Some(prev) if prev <= 0.0 && value > 0.0 => Some(Cross::Bullish),
+            Some(prev) if prev >= 0.0 && value < 0.0 => Some(Cross::Bearish),
+            _ => None,
+        };
+        self.prev_value = Some(value);
+
+        Some(CoppockResult { value, zero_cross })
+    }
+}
+
+impl Default for Coppock {
+    /// Creates a Coppock Curve with the standard 14/11/10 parameters.
+    fn default() -> Self {
+        Self::new(
+            Self::DEFAULT_LONG_ROC,
+            Self::DEFAULT_SHORT_ROC,
+            Self::DEFAULT_WMA_PERIOD,
+        )
+        .expect("Default parameters should always be valid")
+    }
+}
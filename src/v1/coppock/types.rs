@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+use crate::v1::types::Cross;
+
+/// The result of a Coppock Curve calculation.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CoppockResult {
+    /// The Coppock Curve value (WMA of the summed ROCs).
+    pub value: f64,
+    /// Set on the bar where the curve crosses the zero line.
+    pub zero_cross: Option<Cross>,
+}
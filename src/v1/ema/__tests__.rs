@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
     use crate::v1::ema::main::ExponentialMovingAverage;
+    use crate::v1::ema::types::EMAError;
+    use crate::v1::types::V1Error;
 
     #[test]
     fn test_initial_value() {
@@ -28,4 +30,44 @@ mod tests {
         ema.add_value(100.0);
         assert_eq!(ema.get_current_value().unwrap(), 100.0);
     }
+
+    #[test]
+    fn test_from_alpha_matches_period_constructor() {
+        for period in [1, 5, 10, 26] {
+            let mut by_period = ExponentialMovingAverage::new(period);
+            let mut by_alpha =
+                ExponentialMovingAverage::from_alpha(2.0 / (period as f64 + 1.0)).unwrap();
+            assert_eq!(by_alpha.alpha(), by_period.alpha());
+
+            for price in [100.0, 102.5, 101.0, 99.75, 104.0] {
+                assert_eq!(by_alpha.add_value(price), by_period.add_value(price));
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_alpha_rejects_out_of_range() {
+        for alpha in [0.0, -0.5, 1.5, f64::NAN] {
+            assert_eq!(
+                ExponentialMovingAverage::from_alpha(alpha).err(),
+                Some(EMAError::InvalidAlpha)
+            );
+        }
+        assert!(ExponentialMovingAverage::from_alpha(1.0).is_ok());
+    }
+
+    #[test]
+    fn test_try_new_rejects_zero_period() {
+        assert_eq!(
+            ExponentialMovingAverage::try_new(0).err(),
+            Some(V1Error::InvalidPeriod)
+        );
+        assert!(ExponentialMovingAverage::try_new(10).is_ok());
+
+        let error: V1Error = ExponentialMovingAverage::from_alpha(2.0)
+            .err()
+            .unwrap()
+            .into();
+        assert_eq!(error, V1Error::InvalidAlpha);
+    }
 }
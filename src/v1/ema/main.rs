@@ -30,7 +30,12 @@
 //! assert_eq!(ema.get_current_value().unwrap(), second);
 //! ```
 
+use super::types::EMAError;
+use crate::v1::types::V1Error;
+use serde::{Deserialize, Serialize};
+
 /// An Exponential Moving Average (EMA) indicator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ExponentialMovingAverage {
     /// The smoothing factor (alpha).
     pub alpha: f64,
@@ -59,6 +64,60 @@ impl ExponentialMovingAverage {
         }
     }
 
+    /// Creates a new `ExponentialMovingAverage` indicator, rejecting a zero period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of periods for the EMA calculation.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ExponentialMovingAverage)` for a non-zero period.
+    /// * `Err(V1Error::InvalidPeriod)` if `period` is zero.
+    pub fn try_new(period: usize) -> Result<Self, V1Error> {
+        if period == 0 {
+            return Err(V1Error::InvalidPeriod);
+        }
+
+        Ok(Self::new(period))
+    }
+
+    /// Creates a new `ExponentialMovingAverage` indicator with a custom smoothing factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The smoothing factor, which must satisfy `0 < alpha <= 1`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ExponentialMovingAverage)` for a valid alpha.
+    /// * `Err(EMAError::InvalidAlpha)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use indexes_rs::v1::ema::main::ExponentialMovingAverage;
+    ///
+    /// let ema = ExponentialMovingAverage::from_alpha(0.1).unwrap();
+    /// assert_eq!(ema.alpha(), 0.1);
+    /// assert!(ExponentialMovingAverage::from_alpha(0.0).is_err());
+    /// ```
+    pub fn from_alpha(alpha: f64) -> Result<Self, EMAError> {
+        if !(alpha > 0.0 && alpha <= 1.0) {
+            return Err(EMAError::InvalidAlpha);
+        }
+
+        Ok(ExponentialMovingAverage {
+            alpha,
+            current_ema: None,
+        })
+    }
+
+    /// Returns the smoothing factor (alpha).
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
     /// Adds a new price value to update the EMA.
     ///
     /// If no previous EMA exists, the current price is used as the initial EMA.
@@ -0,0 +1,6 @@
+/// An error type for the ExponentialMovingAverage.
+#[derive(Debug, PartialEq)]
+pub enum EMAError {
+    /// Indicates that the smoothing factor is outside `(0, 1]` or not finite.
+    InvalidAlpha,
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use crate::v1::envelope::{
+        main::Envelope,
+        types::{EnvelopeError, EnvelopeMAType},
+    };
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(matches!(
+            Envelope::new(0, 0.025, EnvelopeMAType::Simple),
+            Err(EnvelopeError::InvalidPeriod)
+        ));
+        assert!(matches!(
+            Envelope::new(20, -0.01, EnvelopeMAType::Simple),
+            Err(EnvelopeError::InvalidPercent)
+        ));
+        assert!(matches!(
+            Envelope::new(20, 1.0, EnvelopeMAType::Exponential),
+            Err(EnvelopeError::InvalidPercent)
+        ));
+    }
+
+    #[test]
+    fn test_sma_envelope_band_distances() {
+        let pct = 0.025;
+        let mut envelope = Envelope::new(3, pct, EnvelopeMAType::Simple).unwrap();
+
+        assert!(envelope.calculate(10.0).is_none());
+        assert!(envelope.calculate(20.0).is_none());
+
+        let result = envelope.calculate(30.0).unwrap();
+        assert!((result.middle - 20.0).abs() < 1e-12);
+        assert!((result.upper - result.middle - result.middle * pct).abs() < 1e-12);
+        assert!((result.middle - result.lower - result.middle * pct).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ema_envelope_band_distances() {
+        let pct = 0.05;
+        let mut envelope = Envelope::new(10, pct, EnvelopeMAType::Exponential).unwrap();
+
+        for price in [100.0, 102.0, 101.0, 105.0, 110.0, 108.0] {
+            let result = envelope.calculate(price).unwrap();
+            assert!(((result.upper - result.middle) / result.middle - pct).abs() < 1e-12);
+            assert!(((result.middle - result.lower) / result.middle - pct).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_bands_stay_ordered_below_zero() {
+        let mut envelope = Envelope::new(3, 0.1, EnvelopeMAType::Simple).unwrap();
+        envelope.calculate(-10.0);
+        envelope.calculate(-20.0);
+        let result = envelope.calculate(-30.0).unwrap();
+        assert_eq!(result.middle, -20.0);
+        assert!((result.upper + 18.0).abs() < 1e-12);
+        assert!((result.lower + 22.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,106 @@
+//! # Moving Average Envelope Module
+//!
+//! This module implements a fixed-percent moving average envelope. Bands are placed a constant
+//! percentage above and below a moving average (SMA or EMA):
+//!
+//! \[\text{Upper} = \text{MA} \times (1 + pct)\]
+//!
+//! \[\text{Lower} = \text{MA} \times (1 - pct)\]
+//!
+//! The offset is taken from the magnitude of the moving average, so the upper band stays above the
+//! lower one for series that trade below zero.
+//!
+//! Unlike Bollinger Bands, the band width does not react to volatility; it is always the same
+//! fraction of the moving average.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use indexes_rs::v1::envelope::main::Envelope;
+//! use indexes_rs::v1::envelope::types::EnvelopeMAType;
+//!
+//! // 20-period SMA with bands 2.5% away from it.
+//! let mut envelope = Envelope::new(20, 0.025, EnvelopeMAType::Simple).unwrap();
+//!
+//! for price in 0..25 {
+//!     if let Some(result) = envelope.calculate(100.0 + price as f64) {
+//!         println!("Upper: {:.2}, Middle: {:.2}, Lower: {:.2}", result.upper, result.middle, result.lower);
+//!     }
+//! }
+//! ```
+
+use super::types::{EnvelopeError, EnvelopeMAType, EnvelopeResult};
+use crate::v1::{ema::main::ExponentialMovingAverage, sma::main::SimpleMovingAverage};
+
+/// The moving average backing the envelope.
+enum EnvelopeMA {
+    Simple(SimpleMovingAverage),
+    Exponential(ExponentialMovingAverage),
+}
+
+/// A fixed-percent moving average envelope indicator.
+pub struct Envelope {
+    ma: EnvelopeMA,
+    /// The band distance from the moving average, as a fraction (0.025 = 2.5%).
+    pub pct: f64,
+}
+
+impl Envelope {
+    /// Creates a new Envelope indicator.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The moving average period.
+    /// * `pct` - The band distance as a fraction of the moving average (e.g. `0.025` for 2.5%).
+    /// * `ma_type` - Whether the middle line is an SMA or an EMA.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Envelope)` on success.
+    /// * `Err(EnvelopeError)` if the period is zero or the percent is outside `[0, 1)`.
+    pub fn new(period: usize, pct: f64, ma_type: EnvelopeMAType) -> Result<Self, EnvelopeError> {
+        if period == 0 {
+            return Err(EnvelopeError::InvalidPeriod);
+        }
+        if !pct.is_finite() || !(0.0..1.0).contains(&pct) {
+            return Err(EnvelopeError::InvalidPercent);
+        }
+
+        let ma = match ma_type {
+            EnvelopeMAType::Simple => EnvelopeMA::Simple(
+                SimpleMovingAverage::new(period).map_err(|_| EnvelopeError::InvalidPeriod)?,
+            ),
+            EnvelopeMAType::Exponential => {
+                EnvelopeMA::Exponential(ExponentialMovingAverage::new(period))
+            }
+        };
+
+        Ok(Envelope { ma, pct })
+    }
+
+    /// Updates the envelope with a new price.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The latest price.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(EnvelopeResult)` once the moving average is available.
+    /// * `None` while an SMA is still filling its window.
+    pub fn calculate(&mut self, price: f64) -> Option<EnvelopeResult> {
+        let middle = match &mut self.ma {
+            EnvelopeMA::Simple(sma) => {
+                sma.add_value(price);
+                sma.calculate()?.value
+            }
+            EnvelopeMA::Exponential(ema) => ema.add_value(price)?,
+        };
+
+        Some(EnvelopeResult {
+            upper: middle + middle.abs() * self.pct,
+            middle,
+            lower: middle - middle.abs() * self.pct,
+        })
+    }
+}
@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+/// The moving average type used for the envelope's middle line.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum EnvelopeMAType {
+    /// Simple Moving Average.
+    Simple,
+    /// Exponential Moving Average.
+    Exponential,
+}
+
+/// The result of a moving-average envelope calculation.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct EnvelopeResult {
+    /// The upper band (`middle * (1 + pct)`).
+    pub upper: f64,
+    /// The middle line (the moving average).
+    pub middle: f64,
+    /// The lower band (`middle * (1 - pct)`).
+    pub lower: f64,
+}
+
+/// An error type for the Envelope indicator.
+#[derive(Debug, PartialEq)]
+pub enum EnvelopeError {
+    /// Indicates that the provided period is invalid (e.g., zero).
+    InvalidPeriod,
+    /// Indicates that the percent is negative, not finite, or 1.0 or more.
+    InvalidPercent,
+}
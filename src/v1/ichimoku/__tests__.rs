@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use crate::v1::{ichimoku::main::Ichimoku, types::V1Error};
+
+    /// A zig-zagging series so highs and lows come from different bars.
+    fn bars() -> Vec<(f64, f64, f64)> {
+        (0..120)
+            .map(|i| {
+                let mid = 100.0 + (i as f64 * 0.3).sin() * 8.0 + i as f64 * 0.1;
+                let spread = 1.0 + (i % 4) as f64 * 0.5;
+                (mid + spread, mid - spread, mid + spread / 3.0)
+            })
+            .collect()
+    }
+
+    fn midpoint(bars: &[(f64, f64, f64)]) -> f64 {
+        let highest = bars.iter().map(|b| b.0).fold(f64::MIN, f64::max);
+        let lowest = bars.iter().map(|b| b.1).fold(f64::MAX, f64::min);
+        (highest + lowest) / 2.0
+    }
+
+    #[test]
+    fn test_invalid_period() {
+        assert!(matches!(
+            Ichimoku::new(9, 0, 52, 26),
+            Err(V1Error::InvalidPeriod)
+        ));
+        assert!(Ichimoku::new(9, 26, 52, 0).is_ok());
+    }
+
+    #[test]
+    fn test_tenkan_is_nine_bar_midpoint() {
+        let bars = bars();
+        let mut ichimoku = Ichimoku::default();
+
+        for (i, &(high, low, close)) in bars.iter().enumerate() {
+            let result = ichimoku.calculate(high, low, close);
+            if i < 8 {
+                assert_eq!(result.tenkan, None);
+            } else {
+                assert_eq!(result.tenkan, Some(midpoint(&bars[i - 8..=i])));
+            }
+            if i >= 25 {
+                assert_eq!(result.kijun, Some(midpoint(&bars[i - 25..=i])));
+            }
+        }
+    }
+
+    #[test]
+    fn test_spans_are_displaced() {
+        let bars = bars();
+        let mut ichimoku = Ichimoku::default();
+        let results: Vec<_> = bars
+            .iter()
+            .map(|&(high, low, close)| ichimoku.calculate(high, low, close))
+            .collect();
+
+        for (i, result) in results.iter().enumerate() {
+            // Senkou A needs the kijun (bar 25) and 26 bars of displacement
+            if i < 25 + 26 {
+                assert_eq!(result.senkou_a, None, "bar {}", i);
+            } else {
+                let source = &results[i - 26];
+                let expected = (source.tenkan.unwrap() + source.kijun.unwrap()) / 2.0;
+                assert_eq!(result.senkou_a, Some(expected));
+            }
+
+            // Senkou B needs 52 bars and 26 bars of displacement
+            if i < 51 + 26 {
+                assert_eq!(result.senkou_b, None, "bar {}", i);
+            } else {
+                assert_eq!(result.senkou_b, Some(midpoint(&bars[i - 26 - 51..=i - 26])));
+            }
+
+            // The close of bar i is the Chikou Span of bar i - 26
+            if i < 26 {
+                assert_eq!(result.chikou, None);
+            } else {
+                assert_eq!(result.chikou, Some(bars[i].2));
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_calculate_rejects_non_finite() {
+        let mut ichimoku = Ichimoku::new(2, 3, 4, 1).unwrap();
+        assert_eq!(
+            ichimoku.try_calculate(f64::NAN, 1.0, 1.0),
+            Err(V1Error::InvalidPrice)
+        );
+        // The rejected bar left no trace: the second valid bar completes the 2-bar tenkan
+        ichimoku.try_calculate(11.0, 9.0, 10.0).unwrap();
+        let result = ichimoku.try_calculate(13.0, 10.0, 12.0).unwrap();
+        assert_eq!(result.tenkan, Some(11.0));
+        assert_eq!(result.chikou, Some(12.0));
+    }
+}
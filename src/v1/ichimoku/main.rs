@@ -0,0 +1,207 @@
+//! # Ichimoku Cloud Module
+//!
+//! This module implements the Ichimoku Kinko Hyo ("Ichimoku Cloud"). Its lines are midpoints of
+//! the highest high and lowest low over a window, some of them displaced in time:
+//!
+//! - **Tenkan-sen:** midpoint over the last 9 bars.
+//! - **Kijun-sen:** midpoint over the last 26 bars.
+//! - **Senkou Span A:** `(tenkan + kijun) / 2`, plotted 26 bars ahead.
+//! - **Senkou Span B:** midpoint over the last 52 bars, plotted 26 bars ahead.
+//! - **Chikou Span:** the close, plotted 26 bars back.
+//!
+//! Like the Alligator, a line displaced forward reports, on the current bar, the value computed
+//! `displacement` bars ago, so the two Senkou spans returned for a bar form the cloud drawn under
+//! it. The Chikou Span cannot be drawn on the current bar; it is returned as the value belonging
+//! to the bar `displacement` bars ago.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use indexes_rs::v1::ichimoku::main::Ichimoku;
+//!
+//! let mut ichimoku = Ichimoku::default();
+//! let mut result = None;
+//! for i in 0..80 {
+//!     let close = 100.0 + i as f64;
+//!     result = Some(ichimoku.calculate(close + 1.0, close - 1.0, close));
+//! }
+//!
+//! let lines = result.unwrap();
+//! // In a steady uptrend the fast line leads and price is above the cloud
+//! assert!(lines.tenkan.unwrap() > lines.kijun.unwrap());
+//! assert!(lines.senkou_a.unwrap() > lines.senkou_b.unwrap());
+//! ```
+
+use super::types::IchimokuResult;
+use crate::util::rolling_extremes::main::RollingExtremes;
+use crate::v1::types::V1Error;
+use std::collections::VecDeque;
+
+/// Midpoint of the highest high and lowest low over a window.
+struct MidpointLine {
+    highs: RollingExtremes,
+    lows: RollingExtremes,
+}
+
+impl MidpointLine {
+    fn new(period: usize) -> Self {
+        MidpointLine {
+            highs: RollingExtremes::new(period),
+            lows: RollingExtremes::new(period),
+        }
+    }
+
+    /// Feeds a bar and returns the midpoint once the window is full.
+    fn update(&mut self, high: f64, low: f64) -> Option<f64> {
+        let (_, highest) = self.highs.push(high);
+        let (lowest, _) = self.lows.push(low);
+        self.highs.is_full().then(|| (highest + lowest) / 2.0)
+    }
+}
+
+/// Values delayed by `shift` bars.
+///
+/// Every bar is buffered, including warmup bars without a value, so the output on any bar is
+/// exactly the input from `shift` bars earlier.
+struct Displacement {
+    shift: usize,
+    buffer: VecDeque<Option<f64>>,
+}
+
+impl Displacement {
+    fn new(shift: usize) -> Self {
+        Displacement {
+            shift,
+            buffer: VecDeque::with_capacity(shift + 1),
+        }
+    }
+
+    /// Feeds the current bar's value and returns the one from `shift` bars ago.
+    fn update(&mut self, value: Option<f64>) -> Option<f64> {
+        self.buffer.push_back(value);
+        if self.buffer.len() > self.shift {
+            self.buffer.pop_front().flatten()
+        } else {
+            None
+        }
+    }
+}
+
+/// An Ichimoku Cloud indicator.
+pub struct Ichimoku {
+    tenkan: MidpointLine,
+    kijun: MidpointLine,
+    senkou_b_line: MidpointLine,
+    senkou_a: Displacement,
+    senkou_b: Displacement,
+    displacement: usize,
+    /// Number of bars processed so far.
+    bars: usize,
+}
+
+impl Ichimoku {
+    /// Default Tenkan-sen period.
+    pub const DEFAULT_TENKAN_PERIOD: usize = 9;
+    /// Default Kijun-sen period.
+    pub const DEFAULT_KIJUN_PERIOD: usize = 26;
+    /// Default Senkou Span B period.
+    pub const DEFAULT_SENKOU_B_PERIOD: usize = 52;
+    /// Default displacement of the Senkou spans (forward) and the Chikou Span (backward).
+    pub const DEFAULT_DISPLACEMENT: usize = 26;
+
+    /// Creates a new Ichimoku Cloud indicator.
+    ///
+    /// # Arguments
+    ///
+    /// * `tenkan_period` - The window of the Tenkan-sen.
+    /// * `kijun_period` - The window of the Kijun-sen.
+    /// * `senkou_b_period` - The window of Senkou Span B.
+    /// * `displacement` - How many bars the Senkou spans are shifted forward and the Chikou Span
+    ///   back.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Ichimoku)` on success, or `Err(V1Error::InvalidPeriod)` if any period is zero.
+    pub fn new(
+        tenkan_period: usize,
+        kijun_period: usize,
+        senkou_b_period: usize,
+        displacement: usize,
+    ) -> Result<Self, V1Error> {
+        if tenkan_period == 0 || kijun_period == 0 || senkou_b_period == 0 {
+            return Err(V1Error::InvalidPeriod);
+        }
+
+        Ok(Ichimoku {
+            tenkan: MidpointLine::new(tenkan_period),
+            kijun: MidpointLine::new(kijun_period),
+            senkou_b_line: MidpointLine::new(senkou_b_period),
+            senkou_a: Displacement::new(displacement),
+            senkou_b: Displacement::new(displacement),
+            displacement,
+            bars: 0,
+        })
+    }
+
+    /// Updates the indicator with a new bar and returns every line available so far.
+    ///
+    /// # Arguments
+    ///
+    /// * `high` - The bar's high.
+    /// * `low` - The bar's low.
+    /// * `close` - The bar's close, used for the Chikou Span.
+    pub fn calculate(&mut self, high: f64, low: f64, close: f64) -> IchimokuResult {
+        // Update every window so each keeps its own history even while the others are warming up.
+        let tenkan = self.tenkan.update(high, low);
+        let kijun = self.kijun.update(high, low);
+        let senkou_b_leading = self.senkou_b_line.update(high, low);
+        let senkou_a_leading = tenkan
+            .zip(kijun)
+            .map(|(tenkan, kijun)| (tenkan + kijun) / 2.0);
+
+        self.bars += 1;
+        // The current close belongs to the bar `displacement` bars back, once that bar exists
+        let chikou = (self.bars > self.displacement).then_some(close);
+
+        IchimokuResult {
+            tenkan,
+            kijun,
+            senkou_a: self.senkou_a.update(senkou_a_leading),
+            senkou_b: self.senkou_b.update(senkou_b_leading),
+            chikou,
+        }
+    }
+
+    /// Like [`calculate`](Self::calculate), but rejects non-finite prices.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(IchimokuResult)` for finite prices.
+    /// * `Err(V1Error::InvalidPrice)` if any price is NaN or infinite; the indicator state is left
+    ///   unchanged.
+    pub fn try_calculate(
+        &mut self,
+        high: f64,
+        low: f64,
+        close: f64,
+    ) -> Result<IchimokuResult, V1Error> {
+        if !(high.is_finite() && low.is_finite() && close.is_finite()) {
+            return Err(V1Error::InvalidPrice);
+        }
+
+        Ok(self.calculate(high, low, close))
+    }
+}
+
+impl Default for Ichimoku {
+    /// Creates an Ichimoku Cloud with the classic 9/26/52 periods and 26-bar displacement.
+    fn default() -> Self {
+        Self::new(
+            Self::DEFAULT_TENKAN_PERIOD,
+            Self::DEFAULT_KIJUN_PERIOD,
+            Self::DEFAULT_SENKOU_B_PERIOD,
+            Self::DEFAULT_DISPLACEMENT,
+        )
+        .expect("default Ichimoku periods are non-zero")
+    }
+}
@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+/// The result of an Ichimoku Cloud calculation.
+///
+/// Each line becomes available once its own window (and displacement) has filled, so
+/// every field is optional.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct IchimokuResult {
+    /// Tenkan-sen (conversion line): midpoint of the highest high and lowest low over the tenkan
+    /// period.
+    pub tenkan: Option<f64>,
+    /// Kijun-sen (base line): midpoint of the highest high and lowest low over the kijun period.
+    pub kijun: Option<f64>,
+    /// Senkou Span A on the current bar: `(tenkan + kijun) / 2` computed `displacement` bars
+    /// ago.
+    pub senkou_a: Option<f64>,
+    /// Senkou Span B on the current bar: the senkou B period midpoint computed `displacement`
+    /// bars ago.
+    pub senkou_b: Option<f64>,
+    /// Chikou Span of the bar `displacement` bars ago, i.e. the current close plotted that far
+    /// back.
+    pub chikou: Option<f64>,
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use crate::v1::{kst::main::KST, sma::main::SMAError};
+
+    fn short_kst() -> KST {
+        KST::new([2, 3, 4, 5], [2, 2, 2, 3], [1.0, 2.0, 3.0, 4.0], 3).unwrap()
+    }
+
+    #[test]
+    fn test_insufficient_data() {
+        let mut kst = short_kst();
+        // The longest component needs 5 + 1 prices for ROC plus 3 ROC values for its SMA.
+        for price in [100.0, 101.0, 102.0, 103.0, 104.0, 105.0, 106.0] {
+            assert!(kst.calculate(price).is_none());
+        }
+        assert!(kst.calculate(107.0).is_some());
+    }
+
+    #[test]
+    fn test_kst_zero_on_flat_data() {
+        let mut kst = KST::default();
+        let mut result = None;
+        for _ in 0..80 {
+            result = kst.calculate(100.0);
+        }
+        let res = result.unwrap();
+        assert_eq!(res.kst, 0.0);
+        assert_eq!(res.signal, Some(0.0));
+    }
+
+    #[test]
+    fn test_kst_crosses_signal_on_trend_change() {
+        let mut kst = short_kst();
+
+        // Accelerating uptrend: KST should sit above its signal.
+        let mut last = None;
+        for i in 0..20 {
+            last = kst.calculate(100.0 * 1.01_f64.powi(i * i / 4));
+        }
+        let res = last.unwrap();
+        assert!(res.kst > 0.0);
+        assert!(res.kst >= res.signal.unwrap());
+
+        // Reversal: KST must drop below its signal at some point.
+        let top = 100.0 * 1.01_f64.powi(19 * 19 / 4);
+        let mut crossed_below = false;
+        for i in 1..15 {
+            let res = kst.calculate(top * 0.98_f64.powi(i)).unwrap();
+            if res.kst < res.signal.unwrap() {
+                crossed_below = true;
+                break;
+            }
+        }
+        assert!(crossed_below);
+    }
+
+    #[test]
+    fn test_invalid_smoothing_period() {
+        let kst = KST::new([2, 3, 4, 5], [2, 0, 2, 3], [1.0, 2.0, 3.0, 4.0], 3);
+        assert!(matches!(kst, Err(SMAError::InvalidPeriod)));
+    }
+}
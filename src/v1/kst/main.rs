@@ -0,0 +1,132 @@
+//! # KST (Know Sure Thing) Module
+//!
+//! This module implements Martin Pring's Know Sure Thing oscillator. KST combines four
+//! Rate of Change (ROC) series of increasing length, each smoothed by its own Simple Moving
+//! Average, into a single weighted momentum line:
+//!
+//! \[\text{KST} = \sum_{i=1}^{4} w_i \times \text{SMA}_i(\text{ROC}_i)\]
+//!
+//! A signal line (SMA of KST) is maintained alongside; crossovers between KST and its
+//! signal are the usual trading triggers.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use indexes_rs::v1::kst::main::KST;
+//! use indexes_rs::v1::kst::types::KstResult;
+//!
+//! // Short periods so the example warms up quickly.
+//! let mut kst = KST::new([2, 3, 4, 5], [2, 2, 2, 3], [1.0, 2.0, 3.0, 4.0], 3).unwrap();
+//!
+//! let mut result: Option<KstResult> = None;
+//! for price in (0..20).map(|i| 100.0 + i as f64) {
+//!     result = kst.calculate(price);
+//! }
+//!
+//! if let Some(res) = result {
+//!     println!("KST: {:.2}, Signal: {:?}", res.kst, res.signal);
+//! }
+//! ```
+
+use super::types::KstResult;
+use crate::v1::{
+    roc::main::ROC,
+    sma::main::{SMAError, SimpleMovingAverage},
+};
+
+/// A KST (Know Sure Thing) oscillator built from four smoothed ROC series.
+pub struct KST {
+    rocs: [ROC; 4],
+    smas: [SimpleMovingAverage; 4],
+    weights: [f64; 4],
+    signal_sma: SimpleMovingAverage,
+}
+
+impl KST {
+    /// Default ROC periods.
+    pub const DEFAULT_ROC_PERIODS: [usize; 4] = [10, 15, 20, 30];
+    /// Default SMA smoothing periods applied to each ROC.
+    pub const DEFAULT_SMA_PERIODS: [usize; 4] = [10, 10, 10, 15];
+    /// Default weights applied to each smoothed ROC.
+    pub const DEFAULT_WEIGHTS: [f64; 4] = [1.0, 2.0, 3.0, 4.0];
+    /// Default signal line period.
+    pub const DEFAULT_SIGNAL_PERIOD: usize = 9;
+
+    /// Creates a new KST oscillator.
+    ///
+    /// # Arguments
+    ///
+    /// * `roc_periods` - The periods of the four ROC series.
+    /// * `sma_periods` - The SMA smoothing period applied to each ROC series.
+    /// * `weights` - The weight applied to each smoothed ROC series.
+    /// * `signal_period` - The SMA period of the signal line.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(KST)` on success, or `Err(SMAError)` if any smoothing period is zero.
+    pub fn new(
+        roc_periods: [usize; 4],
+        sma_periods: [usize; 4],
+        weights: [f64; 4],
+        signal_period: usize,
+    ) -> Result<Self, SMAError> {
+        Ok(KST {
+            rocs: roc_periods.map(ROC::new),
+            smas: [
+                SimpleMovingAverage::new(sma_periods[0])?,
+                SimpleMovingAverage::new(sma_periods[1])?,
+                SimpleMovingAverage::new(sma_periods[2])?,
+                SimpleMovingAverage::new(sma_periods[3])?,
+            ],
+            weights,
+            signal_sma: SimpleMovingAverage::new(signal_period)?,
+        })
+    }
+
+    /// Updates the oscillator with a new price and returns the current KST result.
+    ///
+    /// Every ROC and smoothing SMA is updated on each call so that all four components
+    /// warm up in parallel.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The latest price.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(KstResult)` once all four smoothed ROC series are available.
+    /// * `None` while the longest component is still warming up.
+    pub fn calculate(&mut self, price: f64) -> Option<KstResult> {
+        let mut smoothed = [None; 4];
+        for (i, slot) in smoothed.iter_mut().enumerate() {
+            if let Some(roc) = self.rocs[i].calculate(price) {
+                self.smas[i].add_value(roc.value);
+                *slot = self.smas[i].calculate().map(|sma| sma.value);
+            }
+        }
+
+        let mut kst = 0.0;
+        for (value, weight) in smoothed.iter().zip(self.weights.iter()) {
+            kst += (*value)? * weight;
+        }
+
+        self.signal_sma.add_value(kst);
+        let signal = self.signal_sma.calculate().map(|sma| sma.value);
+
+        Some(KstResult { kst, signal })
+    }
+}
+
+impl Default for KST {
+    /// Creates a KST oscillator with Pring's standard parameters:
+    /// ROC 10/15/20/30, SMA 10/10/10/15, weights 1/2/3/4, and a 9-period signal.
+    fn default() -> Self {
+        Self::new(
+            Self::DEFAULT_ROC_PERIODS,
+            Self::DEFAULT_SMA_PERIODS,
+            Self::DEFAULT_WEIGHTS,
+            Self::DEFAULT_SIGNAL_PERIOD,
+        )
+        .expect("Default parameters should always be valid")
+    }
+}
@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+/// The result of a KST calculation.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct KstResult {
+    /// The weighted sum of the four smoothed ROC values.
+    pub kst: f64,
+    /// The signal line (SMA of KST), once enough KST values are available.
+    pub signal: Option<f64>,
+}
@@ -2,7 +2,9 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::v1::ma::main::{EMAValues, MovingAverageResults, MovingAverages, SMAValues};
+    use crate::v1::ma::main::{
+        EMAValues, MovingAverageParams, MovingAverageResults, MovingAverages, SMAValues,
+    };
 
     /// Helper function to simulate price data.
     fn simulate_prices(ma: &mut MovingAverages, prices: &[f64]) -> MovingAverageResults {
@@ -65,4 +67,62 @@ mod tests {
             assert!(macd_result.macd_line.is_finite());
         }
     }
+
+    #[test]
+    fn test_labeled_values_after_warmup() {
+        let mut ma = MovingAverages::with_params(MovingAverageParams {
+            sma_short: Some(2),
+            sma_medium: Some(3),
+            sma_long: Some(4),
+            ema_short: Some(2),
+            ema_medium: Some(3),
+            ema_long: Some(4),
+            macd_fast: Some(2),
+            macd_slow: Some(3),
+            macd_signal: Some(2),
+        })
+        .unwrap();
+        let prices: Vec<f64> = (1..=10).map(|x| x as f64).collect();
+        let result = simulate_prices(&mut ma, &prices);
+
+        let pairs = result.labeled_values();
+        let labels: Vec<&str> = pairs.iter().map(|(label, _)| *label).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "sma_short",
+                "sma_medium",
+                "sma_long",
+                "ema_short",
+                "ema_medium",
+                "ema_long",
+                "macd_line",
+                "macd_signal",
+                "macd_histogram"
+            ]
+        );
+
+        assert!(pairs.iter().all(|(_, value)| value.is_some()));
+        assert_eq!(pairs[0].1, Some(9.5));
+        assert_eq!(pairs[1].1, Some(9.0));
+        assert_eq!(pairs[2].1, Some(8.5));
+        assert_eq!(pairs[3].1, result.ema.short);
+        let macd = result.macd.as_ref().unwrap();
+        assert_eq!(pairs[6].1, Some(macd.macd_line));
+        assert_eq!(pairs[7].1, Some(macd.signal_line));
+        assert_eq!(pairs[8].1, Some(macd.histogram));
+    }
+
+    #[test]
+    fn test_labeled_values_before_warmup() {
+        let mut ma = MovingAverages::default();
+        let result = ma.calculate(100.0);
+        let values: Vec<Option<f64>> = result
+            .labeled_values()
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect();
+        assert_eq!(values[..3], [None, None, None]);
+        assert_eq!(values[3..6], [Some(100.0), Some(100.0), Some(100.0)]);
+    }
 }
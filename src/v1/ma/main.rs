@@ -10,18 +10,18 @@
 //! ## Example
 //!
 //! ```rust
-//! use indexes_rs::v1::ma::main::{MovingAverages, MovingAverageResults};
+//! use indexes_rs::v1::ma::main::{MovingAverageParams, MovingAverages, MovingAverageResults};
 //!
 //! // Create a new moving averages calculator with default parameters.
 //! let mut ma = MovingAverages::default();
 //!
 //! // Or supply custom parameters:
 //! // SMA: 10, 30, 100; EMA: 10, 30, 100; MACD: fast=8, slow=17, signal=9
-//! // let mut ma = MovingAverages::with_params(
-//! //     Some(10), Some(30), Some(100),
-//! //     Some(10), Some(30), Some(100),
-//! //     Some(8), Some(17), Some(9)
-//! // ).unwrap();
+//! // let mut ma = MovingAverages::with_params(MovingAverageParams {
+//! //     sma_short: Some(10), sma_medium: Some(30), sma_long: Some(100),
+//! //     ema_short: Some(10), ema_medium: Some(30), ema_long: Some(100),
+//! //     macd_fast: Some(8), macd_slow: Some(17), macd_signal: Some(9),
+//! // }).unwrap();
 //!
 //! // Simulate a stream of prices.
 //! let prices = vec![10.0, 10.5, 11.0, 10.8, 11.2, 11.5, 11.3];
@@ -47,6 +47,29 @@ pub struct MovingAverages {
     pub macd: MACD,
 }
 
+/// Optional periods for [`MovingAverages::with_params`]; `None` keeps the default.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MovingAverageParams {
+    /// SMA short period (default: 20).
+    pub sma_short: Option<usize>,
+    /// SMA medium period (default: 50).
+    pub sma_medium: Option<usize>,
+    /// SMA long period (default: 200).
+    pub sma_long: Option<usize>,
+    /// EMA short period (default: 20).
+    pub ema_short: Option<usize>,
+    /// EMA medium period (default: 50).
+    pub ema_medium: Option<usize>,
+    /// EMA long period (default: 200).
+    pub ema_long: Option<usize>,
+    /// MACD fast period (default: 12).
+    pub macd_fast: Option<usize>,
+    /// MACD slow period (default: 26).
+    pub macd_slow: Option<usize>,
+    /// MACD signal period (default: 9).
+    pub macd_signal: Option<usize>,
+}
+
 /// Holds SMA indicators for different periods.
 pub struct SMAPeriods {
     /// Short period SMA.
@@ -107,7 +130,8 @@ impl Default for MovingAverages {
     /// - EMA periods: short = 20, medium = 50, long = 200
     /// - MACD parameters: fast = 12, slow = 26, signal = 9
     fn default() -> Self {
-        Self::with_params(None, None, None, None, None, None, None, None, None).expect("Default parameters should always be valid")
+        Self::with_params(MovingAverageParams::default())
+            .expect("Default parameters should always be valid")
     }
 }
 
@@ -116,30 +140,16 @@ impl MovingAverages {
     ///
     /// # Arguments
     ///
-    /// * `sma_short` - Optional SMA short period (default: 20).
-    /// * `sma_medium` - Optional SMA medium period (default: 50).
-    /// * `sma_long` - Optional SMA long period (default: 200).
-    /// * `ema_short` - Optional EMA short period (default: 20).
-    /// * `ema_medium` - Optional EMA medium period (default: 50).
-    /// * `ema_long` - Optional EMA long period (default: 200).
-    /// * `macd_fast` - Optional MACD fast period (default: 12).
-    /// * `macd_slow` - Optional MACD slow period (default: 26).
-    /// * `macd_signal` - Optional MACD signal period (default: 9).
-    pub fn with_params(
-        sma_short: Option<usize>,
-        sma_medium: Option<usize>,
-        sma_long: Option<usize>,
-        ema_short: Option<usize>,
-        ema_medium: Option<usize>,
-        ema_long: Option<usize>,
-        macd_fast: Option<usize>,
-        macd_slow: Option<usize>,
-        macd_signal: Option<usize>,
-    ) -> Result<Self, SMAError> {
+    /// * `params` - Optional SMA, EMA, and MACD periods; unset periods use the defaults.
+    pub fn with_params(params: MovingAverageParams) -> Result<Self, SMAError> {
         Ok(MovingAverages {
-            sma: SMAPeriods::new(sma_short, sma_medium, sma_long)?,
-            ema: EMAPeriods::new_with_params(ema_short, ema_medium, ema_long),
-            macd: MACD::new(macd_fast.unwrap_or(12), macd_slow.unwrap_or(26), macd_signal.unwrap_or(9)),
+            sma: SMAPeriods::new(params.sma_short, params.sma_medium, params.sma_long)?,
+            ema: EMAPeriods::new_with_params(params.ema_short, params.ema_medium, params.ema_long),
+            macd: MACD::new(
+                params.macd_fast.unwrap_or(12),
+                params.macd_slow.unwrap_or(26),
+                params.macd_signal.unwrap_or(9),
+            ),
         })
     }
 
@@ -160,6 +170,41 @@ impl MovingAverages {
     }
 }
 
+impl MovingAverageResults {
+    /// Number of series returned by [`MovingAverageResults::labeled_values`].
+    pub const SERIES_COUNT: usize = 9;
+
+    /// Flattens the results into `(label, value)` pairs for generic rendering.
+    ///
+    /// The pairs come in a fixed order: `sma_short`, `sma_medium`, `sma_long`, `ema_short`,
+    /// `ema_medium`, `ema_long`, `macd_line`, `macd_signal`, `macd_histogram`. Series that are
+    /// still warming up yield `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use indexes_rs::v1::ma::main::MovingAverages;
+    ///
+    /// let mut ma = MovingAverages::default();
+    /// for (label, value) in ma.calculate(100.0).labeled_values() {
+    ///     println!("{label}: {value:?}");
+    /// }
+    /// ```
+    pub fn labeled_values(&self) -> [(&'static str, Option<f64>); Self::SERIES_COUNT] {
+        [
+            ("sma_short", self.sma.short.as_ref().map(|r| r.value)),
+            ("sma_medium", self.sma.medium.as_ref().map(|r| r.value)),
+            ("sma_long", self.sma.long.as_ref().map(|r| r.value)),
+            ("ema_short", self.ema.short),
+            ("ema_medium", self.ema.medium),
+            ("ema_long", self.ema.long),
+            ("macd_line", self.macd.as_ref().map(|r| r.macd_line)),
+            ("macd_signal", self.macd.as_ref().map(|r| r.signal_line)),
+            ("macd_histogram", self.macd.as_ref().map(|r| r.histogram)),
+        ]
+    }
+}
+
 impl SMAPeriods {
     /// Creates a new set of SMA indicators with the following periods:
     /// - Short: defaults to 20 if not provided.
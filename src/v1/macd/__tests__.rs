@@ -1,8 +1,12 @@
 #[cfg(test)]
 mod tests {
+    use crate::common::types::Candle;
     use crate::v1::{
-        macd::{main::MACD, types::MACDResult},
-        types::TradingSignal,
+        macd::{
+            main::MACD,
+            types::{MACDColumns, MACDResult},
+        },
+        types::{Cross, HistColor, TradingSignal, V1Error},
     };
 
     #[test]
@@ -45,4 +49,178 @@ mod tests {
         assert_eq!(macd_instance.determine_signal(0.5, 1.0), TradingSignal::Sell);
         assert_eq!(macd_instance.determine_signal(0.7, 0.7), TradingSignal::Hold);
     }
+
+    /// Sideways chop around 100 followed by a real rally and sell-off
+    fn choppy_then_trending_prices() -> Vec<f64> {
+        let chop = (0..30).map(|i| if i % 2 == 0 { 100.5 } else { 99.5 });
+        let rally = (1..=10).map(|i| 100.0 + i as f64 * 2.0);
+        let sell_off = (1..=15).map(|i| 120.0 - i as f64 * 2.0);
+        chop.chain(rally).chain(sell_off).collect()
+    }
+
+    fn histogram_crosses(macd: &mut MACD, prices: &[f64]) -> Vec<Cross> {
+        prices
+            .iter()
+            .filter_map(|&price| {
+                macd.calculate(price)
+                    .and_then(|result| result.histogram_cross)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_histogram_cross_without_hysteresis() {
+        // Every sign change of the histogram is a cross, alternating in direction
+        let crosses = histogram_crosses(&mut MACD::new(3, 6, 3), &choppy_then_trending_prices());
+        assert!(crosses.len() > 2);
+        assert!(crosses.windows(2).all(|pair| pair[0] != pair[1]));
+        assert_eq!(crosses.last(), Some(&Cross::Bearish));
+    }
+
+    #[test]
+    fn test_histogram_hysteresis_suppresses_chatter() {
+        let prices = choppy_then_trending_prices();
+
+        let plain_crosses = histogram_crosses(&mut MACD::new(3, 6, 3), &prices).len();
+        assert!(
+            plain_crosses > 2,
+            "without hysteresis the noise produces {} crosses",
+            plain_crosses
+        );
+
+        // The chop stays inside the band; the rally only confirms the first side, so the
+        // sell-off is the one cross
+        let crosses =
+            histogram_crosses(&mut MACD::with_histogram_hysteresis(3, 6, 3, 0.15), &prices);
+        assert_eq!(crosses, vec![Cross::Bearish]);
+    }
+
+    #[test]
+    fn test_histogram_cross_in_results() {
+        let mut macd = MACD::new(3, 6, 3);
+        let mut crosses = Vec::new();
+        for i in 0..40 {
+            // Rise then fall so the histogram changes sign
+            let price = if i < 20 {
+                100.0 + i as f64
+            } else {
+                120.0 - (i - 20) as f64 * 2.0
+            };
+            if let Some(cross) = macd.calculate(price).unwrap().histogram_cross {
+                crosses.push(cross);
+            }
+        }
+        assert_eq!(crosses.first(), Some(&Cross::Bearish));
+    }
+
+    #[test]
+    fn test_hist_color_sequence() {
+        let histogram = [0.5, 0.8, 0.6, 0.0, -0.4, -0.9, -0.7, 0.2];
+        let mut previous = None;
+        let colors: Vec<_> = histogram
+            .iter()
+            .map(|&h| {
+                let color = HistColor::classify(h, previous);
+                previous = Some(h);
+                color
+            })
+            .collect();
+        assert_eq!(
+            colors,
+            vec![
+                HistColor::StrongUp,
+                HistColor::StrongUp,
+                HistColor::WeakUp,
+                HistColor::WeakUp,
+                HistColor::StrongDown,
+                HistColor::StrongDown,
+                HistColor::WeakDown,
+                HistColor::StrongUp,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bar_color_in_results() {
+        let mut macd = MACD::new(3, 6, 3);
+        let mut previous: Option<f64> = None;
+        for i in 0..40 {
+            let price = if i < 20 {
+                100.0 + i as f64
+            } else {
+                120.0 - (i - 20) as f64 * 2.0
+            };
+            if let Some(result) = macd.calculate(price) {
+                assert_eq!(
+                    result.bar_color,
+                    HistColor::classify(result.histogram, previous)
+                );
+                previous = Some(result.histogram);
+            }
+        }
+        assert!(previous.is_some());
+    }
+
+    #[test]
+    fn test_columns_match_row_batch() {
+        let candles: Vec<Candle> = (0..20)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 3.0)
+            .map(|c| Candle::new(c, c + 1.0, c - 1.0, c, 1000.0))
+            .collect();
+
+        let rows = MACD::new(3, 6, 3).calculate_batch(&candles);
+        let columns: MACDColumns = MACD::new(3, 6, 3).calculate_columns(&candles);
+
+        assert_eq!(columns.len(), candles.len());
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(columns.macd_line[i], row.as_ref().map(|r| r.macd_line));
+            assert_eq!(columns.signal_line[i], row.as_ref().map(|r| r.signal_line));
+            assert_eq!(columns.histogram[i], row.as_ref().map(|r| r.histogram));
+        }
+        assert!(rows.iter().any(|row| row.is_some()));
+    }
+
+    #[test]
+    fn test_v1_error_paths() {
+        assert_eq!(
+            MACD::try_new(0, 26, 9).err().map(|e| e.to_string()),
+            Some(V1Error::InvalidPeriod.to_string())
+        );
+        assert!(MACD::try_new(12, 0, 9).is_err());
+        assert!(MACD::try_new(12, 26, 0).is_err());
+
+        let mut macd = MACD::try_new(3, 6, 3).unwrap();
+        assert!(matches!(
+            macd.try_calculate(f64::INFINITY),
+            Err(V1Error::InvalidPrice)
+        ));
+        assert!(macd.try_calculate(100.0).is_ok());
+    }
+
+    #[test]
+    fn test_with_derivatives() {
+        let prices: Vec<f64> = (0..40)
+            .map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0)
+            .collect();
+
+        let mut plain = MACD::new(3, 6, 3);
+        let mut tracked = MACD::new(3, 6, 3).with_derivatives();
+        let results: Vec<MACDResult> = prices
+            .iter()
+            .filter_map(|&p| tracked.calculate(p))
+            .collect();
+        assert!(prices
+            .iter()
+            .filter_map(|&p| plain.calculate(p))
+            .all(|r| r.slope.is_none()));
+
+        assert_eq!(results[0].slope, None);
+        assert_eq!(results[1].acceleration, None);
+        for window in results.windows(3) {
+            let slope = window[2].macd_line - window[1].macd_line;
+            let prev_slope = window[1].macd_line - window[0].macd_line;
+            assert!((window[2].slope.unwrap() - slope).abs() < 1e-12);
+            assert!((window[2].acceleration.unwrap() - (slope - prev_slope)).abs() < 1e-12);
+        }
+    }
 }
@@ -36,7 +36,12 @@
 //! ```
 
 use super::types::*;
-use crate::v1::{ema::main::ExponentialMovingAverage, types::TradingSignal};
+use crate::common::types::Candle;
+use crate::util::derivative::main::Derivative;
+use crate::v1::{
+    ema::main::ExponentialMovingAverage,
+    types::{Cross, HistColor, TradingSignal, V1Error},
+};
 
 /// MACD (Moving Average Convergence Divergence) indicator.
 pub struct MACD {
@@ -44,6 +49,12 @@ pub struct MACD {
     pub slow_ema: ExponentialMovingAverage,
     pub signal_ema: ExponentialMovingAverage,
     pub histogram: Vec<f64>,
+    /// Distance from zero the histogram must exceed before a new side is confirmed.
+    histogram_hysteresis: f64,
+    /// Last confirmed side of the histogram (`true` = above zero).
+    histogram_above: Option<bool>,
+    /// Slope/acceleration tracker of the MACD line (`None` = disabled).
+    derivative: Option<Derivative>,
 }
 
 impl MACD {
@@ -68,9 +79,82 @@ impl MACD {
             slow_ema: ExponentialMovingAverage::new(slow_period),
             signal_ema: ExponentialMovingAverage::new(signal_period),
             histogram: Vec::new(),
+            histogram_hysteresis: 0.0,
+            histogram_above: None,
+            derivative: None,
         }
     }
 
+    /// Creates a new MACD indicator, rejecting zero periods.
+    ///
+    /// # Arguments
+    ///
+    /// * `fast_period` - The period for the fast EMA.
+    /// * `slow_period` - The period for the slow EMA.
+    /// * `signal_period` - The period for the signal EMA.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MACD)` if every period is non-zero.
+    /// * `Err(V1Error::InvalidPeriod)` otherwise.
+    pub fn try_new(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<Self, V1Error> {
+        if fast_period == 0 || slow_period == 0 || signal_period == 0 {
+            return Err(V1Error::InvalidPeriod);
+        }
+
+        Ok(Self::new(fast_period, slow_period, signal_period))
+    }
+
+    /// Creates a new MACD indicator whose histogram zero cross uses hysteresis.
+    ///
+    /// A cross is only reported once the histogram moves beyond `±hysteresis`, so small
+    /// oscillations around zero do not produce repeated crosses.
+    ///
+    /// # Arguments
+    ///
+    /// * `fast_period` - The period for the fast EMA.
+    /// * `slow_period` - The period for the slow EMA.
+    /// * `signal_period` - The period for the signal EMA.
+    /// * `hysteresis` - The half-width of the band around zero. Negative or NaN values are treated as 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use indexes_rs::v1::macd::main::MACD;
+    ///
+    /// let macd = MACD::with_histogram_hysteresis(12, 26, 9, 0.05);
+    /// ```
+    pub fn with_histogram_hysteresis(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+        hysteresis: f64,
+    ) -> Self {
+        let mut macd = Self::new(fast_period, slow_period, signal_period);
+        macd.histogram_hysteresis = hysteresis.max(0.0);
+        macd
+    }
+
+    /// Reports the MACD line's slope and acceleration (first and second differences) in each result.
+    ///
+    /// Without this, `MACDResult::slope` and `MACDResult::acceleration` are always `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use indexes_rs::v1::macd::main::MACD;
+    ///
+    /// let macd = MACD::new(12, 26, 9).with_derivatives();
+    /// ```
+    pub fn with_derivatives(mut self) -> Self {
+        self.derivative = Some(Derivative::new());
+        self
+    }
+
     /// Updates the MACD calculation with a new price and returns the current MACD result.
     ///
     /// The method updates the fast and slow EMAs with the new price, calculates the MACD line
@@ -84,7 +168,7 @@ impl MACD {
     /// # Returns
     ///
     /// * `Some(MACDResult)` containing the MACD line, signal line, histogram, and trading signal,
-    ///    if the EMAs have been sufficiently initialized.
+    ///   if the EMAs have been sufficiently initialized.
     /// * `None` if any of the EMA calculations are not yet available.
     pub fn calculate(&mut self, price: f64) -> Option<MACDResult> {
         let fast = self.fast_ema.add_value(price)?;
@@ -92,17 +176,84 @@ impl MACD {
         let macd_line = fast - slow;
         let signal_line = self.signal_ema.add_value(macd_line)?;
         let histogram = macd_line - signal_line;
+        let bar_color = HistColor::classify(histogram, self.histogram.last().copied());
 
         self.histogram.push(histogram);
+        let derivatives = self
+            .derivative
+            .as_mut()
+            .map(|d| d.update(Some(macd_line)))
+            .unwrap_or_default();
 
         Some(MACDResult {
             macd_line,
             signal_line,
             histogram,
             signal: self.determine_signal(macd_line, signal_line),
+            histogram_cross: self.update_histogram_cross(histogram),
+            bar_color,
+            slope: derivatives.slope,
+            acceleration: derivatives.acceleration,
         })
     }
 
+    /// Calculates MACD for a new price, rejecting non-finite prices.
+    ///
+    /// Unlike [`MACD::calculate`], a NaN or infinite price is reported as an error and leaves
+    /// the state untouched instead of poisoning later readings.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The latest price.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(MACDResult))` once enough data is available.
+    /// * `Ok(None)` while warming up.
+    /// * `Err(V1Error::InvalidPrice)` if `price` is NaN or infinite.
+    pub fn try_calculate(&mut self, price: f64) -> Result<Option<MACDResult>, V1Error> {
+        if !price.is_finite() {
+            return Err(V1Error::InvalidPrice);
+        }
+
+        Ok(self.calculate(price))
+    }
+
+    /// Feeds the close of every candle and returns one result per bar.
+    ///
+    /// # Arguments
+    ///
+    /// * `candles` - The bars to process, oldest first.
+    ///
+    /// # Returns
+    ///
+    /// * A vector with one entry per candle; bars without a result are `None`.
+    pub fn calculate_batch(&mut self, candles: &[Candle]) -> Vec<Option<MACDResult>> {
+        candles
+            .iter()
+            .map(|candle| self.calculate(candle.close))
+            .collect()
+    }
+
+    /// Feeds the close of every candle and returns the MACD line, signal line, and histogram as columns.
+    ///
+    /// Produces the same values as [`MACD::calculate_batch`], laid out as one vector per series,
+    /// alongside each candle's timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `candles` - The bars to process, oldest first.
+    ///
+    /// # Returns
+    ///
+    /// * A `MACDColumns` whose columns each have one entry per candle.
+    pub fn calculate_columns(&mut self, candles: &[Candle]) -> MACDColumns {
+        candles
+            .iter()
+            .map(|candle| (candle.timestamp, self.calculate(candle.close)))
+            .collect()
+    }
+
     /// Determines the trading signal based on the MACD line and the signal line.
     ///
     /// If the MACD line is above the signal line, returns `Buy`.
@@ -126,4 +277,36 @@ impl MACD {
             TradingSignal::Hold
         }
     }
+
+    /// Updates the confirmed histogram side and reports a zero cross.
+    ///
+    /// The side only changes once the histogram is strictly beyond `±hysteresis`; values inside
+    /// the band keep the previous side. The first confirmed side never reports a cross.
+    ///
+    /// # Arguments
+    ///
+    /// * `histogram` - The latest histogram value.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Cross::Bullish)` when the histogram is confirmed above zero after being below.
+    /// * `Some(Cross::Bearish)` when the histogram is confirmed below zero after being above.
+    /// * `None` otherwise.
+    fn update_histogram_cross(&mut self, histogram: f64) -> Option<Cross> {
+        let above = if histogram > self.histogram_hysteresis {
+            true
+        } else if histogram < -self.histogram_hysteresis {
+            false
+        } else {
+            return None;
+        };
+
+        let cross = match self.histogram_above {
+            Some(false) if above => Some(Cross::Bullish),
+            Some(true) if !above => Some(Cross::Bearish),
+            _ => None,
+        };
+        self.histogram_above = Some(above);
+        cross
+    }
 }
@@ -1,6 +1,6 @@
 use serde::Serialize;
 
-use crate::v1::types::TradingSignal;
+use crate::v1::types::{Cross, HistColor, TradingSignal};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct MACDResult {
@@ -8,4 +8,84 @@ pub struct MACDResult {
     pub signal_line: f64,
     pub histogram: f64,
     pub signal: TradingSignal,
+    /// Set on the bar where the histogram crosses zero (beyond the configured hysteresis).
+    pub histogram_cross: Option<Cross>,
+    /// Color hint for rendering the histogram bar, relative to the previous bar.
+    pub bar_color: HistColor,
+    /// Change in the MACD line from the previous bar (only with `MACD::with_derivatives`).
+    pub slope: Option<f64>,
+    /// Change in slope from the previous bar (only with `MACD::with_derivatives`).
+    pub acceleration: Option<f64>,
+}
+
+/// MACD output in columnar (struct-of-arrays) form.
+///
+/// Each column has one entry per input bar; warmup bars are `None`, as are
+/// timestamps of bars that did not carry one.
+#[derive(Debug, Default, PartialEq, Clone, Serialize)]
+pub struct MACDColumns {
+    /// The timestamp of each bar.
+    pub timestamp: Vec<Option<i64>>,
+    /// The MACD line per bar.
+    pub macd_line: Vec<Option<f64>>,
+    /// The signal line per bar.
+    pub signal_line: Vec<Option<f64>>,
+    /// The histogram per bar.
+    pub histogram: Vec<Option<f64>>,
+}
+
+impl MACDColumns {
+    /// Appends one bar's result to every column.
+    pub fn push(&mut self, timestamp: Option<i64>, result: Option<&MACDResult>) {
+        self.timestamp.push(timestamp);
+        self.macd_line.push(result.map(|r| r.macd_line));
+        self.signal_line.push(result.map(|r| r.signal_line));
+        self.histogram.push(result.map(|r| r.histogram));
+    }
+
+    /// Returns the number of bars in the columns.
+    pub fn len(&self) -> usize {
+        self.macd_line.len()
+    }
+
+    /// Returns `true` if the columns hold no bars.
+    pub fn is_empty(&self) -> bool {
+        self.macd_line.is_empty()
+    }
+
+    /// Converts the columns into a Polars `DataFrame`.
+    ///
+    /// The frame has a `timestamp` column followed by one column per series, named after
+    /// the struct fields. `None` entries (warmup bars, missing timestamps) become nulls.
+    #[cfg(feature = "polars")]
+    pub fn to_dataframe(&self) -> polars::prelude::PolarsResult<polars::prelude::DataFrame> {
+        use polars::prelude::{Column, DataFrame};
+
+        DataFrame::new(vec![
+            Column::new("timestamp".into(), &self.timestamp),
+            Column::new("macd_line".into(), &self.macd_line),
+            Column::new("signal_line".into(), &self.signal_line),
+            Column::new("histogram".into(), &self.histogram),
+        ])
+    }
+}
+
+impl FromIterator<Option<MACDResult>> for MACDColumns {
+    fn from_iter<I: IntoIterator<Item = Option<MACDResult>>>(iter: I) -> Self {
+        let mut columns = MACDColumns::default();
+        for result in iter {
+            columns.push(None, result.as_ref());
+        }
+        columns
+    }
+}
+
+impl FromIterator<(Option<i64>, Option<MACDResult>)> for MACDColumns {
+    fn from_iter<I: IntoIterator<Item = (Option<i64>, Option<MACDResult>)>>(iter: I) -> Self {
+        let mut columns = MACDColumns::default();
+        for (timestamp, result) in iter {
+            columns.push(timestamp, result.as_ref());
+        }
+        columns
+    }
 }
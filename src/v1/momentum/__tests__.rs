@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::v1::momentum::{main::Momentum, types::MomentumResult};
+    use crate::v1::types::V1Error;
 
     #[test]
     fn test_insufficient_data() {
@@ -49,4 +50,20 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_negative_prices_ratio() {
+        let mut momentum = Momentum::new(2);
+        momentum.calculate(-10.0);
+        let res = momentum.calculate(-5.0).unwrap();
+        assert_eq!(res.value, 5.0);
+        // Rising series: the ratio stays above 100
+        assert!((res.ratio - 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_try_new_rejects_zero_period() {
+        assert_eq!(Momentum::try_new(0).err(), Some(V1Error::InvalidPeriod));
+        assert!(Momentum::try_new(10).is_ok());
+    }
 }
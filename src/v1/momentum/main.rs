@@ -5,6 +5,10 @@
 //! from a specified number of periods ago. In addition, it computes a momentum ratio, defined as
 //! the current price as a percentage of the past price.
 //!
+//! The momentum difference is defined for any price. The ratio is measured against the magnitude
+//! of the past price, so it stays above 100 for a rising series even when prices are negative;
+//! when the past price is zero (or within `f64::EPSILON` of it) no result is reported.
+//!
 //! # Examples
 //!
 //! ```rust
@@ -22,6 +26,7 @@
 //! ```
 
 use super::types::MomentumResult;
+use crate::v1::types::V1Error;
 use std::collections::VecDeque;
 
 /// A Momentum indicator that calculates the change between the current price and the price
@@ -57,10 +62,29 @@ impl Momentum {
         }
     }
 
+    /// Creates a new `Momentum` indicator, rejecting a zero period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of periods for the momentum lookback.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Momentum)` for a non-zero period.
+    /// * `Err(V1Error::InvalidPeriod)` if `period` is zero.
+    pub fn try_new(period: usize) -> Result<Self, V1Error> {
+        if period == 0 {
+            return Err(V1Error::InvalidPeriod);
+        }
+
+        Ok(Self::new(period))
+    }
+
     /// Calculates the current momentum and momentum ratio.
     ///
     /// The momentum is computed as the difference between the current price and the price from `period` periods ago.
-    /// The momentum ratio is computed as `(current_price / past_price) * 100.0`.
+    /// The momentum ratio is computed as `100.0 + (current_price - past_price) / |past_price| * 100.0`,
+    /// which equals `(current_price / past_price) * 100.0` for positive prices.
     ///
     /// # Arguments
     ///
@@ -69,7 +93,7 @@ impl Momentum {
     /// # Returns
     ///
     /// * `Some(MomentumResult)` if there are enough data points and the past price is not zero.
-    /// * `None` if there aren't enough values or if the past price is zero (to avoid division by zero).
+    /// * `None` if there aren't enough values or if the past price is (near) zero.
     ///
     /// # Example
     ///
@@ -96,11 +120,12 @@ impl Momentum {
             return None;
         }
         let past_price = *self.values.front()?;
-        if past_price == 0.0 {
-            return None; // Avoid division by zero.
+        let base = past_price.abs();
+        if base <= f64::EPSILON {
+            return None; // Avoid division by (near) zero.
         }
         let momentum = price - past_price;
-        let momentum_ratio = (price / past_price) * 100.0;
+        let momentum_ratio = 100.0 + momentum / base * 100.0;
         Some(MomentumResult {
             value: momentum,
             ratio: momentum_ratio,
@@ -5,6 +5,6 @@ use serde::Serialize;
 pub struct MomentumResult {
     /// The momentum value (current price minus past price).
     pub value: f64,
-    /// The momentum ratio (current price as a percentage of the past price).
+    /// The momentum ratio (current price as a percentage of the past price's magnitude).
     pub ratio: f64,
 }
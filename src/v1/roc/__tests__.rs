@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use crate::v1::{roc::main::ROC, types::TradingSignal};
+    use crate::v1::{
+        roc::main::ROC,
+        types::{TradingSignal, V1Error},
+    };
 
     #[test]
     fn test_insufficient_data() {
@@ -50,4 +53,36 @@ mod tests {
         let res = result.unwrap();
         assert_eq!(res.signal, TradingSignal::Sell);
     }
+
+    #[test]
+    fn test_negative_prices_keep_direction() {
+        let mut roc = ROC::new(2);
+        roc.calculate(-10.0);
+        roc.calculate(-8.0);
+        // Rising from -10 to -6 is a 40% gain relative to the magnitude of -10
+        let res = roc.calculate(-6.0).unwrap();
+        assert!((res.value - 40.0).abs() < 1e-9);
+        assert_eq!(res.signal, TradingSignal::Buy);
+
+        // Falling from -8 to -12 is a 50% loss
+        let res = roc.calculate(-12.0).unwrap();
+        assert!((res.value + 50.0).abs() < 1e-9);
+        assert_eq!(res.signal, TradingSignal::Sell);
+    }
+
+    #[test]
+    fn test_zero_base_price_returns_none() {
+        let mut roc = ROC::new(2);
+        roc.calculate(0.0);
+        roc.calculate(1.0);
+        assert!(roc.calculate(2.0).is_none());
+        // Once the zero leaves the window the ROC resumes
+        assert!((roc.calculate(3.0).unwrap().value - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_try_new_rejects_zero_period() {
+        assert_eq!(ROC::try_new(0).err(), Some(V1Error::InvalidPeriod));
+        assert!(ROC::try_new(12).is_ok());
+    }
 }
@@ -5,6 +5,10 @@
 //! from a specified number of periods ago. Additionally, it calculates a normalized momentum,
 //! an acceleration (change in ROC from the previous value), and generates a trading signal.
 //!
+//! The change is measured against the magnitude of the past price, so ROC keeps its sign for
+//! instruments that trade at negative values (spreads, funding rates): a rise from -10 to -5
+//! is +50%. When the past price is zero (or within `f64::EPSILON` of it) no ROC is reported.
+//!
 //! Typical usage example:
 //!
 //! ```rust
@@ -25,7 +29,7 @@
 //! ```
 
 use super::types::ROCResult;
-use crate::v1::types::TradingSignal;
+use crate::v1::types::{TradingSignal, V1Error};
 use std::collections::VecDeque;
 
 /// A Rate of Change (ROC) indicator.
@@ -62,6 +66,24 @@ impl ROC {
         }
     }
 
+    /// Creates a new `ROC` indicator, rejecting a zero period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of periods for the ROC lookback.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ROC)` for a non-zero period.
+    /// * `Err(V1Error::InvalidPeriod)` if `period` is zero.
+    pub fn try_new(period: usize) -> Result<Self, V1Error> {
+        if period == 0 {
+            return Err(V1Error::InvalidPeriod);
+        }
+
+        Ok(Self::new(period))
+    }
+
     /// Calculates the current ROC value based on the latest price.
     ///
     /// This method updates the sliding window of prices and computes:
@@ -76,7 +98,8 @@ impl ROC {
     ///
     /// # Returns
     ///
-    /// * `Some(ROCResult)` if there is enough data; otherwise, `None`.
+    /// * `Some(ROCResult)` if there is enough data.
+    /// * `None` if there is not enough data, or the price `period` bars ago is (near) zero.
     pub fn calculate(&mut self, price: f64) -> Option<ROCResult> {
         // Update the sliding window.
         self.values.push_back(price);
@@ -90,12 +113,14 @@ impl ROC {
         }
 
         let old_price = *self.values.front()?;
-        // Avoid division by zero: if the old price is 0, return None.
-        if old_price == 0.0 {
+        // A (near) zero base has no meaningful percentage change.
+        let base = old_price.abs();
+        if base <= f64::EPSILON {
             return None;
         }
 
-        let current_roc = ((price - old_price) / old_price) * 100.0;
+        // Dividing by the magnitude keeps the sign of the move for negative prices.
+        let current_roc = ((price - old_price) / base) * 100.0;
 
         // Calculate acceleration if previous ROC exists.
         let acceleration = self.prev_roc.map(|prev| current_roc - prev);
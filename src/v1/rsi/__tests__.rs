@@ -2,10 +2,13 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::common::precision::round_to;
+    use crate::common::types::Candle;
     use crate::v1::rsi::{
         main::RSI,
-        types::{MarketCondition, RSIResult},
+        types::{MarketCondition, RSIError, RSIResult},
     };
+    use crate::v1::types::V1Error;
 
     /// Test that the calculator returns `None` until sufficient data has been provided.
     #[test]
@@ -53,4 +56,205 @@ mod tests {
         assert_eq!(rsi.determine_condition(18.0), MarketCondition::Oversold);
         assert_eq!(rsi.determine_condition(50.0), MarketCondition::Neutral);
     }
+
+    /// Test RSI over a non-price series against a manual calculation.
+    #[test]
+    fn test_rsi_on_non_price_series() {
+        let mut rsi = RSI::new(3, None, None);
+        // An equity curve that dips below zero.
+        let series = [0.0, -5.0, 10.0, 4.0, 6.0];
+        let mut result = None;
+        for value in series {
+            result = rsi.update(value);
+        }
+
+        // Last three changes: +15, -6, +2 -> avg gain 17/3, avg loss 6/3.
+        let rs = (17.0 / 3.0) / (6.0 / 3.0);
+        let expected = 100.0 - 100.0 / (1.0 + rs);
+        let result = result.unwrap();
+        assert!((result.value - expected).abs() < 1e-10);
+
+        // `update` and `calculate` are interchangeable.
+        let mut via_calculate = RSI::new(3, None, None);
+        let mut last = None;
+        for value in series {
+            last = via_calculate.calculate(value);
+        }
+        assert_eq!(last, Some(result));
+    }
+
+    /// Test that adaptive thresholds follow a persistently high-RSI regime.
+    #[test]
+    fn test_adaptive_thresholds_rise_in_strong_regime() {
+        let mut rsi = RSI::with_adaptive_thresholds(14, 20, 90.0).unwrap();
+        assert_eq!(rsi.thresholds(), (70.0, 30.0));
+
+        // Steady uptrend with a small pullback every fifth bar keeps RSI well above 70.
+        let mut price = 100.0;
+        let mut last = None;
+        for i in 0..80 {
+            price += if i % 5 == 4 { -0.4 } else { 1.0 };
+            last = rsi.calculate(price);
+        }
+
+        let (overbought, oversold) = rsi.thresholds();
+        assert!(overbought > 70.0, "adaptive overbought {}", overbought);
+        assert!(oversold > 30.0 && oversold <= overbought);
+        assert!(last.unwrap().value > 70.0);
+        // A reading that was overbought under the fixed 70 level is now judged against the regime.
+        assert_ne!(rsi.determine_condition(71.0), MarketCondition::Overbought);
+
+        // A fixed-threshold RSI keeps its levels.
+        let mut fixed = RSI::new(14, None, None);
+        for i in 0..20 {
+            fixed.calculate(100.0 + i as f64);
+        }
+        assert_eq!(fixed.thresholds(), (70.0, 30.0));
+    }
+
+    /// Test that invalid adaptive parameters are rejected.
+    #[test]
+    fn test_adaptive_thresholds_validation() {
+        assert_eq!(
+            RSI::with_adaptive_thresholds(14, 0, 90.0).err(),
+            Some(RSIError::InvalidLookback)
+        );
+        assert_eq!(
+            RSI::with_adaptive_thresholds(14, 20, 40.0).err(),
+            Some(RSIError::InvalidPercentile)
+        );
+        assert_eq!(
+            RSI::with_adaptive_thresholds(14, 20, f64::NAN).err(),
+            Some(RSIError::InvalidPercentile)
+        );
+    }
+
+    #[test]
+    fn test_precision_rounds_value() {
+        let prices = [44.34, 44.09, 44.15, 43.61, 44.33, 44.83];
+        let mut raw = RSI::new(3, None, None);
+        let mut rounded = RSI::new(3, None, None).with_precision(2);
+
+        let mut compared = 0;
+        for price in prices {
+            if let (Some(a), Some(b)) = (raw.calculate(price), rounded.calculate(price)) {
+                assert_eq!(b.value, (a.value * 100.0).round() / 100.0);
+                assert_eq!(b.value, round_to(b.value, 2));
+                assert_eq!(a.condition, b.condition);
+                compared += 1;
+            }
+        }
+        assert_eq!(compared, 3);
+    }
+
+    /// Test that `try_new` and `try_calculate` reject unusable inputs.
+    #[test]
+    fn test_v1_error_paths() {
+        assert_eq!(
+            RSI::try_new(0, None, None).err(),
+            Some(V1Error::InvalidPeriod)
+        );
+        assert_eq!(
+            RSI::try_new(14, Some(30.0), Some(70.0)).err(),
+            Some(V1Error::InvalidThresholds)
+        );
+        assert_eq!(
+            RSI::try_new(14, Some(120.0), None).err(),
+            Some(V1Error::InvalidThresholds)
+        );
+        assert_eq!(
+            RSI::try_new(14, Some(f64::NAN), None).err(),
+            Some(V1Error::InvalidThresholds)
+        );
+
+        let mut rsi = RSI::try_new(3, None, None).unwrap();
+        assert_eq!(rsi.try_calculate(100.0), Ok(None));
+        assert_eq!(rsi.try_calculate(f64::NAN), Err(V1Error::InvalidPrice));
+        for price in [101.0, 102.0, 101.5] {
+            rsi.try_calculate(price).unwrap();
+        }
+        // The rejected price did not poison the running sums
+        assert!(rsi.try_calculate(102.5).unwrap().unwrap().value.is_finite());
+
+        let error: V1Error = RSI::with_adaptive_thresholds(14, 0, 90.0)
+            .err()
+            .unwrap()
+            .into();
+        assert_eq!(error, V1Error::InvalidPeriod);
+    }
+
+    /// Test that an RSI oscillating around the overbought threshold by less than the
+    /// hysteresis band keeps a single condition.
+    #[test]
+    fn test_hysteresis_keeps_condition() {
+        // After two gains (RSI 100) the RSI alternates between 75 and ~66.7 around 70.
+        let prices = [10.0, 13.0, 16.0, 15.0, 17.0, 16.0, 19.0, 18.0, 20.0];
+
+        let mut plain = RSI::new(2, None, None);
+        let mut sticky = RSI::new(2, None, None).with_hysteresis(5.0);
+        let mut plain_conditions = Vec::new();
+        let mut sticky_conditions = Vec::new();
+        for &price in &prices {
+            if let Some(result) = plain.calculate(price) {
+                plain_conditions.push(result.condition);
+            }
+            if let Some(result) = sticky.calculate(price) {
+                assert!(result.value > 65.0);
+                sticky_conditions.push(result.condition);
+            }
+        }
+
+        assert!(plain_conditions.contains(&MarketCondition::Neutral));
+        assert!(sticky_conditions
+            .iter()
+            .all(|c| *c == MarketCondition::Overbought));
+
+        // The band survives a state round trip
+        let mut restored = RSI::new(2, None, None);
+        restored.set_state(sticky.get_state());
+        assert_eq!(
+            restored.calculate(19.0).unwrap().condition,
+            MarketCondition::Overbought
+        );
+    }
+
+    /// Test that slope and acceleration are the first and second differences of the RSI.
+    #[test]
+    fn test_with_derivatives() {
+        let prices = [10.0, 11.0, 10.5, 12.0, 11.0, 13.0, 12.5, 12.0, 14.0];
+
+        let mut rsi = RSI::new(2, None, None).with_derivatives();
+        let results: Vec<RSIResult> = prices.iter().filter_map(|&p| rsi.calculate(p)).collect();
+
+        assert_eq!(results[0].slope, None);
+        assert_eq!(results[1].acceleration, None);
+        for window in results.windows(3) {
+            let slope = window[2].value - window[1].value;
+            let prev_slope = window[1].value - window[0].value;
+            assert!((window[2].slope.unwrap() - slope).abs() < 1e-9);
+            assert!((window[2].acceleration.unwrap() - (slope - prev_slope)).abs() < 1e-9);
+        }
+
+        // Disabled by default
+        let mut plain = RSI::new(2, None, None);
+        assert!(prices
+            .iter()
+            .filter_map(|&p| plain.calculate(p))
+            .all(|r| r.slope.is_none()));
+    }
+
+    /// Test that empty and single-candle batches give no readings.
+    #[test]
+    fn test_empty_and_single_element_batches() {
+        let mut rsi = RSI::new(1, None, None);
+        assert!(rsi.calculate_batch(&[]).is_empty());
+        assert_eq!(
+            rsi.calculate_batch(&[Candle::new(10.0, 11.0, 9.0, 10.0, 100.0)]),
+            vec![None]
+        );
+
+        // The batch continues the stream
+        let next = rsi.calculate_batch(&[Candle::new(10.0, 12.0, 9.0, 11.0, 100.0)]);
+        assert!(next[0].as_ref().is_some_and(|r| r.value > 99.0));
+    }
 }
@@ -6,6 +6,10 @@
 //! The RSI is a momentum oscillator that measures the speed and change of price movements.
 //! It is used to indicate overbought or oversold market conditions.
 //!
+//! Nothing in the calculation assumes price semantics: it only looks at the change between
+//! consecutive values. RSI can therefore be run over volume, an equity curve, or another
+//! indicator's output (e.g. RSI-of-OBV, or StochRSI built on top of it) via [`RSI::update`].
+//!
 //! # Examples
 //!
 //! Using the default thresholds (70 for overbought, 30 for oversold):
@@ -32,8 +36,34 @@
 //! use indexes_rs::v1::rsi::main::RSI;
 //! let mut rsi = RSI::new(14, Some(80.0), Some(20.0));
 //! ```
+//!
+//! Using adaptive thresholds taken from the RSI's own recent distribution:
+//!
+//! ```rust
+//! use indexes_rs::v1::rsi::main::RSI;
+//!
+//! // Overbought/oversold become the 90th/10th percentiles of the last 50 RSI values.
+//! let mut rsi = RSI::with_adaptive_thresholds(14, 50, 90.0).unwrap();
+//! ```
+//!
+//! Running RSI over a non-price series:
+//!
+//! ```rust
+//! use indexes_rs::v1::rsi::main::RSI;
+//!
+//! let mut volume_rsi = RSI::new(3, None, None);
+//! for volume in [1200.0, 1500.0, 900.0, 1800.0] {
+//!     if let Some(result) = volume_rsi.update(volume) {
+//!         println!("Volume RSI: {:.2}", result.value);
+//!     }
+//! }
+//! ```
 
-use super::types::{MarketCondition, RSIResult};
+use super::types::{MarketCondition, RSIError, RSIResult, RSIState};
+use crate::common::precision::round_to;
+use crate::common::types::Candle;
+use crate::util::derivative::main::Derivative;
+use crate::v1::types::V1Error;
 use std::collections::VecDeque;
 
 /// A struct for calculating the Relative Strength Index (RSI) with customizable thresholds.
@@ -48,11 +78,25 @@ pub struct RSI {
     losses: VecDeque<f64>,
     sum_gains: f64,
     sum_losses: f64,
-    prev_price: Option<f64>,
+    prev_value: Option<f64>,
     /// The overbought threshold (default is 70.0).
     overbought: f64,
     /// The oversold threshold (default is 30.0).
     oversold: f64,
+    /// Recent RSI values used for adaptive thresholds (empty when disabled).
+    history: VecDeque<f64>,
+    /// Number of RSI values the adaptive thresholds are computed over (0 = fixed thresholds).
+    adaptive_lookback: usize,
+    /// Percentile used for the adaptive overbought level; oversold uses `100 - pct`.
+    adaptive_pct: f64,
+    /// Decimal places the reported RSI value is rounded to (`None` = unrounded).
+    precision: Option<u8>,
+    /// Hysteresis band around the thresholds (0.0 = none).
+    hysteresis: f64,
+    /// The condition reported for the previous RSI value, kept for hysteresis.
+    last_condition: Option<MarketCondition>,
+    /// Slope/acceleration tracker of the RSI value (`None` = disabled).
+    derivative: Option<Derivative>,
 }
 
 impl RSI {
@@ -70,10 +114,148 @@ impl RSI {
             losses: VecDeque::with_capacity(period),
             sum_gains: 0.0,
             sum_losses: 0.0,
-            prev_price: None,
+            prev_value: None,
             overbought: overbought.unwrap_or(70.0),
             oversold: oversold.unwrap_or(30.0),
+            history: VecDeque::new(),
+            adaptive_lookback: 0,
+            adaptive_pct: 0.0,
+            precision: None,
+            hysteresis: 0.0,
+            last_condition: None,
+            derivative: None,
+        }
+    }
+
+    /// Creates a new RSI calculator, rejecting unusable parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of periods over which to calculate the RSI.
+    /// * `overbought` - Optional overbought threshold. If `None`, defaults to 70.0.
+    /// * `oversold` - Optional oversold threshold. If `None`, defaults to 30.0.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RSI)` for valid parameters.
+    /// * `Err(V1Error::InvalidPeriod)` if `period` is zero.
+    /// * `Err(V1Error::InvalidThresholds)` unless `0 <= oversold < overbought <= 100`.
+    pub fn try_new(
+        period: usize,
+        overbought: Option<f64>,
+        oversold: Option<f64>,
+    ) -> Result<Self, V1Error> {
+        if period == 0 {
+            return Err(V1Error::InvalidPeriod);
+        }
+
+        let rsi = Self::new(period, overbought, oversold);
+        if !(0.0 <= rsi.oversold && rsi.oversold < rsi.overbought && rsi.overbought <= 100.0) {
+            return Err(V1Error::InvalidThresholds);
+        }
+
+        Ok(rsi)
+    }
+
+    /// Creates a new RSI calculator whose thresholds adapt to its own recent values.
+    ///
+    /// Once `lookback` RSI values are available, the overbought level is their `pct`th percentile
+    /// and the oversold level their `(100 - pct)`th percentile. Until then the default 70/30
+    /// thresholds are used.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of periods over which to calculate the RSI.
+    /// * `lookback` - The number of recent RSI values the percentiles are taken over.
+    /// * `pct` - The overbought percentile, between 50 and 100 (e.g. 90.0).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RSI)` on success.
+    /// * `Err(RSIError::InvalidLookback)` if `lookback` is zero.
+    /// * `Err(RSIError::InvalidPercentile)` if `pct` is outside `[50, 100]`.
+    pub fn with_adaptive_thresholds(
+        period: usize,
+        lookback: usize,
+        pct: f64,
+    ) -> Result<Self, RSIError> {
+        if lookback == 0 {
+            return Err(RSIError::InvalidLookback);
         }
+        if !(50.0..=100.0).contains(&pct) {
+            return Err(RSIError::InvalidPercentile);
+        }
+
+        let mut rsi = Self::new(period, None, None);
+        rsi.history = VecDeque::with_capacity(lookback);
+        rsi.adaptive_lookback = lookback;
+        rsi.adaptive_pct = pct;
+        Ok(rsi)
+    }
+
+    /// Rounds the reported RSI value to `decimals` decimal places.
+    ///
+    /// Only the returned value is rounded; the running sums and the market condition
+    /// use full precision.
+    ///
+    /// # Arguments
+    ///
+    /// * `decimals` - The number of decimal places to keep.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use indexes_rs::v1::rsi::main::RSI;
+    ///
+    /// let mut rsi = RSI::new(3, None, None).with_precision(2);
+    /// ```
+    pub fn with_precision(mut self, decimals: u8) -> Self {
+        self.precision = Some(decimals);
+        self
+    }
+
+    /// Makes the reported market condition sticky with a hysteresis band of `band` RSI points.
+    ///
+    /// Entering overbought requires RSI >= overbought + band, and the condition is kept until
+    /// RSI drops to overbought - band or below (mirrored for oversold), so an RSI hovering at a
+    /// threshold no longer flips condition every bar. Negative or non-finite bands are treated
+    /// as 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `band` - The hysteresis half-width in RSI points.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use indexes_rs::v1::rsi::main::RSI;
+    ///
+    /// let mut rsi = RSI::new(14, None, None).with_hysteresis(2.0);
+    /// ```
+    pub fn with_hysteresis(mut self, band: f64) -> Self {
+        self.hysteresis = if band.is_finite() { band.max(0.0) } else { 0.0 };
+        self
+    }
+
+    /// Reports the RSI's slope and acceleration (first and second differences) in each result.
+    ///
+    /// Without this, `RSIResult::slope` and `RSIResult::acceleration` are always `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use indexes_rs::v1::rsi::main::RSI;
+    ///
+    /// let mut rsi = RSI::new(14, None, None).with_derivatives();
+    /// ```
+    pub fn with_derivatives(mut self) -> Self {
+        self.derivative = Some(Derivative::new());
+        self
+    }
+
+    /// Returns the current `(overbought, oversold)` thresholds.
+    pub fn thresholds(&self) -> (f64, f64) {
+        (self.overbought, self.oversold)
     }
 
     /// Updates the RSI calculation with a new price and returns the current RSI result if available.
@@ -90,6 +272,7 @@ impl RSI {
     /// An `Option<RSIResult>` containing:
     /// - `value`: The calculated RSI.
     /// - `condition`: The market condition determined from the RSI value.
+    /// - `slope`/`acceleration`: Changes in RSI, if enabled with [`RSI::with_derivatives`].
     ///
     /// Returns `None` if insufficient data has been provided.
     ///
@@ -108,8 +291,8 @@ impl RSI {
     /// }
     /// ```
     pub fn calculate(&mut self, price: f64) -> Option<RSIResult> {
-        // If a previous price exists, compute the change and update gains/losses.
-        if let Some(prev) = self.prev_price {
+        // If a previous value exists, compute the change and update gains/losses.
+        if let Some(prev) = self.prev_value {
             let change = price - prev;
             let (gain, loss) = if change >= 0.0 { (change, 0.0) } else { (0.0, change.abs()) };
 
@@ -129,7 +312,7 @@ impl RSI {
             }
         }
 
-        self.prev_price = Some(price);
+        self.prev_value = Some(price);
 
         // Return None if not enough data is available.
         if self.gains.len() < self.period {
@@ -143,12 +326,133 @@ impl RSI {
         let rs = if avg_loss == 0.0 { 100.0 } else { avg_gain / avg_loss };
         let rsi = 100.0 - (100.0 / (1.0 + rs));
 
+        if self.adaptive_lookback > 0 {
+            self.update_adaptive_thresholds(rsi);
+        }
+
+        let condition = self.classify(rsi);
+        let value = match self.precision {
+            Some(decimals) => round_to(rsi, decimals),
+            None => rsi,
+        };
+
+        let derivatives = self
+            .derivative
+            .as_mut()
+            .map(|d| d.update(Some(rsi)))
+            .unwrap_or_default();
+
         Some(RSIResult {
-            value: rsi,
-            condition: self.determine_condition(rsi),
+            value,
+            condition,
+            slope: derivatives.slope,
+            acceleration: derivatives.acceleration,
         })
     }
 
+    /// Calculates the RSI for a new price, rejecting non-finite prices.
+    ///
+    /// Unlike [`RSI::calculate`], a NaN or infinite price is reported as an error and leaves
+    /// the state untouched instead of poisoning later readings.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The latest price.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(RSIResult))` once enough data is available.
+    /// * `Ok(None)` while warming up.
+    /// * `Err(V1Error::InvalidPrice)` if `price` is NaN or infinite.
+    pub fn try_calculate(&mut self, price: f64) -> Result<Option<RSIResult>, V1Error> {
+        if !price.is_finite() {
+            return Err(V1Error::InvalidPrice);
+        }
+
+        Ok(self.calculate(price))
+    }
+
+    /// Feeds the close of every candle and returns one result per bar.
+    ///
+    /// # Arguments
+    ///
+    /// * `candles` - The bars to process, oldest first.
+    ///
+    /// # Returns
+    ///
+    /// * A vector with one entry per candle; warmup bars are `None`. An empty slice gives an
+    ///   empty vector, and a single candle always gives `[None]` since RSI needs a price change.
+    pub fn calculate_batch(&mut self, candles: &[Candle]) -> Vec<Option<RSIResult>> {
+        candles
+            .iter()
+            .map(|candle| self.calculate(candle.close))
+            .collect()
+    }
+
+    /// Returns a serializable copy of the calculator's internal state.
+    ///
+    /// # Returns
+    ///
+    /// An `RSIState` that [`RSI::set_state`] accepts to resume from this point.
+    pub fn get_state(&self) -> RSIState {
+        RSIState {
+            period: self.period,
+            gains: self.gains.clone(),
+            losses: self.losses.clone(),
+            sum_gains: self.sum_gains,
+            sum_losses: self.sum_losses,
+            prev_value: self.prev_value,
+            overbought: self.overbought,
+            oversold: self.oversold,
+            history: self.history.clone(),
+            adaptive_lookback: self.adaptive_lookback,
+            adaptive_pct: self.adaptive_pct,
+            precision: self.precision,
+            hysteresis: self.hysteresis,
+            last_condition: self.last_condition.clone(),
+            derivative: self.derivative,
+        }
+    }
+
+    /// Restores the internal state captured by [`RSI::get_state`].
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state to resume from.
+    pub fn set_state(&mut self, state: RSIState) {
+        self.period = state.period;
+        self.gains = state.gains;
+        self.losses = state.losses;
+        self.sum_gains = state.sum_gains;
+        self.sum_losses = state.sum_losses;
+        self.prev_value = state.prev_value;
+        self.overbought = state.overbought;
+        self.oversold = state.oversold;
+        self.history = state.history;
+        self.adaptive_lookback = state.adaptive_lookback;
+        self.adaptive_pct = state.adaptive_pct;
+        self.precision = state.precision;
+        self.hysteresis = state.hysteresis;
+        self.last_condition = state.last_condition;
+        self.derivative = state.derivative;
+    }
+
+    /// Updates the RSI with a new value from an arbitrary series.
+    ///
+    /// This is an alias of [`RSI::calculate`] for inputs that are not prices, such as volume,
+    /// an equity curve, or another indicator's output.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The latest value of the series.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<RSIResult>`, or `None` if insufficient data has been provided.
+    pub fn update(&mut self, value: f64) -> Option<RSIResult> {
+        self.calculate(value)
+    }
+
     /// Determines the market condition based on the given RSI value and the configured thresholds.
     ///
     /// - Returns `Overbought` if RSI is greater than or equal to the overbought threshold.
@@ -176,4 +480,46 @@ impl RSI {
             MarketCondition::Neutral
         }
     }
+
+    /// Determines the market condition, applying the hysteresis band against the previous condition.
+    fn classify(&mut self, rsi: f64) -> MarketCondition {
+        let band = self.hysteresis;
+        let condition = match self.last_condition {
+            Some(MarketCondition::Overbought) if rsi > self.overbought - band => {
+                MarketCondition::Overbought
+            }
+            Some(MarketCondition::Oversold) if rsi < self.oversold + band => {
+                MarketCondition::Oversold
+            }
+            _ if rsi >= self.overbought + band => MarketCondition::Overbought,
+            _ if rsi <= self.oversold - band => MarketCondition::Oversold,
+            _ => MarketCondition::Neutral,
+        };
+        self.last_condition = Some(condition.clone());
+        condition
+    }
+
+    /// Adds an RSI value to the adaptive window and recomputes the thresholds once it is full.
+    fn update_adaptive_thresholds(&mut self, rsi: f64) {
+        self.history.push_back(rsi);
+        if self.history.len() > self.adaptive_lookback {
+            self.history.pop_front();
+        }
+        if self.history.len() < self.adaptive_lookback {
+            return;
+        }
+
+        let mut sorted: Vec<f64> = self.history.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        self.overbought = percentile(&sorted, self.adaptive_pct);
+        self.oversold = percentile(&sorted, 100.0 - self.adaptive_pct);
+    }
+}
+
+/// Linearly interpolated percentile (0-100) of an ascending, non-empty slice.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    let rank = pct / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
 }
@@ -1,6 +1,8 @@
-use serde::Serialize;
+use crate::util::derivative::main::Derivative;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum MarketCondition {
     Overbought,
     Oversold,
@@ -11,4 +13,58 @@ pub enum MarketCondition {
 pub struct RSIResult {
     pub value: f64,
     pub condition: MarketCondition,
+    /// Change in RSI from the previous bar (only with `RSI::with_derivatives`).
+    pub slope: Option<f64>,
+    /// Change in slope from the previous bar (only with `RSI::with_derivatives`).
+    pub acceleration: Option<f64>,
+}
+
+/// A serializable copy of an RSI calculator's internal state.
+///
+/// Obtained from [`RSI::get_state`](super::main::RSI::get_state) and restored with
+/// [`RSI::set_state`](super::main::RSI::set_state) to resume streaming after a restart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RSIState {
+    /// The RSI period.
+    pub period: usize,
+    /// Gains over the current window.
+    pub gains: VecDeque<f64>,
+    /// Losses over the current window.
+    pub losses: VecDeque<f64>,
+    /// Running sum of `gains`.
+    pub sum_gains: f64,
+    /// Running sum of `losses`.
+    pub sum_losses: f64,
+    /// The previous input value.
+    pub prev_value: Option<f64>,
+    /// The overbought threshold.
+    pub overbought: f64,
+    /// The oversold threshold.
+    pub oversold: f64,
+    /// Recent RSI values used for adaptive thresholds.
+    pub history: VecDeque<f64>,
+    /// Number of RSI values the adaptive thresholds are computed over (0 = fixed thresholds).
+    pub adaptive_lookback: usize,
+    /// Percentile used for the adaptive overbought level.
+    pub adaptive_pct: f64,
+    /// Decimal places the reported RSI value is rounded to.
+    pub precision: Option<u8>,
+    /// Hysteresis band around the thresholds.
+    #[serde(default)]
+    pub hysteresis: f64,
+    /// The condition reported for the previous RSI value.
+    #[serde(default)]
+    pub last_condition: Option<MarketCondition>,
+    /// Slope/acceleration tracker (`None` unless derivatives are enabled).
+    #[serde(default)]
+    pub derivative: Option<Derivative>,
+}
+
+/// An error type for the RSI calculator.
+#[derive(Debug, PartialEq)]
+pub enum RSIError {
+    /// Indicates that the adaptive threshold lookback is zero.
+    InvalidLookback,
+    /// Indicates that the adaptive threshold percentile is outside `[50, 100]`.
+    InvalidPercentile,
 }
@@ -3,7 +3,7 @@
 #[cfg(test)]
 mod tests {
     use crate::v1::{
-        sma::main::{SMAError, SMAResult, SimpleMovingAverage},
+        sma::main::{SMADeadband, SMAError, SMAResult, SimpleMovingAverage},
         types::TrendDirection,
     };
 
@@ -86,4 +86,80 @@ mod tests {
             }
         );
     }
+
+    /// Test that an invalid deadband is rejected.
+    #[test]
+    fn test_deadband_invalid() {
+        assert_eq!(
+            SimpleMovingAverage::with_deadband(3, SMADeadband::Absolute(-0.1)),
+            Err(SMAError::InvalidDeadband)
+        );
+        assert_eq!(
+            SimpleMovingAverage::with_deadband(3, SMADeadband::Relative(f64::NAN)),
+            Err(SMAError::InvalidDeadband)
+        );
+        assert_eq!(
+            SimpleMovingAverage::with_deadband(0, SMADeadband::Absolute(0.1)),
+            Err(SMAError::InvalidPeriod)
+        );
+    }
+
+    /// Test that changes within an absolute deadband stay Sideways.
+    #[test]
+    fn test_absolute_deadband() {
+        let mut sma = SimpleMovingAverage::with_deadband(1, SMADeadband::Absolute(0.5)).unwrap();
+        sma.add_value(100.0);
+        sma.calculate();
+
+        sma.add_value(100.3);
+        assert_eq!(sma.calculate().unwrap().trend, TrendDirection::Sideways);
+
+        sma.add_value(99.9);
+        assert_eq!(sma.calculate().unwrap().trend, TrendDirection::Sideways);
+
+        sma.add_value(101.0);
+        assert_eq!(sma.calculate().unwrap().trend, TrendDirection::Up);
+
+        sma.add_value(100.0);
+        assert_eq!(sma.calculate().unwrap().trend, TrendDirection::Down);
+    }
+
+    /// Test that a relative deadband scales with the SMA level.
+    #[test]
+    fn test_relative_deadband() {
+        // 1% deadband
+        let mut sma = SimpleMovingAverage::with_deadband(1, SMADeadband::Relative(0.01)).unwrap();
+        sma.add_value(1000.0);
+        sma.calculate();
+
+        // +5 is 0.5% of 1000: inside the deadband.
+        sma.add_value(1005.0);
+        assert_eq!(sma.calculate().unwrap().trend, TrendDirection::Sideways);
+
+        // -20 is ~2% of 1005: a real move down.
+        sma.add_value(985.0);
+        assert_eq!(sma.calculate().unwrap().trend, TrendDirection::Down);
+    }
+
+    /// Test that replacing the live value matches feeding only the revised value.
+    #[test]
+    fn test_update_or_replace_last() {
+        let closes = [10.0, 11.0, 12.0, 11.5, 13.0, 12.5, 14.0];
+        let mut live = SimpleMovingAverage::new(3).unwrap();
+        let mut reference = SimpleMovingAverage::new(3).unwrap();
+
+        for &close in &closes {
+            // Each bar first arrives 1.0 too high, then is revised to its final value
+            live.add_value(close + 1.0);
+            live.calculate();
+            let revised = live.update_or_replace_last(close);
+
+            reference.add_value(close);
+            assert_eq!(revised, reference.calculate());
+        }
+
+        // With nothing to replace, the value is simply added
+        let mut sma = SimpleMovingAverage::new(1).unwrap();
+        assert_eq!(sma.update_or_replace_last(5.0).unwrap().value, 5.0);
+    }
 }
@@ -7,6 +7,8 @@
 //! This implementation uses a sliding window backed by a [`VecDeque`](https://doc.rust-lang.org/std/collections/struct.VecDeque.html)
 //! and maintains a running sum for improved performance. In addition to calculating the SMA value,
 //! it also determines the trend (Up, Down, or Neutral) based on the change from the previous SMA.
+//! An optional [`SMADeadband`] keeps the trend `Sideways` until the SMA moves by more than a
+//! configurable absolute or relative amount, filtering out floating-point wiggles.
 //!
 //! # Examples
 //!
@@ -27,7 +29,7 @@
 //! assert_eq!(result.trend, TrendDirection::Sideways);
 //! ```
 
-pub use super::types::{SMAError, SMAResult};
+pub use super::types::{SMADeadband, SMAError, SMAResult};
 use crate::v1::types::TrendDirection;
 use std::collections::VecDeque;
 
@@ -40,6 +42,8 @@ use std::collections::VecDeque;
 /// - If the current SMA is greater, the trend is `Up`.
 /// - If it is lower, the trend is `Down`.
 /// - If it is the same (or if no previous value exists), the trend is `Sideways`.
+///
+/// With a deadband, changes no larger than the deadband also report `Sideways`.
 #[derive(Debug, PartialEq)]
 pub struct SimpleMovingAverage {
     /// The period over which the moving average is calculated.
@@ -50,6 +54,12 @@ pub struct SimpleMovingAverage {
     sum: f64,
     /// The previous calculated SMA value.
     last_value: Option<f64>,
+    /// The minimum change required to report an `Up` or `Down` trend.
+    deadband: SMADeadband,
+    /// The running sum before the latest `add_value` and the value it evicted, for rolling it back.
+    last_add: Option<(f64, Option<f64>)>,
+    /// The SMA value reported before the latest `add_value` (the baseline for its trend).
+    value_before_last: Option<f64>,
 }
 
 impl SimpleMovingAverage {
@@ -92,9 +102,51 @@ impl SimpleMovingAverage {
             values: VecDeque::with_capacity(period),
             sum: 0.0,
             last_value: None,
+            deadband: SMADeadband::default(),
+            last_add: None,
+            value_before_last: None,
         })
     }
 
+    /// Creates a new `SimpleMovingAverage` whose trend ignores changes within a deadband.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of values to include in the moving average calculation.
+    /// * `deadband` - The absolute or relative change the SMA must exceed to report `Up` or `Down`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SimpleMovingAverage)` - A new instance with the specified period and deadband.
+    /// * `Err(SMAError)` - If the period is zero or the deadband is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use indexes_rs::v1::sma::main::{SMADeadband, SimpleMovingAverage};
+    /// use indexes_rs::v1::types::TrendDirection;
+    ///
+    /// let mut sma = SimpleMovingAverage::with_deadband(2, SMADeadband::Absolute(0.5)).unwrap();
+    /// sma.add_value(10.0);
+    /// sma.add_value(10.0);
+    /// sma.calculate();
+    ///
+    /// // The SMA moves by only 0.1, which stays inside the deadband.
+    /// sma.add_value(10.2);
+    /// assert_eq!(sma.calculate().unwrap().trend, TrendDirection::Sideways);
+    /// ```
+    pub fn with_deadband(period: usize, deadband: SMADeadband) -> Result<Self, SMAError> {
+        let amount = match deadband {
+            SMADeadband::Absolute(amount) | SMADeadband::Relative(amount) => amount,
+        };
+        if !amount.is_finite() || amount < 0.0 {
+            return Err(SMAError::InvalidDeadband);
+        }
+        let mut sma = Self::new(period)?;
+        sma.deadband = deadband;
+        Ok(sma)
+    }
+
     /// Adds a new value to the moving window.
     ///
     /// If the window is full (i.e., the number of stored values equals the period),
@@ -115,13 +167,61 @@ impl SimpleMovingAverage {
     /// sma.add_value(6.0);
     /// ```
     pub fn add_value(&mut self, value: f64) {
+        let sum_before = self.sum;
+        let mut evicted = None;
         if self.values.len() == self.period {
             if let Some(old_value) = self.values.pop_front() {
                 self.sum -= old_value;
+                evicted = Some(old_value);
             }
         }
         self.values.push_back(value);
         self.sum += value;
+        self.last_add = Some((sum_before, evicted));
+        self.value_before_last = self.last_value;
+    }
+
+    /// Replaces the most recent value with a revised one and recalculates.
+    ///
+    /// Intended for live feeds where the latest (unclosed) bar is revised before it finalizes:
+    /// call [`SimpleMovingAverage::add_value`] when a new bar opens and this method for each
+    /// revision. The result, including the trend, is the same as if only the revised value had
+    /// been added. With no value to replace yet, the value is simply added.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The revised value of the latest bar.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(SMAResult)` - The recalculated SMA and trend if enough values are available.
+    /// * `None` - If there aren't enough values to calculate the average.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use indexes_rs::v1::sma::main::SimpleMovingAverage;
+    ///
+    /// let mut sma = SimpleMovingAverage::new(3).unwrap();
+    /// sma.add_value(2.0);
+    /// sma.add_value(4.0);
+    /// sma.add_value(7.0);
+    /// sma.calculate();
+    ///
+    /// // The live bar is revised from 7.0 to 6.0
+    /// assert_eq!(sma.update_or_replace_last(6.0).unwrap().value, 4.0);
+    /// ```
+    pub fn update_or_replace_last(&mut self, value: f64) -> Option<SMAResult> {
+        if let Some((sum_before, evicted)) = self.last_add.take() {
+            self.values.pop_back();
+            if let Some(old_value) = evicted {
+                self.values.push_front(old_value);
+            }
+            self.sum = sum_before;
+            self.last_value = self.value_before_last;
+        }
+        self.add_value(value);
+        self.calculate()
     }
 
     /// Calculates the current Simple Moving Average and determines the trend.
@@ -153,9 +253,21 @@ impl SimpleMovingAverage {
         }
         let current_sma = self.sum / self.period as f64;
         let trend = match self.last_value {
-            Some(prev) if current_sma > prev => TrendDirection::Up,
-            Some(prev) if current_sma < prev => TrendDirection::Down,
-            _ => TrendDirection::Sideways,
+            Some(prev) => {
+                let threshold = match self.deadband {
+                    SMADeadband::Absolute(amount) => amount,
+                    SMADeadband::Relative(fraction) => prev.abs() * fraction,
+                };
+                let change = current_sma - prev;
+                if change > threshold {
+                    TrendDirection::Up
+                } else if change < -threshold {
+                    TrendDirection::Down
+                } else {
+                    TrendDirection::Sideways
+                }
+            }
+            None => TrendDirection::Sideways,
         };
         self.last_value = Some(current_sma);
         Some(SMAResult { value: current_sma, trend })
@@ -10,9 +10,27 @@ pub struct SMAResult {
     pub trend: TrendDirection,
 }
 
+/// The minimum SMA change required before the trend leaves `Sideways`.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum SMADeadband {
+    /// An absolute change in SMA value (e.g. `0.05` price units).
+    Absolute(f64),
+    /// A change relative to the previous SMA value (e.g. `0.001` for 0.1%).
+    Relative(f64),
+}
+
+impl Default for SMADeadband {
+    /// No deadband: any nonzero change flips the trend.
+    fn default() -> Self {
+        SMADeadband::Absolute(0.0)
+    }
+}
+
 /// An error type for the SimpleMovingAverage.
 #[derive(Debug, PartialEq)]
 pub enum SMAError {
     /// Indicates that the provided period is invalid (e.g., zero).
     InvalidPeriod,
+    /// Indicates that the deadband is negative or not finite.
+    InvalidDeadband,
 }
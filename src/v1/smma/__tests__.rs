@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use crate::v1::smma::main::{SMMAError, SmoothedMovingAverage};
+
+    #[test]
+    fn test_invalid_period() {
+        assert_eq!(SmoothedMovingAverage::new(0), Err(SMMAError::InvalidPeriod));
+    }
+
+    #[test]
+    fn test_smma_calculation() {
+        let mut smma = SmoothedMovingAverage::new(4).unwrap();
+        for value in [10.0, 11.0, 12.0] {
+            assert_eq!(smma.add_value(value), None);
+        }
+        // Seed: (10 + 11 + 12 + 13) / 4
+        assert_eq!(smma.add_value(13.0), Some(11.5));
+        // (11.5 * 3 + 15.5) / 4
+        assert_eq!(smma.add_value(15.5), Some(12.5));
+        assert_eq!(smma.get_current_value(), Some(12.5));
+    }
+
+    #[test]
+    fn test_period_one_tracks_input() {
+        let mut smma = SmoothedMovingAverage::new(1).unwrap();
+        for value in [3.0, 7.0, 5.0] {
+            assert_eq!(smma.add_value(value), Some(value));
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! # Smoothed Moving Average (SMMA) Module
+//!
+//! This module implements the Smoothed Moving Average (also known as Wilder's moving average or
+//! RMA). The first value is the simple average of the first `period` values; every later value is:
+//!
+//! \[\text{SMMA}_{\text{new}} = \frac{\text{SMMA}_{\text{prev}} \times (n - 1) + \text{price}}{n}\]
+//!
+//! # Examples
+//!
+//! ```rust
+//! use indexes_rs::v1::smma::main::SmoothedMovingAverage;
+//!
+//! let mut smma = SmoothedMovingAverage::new(3).unwrap();
+//! assert_eq!(smma.add_value(1.0), None);
+//! assert_eq!(smma.add_value(2.0), None);
+//! // Seeded with the simple average of the first three values
+//! assert_eq!(smma.add_value(3.0), Some(2.0));
+//! // (2 * 2 + 5) / 3
+//! assert_eq!(smma.add_value(5.0), Some(3.0));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+pub use super::types::SMMAError;
+
+/// A Smoothed Moving Average (SMMA) indicator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmoothedMovingAverage {
+    /// The period over which the moving average is calculated.
+    pub period: usize,
+    /// Running sum of the values seen while seeding.
+    seed_sum: f64,
+    /// Number of values seen while seeding.
+    seed_count: usize,
+    /// The current SMMA value.
+    current_value: Option<f64>,
+}
+
+impl SmoothedMovingAverage {
+    /// Creates a new `SmoothedMovingAverage` with the specified period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The smoothing period.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SmoothedMovingAverage)` - A new instance with the specified period.
+    /// * `Err(SMMAError)` - If the period is invalid (e.g., zero).
+    pub fn new(period: usize) -> Result<Self, SMMAError> {
+        if period == 0 {
+            return Err(SMMAError::InvalidPeriod);
+        }
+        Ok(SmoothedMovingAverage {
+            period,
+            seed_sum: 0.0,
+            seed_count: 0,
+            current_value: None,
+        })
+    }
+
+    /// Adds a new value and returns the current SMMA.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(f64)` once `period` values have been seen.
+    /// * `None` while the seed average is still filling.
+    pub fn add_value(&mut self, value: f64) -> Option<f64> {
+        self.current_value = match self.current_value {
+            Some(prev) => Some((prev * (self.period - 1) as f64 + value) / self.period as f64),
+            None => {
+                self.seed_sum += value;
+                self.seed_count += 1;
+                (self.seed_count == self.period).then(|| self.seed_sum / self.period as f64)
+            }
+        };
+        self.current_value
+    }
+
+    /// Returns the current SMMA value.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(f64)` if the SMMA has been computed.
+    /// * `None` if fewer than `period` values have been added.
+    pub fn get_current_value(&self) -> Option<f64> {
+        self.current_value
+    }
+}
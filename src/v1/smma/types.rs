@@ -0,0 +1,6 @@
+/// An error type for the SmoothedMovingAverage.
+#[derive(Debug, PartialEq)]
+pub enum SMMAError {
+    /// Indicates that the provided period is invalid (e.g., zero).
+    InvalidPeriod,
+}
@@ -1,6 +1,10 @@
 #[cfg(test)]
 mod tests {
-    use crate::v1::stochastic::{main::StochasticOscillator, types::StochResult};
+    use crate::v1::stochastic::{
+        main::StochasticOscillator,
+        types::{StochCrossover, StochResult},
+    };
+    use crate::v1::types::V1Error;
 
     #[test]
     fn test_insufficient_data() {
@@ -29,4 +33,119 @@ mod tests {
         assert!(res.k_value >= 0.0 && res.k_value <= 100.0);
         assert!(res.d_value >= 0.0 && res.d_value <= 100.0);
     }
+
+    #[test]
+    fn test_bars_since_extremes() {
+        let mut stoch = StochasticOscillator::new(5, 3, 3);
+        let mut result = None;
+        for price in [100.0, 95.0, 98.0, 97.0, 99.0] {
+            result = stoch.calculate(price);
+        }
+        let res = result.unwrap();
+        assert_eq!(res.bars_since_high, 4);
+        assert_eq!(res.bars_since_low, 3);
+
+        // A fresh new high is the current bar
+        let res = stoch.calculate(105.0).unwrap();
+        assert_eq!(res.bars_since_high, 0);
+        assert_eq!(res.bars_since_low, 4);
+
+        // The low at 95.0 leaves the window; 97.0 is now the lowest
+        let res = stoch.calculate(104.0).unwrap();
+        assert_eq!(res.bars_since_high, 1);
+        assert_eq!(res.bars_since_low, 3);
+    }
+
+    #[test]
+    fn test_raw_k_fast_and_smoothed() {
+        let prices = [
+            100.0, 102.0, 101.5, 103.0, 104.0, 102.5, 101.0, 100.5, 99.5, 101.5,
+        ];
+
+        let mut fast = StochasticOscillator::new(5, 1, 3);
+        for price in prices {
+            if let Some(res) = fast.calculate(price) {
+                assert_eq!(res.raw_k, res.k_value);
+            }
+        }
+
+        let mut slow = StochasticOscillator::new(5, 3, 3);
+        let mut result = None;
+        for price in prices {
+            result = slow.calculate(price);
+        }
+        let res = result.unwrap();
+        assert!((res.raw_k - res.k_value).abs() > 1e-9);
+    }
+
+    #[test]
+    fn test_try_new_rejects_zero_periods() {
+        assert_eq!(
+            StochasticOscillator::try_new(0, 3, 3).err(),
+            Some(V1Error::InvalidPeriod)
+        );
+        assert_eq!(
+            StochasticOscillator::try_new(14, 0, 3).err(),
+            Some(V1Error::InvalidPeriod)
+        );
+        assert_eq!(
+            StochasticOscillator::try_new(14, 3, 0).err(),
+            Some(V1Error::InvalidPeriod)
+        );
+        assert!(StochasticOscillator::try_new(14, 3, 3).is_ok());
+    }
+
+    #[test]
+    fn test_update_or_replace_last() {
+        let closes = [10.0, 11.0, 12.0, 11.5, 13.0, 12.5, 14.0, 13.0, 12.0, 12.5];
+        let mut live = StochasticOscillator::new(4, 2, 2);
+        let mut reference = StochasticOscillator::new(4, 2, 2);
+
+        for &close in &closes {
+            // Each bar first prints a spike, then is revised to its final value
+            live.calculate(close * 1.5);
+            live.update_or_replace_last(close * 0.5);
+            let revised = live.update_or_replace_last(close);
+
+            let expected = reference.calculate(close);
+            assert_eq!(
+                revised.map(|r| (r.raw_k, r.k_value, r.d_value)),
+                expected.map(|r| (r.raw_k, r.k_value, r.d_value))
+            );
+        }
+    }
+
+    #[test]
+    fn test_zone_cross_only_fires_in_extreme_zones() {
+        // %K over 3 prices, unsmoothed, %D over 2 values; the falling prices pin %K at 0
+        let falling = [10.0, 9.0, 8.0, 7.0];
+
+        // Rebound to the middle of the range: %K 50 crosses above %D 25 in the neutral zone
+        let mut stoch = StochasticOscillator::new(3, 1, 2);
+        falling.iter().for_each(|&price| {
+            stoch.calculate(price);
+        });
+        let neutral = stoch.calculate(7.5).unwrap();
+        assert_eq!(neutral.crossover, StochCrossover::Bullish);
+        assert_eq!(neutral.zone_cross, None);
+
+        // A smaller rebound: %K 10 crosses above %D 5 while still oversold
+        let mut stoch = StochasticOscillator::new(3, 1, 2);
+        falling.iter().for_each(|&price| {
+            stoch.calculate(price);
+        });
+        let oversold = stoch.calculate(7.1).unwrap();
+        assert!((oversold.k_value - 10.0).abs() < 1e-9);
+        assert_eq!(oversold.crossover, StochCrossover::Bullish);
+        assert_eq!(oversold.zone_cross, Some(StochCrossover::Bullish));
+
+        // Falling back crosses %K under %D inside the zone too
+        let bearish = stoch.calculate(6.0).unwrap();
+        assert_eq!(bearish.zone_cross, Some(StochCrossover::Bearish));
+
+        // Pinned at the bottom of the range: deep in the zone, but no crossover
+        let pinned = stoch.calculate(5.0).unwrap();
+        assert_eq!(pinned.crossover, StochCrossover::None);
+        assert_eq!(pinned.zone_cross, None);
+    }
 }
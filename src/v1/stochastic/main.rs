@@ -35,19 +35,23 @@
 //! ```
 
 use super::types::*; // This module should define StochResult, StochSignal, StochCondition, and StochCrossover.
+use crate::util::rolling_extremes::main::RollingExtremes;
+use crate::v1::types::V1Error;
 
 /// A Stochastic Oscillator indicator.
 pub struct StochasticOscillator {
     period: usize,   // %K period (typically 14)
     k_smooth: usize, // %K smoothing period (typically 3)
     d_period: usize, // %D period (typically 3)
-    prices: Vec<f64>,
-    highs: Vec<f64>,
-    lows: Vec<f64>,
+    /// Rolling highest high / lowest low over the %K window.
+    extremes: RollingExtremes,
     /// Stores the smoothed %K values (used for %D calculation).
     k_values: Vec<f64>,
     /// Stores raw %K values for smoothing calculation.
     raw_k_values: Vec<f64>,
+    /// The raw %K and %K values evicted by the latest calculation, for rolling it back
+    /// (`None` if that calculation stored no %K).
+    last_k_evictions: Option<(Option<f64>, Option<f64>)>,
 }
 
 impl StochasticOscillator {
@@ -73,14 +77,33 @@ impl StochasticOscillator {
             period,
             k_smooth,
             d_period,
-            prices: Vec::new(),
-            highs: Vec::new(),
-            lows: Vec::new(),
+            extremes: RollingExtremes::new(period),
             k_values: Vec::new(),
             raw_k_values: Vec::new(),
+            last_k_evictions: None,
         }
     }
 
+    /// Creates a new Stochastic Oscillator, rejecting zero periods.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The lookback period for the highest high and lowest low.
+    /// * `k_smooth` - The smoothing period for %K.
+    /// * `d_period` - The period for %D.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StochasticOscillator)` if every period is non-zero.
+    /// * `Err(V1Error::InvalidPeriod)` otherwise.
+    pub fn try_new(period: usize, k_smooth: usize, d_period: usize) -> Result<Self, V1Error> {
+        if period == 0 || k_smooth == 0 || d_period == 0 {
+            return Err(V1Error::InvalidPeriod);
+        }
+
+        Ok(Self::new(period, k_smooth, d_period))
+    }
+
     /// Updates the oscillator with a new price and returns the current oscillator result.
     ///
     /// The oscillator calculates the raw %K value based on the current window of prices,
@@ -95,38 +118,21 @@ impl StochasticOscillator {
     ///
     /// * `Some(StochResult)` if there is sufficient data, else `None`.
     pub fn calculate(&mut self, price: f64) -> Option<StochResult> {
-        // Update price history (for simplicity, highs and lows are the same as price).
-        self.prices.push(price);
-        self.highs.push(price);
-        self.lows.push(price);
-
-        // Keep sliding window for price/high/low arrays.
-        if self.prices.len() > self.period {
-            self.prices.remove(0);
-            self.highs.remove(0);
-            self.lows.remove(0);
-        }
+        // Update the price window (for simplicity, highs and lows are the same as price).
+        let (lowest_low, highest_high) = self.extremes.push(price);
+        self.last_k_evictions = None;
 
-        if self.prices.len() < self.period {
+        if self.extremes.len() < self.period {
             return None;
         }
 
-        // Determine the highest high and lowest low in the window.
-        let highest_high = self.highs.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-        let lowest_low = self.lows.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-
         // Compute raw %K value. If highest equals lowest, use 50.
-        let raw_k = if highest_high == lowest_low {
-            50.0
-        } else {
-            ((price - lowest_low) / (highest_high - lowest_low)) * 100.0
-        };
+        let raw_k = percent_k(price, lowest_low, highest_high).unwrap_or(50.0);
 
         // Store raw %K for smoothing.
         self.raw_k_values.push(raw_k);
-        if self.raw_k_values.len() > self.k_smooth {
-            self.raw_k_values.remove(0);
-        }
+        let evicted_raw_k =
+            (self.raw_k_values.len() > self.k_smooth).then(|| self.raw_k_values.remove(0));
 
         // Compute the smoothed %K value if we have enough raw values; otherwise, use raw_k.
         let k = if self.raw_k_values.len() < self.k_smooth {
@@ -137,26 +143,60 @@ impl StochasticOscillator {
 
         // Store the smoothed %K value for %D calculation.
         self.k_values.push(k);
-        if self.k_values.len() > self.d_period {
-            self.k_values.remove(0);
-        }
+        let evicted_k = (self.k_values.len() > self.d_period).then(|| self.k_values.remove(0));
+        self.last_k_evictions = Some((evicted_raw_k, evicted_k));
 
         let d = self.calculate_d();
         let signal = self.generate_signal(k, d);
         let condition = self.determine_condition(k);
         let crossover = self.detect_crossover(k, d);
+        let zone_cross = Self::zone_cross(&crossover, &condition);
         let strength = self.calculate_strength(k, d);
 
         Some(StochResult {
+            raw_k,
             k_value: k,
             d_value: d,
             signal,
             condition,
             crossover,
+            zone_cross,
             strength,
+            bars_since_high: self.extremes.bars_since_max().unwrap_or(0),
+            bars_since_low: self.extremes.bars_since_min().unwrap_or(0),
         })
     }
 
+    /// Replaces the most recent price with a revised one and recalculates.
+    ///
+    /// Intended for live feeds where the latest (unclosed) bar is revised before it finalizes:
+    /// call [`StochasticOscillator::calculate`] when a new bar opens and this method for each
+    /// revision. The result is the same as if only the revised price had been fed. With no price
+    /// to replace yet, the price is simply added.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The revised price of the latest bar.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(StochResult)` if there is sufficient data, else `None`.
+    pub fn update_or_replace_last(&mut self, price: f64) -> Option<StochResult> {
+        if self.extremes.undo_last() {
+            if let Some((evicted_raw_k, evicted_k)) = self.last_k_evictions.take() {
+                self.raw_k_values.pop();
+                if let Some(raw_k) = evicted_raw_k {
+                    self.raw_k_values.insert(0, raw_k);
+                }
+                self.k_values.pop();
+                if let Some(k) = evicted_k {
+                    self.k_values.insert(0, k);
+                }
+            }
+        }
+        self.calculate(price)
+    }
+
     /// Calculates the %D value as the average of the last `d_period` %K values.
     fn calculate_d(&self) -> f64 {
         if self.k_values.len() < self.d_period {
@@ -205,6 +245,18 @@ impl StochasticOscillator {
         }
     }
 
+    /// Keeps a crossover only if %K is in the overbought or oversold zone.
+    fn zone_cross(
+        crossover: &StochCrossover,
+        condition: &StochCondition,
+    ) -> Option<StochCrossover> {
+        match (crossover, condition) {
+            (StochCrossover::None, _) => None,
+            (_, StochCondition::Overbought | StochCondition::Oversold) => Some(crossover.clone()),
+            _ => None,
+        }
+    }
+
     /// Calculates an overall strength value (0-100) for the oscillator.
     ///
     /// Combines the deviation of %K from 50 and the absolute difference between %K and %D.
@@ -214,3 +266,22 @@ impl StochasticOscillator {
         ((trend_strength + momentum) / 2.0 * 100.0).min(100.0)
     }
 }
+
+/// Computes the raw %K: where `price` sits within `[lowest, highest]`, from 0 to 100.
+///
+/// # Returns
+///
+/// * `Some(f64)` for a non-empty range.
+/// * `None` if `highest` equals `lowest`, where %K is undefined.
+///
+/// # Examples
+///
+/// ```rust
+/// use indexes_rs::v1::stochastic::main::percent_k;
+///
+/// assert_eq!(percent_k(15.0, 10.0, 30.0), Some(25.0));
+/// assert_eq!(percent_k(10.0, 10.0, 10.0), None);
+/// ```
+pub fn percent_k(price: f64, lowest: f64, highest: f64) -> Option<f64> {
+    (highest > lowest).then(|| (price - lowest) / (highest - lowest) * 100.0)
+}
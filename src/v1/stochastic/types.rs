@@ -27,10 +27,20 @@ pub enum StochCrossover {
 
 #[derive(Serialize, Clone)]
 pub struct StochResult {
+    /// Unsmoothed %K for the current bar.
+    pub raw_k: f64,
+    /// %K after `k_smooth` averaging (equal to `raw_k` in fast mode).
     pub k_value: f64,
     pub d_value: f64,
     pub signal: StochSignal,
     pub condition: StochCondition,
     pub crossover: StochCrossover,
+    /// The %K/%D crossover, only when it happens with %K in the overbought (>= 80) or
+    /// oversold (<= 20) zone.
+    pub zone_cross: Option<StochCrossover>,
     pub strength: f64,
+    /// Bars since the highest high in the %K window (0 = the current bar).
+    pub bars_since_high: usize,
+    /// Bars since the lowest low in the %K window (0 = the current bar).
+    pub bars_since_low: usize,
 }
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
-    use crate::v1::support_resistance::main::SupportResistance;
+    use crate::v1::support_resistance::{main::SupportResistance, types::BreakoutEvent};
+    use crate::v1::types::V1Error;
 
     #[test]
     fn test_insufficient_prices() {
@@ -30,4 +31,95 @@ mod tests {
         }
         // Further assertions can be added based on your expected behavior.
     }
+
+    #[test]
+    fn test_smaller_swing_lookback_detects_more_levels() {
+        // Small zig-zags riding on a larger wave
+        let prices: Vec<f64> = (0..80)
+            .map(|i| 100.0 + 5.0 * (i as f64 * 0.2).sin() + if i % 2 == 0 { 0.6 } else { -0.6 })
+            .collect();
+
+        let mut narrow = SupportResistance::with_swing_lookback(10, 0.5, 1);
+        let mut wide = SupportResistance::with_swing_lookback(10, 0.5, 3);
+        for &price in &prices {
+            narrow.calculate(price);
+            wide.calculate(price);
+        }
+
+        let narrow_count = narrow.support_levels().len() + narrow.resistance_levels().len();
+        let wide_count = wide.support_levels().len() + wide.resistance_levels().len();
+        assert!(wide_count > 0);
+        assert!(
+            narrow_count > wide_count,
+            "lookback 1 found {} levels, lookback 3 found {}",
+            narrow_count,
+            wide_count
+        );
+    }
+
+    #[test]
+    fn test_swing_lookback_pivot_is_lookback_bars_back() {
+        let mut sr = SupportResistance::with_swing_lookback(3, 0.5, 2);
+        for price in [100.0, 101.0, 105.0, 102.0, 101.0] {
+            sr.calculate(price);
+        }
+        assert_eq!(sr.resistance_levels(), &[105.0]);
+        assert!(sr.support_levels().is_empty());
+    }
+
+    #[test]
+    fn test_resistance_breakout_fires_once() {
+        let mut sr = SupportResistance::new(5, 0.05);
+        // Swing high at 105, then price climbs back through it and keeps going
+        let prices = [
+            100.0, 102.0, 105.0, 102.0, 100.0, 101.0, 103.0, 104.0, 106.0, 107.0, 108.0,
+        ];
+        let events: Vec<_> = prices
+            .iter()
+            .filter_map(|&p| sr.calculate(p))
+            .map(|r| r.breakout)
+            .collect();
+
+        let fired: Vec<_> = events.iter().flatten().collect();
+        assert_eq!(fired, vec![&BreakoutEvent::ResistanceBroken]);
+        // Fires on the close from 104 to 106
+        assert_eq!(
+            events[events.len() - 3],
+            Some(BreakoutEvent::ResistanceBroken)
+        );
+        assert_eq!(sr.resistance_levels(), &[105.0]);
+    }
+
+    #[test]
+    fn test_support_breakout() {
+        let mut sr = SupportResistance::new(5, 0.05);
+        let prices = [104.0, 102.0, 100.0, 102.0, 104.0, 103.0, 101.0, 99.0, 98.0];
+        let fired: Vec<_> = prices
+            .iter()
+            .filter_map(|&p| sr.calculate(p))
+            .filter_map(|r| r.breakout)
+            .collect();
+        assert_eq!(fired, vec![BreakoutEvent::SupportBroken]);
+    }
+
+    #[test]
+    fn test_try_new_rejects_invalid_parameters() {
+        assert_eq!(
+            SupportResistance::try_new(0, 0.02).err(),
+            Some(V1Error::InvalidPeriod)
+        );
+        assert_eq!(
+            SupportResistance::try_new(20, -0.1).err(),
+            Some(V1Error::InvalidThresholds)
+        );
+        assert_eq!(
+            SupportResistance::try_new(20, 1.0).err(),
+            Some(V1Error::InvalidThresholds)
+        );
+        assert_eq!(
+            SupportResistance::try_new(20, f64::NAN).err(),
+            Some(V1Error::InvalidThresholds)
+        );
+        assert!(SupportResistance::try_new(20, 0.02).is_ok());
+    }
 }
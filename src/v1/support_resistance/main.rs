@@ -11,6 +11,7 @@
 //! - A resistance strength (0-100%)
 //! - A breakout potential (based on the weaker of the two strengths)
 //! - A price position (relative to the support/resistance levels)
+//! - A breakout event when price crosses through a tracked level
 //!
 //! # Example
 //!
@@ -38,6 +39,7 @@
 //! ```
 
 use super::types::*; // This module should define SRResult and PricePosition
+use crate::v1::types::V1Error;
 
 /// A Support/Resistance indicator based on a sliding window of prices and swing detection.
 pub struct SupportResistance {
@@ -47,6 +49,8 @@ pub struct SupportResistance {
     swing_low_threshold: f64,
     support_levels: Vec<f64>,
     resistance_levels: Vec<f64>,
+    /// Bars on each side a pivot must dominate; `None` centers the pivot in the `period` window.
+    swing_lookback: Option<usize>,
 }
 
 impl SupportResistance {
@@ -61,7 +65,7 @@ impl SupportResistance {
     ///
     /// * `period` - The number of prices to include in the sliding window.
     /// * `threshold` - The percentage threshold (as a decimal, e.g. 0.02 for 2%)
-    ///                 to determine swing levels.
+    ///   to determine swing levels.
     pub fn new(period: usize, threshold: f64) -> Self {
         SupportResistance {
             period,
@@ -70,9 +74,69 @@ impl SupportResistance {
             swing_low_threshold: 1.0 - threshold,
             support_levels: Vec::new(),
             resistance_levels: Vec::new(),
+            swing_lookback: None,
         }
     }
 
+    /// Creates a new Support/Resistance indicator, rejecting unusable parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of periods to consider.
+    /// * `threshold` - The swing threshold as a fraction, in `[0, 1)`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SupportResistance)` for valid parameters.
+    /// * `Err(V1Error::InvalidPeriod)` if `period` is zero.
+    /// * `Err(V1Error::InvalidThresholds)` if `threshold` is not finite or outside `[0, 1)`.
+    pub fn try_new(period: usize, threshold: f64) -> Result<Self, V1Error> {
+        if period == 0 {
+            return Err(V1Error::InvalidPeriod);
+        }
+        if !(0.0..1.0).contains(&threshold) {
+            return Err(V1Error::InvalidThresholds);
+        }
+
+        Ok(Self::new(period, threshold))
+    }
+
+    /// Creates a new `SupportResistance` indicator whose swing width is decoupled from `period`.
+    ///
+    /// A pivot is the bar `swing_lookback` bars before the latest price that is strictly higher
+    /// (or lower) than the `swing_lookback` bars on each side. A smaller lookback finds more,
+    /// weaker pivots; `period` still controls when results start.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of prices required before results are returned.
+    /// * `threshold` - The percentage threshold (as a decimal, e.g. 0.02 for 2%)
+    ///   to determine swing levels.
+    /// * `swing_lookback` - The number of bars on each side of a pivot. A value of 0 is treated as 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use indexes_rs::v1::support_resistance::main::SupportResistance;
+    ///
+    /// let sr = SupportResistance::with_swing_lookback(20, 0.02, 2);
+    /// ```
+    pub fn with_swing_lookback(period: usize, threshold: f64, swing_lookback: usize) -> Self {
+        let mut sr = Self::new(period, threshold);
+        sr.swing_lookback = Some(swing_lookback.max(1));
+        sr
+    }
+
+    /// Returns the currently tracked support levels.
+    pub fn support_levels(&self) -> &[f64] {
+        &self.support_levels
+    }
+
+    /// Returns the currently tracked resistance levels.
+    pub fn resistance_levels(&self) -> &[f64] {
+        &self.resistance_levels
+    }
+
     /// Updates the indicator with a new price and returns the current support/resistance result.
     ///
     /// This method pushes the new price into the internal price window, updates the
@@ -89,8 +153,9 @@ impl SupportResistance {
     /// * `None` if there aren't enough prices yet.
     pub fn calculate(&mut self, price: f64) -> Option<SRResult> {
         self.prices.push(price);
-        // Keep the sliding window limited to at most period*2 values.
-        if self.prices.len() > self.period * 2 {
+        // Keep the sliding window limited to at most period*2 values (or one full swing window).
+        let capacity = (self.period * 2).max(self.swing_window_len());
+        if self.prices.len() > capacity {
             self.prices.remove(0);
         }
 
@@ -99,6 +164,8 @@ impl SupportResistance {
             return None;
         }
 
+        // Check the levels tracked so far before this price can add or clean any.
+        let breakout = self.detect_breakout(price);
         self.update_levels();
 
         Some(SRResult {
@@ -108,15 +175,46 @@ impl SupportResistance {
             resistance_strength: self.calculate_resistance_strength(price),
             breakout_potential: self.calculate_breakout_potential(price),
             price_position: self.determine_price_position(price),
+            breakout,
         })
     }
 
+    /// Detects a breakout through a tracked level.
+    ///
+    /// A level is broken when the previous price was on one side of it (or touching it) and the
+    /// current price is strictly on the other side, so each crossing fires only once.
+    fn detect_breakout(&self, price: f64) -> Option<BreakoutEvent> {
+        let previous = *self.prices.iter().rev().nth(1)?;
+        if self
+            .resistance_levels
+            .iter()
+            .any(|&level| previous <= level && price > level)
+        {
+            Some(BreakoutEvent::ResistanceBroken)
+        } else if self
+            .support_levels
+            .iter()
+            .any(|&level| previous >= level && price < level)
+        {
+            Some(BreakoutEvent::SupportBroken)
+        } else {
+            None
+        }
+    }
+
     /// Updates support and resistance levels based on the latest price window.
     ///
     /// This method checks if the current window shows a swing high or swing low,
     /// updates the respective levels, and cleans out old or invalidated levels.
     fn update_levels(&mut self) {
-        if let Some(window) = self.prices.get(self.prices.len().saturating_sub(self.period)..) {
+        // Only a full window can confirm a pivot.
+        let window_len = self.swing_window_len();
+        if let Some(window) = self
+            .prices
+            .len()
+            .checked_sub(window_len)
+            .map(|start| &self.prices[start..])
+        {
             let mid_index = window.len() / 2;
             if self.is_swing_high(window) {
                 // Record the swing high (the mid value in the window).
@@ -134,6 +232,12 @@ impl SupportResistance {
         self.clean_levels(current_price);
     }
 
+    /// Returns the length of the window checked for a swing: `period`, or `2 * swing_lookback + 1`.
+    fn swing_window_len(&self) -> usize {
+        self.swing_lookback
+            .map_or(self.period, |lookback| 2 * lookback + 1)
+    }
+
     /// Determines if the given window is a swing high.
     ///
     /// A swing high is defined as the middle value being higher than all other values in the window.
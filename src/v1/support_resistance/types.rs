@@ -10,6 +10,15 @@ pub enum PricePosition {
     Unknown,
 }
 
+/// A price closing through a tracked support or resistance level.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum BreakoutEvent {
+    /// Price moved from at or below a resistance level to above it.
+    ResistanceBroken,
+    /// Price moved from at or above a support level to below it.
+    SupportBroken,
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub struct SRResult {
     pub nearest_support: Option<f64>,
@@ -18,4 +27,6 @@ pub struct SRResult {
     pub resistance_strength: f64,
     pub breakout_potential: f64,
     pub price_position: PricePosition,
+    /// Set on the bar where price crosses through a tracked level.
+    pub breakout: Option<BreakoutEvent>,
 }
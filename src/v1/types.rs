@@ -1,10 +1,115 @@
 use serde::Serialize;
 
+pub use crate::common::types::TrendDirection;
+
 use super::{
-    bollinger::types::BBResult, ma::main::MovingAverageResults, momentum::types::MomentumResult, roc::types::ROCResult, rsi::types::RSIResult, stochastic::types::StochResult,
+    bollinger::types::{BBResult, BollingerError},
+    ema::types::EMAError,
+    envelope::types::EnvelopeError,
+    ma::main::MovingAverageResults,
+    momentum::types::MomentumResult,
+    roc::types::ROCResult,
+    rsi::types::{RSIError, RSIResult},
+    sma::types::SMAError,
+    smma::types::SMMAError,
+    stochastic::types::StochResult,
     support_resistance::types::SRResult,
+    wma::types::WMAError,
 };
 
+/// An error type shared by the v1 indicators.
+///
+/// Returned by the `try_new` constructors and `try_calculate` methods. `Option` is still used for
+/// "not enough data yet"; this type is only for inputs that can never produce a valid reading.
+/// The per-indicator error types convert into it, so `?` works across indicators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum V1Error {
+    /// A period (or the equivalent lookback) is zero or otherwise unusable.
+    InvalidPeriod,
+    /// A smoothing factor (alpha) is outside `(0, 1]` or not finite.
+    InvalidAlpha,
+    /// A price is NaN or infinite.
+    InvalidPrice,
+    /// Threshold levels are not finite, out of range, or in the wrong order.
+    InvalidThresholds,
+    /// A band multiplier or percent is negative, zero, or not finite.
+    InvalidMultiplier,
+}
+
+impl std::fmt::Display for V1Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            V1Error::InvalidPeriod => write!(f, "Invalid period"),
+            V1Error::InvalidAlpha => write!(f, "Invalid smoothing factor (alpha)"),
+            V1Error::InvalidPrice => write!(f, "Invalid price (NaN or infinite)"),
+            V1Error::InvalidThresholds => write!(f, "Invalid threshold levels"),
+            V1Error::InvalidMultiplier => write!(f, "Invalid band multiplier"),
+        }
+    }
+}
+
+impl std::error::Error for V1Error {}
+
+impl From<SMAError> for V1Error {
+    fn from(error: SMAError) -> Self {
+        match error {
+            SMAError::InvalidPeriod => V1Error::InvalidPeriod,
+            SMAError::InvalidDeadband => V1Error::InvalidThresholds,
+        }
+    }
+}
+
+impl From<EMAError> for V1Error {
+    fn from(error: EMAError) -> Self {
+        match error {
+            EMAError::InvalidAlpha => V1Error::InvalidAlpha,
+        }
+    }
+}
+
+impl From<WMAError> for V1Error {
+    fn from(error: WMAError) -> Self {
+        match error {
+            WMAError::InvalidPeriod => V1Error::InvalidPeriod,
+        }
+    }
+}
+
+impl From<SMMAError> for V1Error {
+    fn from(error: SMMAError) -> Self {
+        match error {
+            SMMAError::InvalidPeriod => V1Error::InvalidPeriod,
+        }
+    }
+}
+
+impl From<BollingerError> for V1Error {
+    fn from(error: BollingerError) -> Self {
+        match error {
+            BollingerError::InvalidPeriod => V1Error::InvalidPeriod,
+            BollingerError::InvalidMultiplier => V1Error::InvalidMultiplier,
+        }
+    }
+}
+
+impl From<EnvelopeError> for V1Error {
+    fn from(error: EnvelopeError) -> Self {
+        match error {
+            EnvelopeError::InvalidPeriod => V1Error::InvalidPeriod,
+            EnvelopeError::InvalidPercent => V1Error::InvalidMultiplier,
+        }
+    }
+}
+
+impl From<RSIError> for V1Error {
+    fn from(error: RSIError) -> Self {
+        match error {
+            RSIError::InvalidLookback => V1Error::InvalidPeriod,
+            RSIError::InvalidPercentile => V1Error::InvalidThresholds,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub enum TradingSignal {
     Buy,
@@ -12,11 +117,46 @@ pub enum TradingSignal {
     Hold,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
-pub enum TrendDirection {
-    Up,
-    Down,
-    Sideways,
+/// Direction of a line crossing (e.g. a zero-line or signal-line cross).
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum Cross {
+    Bullish,
+    Bearish,
+}
+
+/// Rendering hint for a histogram bar, based on its sign and change versus the previous bar.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum HistColor {
+    /// At or above zero and not falling.
+    StrongUp,
+    /// At or above zero but falling.
+    WeakUp,
+    /// Below zero but not falling.
+    WeakDown,
+    /// Below zero and falling.
+    StrongDown,
+}
+
+impl HistColor {
+    /// Classifies a histogram bar.
+    ///
+    /// # Arguments
+    ///
+    /// * `histogram` - The current histogram value.
+    /// * `previous` - The previous histogram value, if any. The first bar counts as not falling.
+    ///
+    /// # Returns
+    ///
+    /// The color hint for the bar.
+    pub fn classify(histogram: f64, previous: Option<f64>) -> Self {
+        let falling = previous.is_some_and(|previous| histogram < previous);
+        match (histogram >= 0.0, falling) {
+            (true, false) => HistColor::StrongUp,
+            (true, true) => HistColor::WeakUp,
+            (false, false) => HistColor::WeakDown,
+            (false, true) => HistColor::StrongDown,
+        }
+    }
 }
 
 pub struct BasicIndexes {
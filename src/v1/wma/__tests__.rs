@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+    use crate::v1::wma::main::{WMAError, WeightedMovingAverage};
+
+    #[test]
+    fn test_invalid_period() {
+        assert_eq!(WeightedMovingAverage::new(0), Err(WMAError::InvalidPeriod));
+    }
+
+    #[test]
+    fn test_wma_calculation() {
+        let mut wma = WeightedMovingAverage::new(3).unwrap();
+        assert_eq!(wma.add_value(1.0), None);
+        assert_eq!(wma.add_value(2.0), None);
+        // (1*1 + 2*2 + 3*3) / 6
+        let first = wma.add_value(3.0).unwrap();
+        assert!((first - 14.0 / 6.0).abs() < 1e-12);
+        // Window slides to [2, 3, 4]: (2*1 + 3*2 + 4*3) / 6
+        let second = wma.add_value(4.0).unwrap();
+        assert!((second - 20.0 / 6.0).abs() < 1e-12);
+        assert_eq!(wma.get_current_value(), Some(second));
+    }
+
+    #[test]
+    fn test_wma_weights_recent_values_more() {
+        let mut wma = WeightedMovingAverage::new(4).unwrap();
+        let mut result = None;
+        for value in [10.0, 10.0, 10.0, 20.0] {
+            result = wma.add_value(value);
+        }
+        // A simple average would be 12.5; the WMA leans toward the latest value.
+        assert!(result.unwrap() > 12.5);
+    }
+}
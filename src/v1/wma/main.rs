@@ -0,0 +1,96 @@
+//! # Weighted Moving Average (WMA) Module
+//!
+//! This module implements a linearly Weighted Moving Average. Within the window, the most
+//! recent value gets weight `period`, the one before it `period - 1`, and so on down to 1:
+//!
+//! \[\text{WMA} = \frac{\sum_{i=1}^{n} i \times x_i}{n(n+1)/2}\]
+//!
+//! # Examples
+//!
+//! ```rust
+//! use indexes_rs::v1::wma::main::WeightedMovingAverage;
+//!
+//! let mut wma = WeightedMovingAverage::new(3).unwrap();
+//! assert_eq!(wma.add_value(1.0), None);
+//! assert_eq!(wma.add_value(2.0), None);
+//! // (1*1 + 2*2 + 3*3) / 6 = 14 / 6
+//! assert_eq!(wma.add_value(3.0), Some(14.0 / 6.0));
+//! ```
+
+pub use super::types::WMAError;
+use std::collections::VecDeque;
+
+/// A linearly Weighted Moving Average (WMA) indicator.
+#[derive(Debug, PartialEq)]
+pub struct WeightedMovingAverage {
+    /// The period over which the moving average is calculated.
+    pub period: usize,
+    /// The collection of values in the moving window.
+    values: VecDeque<f64>,
+    /// The current WMA value.
+    current_value: Option<f64>,
+}
+
+impl WeightedMovingAverage {
+    /// Creates a new `WeightedMovingAverage` with the specified period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of values to include in the moving average calculation.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WeightedMovingAverage)` - A new instance with the specified period.
+    /// * `Err(WMAError)` - If the period is invalid (e.g., zero).
+    pub fn new(period: usize) -> Result<Self, WMAError> {
+        if period == 0 {
+            return Err(WMAError::InvalidPeriod);
+        }
+        Ok(WeightedMovingAverage {
+            period,
+            values: VecDeque::with_capacity(period),
+            current_value: None,
+        })
+    }
+
+    /// Adds a new value to the moving window and returns the current WMA.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value to add to the moving window.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(f64)` once the window holds `period` values.
+    /// * `None` while the window is still filling.
+    pub fn add_value(&mut self, value: f64) -> Option<f64> {
+        if self.values.len() == self.period {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+
+        if self.values.len() < self.period {
+            return None;
+        }
+
+        let weighted_sum: f64 = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| (i + 1) as f64 * v)
+            .sum();
+        let weight_total = (self.period * (self.period + 1)) as f64 / 2.0;
+        self.current_value = Some(weighted_sum / weight_total);
+        self.current_value
+    }
+
+    /// Returns the current WMA value.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(f64)` if the WMA has been computed.
+    /// * `None` if the window has not been filled yet.
+    pub fn get_current_value(&self) -> Option<f64> {
+        self.current_value
+    }
+}
@@ -0,0 +1,6 @@
+/// An error type for the WeightedMovingAverage.
+#[derive(Debug, PartialEq)]
+pub enum WMAError {
+    /// Indicates that the provided period is invalid (e.g., zero).
+    InvalidPeriod,
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use crate::v1::types::V1Error;
+    use crate::v1::{
+        ema::main::ExponentialMovingAverage, zlema::main::ZeroLagExponentialMovingAverage,
+    };
+
+    #[test]
+    fn test_zlema_lag() {
+        assert_eq!(ZeroLagExponentialMovingAverage::new(10).lag, 4);
+        assert_eq!(ZeroLagExponentialMovingAverage::new(21).lag, 10);
+        assert_eq!(ZeroLagExponentialMovingAverage::new(1).lag, 0);
+    }
+
+    #[test]
+    fn test_zlema_constant_price() {
+        let mut zlema = ZeroLagExponentialMovingAverage::new(10);
+        for _ in 0..20 {
+            assert_eq!(zlema.add_value(50.0), Some(50.0));
+        }
+    }
+
+    #[test]
+    fn test_zlema_tracks_step_with_less_lag_than_ema() {
+        let period = 10;
+        let mut zlema = ZeroLagExponentialMovingAverage::new(period);
+        let mut ema = ExponentialMovingAverage::new(period);
+
+        for _ in 0..20 {
+            zlema.add_value(100.0);
+            ema.add_value(100.0);
+        }
+
+        // Step change from 100 to 110: ZLEMA should close the gap faster on every bar.
+        for bar in 0..period {
+            let z = zlema.add_value(110.0).unwrap();
+            let e = ema.add_value(110.0).unwrap();
+            assert!(
+                (110.0 - z).abs() < (110.0 - e).abs(),
+                "bar {}: zlema {} ema {}",
+                bar,
+                z,
+                e
+            );
+        }
+    }
+
+    #[test]
+    fn test_try_new_rejects_zero_period() {
+        assert_eq!(
+            ZeroLagExponentialMovingAverage::try_new(0).err(),
+            Some(V1Error::InvalidPeriod)
+        );
+        assert!(ZeroLagExponentialMovingAverage::try_new(5).is_ok());
+    }
+}
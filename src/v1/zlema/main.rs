@@ -0,0 +1,114 @@
+//! # Zero-Lag Exponential Moving Average (ZLEMA) Module
+//!
+//! This module implements the Zero-Lag EMA (Ehlers/Way). An EMA lags the price by roughly
+//! `(period - 1) / 2` bars, so ZLEMA feeds the EMA a "de-lagged" price that adds the momentum
+//! over that lag back in:
+//!
+//! \[\text{lag} = \frac{\text{period} - 1}{2}\]
+//!
+//! \[\text{ZLEMA} = \text{EMA}(\text{price} + (\text{price} - \text{price}_{\text{lag}}))\]
+//!
+//! Until `lag` earlier prices are available the raw price is fed to the EMA unchanged.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use indexes_rs::v1::zlema::main::ZeroLagExponentialMovingAverage;
+//!
+//! let mut zlema = ZeroLagExponentialMovingAverage::new(5);
+//! assert_eq!(zlema.lag, 2);
+//!
+//! // The first value seeds the EMA with the price itself.
+//! assert_eq!(zlema.add_value(100.0).unwrap(), 100.0);
+//!
+//! for price in [101.0, 102.0, 103.0] {
+//!     let value = zlema.add_value(price).unwrap();
+//!     println!("ZLEMA: {:.2}", value);
+//! }
+//! ```
+
+use crate::v1::ema::main::ExponentialMovingAverage;
+use crate::v1::types::V1Error;
+use std::collections::VecDeque;
+
+/// A Zero-Lag Exponential Moving Average (ZLEMA) indicator.
+pub struct ZeroLagExponentialMovingAverage {
+    /// The number of bars between the current price and the lagged price.
+    pub lag: usize,
+    /// The recent prices needed for the lagged term.
+    prices: VecDeque<f64>,
+    /// The EMA fed with de-lagged prices.
+    ema: ExponentialMovingAverage,
+}
+
+impl ZeroLagExponentialMovingAverage {
+    /// Creates a new ZLEMA indicator with the specified period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The EMA period; the lag is derived as `(period - 1) / 2`.
+    ///
+    /// # Returns
+    ///
+    /// A new `ZeroLagExponentialMovingAverage` instance.
+    pub fn new(period: usize) -> Self {
+        let lag = period.saturating_sub(1) / 2;
+        ZeroLagExponentialMovingAverage {
+            lag,
+            prices: VecDeque::with_capacity(lag + 1),
+            ema: ExponentialMovingAverage::new(period),
+        }
+    }
+
+    /// Creates a new ZLEMA indicator, rejecting a zero period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of periods for the underlying EMA.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZeroLagExponentialMovingAverage)` for a non-zero period.
+    /// * `Err(V1Error::InvalidPeriod)` if `period` is zero.
+    pub fn try_new(period: usize) -> Result<Self, V1Error> {
+        if period == 0 {
+            return Err(V1Error::InvalidPeriod);
+        }
+
+        Ok(Self::new(period))
+    }
+
+    /// Adds a new price and updates the ZLEMA.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The new price to incorporate.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<f64>` containing the updated ZLEMA value.
+    pub fn add_value(&mut self, price: f64) -> Option<f64> {
+        if self.prices.len() > self.lag {
+            self.prices.pop_front();
+        }
+        self.prices.push_back(price);
+
+        let input = if self.prices.len() > self.lag {
+            let lagged = self.prices[0];
+            price + (price - lagged)
+        } else {
+            price
+        };
+
+        self.ema.add_value(input)
+    }
+
+    /// Returns the current ZLEMA value.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<f64>` containing the current ZLEMA, or `None` if no values have been added.
+    pub fn get_current_value(&self) -> Option<f64> {
+        self.ema.get_current_value()
+    }
+}
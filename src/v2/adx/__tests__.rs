@@ -1,8 +1,13 @@
 #[cfg(test)]
 mod tests {
+    use crate::common::assert_dense_batch;
+    use crate::common::types::{Candle, TiePolicy};
     use crate::v2::adx::{
-        main::{calculate_adx_simple, ADX},
-        types::{ADXError, ADXInput, TrendDirection, TrendStrength},
+        main::{calculate_adx_iter, calculate_adx_simple, calculate_adx_simple_checked, ADX},
+        types::{
+            ADXConfig, ADXError, ADXInput, ADXOutput, ADXState, AdxSmoothing, TrendDirection,
+            TrendStrength,
+        },
     };
 
     #[test]
@@ -20,6 +25,7 @@ mod tests {
 
         for (i, (high, low, close)) in test_data.iter().enumerate() {
             let input = ADXInput {
+                timestamp: None,
                 high: *high,
                 low: *low,
                 close: *close,
@@ -57,7 +63,12 @@ mod tests {
 
         let mut last_result = None;
         for (high, low, close) in uptrend_data {
-            let input = ADXInput { high, low, close };
+            let input = ADXInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
             let result = adx.calculate(input).unwrap();
             last_result = Some(result);
         }
@@ -85,7 +96,12 @@ mod tests {
         ];
 
         for (high, low, close) in test_data {
-            let input = ADXInput { high, low, close };
+            let input = ADXInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
             let _ = adx.calculate(input).unwrap();
         }
 
@@ -99,6 +115,7 @@ mod tests {
 
         // First calculation
         let input1 = ADXInput {
+            timestamp: None,
             high: 10.0,
             low: 8.0,
             close: 9.0,
@@ -108,6 +125,7 @@ mod tests {
 
         // Second calculation with gap
         let input2 = ADXInput {
+            timestamp: None,
             high: 15.0,
             low: 12.0,
             close: 14.0,
@@ -124,6 +142,7 @@ mod tests {
 
         // Test invalid HLC (high < low)
         let input = ADXInput {
+            timestamp: None,
             high: 8.0,
             low: 10.0,
             close: 9.0,
@@ -132,6 +151,7 @@ mod tests {
 
         // Test close out of range
         let input = ADXInput {
+            timestamp: None,
             high: 10.0,
             low: 8.0,
             close: 12.0,
@@ -157,12 +177,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_adx_iter_matches_slice_version() {
+        let highs: Vec<f64> = (0..20)
+            .map(|i| 11.0 + (i as f64 * 0.7).sin() * 3.0)
+            .collect();
+        let lows: Vec<f64> = highs.iter().map(|h| h - 2.0).collect();
+        let closes: Vec<f64> = highs.iter().map(|h| h - 1.0).collect();
+
+        let from_slices = calculate_adx_simple(&highs, &lows, &closes, 5).unwrap();
+        // Bars generated on the fly, never collected into slices
+        let from_iter = calculate_adx_iter(
+            (0..20).map(|i| {
+                let high = 11.0 + (i as f64 * 0.7).sin() * 3.0;
+                (high, high - 2.0, high - 1.0)
+            }),
+            5,
+        )
+        .unwrap();
+
+        assert_eq!(from_iter, from_slices);
+    }
+
+    #[test]
+    fn test_adx_simple_checked_marks_warmup() {
+        let highs: Vec<f64> = (0..10).map(|i| 11.0 + i as f64).collect();
+        let lows: Vec<f64> = (0..10).map(|i| 9.0 + i as f64).collect();
+        let closes: Vec<f64> = (0..10).map(|i| 10.0 + i as f64).collect();
+
+        let plain = calculate_adx_simple(&highs, &lows, &closes, 3).unwrap();
+        let checked = calculate_adx_simple_checked(&highs, &lows, &closes, 3).unwrap();
+        assert_eq!(checked.len(), plain.len());
+
+        // Warmup prefix is None, every later bar matches the plain output
+        let warmup = ADX::with_period(3).unwrap().required_bars() - 1;
+        assert_eq!(warmup, 5);
+        assert!(checked[..warmup].iter().all(Option::is_none));
+        for i in warmup..checked.len() {
+            assert_eq!(checked[i], Some(plain[i]));
+        }
+    }
+
     #[test]
     fn test_directional_movement() {
         let mut adx = ADX::new();
 
         // Setup first point
         let input1 = ADXInput {
+            timestamp: None,
             high: 10.0,
             low: 8.0,
             close: 9.0,
@@ -171,6 +233,7 @@ mod tests {
 
         // Up movement
         let input2 = ADXInput {
+            timestamp: None,
             high: 12.0,
             low: 10.0,
             close: 11.0,
@@ -185,6 +248,7 @@ mod tests {
 
         // Add more data to see the effect
         let input3 = ADXInput {
+            timestamp: None,
             high: 14.0,
             low: 12.0,
             close: 13.0,
@@ -200,4 +264,383 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_adx_smoothing_modes() {
+        let inputs: Vec<ADXInput> = (0..30)
+            .map(|i| {
+                // Uptrend with a wobble so DX varies bar to bar
+                let base = 100.0 + i as f64 * 1.5 + if i % 3 == 0 { -2.0 } else { 0.0 };
+                ADXInput {
+                    timestamp: None,
+                    high: base + 2.0,
+                    low: base - 2.0,
+                    close: base + 1.0,
+                }
+            })
+            .collect();
+
+        let run = |smoothing: AdxSmoothing| {
+            let mut adx = ADX::with_config(ADXConfig {
+                period: 5,
+                adx_smoothing: 5,
+                smoothing,
+                ..Default::default()
+            });
+            adx.calculate_batch(&inputs).unwrap()
+        };
+
+        let default_run = ADX::with_periods(5, 5)
+            .unwrap()
+            .calculate_batch(&inputs)
+            .unwrap();
+        let wilder = run(AdxSmoothing::Wilder);
+        let ema = run(AdxSmoothing::Ema);
+
+        // Wilder is the default
+        assert_eq!(default_run, wilder);
+
+        // Identical until smoothing kicks in (period + 1 bars to seed DI)
+        assert_eq!(wilder[..6], ema[..6]);
+
+        // After that the two modes diverge
+        assert!(wilder[10..]
+            .iter()
+            .zip(&ema[10..])
+            .any(|(w, e)| (w.adx - e.adx).abs() > 1e-6));
+
+        // Each mode follows its own ADX recurrence once ADX is seeded
+        let first_adx = wilder.iter().position(|o| o.adx > 0.0).unwrap();
+        for i in first_adx + 1..wilder.len() {
+            let expected_wilder = (wilder[i - 1].adx * 4.0 + wilder[i].dx) / 5.0;
+            assert!((wilder[i].adx - expected_wilder).abs() < 1e-9);
+
+            let alpha = 2.0 / 6.0;
+            let expected_ema = ema[i].dx * alpha + ema[i - 1].adx * (1.0 - alpha);
+            assert!((ema[i].adx - expected_ema).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_adx_di_sum_and_trend_quality() {
+        let mut adx = ADX::with_period(3).unwrap();
+
+        // ADX is 0 on the first bar, so trend quality is 0
+        let first = adx
+            .calculate(ADXInput {
+                timestamp: None,
+                high: 101.0,
+                low: 99.0,
+                close: 100.0,
+            })
+            .unwrap();
+        assert_eq!(first.adx, 0.0);
+        assert_eq!(first.trend_quality, 0.0);
+
+        let mut previous_quality = 0.0;
+        for i in 1..30 {
+            let base = 100.0 + i as f64 * 2.0;
+            let output = adx
+                .calculate(ADXInput {
+                    timestamp: None,
+                    high: base + 1.0,
+                    low: base - 1.0,
+                    close: base + 0.5,
+                })
+                .unwrap();
+
+            assert!((output.di_sum - (output.plus_di + output.minus_di)).abs() < 1e-12);
+            assert!((0.0..=1.0).contains(&output.trend_quality));
+            assert!((output.trend_quality - (output.adx / 50.0).min(1.0)).abs() < 1e-12);
+            // A steady trend only strengthens ADX, so quality never drops
+            assert!(output.trend_quality >= previous_quality);
+            previous_quality = output.trend_quality;
+        }
+
+        // A persistent one-directional trend drives ADX to the very strong threshold
+        assert!(previous_quality > 0.99, "quality {}", previous_quality);
+    }
+
+    #[test]
+    fn test_candle_timestamp_round_trips() {
+        let mut adx = ADX::with_period(3).unwrap();
+
+        for i in 0..10 {
+            let base = 100.0 + i as f64;
+            let timestamp = 1_700_000_000_000 + i * 60_000;
+            let candle = Candle::new(base, base + 1.0, base - 1.0, base + 0.5, 1_000.0)
+                .with_timestamp(timestamp);
+
+            let output = adx.calculate(ADXInput::from(candle)).unwrap();
+            assert_eq!(output.timestamp, Some(timestamp));
+        }
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let config = ADXConfig::builder()
+            .period(10)
+            .adx_smoothing(10)
+            .strong_trend_threshold(30.0)
+            .smoothing(AdxSmoothing::Ema)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config,
+            ADXConfig {
+                period: 10,
+                adx_smoothing: 10,
+                strong_trend_threshold: 30.0,
+                smoothing: AdxSmoothing::Ema,
+                ..Default::default()
+            }
+        );
+
+        // Same invariants as the constructors and `calculate`
+        assert_eq!(
+            ADXConfig::builder().period(0).build().err(),
+            ADX::with_period(0).err()
+        );
+        assert_eq!(
+            ADXConfig::builder().adx_smoothing(0).build(),
+            Err(ADXError::InvalidPeriod)
+        );
+        let inverted = ADXConfig::builder()
+            .strong_trend_threshold(60.0)
+            .very_strong_trend_threshold(50.0);
+        assert_eq!(inverted.build(), Err(ADXError::InvalidThresholds));
+    }
+
+    #[test]
+    fn test_calculate_checked_hides_warmup_output() {
+        let mut plain = ADX::with_period(3).unwrap();
+        let mut checked = ADX::with_period(3).unwrap();
+
+        let mut warmup_bars = 0;
+        for i in 0..15 {
+            let base = 100.0 + i as f64;
+            let input = ADXInput {
+                timestamp: None,
+                high: base + 1.0,
+                low: base - 1.0,
+                close: base,
+            };
+            let output = plain.calculate(input).unwrap();
+
+            match checked.calculate_checked(input).unwrap() {
+                None => {
+                    // The unchecked output during warmup is a placeholder
+                    assert_eq!(output.trend_strength, TrendStrength::Insufficient);
+                    warmup_bars += 1;
+                }
+                Some(checked_output) => {
+                    assert_ne!(checked_output.trend_strength, TrendStrength::Insufficient);
+                    assert_eq!(checked_output, output);
+                }
+            }
+        }
+
+        assert!(warmup_bars > 0 && warmup_bars < 15);
+    }
+
+    #[test]
+    fn test_first_adx_with_longer_smoothing() {
+        let mut adx = ADX::with_periods(5, 10).unwrap();
+        assert_eq!(adx.required_bars(), 15);
+
+        let mut first_adx_bar = None;
+        for i in 0..30 {
+            let base = 100.0 + i as f64 + (i as f64 * 0.8).sin() * 2.0;
+            let input = ADXInput {
+                timestamp: None,
+                high: base + 1.0,
+                low: base - 1.0,
+                close: base,
+            };
+            if adx.calculate_checked(input).unwrap().is_some() && first_adx_bar.is_none() {
+                first_adx_bar = Some(i + 1);
+            }
+        }
+
+        // DI is seeded on bar 6, then 10 DX values are needed: first ADX on bar 15
+        assert_eq!(first_adx_bar, Some(adx.required_bars()));
+        assert_eq!(adx.get_state().dx_history.len(), 10);
+    }
+
+    #[test]
+    fn test_raw_sums_reproduce_di() {
+        for smoothing in [AdxSmoothing::Wilder, AdxSmoothing::Ema] {
+            let config = ADXConfig {
+                period: 5,
+                smoothing,
+                ..Default::default()
+            };
+            let mut adx = ADX::with_config(config);
+            assert!(adx.raw().is_none());
+
+            for i in 0..30 {
+                let close = 100.0 + (i as f64 * 0.5).sin() * 4.0 + i as f64 * 0.3;
+                let output = adx
+                    .calculate(ADXInput {
+                        timestamp: None,
+                        high: close + 1.0,
+                        low: close - 1.2,
+                        close,
+                    })
+                    .unwrap();
+
+                match adx.raw() {
+                    Some(raw) => {
+                        let plus_di = raw.smoothed_plus_dm / raw.smoothed_tr * 100.0;
+                        let minus_di = raw.smoothed_minus_dm / raw.smoothed_tr * 100.0;
+                        assert!((output.plus_di - plus_di).abs() < 1e-9);
+                        assert!((output.minus_di - minus_di).abs() < 1e-9);
+                    }
+                    // The first bar plus `period` bars seed the smoothing
+                    None => assert!(i < 5),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(ADX::new(), ADXInput);
+    }
+
+    #[test]
+    fn test_empty_and_single_element_batches() {
+        assert!(calculate_adx_simple(&[], &[], &[], 14).unwrap().is_empty());
+        assert!(calculate_adx_simple_checked(&[], &[], &[], 14)
+            .unwrap()
+            .is_empty());
+
+        // One bar is always warmup: a placeholder, or None in the checked variant
+        assert_eq!(
+            calculate_adx_simple(&[11.0], &[9.0], &[10.0], 14).unwrap(),
+            vec![0.0]
+        );
+        assert_eq!(
+            calculate_adx_simple_checked(&[11.0], &[9.0], &[10.0], 14).unwrap(),
+            vec![None]
+        );
+        assert_eq!(
+            calculate_adx_simple_checked(&[11.0], &[9.0], &[10.0], 1).unwrap(),
+            vec![None]
+        );
+    }
+
+    #[test]
+    fn test_tie_policy() {
+        let bar = |high: f64, low: f64, close: f64| ADXInput {
+            timestamp: None,
+            high,
+            low,
+            close,
+        };
+        // An up bar, then an outside bar whose up and down moves cancel (+DI == -DI == 0)
+        let inputs = [
+            bar(11.0, 9.0, 10.0),
+            bar(12.0, 9.5, 11.0),
+            bar(13.0, 8.5, 11.0),
+        ];
+
+        let directions = |tie_policy: TiePolicy| {
+            let config = ADXConfig::builder()
+                .period(1)
+                .adx_smoothing(1)
+                .tie_policy(tie_policy)
+                .build()
+                .unwrap();
+            let mut adx = ADX::with_config(config);
+            let outputs = adx.calculate_batch(&inputs).unwrap();
+            assert_eq!(adx.trend_direction(), Some(outputs[2].trend_direction));
+            outputs
+                .iter()
+                .map(|o| o.trend_direction)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            directions(TiePolicy::Neutral)[1..],
+            [TrendDirection::Up, TrendDirection::Sideways]
+        );
+        assert_eq!(
+            directions(TiePolicy::CarryForward)[1..],
+            [TrendDirection::Up, TrendDirection::Up]
+        );
+
+        // A perfectly flat series never establishes a direction to carry
+        let config = ADXConfig::builder()
+            .period(3)
+            .tie_policy(TiePolicy::CarryForward)
+            .build()
+            .unwrap();
+        let outputs = ADX::with_config(config)
+            .calculate_batch(&[bar(11.0, 9.0, 10.0); 10])
+            .unwrap();
+        assert!(outputs
+            .iter()
+            .all(|o| o.trend_direction == TrendDirection::Sideways));
+    }
+
+    #[test]
+    fn test_restores_baseline_state() {
+        // State saved by indexes-rs 1.0.1 with period 2 and ADX smoothing 2
+        let json = r#"{"config":{"period":2,"adx_smoothing":2,"strong_trend_threshold":25.0,"very_strong_trend_threshold":50.0},"previous_high":12.8,"previous_low":11.6,"previous_close":12.0,"period_data":[{"true_range":2.0,"plus_dm":0.5,"minus_dm":0.0,"plus_di":33.33333333333333,"minus_di":0.0,"dx":100.0},{"true_range":1.200000000000001,"plus_dm":0.0,"minus_dm":0.0,"plus_di":23.809523809523803,"minus_di":0.0,"dx":100.0}],"smoothed_tr":2.1000000000000005,"smoothed_plus_dm":0.5,"smoothed_minus_dm":0.0,"dx_history":[100.0,100.0],"current_adx":100.0,"has_di_data":true,"has_adx_data":true,"is_first":false}"#;
+        let state: ADXState = serde_json::from_str(json).unwrap();
+        let mut restored = ADX::new();
+        restored.set_state(state);
+
+        let input = |(high, low, close)| ADXInput {
+            timestamp: None,
+            high,
+            low,
+            close,
+        };
+        let mut fresh = ADX::with_periods(2, 2).unwrap();
+        for bar in [
+            (11.0, 9.0, 10.0),
+            (12.0, 10.0, 11.5),
+            (12.5, 10.5, 11.0),
+            (13.0, 11.0, 12.5),
+            (12.8, 11.6, 12.0),
+        ] {
+            fresh.calculate(input(bar)).unwrap();
+        }
+
+        let next = (13.4, 12.1, 13.2);
+        let expected = fresh.calculate(input(next)).unwrap();
+        let actual = restored.calculate(input(next)).unwrap();
+        assert!((actual.adx - expected.adx).abs() < 1e-9);
+        assert!((actual.plus_di - expected.plus_di).abs() < 1e-9);
+        assert!((actual.minus_di - expected.minus_di).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_output_without_di_sum_and_trend_quality_deserializes() {
+        let mut adx = ADX::with_period(2).unwrap();
+        let mut output = None;
+        for (high, low, close) in [(11.0, 9.0, 10.0), (12.0, 10.0, 11.5), (12.5, 10.5, 11.0)] {
+            output = Some(
+                adx.calculate(ADXInput {
+                    timestamp: None,
+                    high,
+                    low,
+                    close,
+                })
+                .unwrap(),
+            );
+        }
+
+        // Outputs serialized before these fields existed lack them
+        let mut value = serde_json::to_value(output.unwrap()).unwrap();
+        let fields = value.as_object_mut().unwrap();
+        fields.remove("di_sum");
+        fields.remove("trend_quality");
+
+        let restored: ADXOutput = serde_json::from_value(value).unwrap();
+        assert_eq!(restored.di_sum, 0.0);
+        assert_eq!(restored.trend_quality, 0.0);
+        assert_eq!(restored.adx, output.unwrap().adx);
+    }
 }
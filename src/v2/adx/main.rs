@@ -1,6 +1,9 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction, TiePolicy};
+use crate::util::true_range;
 use crate::v2::adx::types::{
-    ADXConfig, ADXError, ADXInput, ADXOutput, ADXPeriodData, ADXState, TrendDirection,
-    TrendStrength,
+    ADXConfig, ADXError, ADXInput, ADXOutput, ADXPeriodData, ADXRaw, ADXState, AdxSmoothing,
+    TrendDirection, TrendStrength,
 };
 
 /// Average Directional Index (ADX) Indicator
@@ -18,6 +21,8 @@ use crate::v2::adx::types::{
 /// - 50+: Very strong trend
 pub struct ADX {
     state: ADXState,
+    /// Configuration error found at construction
+    config_error: Option<ADXError>,
 }
 
 impl ADX {
@@ -58,20 +63,81 @@ impl ADX {
     pub fn with_config(config: ADXConfig) -> Self {
         Self {
             state: ADXState::new(config),
+            config_error: config.validate().err(),
         }
     }
 
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: ADXConfig) -> Result<Self, ADXError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
     /// Calculate ADX for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
     pub fn calculate(&mut self, input: ADXInput) -> Result<ADXOutput, ADXError> {
-        // Validate input
-        self.validate_input(&input)?;
-        self.validate_config()?;
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| ADXOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(ADXError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(ADXError::InvalidPrice),
+        };
 
-        if self.state.is_first {
-            self.handle_first_calculation(input)
-        } else {
-            self.handle_normal_calculation(input)
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.adx = round_to(output.adx, decimals);
         }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(&mut self, input: ADXInput) -> Result<Option<ADXOutput>, ADXError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_adx_data.then_some(output))
+    }
+
+    /// Number of bars needed before the first ADX value
+    ///
+    /// The first bar only seeds the previous values, DI needs `period` more bars,
+    /// and the DX history must then fill `adx_smoothing` values (the bar that
+    /// seeds DI contributes the first DX).
+    pub fn required_bars(&self) -> usize {
+        self.state.config.period + self.state.config.adx_smoothing
+    }
+
+    /// Smoothed +DM, -DM, and TR behind the latest DI values
+    ///
+    /// `None` until the first `period` bars have seeded the smoothing.
+    pub fn raw(&self) -> Option<ADXRaw> {
+        if !self.state.has_di_data {
+            return None;
+        }
+        Some(ADXRaw {
+            smoothed_plus_dm: self.state.smoothed_plus_dm?,
+            smoothed_minus_dm: self.state.smoothed_minus_dm?,
+            smoothed_tr: self.state.smoothed_tr?,
+        })
     }
 
     /// Calculate ADX for a batch of inputs
@@ -79,6 +145,20 @@ impl ADX {
         inputs.iter().map(|input| self.calculate(*input)).collect()
     }
 
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[ADXInput],
+    ) -> Result<DenseBatch<ADXOutput>, ADXError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            ADXError::InvalidInput,
+        )
+    }
+
     /// Reset the calculator state
     pub fn reset(&mut self) {
         self.state = ADXState::new(self.state.config);
@@ -91,6 +171,7 @@ impl ADX {
 
     /// Restore state (for deserialization)
     pub fn set_state(&mut self, state: ADXState) {
+        self.config_error = state.config.validate().err();
         self.state = state;
     }
 
@@ -113,6 +194,22 @@ impl ADX {
 
     // Private helper methods
 
+    fn calculate_bar(&mut self, input: ADXInput) -> Result<ADXOutput, ADXError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        if self.state.is_first {
+            self.handle_first_calculation(input)
+        } else {
+            self.handle_normal_calculation(input)
+        }
+    }
+
+    fn is_finite_input(input: &ADXInput) -> bool {
+        input.high.is_finite() && input.low.is_finite() && input.close.is_finite()
+    }
+
     fn validate_input(&self, input: &ADXInput) -> Result<(), ADXError> {
         // Check for valid prices
         if !input.high.is_finite() || !input.low.is_finite() || !input.close.is_finite() {
@@ -132,16 +229,10 @@ impl ADX {
     }
 
     fn validate_config(&self) -> Result<(), ADXError> {
-        if self.state.config.period == 0 || self.state.config.adx_smoothing == 0 {
-            return Err(ADXError::InvalidPeriod);
-        }
-
-        if self.state.config.strong_trend_threshold >= self.state.config.very_strong_trend_threshold
-        {
-            return Err(ADXError::InvalidThresholds);
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
         }
-
-        Ok(())
     }
 
     fn handle_first_calculation(&mut self, input: ADXInput) -> Result<ADXOutput, ADXError> {
@@ -153,6 +244,7 @@ impl ADX {
 
         // Return default values for first calculation
         Ok(ADXOutput {
+            timestamp: None,
             adx: 0.0,
             plus_di: 0.0,
             minus_di: 0.0,
@@ -161,6 +253,8 @@ impl ADX {
             trend_strength: TrendStrength::Insufficient,
             trend_direction: TrendDirection::Sideways,
             di_spread: 0.0,
+            di_sum: 0.0,
+            trend_quality: 0.0,
         })
     }
 
@@ -214,8 +308,11 @@ impl ADX {
         let trend_strength = self.classify_trend_strength(adx);
         let trend_direction = self.determine_trend_direction(plus_di, minus_di);
         let di_spread = plus_di - minus_di;
+        let di_sum = plus_di + minus_di;
+        let trend_quality = self.calculate_trend_quality(adx);
 
         Ok(ADXOutput {
+            timestamp: None,
             adx,
             plus_di,
             minus_di,
@@ -224,18 +321,13 @@ impl ADX {
             trend_strength,
             trend_direction,
             di_spread,
+            di_sum,
+            trend_quality,
         })
     }
 
     fn calculate_true_range(&self, input: &ADXInput) -> f64 {
-        if let Some(prev_close) = self.state.previous_close {
-            let hl = input.high - input.low;
-            let hc = (input.high - prev_close).abs();
-            let lc = (input.low - prev_close).abs();
-            hl.max(hc).max(lc)
-        } else {
-            input.high - input.low
-        }
+        true_range(input.high, input.low, self.state.previous_close)
     }
 
     fn calculate_directional_movements(&self, input: &ADXInput) -> (f64, f64) {
@@ -289,19 +381,28 @@ impl ADX {
     fn update_smoothed_values(&mut self, true_range: f64, plus_dm: f64, minus_dm: f64) {
         let period = self.state.config.period as f64;
 
-        // Wilder's smoothing: New = (Old * (n-1) + Current) / n
         if let Some(smoothed_tr) = self.state.smoothed_tr {
-            self.state.smoothed_tr = Some((smoothed_tr * (period - 1.0) + true_range) / period);
+            self.state.smoothed_tr = Some(self.smooth(smoothed_tr, true_range, period));
         }
 
         if let Some(smoothed_plus_dm) = self.state.smoothed_plus_dm {
-            self.state.smoothed_plus_dm =
-                Some((smoothed_plus_dm * (period - 1.0) + plus_dm) / period);
+            self.state.smoothed_plus_dm = Some(self.smooth(smoothed_plus_dm, plus_dm, period));
         }
 
         if let Some(smoothed_minus_dm) = self.state.smoothed_minus_dm {
-            self.state.smoothed_minus_dm =
-                Some((smoothed_minus_dm * (period - 1.0) + minus_dm) / period);
+            self.state.smoothed_minus_dm = Some(self.smooth(smoothed_minus_dm, minus_dm, period));
+        }
+    }
+
+    fn smooth(&self, previous: f64, current: f64, period: f64) -> f64 {
+        match self.state.config.smoothing {
+            // Wilder's smoothing: New = (Old * (n-1) + Current) / n
+            AdxSmoothing::Wilder => (previous * (period - 1.0) + current) / period,
+            // EMA: New = Current * alpha + Old * (1 - alpha)
+            AdxSmoothing::Ema => {
+                let alpha = 2.0 / (period + 1.0);
+                current * alpha + previous * (1.0 - alpha)
+            }
         }
     }
 
@@ -334,6 +435,11 @@ impl ADX {
     }
 
     fn calculate_adx(&mut self, dx: f64) -> f64 {
+        // DX only counts towards ADX once DI is seeded over a full period
+        if !self.state.has_di_data {
+            return 0.0;
+        }
+
         // Add DX to history
         if self.state.dx_history.len() >= self.state.config.adx_smoothing {
             self.state.dx_history.pop_front();
@@ -353,7 +459,7 @@ impl ADX {
                 // Subsequent ADX calculations - use smoothing
                 if let Some(prev_adx) = self.state.current_adx {
                     let period = self.state.config.adx_smoothing as f64;
-                    let adx = (prev_adx * (period - 1.0) + dx) / period;
+                    let adx = self.smooth(prev_adx, dx, period);
                     self.state.current_adx = Some(adx);
                     adx
                 } else {
@@ -377,13 +483,25 @@ impl ADX {
         }
     }
 
+    fn calculate_trend_quality(&self, adx: f64) -> f64 {
+        // Linear scale from 0 (ADX = 0) to 1 (ADX at or above the very strong threshold)
+        (adx / self.state.config.very_strong_trend_threshold).clamp(0.0, 1.0)
+    }
+
     fn determine_trend_direction(&self, plus_di: f64, minus_di: f64) -> TrendDirection {
         if plus_di > minus_di {
             TrendDirection::Up
         } else if minus_di > plus_di {
             TrendDirection::Down
         } else {
-            TrendDirection::Sideways
+            match self.state.config.tie_policy {
+                TiePolicy::Neutral => TrendDirection::Sideways,
+                // The last output is the previous bar's while calculating a new one
+                TiePolicy::CarryForward => self
+                    .state
+                    .last_output
+                    .map_or(TrendDirection::Sideways, |output| output.trend_direction),
+            }
         }
     }
 }
@@ -412,17 +530,68 @@ pub fn calculate_adx_simple(
         return Ok(Vec::new());
     }
 
+    calculate_adx_iter(
+        highs
+            .iter()
+            .zip(lows)
+            .zip(closes)
+            .map(|((&high, &low), &close)| (high, low, close)),
+        period,
+    )
+}
+
+/// Like [`calculate_adx_simple`], but accepts any iterator of `(high, low, close)` bars
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_adx_iter(
+    bars: impl IntoIterator<Item = (f64, f64, f64)>,
+    period: usize,
+) -> Result<Vec<f64>, ADXError> {
+    let mut adx_calculator = ADX::with_period(period)?;
+
+    bars.into_iter()
+        .map(|(high, low, close)| {
+            let input = ADXInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
+            adx_calculator.calculate(input).map(|output| output.adx)
+        })
+        .collect()
+}
+
+/// Like [`calculate_adx_simple`], but warmup bars are `None` instead of placeholder values
+pub fn calculate_adx_simple_checked(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+) -> Result<Vec<Option<f64>>, ADXError> {
+    let len = highs.len();
+    if len != lows.len() || len != closes.len() {
+        return Err(ADXError::InvalidInput(
+            "All price arrays must have same length".to_string(),
+        ));
+    }
+
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
     let mut adx_calculator = ADX::with_period(period)?;
     let mut results = Vec::with_capacity(len);
 
     for i in 0..len {
         let input = ADXInput {
+            timestamp: None,
             high: highs[i],
             low: lows[i],
             close: closes[i],
         };
-        let output = adx_calculator.calculate(input)?;
-        results.push(output.adx);
+        let output = adx_calculator.calculate_checked(input)?;
+        results.push(output.map(|output| output.adx));
     }
 
     Ok(results)
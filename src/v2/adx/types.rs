@@ -1,3 +1,6 @@
+/// Trend direction based on DI comparison (shared with v1)
+pub use crate::common::types::TrendDirection;
+use crate::common::types::{Candle, GapPolicy, TiePolicy};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
@@ -12,6 +15,27 @@ pub struct ADXConfig {
     pub strong_trend_threshold: f64,
     /// Very strong trend threshold (default: 50.0)
     pub very_strong_trend_threshold: f64,
+    /// Smoothing applied to TR/DM and DX after seeding (default: Wilder)
+    #[serde(default)]
+    pub smoothing: AdxSmoothing,
+    /// How a tie between +DI and -DI sets the trend direction (default: TiePolicy::Neutral)
+    #[serde(default)]
+    pub tie_policy: TiePolicy,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    #[serde(default)]
+    pub gap_policy: GapPolicy,
+    /// Decimal places `adx` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+/// Smoothing method for DI and ADX values
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum AdxSmoothing {
+    /// Wilder's smoothing: New = (Old × (n-1) + Current) / n
+    #[default]
+    Wilder,
+    /// Exponential smoothing: New = Current × α + Old × (1 - α), α = 2 / (n + 1)
+    Ema,
 }
 
 impl Default for ADXConfig {
@@ -21,13 +45,102 @@ impl Default for ADXConfig {
             adx_smoothing: 14,
             strong_trend_threshold: 25.0,
             very_strong_trend_threshold: 50.0,
+            smoothing: AdxSmoothing::Wilder,
+            tie_policy: TiePolicy::Neutral,
+            gap_policy: GapPolicy::Error,
+            precision: None,
         }
     }
 }
 
+impl ADXConfig {
+    /// Start building a configuration from the defaults
+    pub fn builder() -> ADXConfigBuilder {
+        ADXConfigBuilder::default()
+    }
+
+    /// Check the invariants the ADX calculator relies on
+    pub fn validate(&self) -> Result<(), ADXError> {
+        if self.period == 0 || self.adx_smoothing == 0 {
+            return Err(ADXError::InvalidPeriod);
+        }
+
+        if self.strong_trend_threshold >= self.very_strong_trend_threshold {
+            return Err(ADXError::InvalidThresholds);
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for [`ADXConfig`] with chainable setters and a validating `build()`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ADXConfigBuilder {
+    config: ADXConfig,
+}
+
+impl ADXConfigBuilder {
+    /// Set the period for DI and ADX calculation
+    pub fn period(mut self, period: usize) -> Self {
+        self.config.period = period;
+        self
+    }
+
+    /// Set the smoothing period for ADX
+    pub fn adx_smoothing(mut self, adx_smoothing: usize) -> Self {
+        self.config.adx_smoothing = adx_smoothing;
+        self
+    }
+
+    /// Set the strong trend threshold
+    pub fn strong_trend_threshold(mut self, strong_trend_threshold: f64) -> Self {
+        self.config.strong_trend_threshold = strong_trend_threshold;
+        self
+    }
+
+    /// Set the very strong trend threshold
+    pub fn very_strong_trend_threshold(mut self, very_strong_trend_threshold: f64) -> Self {
+        self.config.very_strong_trend_threshold = very_strong_trend_threshold;
+        self
+    }
+
+    /// Set the smoothing applied to TR/DM and DX after seeding
+    pub fn smoothing(mut self, smoothing: AdxSmoothing) -> Self {
+        self.config.smoothing = smoothing;
+        self
+    }
+
+    /// Set how a tie between +DI and -DI sets the trend direction
+    pub fn tie_policy(mut self, tie_policy: TiePolicy) -> Self {
+        self.config.tie_policy = tie_policy;
+        self
+    }
+
+    /// Set how non-finite inputs are handled
+    pub fn gap_policy(mut self, gap_policy: GapPolicy) -> Self {
+        self.config.gap_policy = gap_policy;
+        self
+    }
+
+    /// Set the decimal places `adx` is rounded to
+    pub fn precision(mut self, decimals: u8) -> Self {
+        self.config.precision = Some(decimals);
+        self
+    }
+
+    /// Validate and return the configuration
+    pub fn build(self) -> Result<ADXConfig, ADXError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
 /// Input data for ADX calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ADXInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// High price
     pub high: f64,
     /// Low price
@@ -36,6 +149,18 @@ pub struct ADXInput {
     pub close: f64,
 }
 
+impl From<Candle> for ADXInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+        }
+    }
+}
+
 /// Trend strength classification
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TrendStrength {
@@ -49,20 +174,12 @@ pub enum TrendStrength {
     Insufficient,
 }
 
-/// Trend direction based on DI comparison
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub enum TrendDirection {
-    /// +DI > -DI (uptrend)
-    Up,
-    /// -DI > +DI (downtrend)
-    Down,
-    /// +DI = -DI (sideways)
-    Sideways,
-}
-
 /// Output from ADX calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ADXOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// Average Directional Index (trend strength)
     pub adx: f64,
     /// Plus Directional Indicator
@@ -79,6 +196,26 @@ pub struct ADXOutput {
     pub trend_direction: TrendDirection,
     /// DI spread (+DI - -DI)
     pub di_spread: f64,
+    /// DI sum (+DI + -DI)
+    #[serde(default)]
+    pub di_sum: f64,
+    /// Trend quality in [0, 1]: ADX scaled against the very strong trend threshold
+    #[serde(default)]
+    pub trend_quality: f64,
+}
+
+/// Smoothed directional movement and True Range behind the current DI values
+///
+/// +DI = plus_dm / tr × 100 and -DI = minus_dm / tr × 100. With Wilder smoothing
+/// these are running sums; with EMA smoothing they are averages.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ADXRaw {
+    /// Smoothed Plus DM
+    pub smoothed_plus_dm: f64,
+    /// Smoothed Minus DM
+    pub smoothed_minus_dm: f64,
+    /// Smoothed True Range
+    pub smoothed_tr: f64,
 }
 
 /// Internal calculation data for a single period
@@ -127,6 +264,10 @@ pub struct ADXState {
     pub has_adx_data: bool,
     /// Is first calculation
     pub is_first: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<ADXInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<ADXOutput>,
 }
 
 impl ADXState {
@@ -145,6 +286,8 @@ impl ADXState {
             has_di_data: false,
             has_adx_data: false,
             is_first: true,
+            last_valid_input: None,
+            last_output: None,
         }
     }
 }
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::types::GapPolicy;
+    use crate::v2::beta::{
+        main::{calculate_beta_simple, RollingBeta},
+        types::{BetaConfig, BetaError},
+    };
+
+    fn bench_returns(len: usize) -> Vec<f64> {
+        (0..len).map(|i| (i as f64 * 0.9).sin() * 0.02).collect()
+    }
+
+    #[test]
+    fn test_double_mover_has_beta_two() {
+        let mut beta = RollingBeta::new(10).unwrap();
+        for (i, bench) in bench_returns(40).into_iter().enumerate() {
+            let result = beta.add_values(2.0 * bench, bench).unwrap();
+            if i < 9 {
+                assert!(result.is_none());
+            } else {
+                assert!((result.unwrap() - 2.0).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_offset_and_inverse_movers() {
+        let bench = bench_returns(30);
+
+        // A constant offset does not change beta
+        let asset: Vec<f64> = bench.iter().map(|b| 2.0 * b + 0.001).collect();
+        let betas = calculate_beta_simple(&asset, &bench, 8).unwrap();
+        assert!((betas.last().unwrap().unwrap() - 2.0).abs() < 1e-9);
+
+        let inverse: Vec<f64> = bench.iter().map(|b| -0.5 * b).collect();
+        let betas = calculate_beta_simple(&inverse, &bench, 8).unwrap();
+        assert!((betas.last().unwrap().unwrap() + 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_small_moves_around_a_large_level() {
+        // Tiny spread next to the level: running sums lose most of the digits here
+        let bench: Vec<f64> = (0..60)
+            .map(|i| 10_000.0 + (i as f64 * 0.9).sin() * 0.01)
+            .collect();
+        let asset: Vec<f64> = bench.iter().map(|b| 2.0 * b + 5.0).collect();
+
+        let betas = calculate_beta_simple(&asset, &bench, 10).unwrap();
+        for beta in betas.into_iter().skip(9) {
+            assert!((beta.unwrap() - 2.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_flat_benchmark_and_invalid_input() {
+        let mut beta = RollingBeta::new(3).unwrap();
+        for asset in [0.01, -0.02, 0.03] {
+            assert!(beta.add_values(asset, 0.01).unwrap().is_none());
+        }
+
+        assert_eq!(
+            beta.add_values(f64::NAN, 0.01),
+            Err(BetaError::InvalidReturn)
+        );
+        assert_eq!(beta.get_state().pairs.len(), 3);
+
+        assert_eq!(RollingBeta::new(1).err(), Some(BetaError::InvalidPeriod));
+        assert!(matches!(
+            calculate_beta_simple(&[0.01, 0.02], &[0.01], 2),
+            Err(BetaError::InvalidInput(_))
+        ));
+        assert_eq!(
+            calculate_beta_simple(&[0.01, f64::INFINITY], &[0.01, 0.02], 2),
+            Err(BetaError::InvalidReturn)
+        );
+    }
+
+    #[test]
+    fn test_gap_policies() {
+        let bench = bench_returns(6);
+        let with_policy = |gap_policy| {
+            RollingBeta::with_config(BetaConfig {
+                period: 4,
+                gap_policy,
+            })
+        };
+
+        // Skipped pairs leave the window untouched
+        let mut skip = with_policy(GapPolicy::SkipBar);
+        let mut reference = RollingBeta::new(4).unwrap();
+        for (i, &b) in bench.iter().enumerate() {
+            let asset = 1.5 * b + i as f64 * 0.001;
+            let expected = reference.add_values(asset, b).unwrap();
+            assert_eq!(skip.add_values(asset, b).unwrap(), expected);
+            assert_eq!(skip.add_values(asset, f64::NAN).unwrap(), expected);
+        }
+        assert_eq!(skip.get_state().pairs, reference.get_state().pairs);
+
+        // Forward-filled pairs repeat the last valid pair
+        let mut fill = with_policy(GapPolicy::ForwardFill);
+        assert_eq!(
+            fill.add_values(f64::NAN, 0.01),
+            Err(BetaError::InvalidReturn)
+        );
+        fill.add_values(0.02, 0.01).unwrap();
+        fill.add_values(f64::NAN, f64::NAN).unwrap();
+        assert_eq!(
+            fill.get_state().pairs.iter().copied().collect::<Vec<_>>(),
+            vec![(0.02, 0.01), (0.02, 0.01)]
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_is_reported_on_use() {
+        let mut beta = RollingBeta::with_config(BetaConfig {
+            period: 0,
+            ..BetaConfig::default()
+        });
+        assert_eq!(beta.add_values(0.01, 0.02), Err(BetaError::InvalidPeriod));
+        assert!(RollingBeta::try_with_config(BetaConfig {
+            period: 1,
+            ..BetaConfig::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut beta = RollingBeta::new(3).unwrap();
+        for bench in bench_returns(5) {
+            beta.add_values(bench, bench).unwrap();
+        }
+        assert!(beta.beta().is_some());
+
+        beta.reset();
+        assert!(beta.beta().is_none());
+        assert_eq!(beta.period(), 3);
+    }
+}
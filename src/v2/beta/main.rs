@@ -0,0 +1,164 @@
+use crate::common::types::GapAction;
+use crate::v2::beta::types::{BetaConfig, BetaError, RollingBetaState};
+
+/// Rolling Beta of an asset against a benchmark
+///
+/// Beta measures how strongly an asset's returns move with a benchmark's:
+/// beta = cov(asset, benchmark) / var(benchmark) over the last `period` pairs.
+///
+/// Covariance and variance are taken in two passes over the window (means
+/// first, then the deviations from them), so each update is O(period).
+///
+/// Interpretation:
+/// - beta = 1: moves in line with the benchmark
+/// - beta > 1: amplifies benchmark moves
+/// - 0 < beta < 1: dampens them
+/// - beta < 0: tends to move against the benchmark
+///
+/// Inputs are returns (e.g. close-to-close percentage changes), not prices.
+pub struct RollingBeta {
+    state: RollingBetaState,
+    /// Configuration error found at construction
+    config_error: Option<BetaError>,
+}
+
+impl RollingBeta {
+    /// Create a new rolling beta over `period` return pairs
+    pub fn new(period: usize) -> Result<Self, BetaError> {
+        Self::try_with_config(BetaConfig {
+            period,
+            ..BetaConfig::default()
+        })
+    }
+
+    /// Create a new rolling beta calculator with custom configuration
+    pub fn with_config(config: BetaConfig) -> Self {
+        Self {
+            state: RollingBetaState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: BetaConfig) -> Result<Self, BetaError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Add one pair of returns and return the beta once the window is full
+    ///
+    /// Returns `Ok(None)` during warmup or when the benchmark has no variance
+    /// over the window. A pair with a non-finite return is handled according
+    /// to the configured `GapPolicy`.
+    pub fn add_values(&mut self, asset_ret: f64, bench_ret: f64) -> Result<Option<f64>, BetaError> {
+        self.validate_config()?;
+
+        let (asset_ret, bench_ret) = match self.state.config.gap_policy.apply(
+            (asset_ret, bench_ret),
+            asset_ret.is_finite() && bench_ret.is_finite(),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(pair) => pair,
+            GapAction::Skip => return Ok(self.beta()),
+            GapAction::Reject => return Err(BetaError::InvalidReturn),
+        };
+
+        if self.state.pairs.len() == self.state.config.period {
+            self.state.pairs.pop_front();
+        }
+        self.state.pairs.push_back((asset_ret, bench_ret));
+        self.state.last_valid_input = Some((asset_ret, bench_ret));
+
+        Ok(self.beta())
+    }
+
+    /// Current beta, if the window is full and the benchmark varies
+    pub fn beta(&self) -> Option<f64> {
+        let pairs = &self.state.pairs;
+        if pairs.len() < self.state.config.period {
+            return None;
+        }
+
+        let n = pairs.len() as f64;
+        let (sum_asset, sum_bench) = pairs
+            .iter()
+            .fold((0.0, 0.0), |(sum_asset, sum_bench), &(asset, bench)| {
+                (sum_asset + asset, sum_bench + bench)
+            });
+        let (mean_asset, mean_bench) = (sum_asset / n, sum_bench / n);
+
+        // The running-sum shortcut n·Σab - Σa·Σb cancels catastrophically once the
+        // returns are small next to their mean; deviations from the mean do not
+        let (co_moment, bench_moment) =
+            pairs
+                .iter()
+                .fold((0.0, 0.0), |(co_moment, bench_moment), &(asset, bench)| {
+                    let bench_deviation = bench - mean_bench;
+                    (
+                        co_moment + (asset - mean_asset) * bench_deviation,
+                        bench_moment + bench_deviation * bench_deviation,
+                    )
+                });
+
+        // A flat benchmark leaves only the rounding noise of its mean
+        let noise = n * f64::EPSILON * mean_bench.abs();
+        if bench_moment <= n * noise * noise {
+            return None;
+        }
+
+        Some(co_moment / bench_moment)
+    }
+
+    /// Window length
+    pub fn period(&self) -> usize {
+        self.state.config.period
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = RollingBetaState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &RollingBetaState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: RollingBetaState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn validate_config(&self) -> Result<(), BetaError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Calculate rolling beta for slices of asset and benchmark returns
+///
+/// Pairs up the two slices, which must have the same length, and returns one
+/// entry per pair.
+pub fn calculate_beta_simple(
+    asset_returns: &[f64],
+    bench_returns: &[f64],
+    period: usize,
+) -> Result<Vec<Option<f64>>, BetaError> {
+    if asset_returns.len() != bench_returns.len() {
+        return Err(BetaError::InvalidInput(
+            "Asset and benchmark returns must have the same length".to_string(),
+        ));
+    }
+
+    let mut beta = RollingBeta::new(period)?;
+    asset_returns
+        .iter()
+        .zip(bench_returns)
+        .map(|(&asset, &bench)| beta.add_values(asset, bench))
+        .collect()
+}
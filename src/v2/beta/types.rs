@@ -0,0 +1,65 @@
+use crate::common::types::GapPolicy;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Configuration for rolling beta calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BetaConfig {
+    /// Number of return pairs in the window (default: 20)
+    pub period: usize,
+    /// How pairs with a non-finite return are handled (default: GapPolicy::Error)
+    #[serde(default)]
+    pub gap_policy: GapPolicy,
+}
+
+impl Default for BetaConfig {
+    fn default() -> Self {
+        Self {
+            period: 20,
+            gap_policy: GapPolicy::Error,
+        }
+    }
+}
+
+impl BetaConfig {
+    /// Check the invariants the rolling beta calculator relies on
+    pub fn validate(&self) -> Result<(), BetaError> {
+        if self.period < 2 {
+            return Err(BetaError::InvalidPeriod);
+        }
+
+        Ok(())
+    }
+}
+
+/// Rolling beta calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RollingBetaState {
+    /// Configuration
+    pub config: BetaConfig,
+    /// (asset return, benchmark return) pairs in the window
+    pub pairs: VecDeque<(f64, f64)>,
+    /// Last finite pair (used by `GapPolicy::ForwardFill`)
+    pub last_valid_input: Option<(f64, f64)>,
+}
+
+impl RollingBetaState {
+    pub fn new(config: BetaConfig) -> Self {
+        Self {
+            config,
+            pairs: VecDeque::with_capacity(config.period),
+            last_valid_input: None,
+        }
+    }
+}
+
+/// Error types for rolling beta calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BetaError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid return (NaN or infinite)
+    InvalidReturn,
+    /// Invalid period (must be >= 2)
+    InvalidPeriod,
+}
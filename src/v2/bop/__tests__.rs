@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::v2::bop::{
+        main::{calculate_bop_simple, BOP},
+        types::{BOPError, BOPInput},
+    };
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> BOPInput {
+        BOPInput {
+            timestamp: None,
+            open,
+            high,
+            low,
+            close,
+        }
+    }
+
+    #[test]
+    fn test_full_range_and_zero_range_bars() {
+        let mut bop = BOP::new();
+
+        // Opens at the low, closes at the high
+        assert_eq!(bop.calculate(bar(10.0, 12.0, 10.0, 12.0)).unwrap().bop, 1.0);
+        // Opens at the high, closes at the low
+        assert_eq!(
+            bop.calculate(bar(12.0, 12.0, 10.0, 10.0)).unwrap().bop,
+            -1.0
+        );
+        // Zero-range bar
+        let output = bop.calculate(bar(11.0, 11.0, 11.0, 11.0)).unwrap();
+        assert_eq!(output.bop, 0.0);
+        assert!(output.smoothed.is_none());
+
+        let values =
+            calculate_bop_simple(&[10.0, 10.5], &[12.0, 11.0], &[10.0, 10.0], &[11.0, 10.5])
+                .unwrap();
+        assert_eq!(values, vec![0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_sma_smoothing() {
+        let mut bop = BOP::with_smoothing(3).unwrap();
+        let bars = [
+            bar(10.0, 12.0, 10.0, 12.0), // 1.0
+            bar(12.0, 12.0, 10.0, 10.0), // -1.0
+            bar(10.0, 12.0, 10.0, 11.0), // 0.5
+            bar(10.0, 12.0, 10.0, 11.0), // 0.5
+        ];
+
+        let outputs: Vec<_> = bars
+            .iter()
+            .map(|input| bop.calculate_checked(*input).unwrap())
+            .collect();
+        assert!(outputs[0].is_none() && outputs[1].is_none());
+        assert!((outputs[2].unwrap().smoothed.unwrap() - 0.5 / 3.0).abs() < 1e-12);
+        assert!((outputs[3].unwrap().smoothed.unwrap() - 0.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert_eq!(BOP::with_smoothing(0).err(), Some(BOPError::InvalidPeriod));
+
+        let mut bop = BOP::new();
+        assert_eq!(
+            bop.calculate(bar(10.0, 9.0, 10.0, 9.5)),
+            Err(BOPError::InvalidOHLC)
+        );
+        assert_eq!(
+            bop.calculate(bar(13.0, 12.0, 10.0, 11.0)),
+            Err(BOPError::InvalidOHLC)
+        );
+        assert_eq!(
+            bop.calculate(bar(f64::NAN, 12.0, 10.0, 11.0)),
+            Err(BOPError::InvalidPrice)
+        );
+        assert!(calculate_bop_simple(&[1.0], &[], &[], &[]).is_err());
+    }
+}
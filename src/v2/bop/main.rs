@@ -0,0 +1,245 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::v2::bop::types::{BOPConfig, BOPError, BOPInput, BOPOutput, BOPState};
+
+/// Balance of Power (BOP) Indicator
+///
+/// Balance of Power measures how far buyers or sellers moved price within each
+/// bar, relative to the bar's full range.
+///
+/// Formula:
+/// - BOP = (Close - Open) / (High - Low)
+/// - Smoothed BOP = SMA(BOP, smoothing_period), when smoothing is configured
+///
+/// Interpretation:
+/// - +1: opened at the low and closed at the high (buyers in full control)
+/// - -1: opened at the high and closed at the low (sellers in full control)
+/// - A zero-range bar (high == low) carries no information and reads 0
+pub struct BOP {
+    state: BOPState,
+    /// Configuration error found at construction
+    config_error: Option<BOPError>,
+}
+
+impl BOP {
+    /// Create a new unsmoothed Balance of Power calculator
+    pub fn new() -> Self {
+        Self::with_config(BOPConfig::default())
+    }
+
+    /// Create a new Balance of Power calculator smoothed by an SMA of `period` bars
+    pub fn with_smoothing(period: usize) -> Result<Self, BOPError> {
+        Self::try_with_config(BOPConfig {
+            smoothing_period: Some(period),
+            ..BOPConfig::default()
+        })
+    }
+
+    /// Create a new Balance of Power calculator with custom configuration
+    pub fn with_config(config: BOPConfig) -> Self {
+        Self {
+            state: BOPState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: BOPConfig) -> Result<Self, BOPError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Calculate Balance of Power for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(&mut self, input: BOPInput) -> Result<BOPOutput, BOPError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| BOPOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(BOPError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(BOPError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.bop = round_to(output.bop, decimals);
+            output.smoothed = output.smoothed.map(|value| round_to(value, decimals));
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// Without smoothing every bar is ready; with smoothing, bars before the SMA
+    /// window fills return `Ok(None)`.
+    pub fn calculate_checked(&mut self, input: BOPInput) -> Result<Option<BOPOutput>, BOPError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Calculate Balance of Power for a batch of inputs
+    pub fn calculate_batch(&mut self, inputs: &[BOPInput]) -> Result<Vec<BOPOutput>, BOPError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[BOPInput],
+    ) -> Result<DenseBatch<BOPOutput>, BOPError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            BOPError::InvalidInput,
+        )
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = BOPState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &BOPState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: BOPState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(&mut self, input: BOPInput) -> Result<BOPOutput, BOPError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        let range = input.high - input.low;
+        let bop = if range <= f64::EPSILON * input.high.abs().max(1.0) {
+            0.0 // Zero-range bar - no balance either way
+        } else {
+            ((input.close - input.open) / range).clamp(-1.0, 1.0)
+        };
+
+        let smoothed = match self.state.config.smoothing_period {
+            Some(period) => self.update_smoothing(bop, period),
+            None => {
+                self.state.has_sufficient_data = true;
+                None
+            }
+        };
+
+        Ok(BOPOutput {
+            timestamp: None,
+            bop,
+            smoothed,
+        })
+    }
+
+    fn is_finite_input(input: &BOPInput) -> bool {
+        input.open.is_finite()
+            && input.high.is_finite()
+            && input.low.is_finite()
+            && input.close.is_finite()
+    }
+
+    fn validate_input(&self, input: &BOPInput) -> Result<(), BOPError> {
+        if !Self::is_finite_input(input) {
+            return Err(BOPError::InvalidPrice);
+        }
+
+        // Check OHLC relationship
+        if input.high < input.low {
+            return Err(BOPError::InvalidOHLC);
+        }
+
+        for price in [input.open, input.close] {
+            if price < input.low || price > input.high {
+                return Err(BOPError::InvalidOHLC);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), BOPError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn update_smoothing(&mut self, bop: f64, period: usize) -> Option<f64> {
+        // Remove oldest value if at capacity
+        if self.state.values.len() >= period {
+            if let Some(oldest) = self.state.values.pop_front() {
+                self.state.sum -= oldest;
+            }
+        }
+
+        self.state.values.push_back(bop);
+        self.state.sum += bop;
+
+        self.state.has_sufficient_data = self.state.values.len() >= period;
+        self.state
+            .has_sufficient_data
+            .then(|| self.state.sum / period as f64)
+    }
+}
+
+impl Default for BOP {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to calculate per-bar Balance of Power for OHLC data without maintaining state
+pub fn calculate_bop_simple(
+    opens: &[f64],
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+) -> Result<Vec<f64>, BOPError> {
+    if opens.len() != highs.len() || highs.len() != lows.len() || lows.len() != closes.len() {
+        return Err(BOPError::InvalidInput(
+            "All price arrays must have the same length".to_string(),
+        ));
+    }
+
+    let mut bop_calculator = BOP::new();
+
+    (0..opens.len())
+        .map(|i| {
+            let input = BOPInput {
+                timestamp: None,
+                open: opens[i],
+                high: highs[i],
+                low: lows[i],
+                close: closes[i],
+            };
+            bop_calculator.calculate(input).map(|output| output.bop)
+        })
+        .collect()
+}
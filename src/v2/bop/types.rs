@@ -0,0 +1,119 @@
+use crate::common::types::{Candle, GapPolicy};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Configuration for Balance of Power calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BOPConfig {
+    /// Period of the SMA smoothing the per-bar values (default: None, unsmoothed)
+    pub smoothing_period: Option<usize>,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+    /// Decimal places `bop` and `smoothed` are rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+impl Default for BOPConfig {
+    fn default() -> Self {
+        Self {
+            smoothing_period: None,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl BOPConfig {
+    /// Check the invariants the Balance of Power calculator relies on
+    pub fn validate(&self) -> Result<(), BOPError> {
+        if self.smoothing_period == Some(0) {
+            return Err(BOPError::InvalidPeriod);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for Balance of Power calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BOPInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Open price
+    pub open: f64,
+    /// High price
+    pub high: f64,
+    /// Low price
+    pub low: f64,
+    /// Close price
+    pub close: f64,
+}
+
+impl From<Candle> for BOPInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+        }
+    }
+}
+
+/// Output from Balance of Power calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BOPOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Balance of Power of this bar, within [-1, 1] (0 for a zero-range bar)
+    pub bop: f64,
+    /// SMA of the per-bar values (None without smoothing or during warmup)
+    pub smoothed: Option<f64>,
+}
+
+/// Balance of Power calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BOPState {
+    /// Configuration
+    pub config: BOPConfig,
+    /// Recent per-bar values for the smoothing SMA
+    pub values: VecDeque<f64>,
+    /// Running sum of the values in the window
+    pub sum: f64,
+    /// Whether we have enough data for calculation
+    pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<BOPInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<BOPOutput>,
+}
+
+impl BOPState {
+    pub fn new(config: BOPConfig) -> Self {
+        Self {
+            config,
+            values: VecDeque::with_capacity(config.smoothing_period.unwrap_or(0)),
+            sum: 0.0,
+            has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Error types for Balance of Power calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BOPError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid OHLC relationship (high < low, or open/close outside the range)
+    InvalidOHLC,
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid smoothing period (must be > 0)
+    InvalidPeriod,
+}
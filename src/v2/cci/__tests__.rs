@@ -1,8 +1,10 @@
 #[cfg(test)]
 mod tests {
+    use crate::common::assert_dense_batch;
+    use crate::common::types::{BarMode, DistanceUnits, GapPolicy};
     use crate::v2::cci::{
         main::{calculate_cci_simple, CCI},
-        types::{CCIConfig, CCIError, CCIInput, CCIMarketCondition},
+        types::{CCIConfig, CCIError, CCIInput, CCIMarketCondition, CciDeviation},
     };
 
     #[test]
@@ -20,6 +22,7 @@ mod tests {
 
         for (i, (high, low, close)) in test_data.iter().enumerate() {
             let input = CCIInput {
+                timestamp: None,
                 high: *high,
                 low: *low,
                 close: *close,
@@ -60,7 +63,12 @@ mod tests {
 
         let mut results = Vec::new();
         for (high, low, close) in uptrend_data {
-            let input = CCIInput { high, low, close };
+            let input = CCIInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
             let result = cci.calculate(input).unwrap();
             results.push(result);
         }
@@ -81,11 +89,13 @@ mod tests {
             oversold: -100.0,
             extreme_overbought: 200.0,
             extreme_oversold: -200.0,
+            ..Default::default()
         };
         let mut cci = CCI::with_config(config);
 
         // Test insufficient data
         let input1 = CCIInput {
+            timestamp: None,
             high: 10.0,
             low: 8.0,
             close: 9.0,
@@ -95,6 +105,7 @@ mod tests {
 
         // Add more data
         let input2 = CCIInput {
+            timestamp: None,
             high: 11.0,
             low: 9.0,
             close: 10.0,
@@ -104,6 +115,7 @@ mod tests {
 
         // Now should have sufficient data
         let input3 = CCIInput {
+            timestamp: None,
             high: 12.0,
             low: 10.0,
             close: 11.0,
@@ -117,6 +129,7 @@ mod tests {
         let mut cci = CCI::new();
 
         let input = CCIInput {
+            timestamp: None,
             high: 15.0,
             low: 10.0,
             close: 12.0,
@@ -140,7 +153,12 @@ mod tests {
         ];
 
         for (high, low, close) in identical_data {
-            let input = CCIInput { high, low, close };
+            let input = CCIInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
             let result = cci.calculate(input).unwrap();
 
             if result.market_condition != CCIMarketCondition::Insufficient {
@@ -157,6 +175,7 @@ mod tests {
 
         // Test invalid HLC (high < low)
         let input = CCIInput {
+            timestamp: None,
             high: 8.0,
             low: 10.0,
             close: 9.0,
@@ -165,6 +184,7 @@ mod tests {
 
         // Test close out of range
         let input = CCIInput {
+            timestamp: None,
             high: 10.0,
             low: 8.0,
             close: 12.0,
@@ -208,7 +228,12 @@ mod tests {
         ];
 
         for (high, low, close) in small_range_data {
-            let input = CCIInput { high, low, close };
+            let input = CCIInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
             let result = cci.calculate(input).unwrap();
 
             // Should handle small ranges without issues
@@ -232,4 +257,431 @@ mod tests {
         assert!(cci.is_extreme_condition(-250.0));
         assert!(!cci.is_extreme_condition(50.0));
     }
+
+    #[test]
+    fn test_config_builder() {
+        let config = CCIConfig::builder()
+            .period(20)
+            .overbought(150.0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config,
+            CCIConfig {
+                period: 20,
+                overbought: 150.0,
+                ..Default::default()
+            }
+        );
+
+        // Same invariants as the constructors
+        assert_eq!(
+            CCIConfig::builder().period(0).build().err(),
+            CCI::with_period(0).err()
+        );
+        let inverted = CCIConfig::builder().overbought(-150.0).oversold(100.0);
+        assert_eq!(
+            inverted.build().err(),
+            CCI::with_thresholds(14, -150.0, 100.0, 200.0, -200.0).err()
+        );
+        assert_eq!(inverted.build(), Err(CCIError::InvalidThresholds));
+    }
+
+    #[test]
+    fn test_ema_deviation_smooths_outlier_exit() {
+        let period = 5;
+        // Steady uptrend with one outlier bar at index 8
+        let closes: Vec<f64> = (0..20)
+            .map(|i| {
+                if i == 8 {
+                    130.0
+                } else {
+                    100.0 + i as f64 * 0.5
+                }
+            })
+            .collect();
+        let exit_bar = 8 + period;
+
+        let run = |deviation: CciDeviation| -> Vec<f64> {
+            let config = CCIConfig::builder()
+                .period(period)
+                .deviation(deviation)
+                .build()
+                .unwrap();
+            let mut cci = CCI::with_config(config);
+            closes
+                .iter()
+                .map(|&close| {
+                    let input = CCIInput {
+                        timestamp: None,
+                        high: close + 1.0,
+                        low: close - 1.0,
+                        close,
+                    };
+                    cci.calculate(input).unwrap().cci
+                })
+                .collect()
+        };
+
+        let simple = run(CciDeviation::Simple);
+        let ema = run(CciDeviation::Ema);
+
+        let simple_jump = (simple[exit_bar] - simple[exit_bar - 1]).abs();
+        let ema_jump = (ema[exit_bar] - ema[exit_bar - 1]).abs();
+        assert!(
+            ema_jump < simple_jump,
+            "ema jump {ema_jump} should be smaller than simple jump {simple_jump}"
+        );
+    }
+
+    #[test]
+    fn test_rejected_revision_keeps_latest_bar() {
+        let bars = [
+            (10.0, 8.0, 9.0),
+            (11.0, 9.0, 10.0),
+            (12.0, 10.0, 11.0),
+            (13.0, 11.0, 12.0),
+        ];
+        let input = |(high, low, close): (f64, f64, f64)| CCIInput {
+            timestamp: None,
+            high,
+            low,
+            close,
+        };
+        let mut live = CCI::with_period(3).unwrap();
+        let mut reference = CCI::with_period(3).unwrap();
+        for &bar in &bars[..3] {
+            live.calculate(input(bar)).unwrap();
+            reference.calculate(input(bar)).unwrap();
+        }
+
+        assert_eq!(
+            live.update_or_replace_last(input((f64::NAN, 10.0, 11.0))),
+            Err(CCIError::InvalidPrice)
+        );
+        assert_eq!(
+            live.update_or_replace_last(input((9.0, 10.0, 9.5))),
+            Err(CCIError::InvalidHLC)
+        );
+        assert_eq!(
+            live.calculate(input(bars[3])),
+            reference.calculate(input(bars[3]))
+        );
+    }
+
+    #[test]
+    fn test_deviation_defaults_to_simple() {
+        assert_eq!(CCIConfig::default().deviation, CciDeviation::Simple);
+    }
+
+    #[test]
+    fn test_update_or_replace_last() {
+        let bars = [
+            (10.0, 8.0, 9.0),
+            (11.0, 9.0, 10.0),
+            (12.0, 10.0, 11.0),
+            (13.0, 11.0, 12.0),
+            (12.5, 10.5, 11.0),
+            (14.0, 12.0, 13.5),
+        ];
+
+        for deviation in [CciDeviation::Simple, CciDeviation::Ema] {
+            let config = CCIConfig {
+                period: 3,
+                deviation,
+                ..CCIConfig::default()
+            };
+            let mut live = CCI::with_config(config);
+            let mut reference = CCI::with_config(config);
+
+            for &(high, low, close) in &bars {
+                let input = CCIInput {
+                    timestamp: None,
+                    high,
+                    low,
+                    close,
+                };
+                let provisional = CCIInput {
+                    high: high + 2.0,
+                    close: high + 2.0,
+                    ..input
+                };
+                live.calculate(provisional).unwrap();
+                let revised = live.update_or_replace_last(input).unwrap();
+
+                assert_eq!(revised, reference.calculate(input).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(CCI::new(), CCIInput);
+    }
+
+    #[test]
+    fn test_percent_distances_are_scale_invariant() {
+        let config = CCIConfig::builder()
+            .period(5)
+            .distance_units(DistanceUnits::Percent)
+            .build()
+            .unwrap();
+        let bars = [
+            (10.0, 8.0, 9.0),
+            (12.0, 9.0, 11.0),
+            (11.0, 8.5, 10.0),
+            (13.0, 10.0, 12.5),
+            (9.0, 7.0, 7.5),
+            (11.0, 8.0, 10.5),
+            (12.5, 10.0, 12.0),
+        ];
+
+        let run = |scale: f64| {
+            let mut indicator = CCI::with_config(config);
+            bars.iter()
+                .map(|&(high, low, close)| {
+                    let input = CCIInput {
+                        timestamp: None,
+                        high: high * scale,
+                        low: low * scale,
+                        close: close * scale,
+                    };
+                    indicator.calculate(input).unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let (base, scaled) = (run(1.0), run(250.0));
+        for (a, b) in base.iter().zip(&scaled) {
+            assert!((a.distance_from_zero - b.distance_from_zero).abs() < 1e-9);
+        }
+
+        // Default thresholds are ±100, so the percentage equals |CCI|
+        let last = base.last().unwrap();
+        assert!((last.distance_from_zero - last.cci.abs()).abs() < 1e-9);
+
+        let raw = CCIConfig {
+            distance_units: DistanceUnits::Raw,
+            overbought: 50.0,
+            oversold: -50.0,
+            ..config
+        };
+        let mut cci = CCI::with_config(raw);
+        let mut half_band = CCI::with_config(CCIConfig {
+            distance_units: DistanceUnits::Percent,
+            ..raw
+        });
+        for &(high, low, close) in &bars {
+            let input = CCIInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
+            let (r, p) = (
+                cci.calculate(input).unwrap(),
+                half_band.calculate(input).unwrap(),
+            );
+            assert!((p.distance_from_zero - r.distance_from_zero * 2.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_calculate_batch_vs_independent() {
+        let inputs: Vec<CCIInput> = (0..6)
+            .map(|i| {
+                let base = 10.0 + (i % 3) as f64;
+                CCIInput {
+                    timestamp: Some(i),
+                    high: base + 1.0,
+                    low: base - 1.0,
+                    close: base + 0.5,
+                }
+            })
+            .collect();
+
+        let mut calculator = CCI::with_period(3).unwrap();
+        calculator.calculate(inputs[0]).unwrap();
+        let state_before = calculator.get_state().clone();
+
+        // Independent evaluation neither reads nor touches the running state
+        let independent = calculator.calculate_independent(&inputs).unwrap();
+        assert_eq!(calculator.get_state(), &state_before);
+        for (input, output) in inputs.iter().zip(&independent) {
+            assert_eq!(
+                *output,
+                CCI::with_period(3).unwrap().calculate(*input).unwrap()
+            );
+        }
+
+        // The batch continues the series from the existing state
+        let mut fresh = CCI::with_period(3).unwrap();
+        let batch = fresh.calculate_batch(&inputs).unwrap();
+        assert_ne!(batch[2], independent[2]);
+        assert_ne!(
+            calculator.calculate_batch(&inputs[1..]).unwrap()[1],
+            independent[2]
+        );
+        assert_eq!(batch.len(), independent.len());
+    }
+
+    #[test]
+    fn test_clamp_bounds_runaway_cci() {
+        // 19 flat bars then a jump: the tiny mean deviation sends CCI to ~666.7
+        let mut inputs = vec![
+            CCIInput {
+                timestamp: None,
+                high: 10.0,
+                low: 10.0,
+                close: 10.0,
+            };
+            19
+        ];
+        inputs.push(CCIInput {
+            timestamp: None,
+            high: 20.0,
+            low: 20.0,
+            close: 20.0,
+        });
+
+        let unbounded = CCI::new().calculate_batch(&inputs).unwrap();
+        let runaway = unbounded.last().unwrap();
+        assert!((runaway.cci - 666.666_666).abs() < 1e-3);
+        assert!(!runaway.clamped);
+
+        let config = CCIConfig::builder().clamp(-300.0, 300.0).build().unwrap();
+        let clamped = CCI::with_config(config).calculate_batch(&inputs).unwrap();
+        let last = clamped.last().unwrap();
+        assert_eq!(last.cci, 300.0);
+        assert!(last.clamped);
+        assert_eq!(last.distance_from_zero, 300.0);
+        assert_eq!(last.market_condition, CCIMarketCondition::ExtremeOverbought);
+        // Readings inside the bounds are untouched
+        assert!(clamped[..19].iter().all(|o| !o.clamped));
+
+        for (lower, upper) in [(300.0, -300.0), (0.0, 0.0), (f64::NAN, 300.0)] {
+            assert_eq!(
+                CCIConfig::builder().clamp(lower, upper).build(),
+                Err(CCIError::InvalidThresholds)
+            );
+        }
+    }
+
+    #[test]
+    fn test_tick_mode_skipped_tick_keeps_forming_bar() {
+        let bars: Vec<CCIInput> = [
+            (10.0, 8.0, 9.0),
+            (12.0, 9.0, 11.0),
+            (11.0, 8.5, 10.0),
+            (13.0, 10.0, 12.5),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, &(high, low, close))| CCIInput {
+            timestamp: Some(i as i64),
+            high,
+            low,
+            close,
+        })
+        .collect();
+
+        let config = CCIConfig::builder()
+            .period(3)
+            .bar_mode(BarMode::Tick)
+            .gap_policy(GapPolicy::SkipBar)
+            .build()
+            .unwrap();
+        let mut ticked = CCI::with_config(config);
+        let mut reference = CCI::with_config(config);
+        for bar in &bars[..3] {
+            ticked.calculate(*bar).unwrap();
+            reference.calculate(*bar).unwrap();
+        }
+
+        // A NaN tick of the forming bar is skipped, an inconsistent one rejected;
+        // neither erases the bar
+        let skipped = ticked
+            .calculate(CCIInput {
+                close: f64::NAN,
+                ..bars[2]
+            })
+            .unwrap();
+        assert_eq!(skipped, reference.get_state().last_output.unwrap());
+        assert_eq!(
+            ticked.calculate(CCIInput {
+                high: bars[2].low - 1.0,
+                ..bars[2]
+            }),
+            Err(CCIError::InvalidHLC)
+        );
+
+        // The bar can still be revised, and the next bar is unaffected
+        assert_eq!(ticked.calculate(bars[2]), reference.calculate(bars[2]));
+        assert_eq!(ticked.calculate(bars[3]), reference.calculate(bars[3]));
+    }
+
+    #[test]
+    fn test_tick_mode_matches_closed_bars() {
+        // Closed bars, each preceded by two intrabar ticks with the same timestamp
+        let closed: Vec<CCIInput> = [
+            (12.0, 9.0, 11.0),
+            (13.0, 10.0, 12.5),
+            (12.5, 10.5, 11.0),
+            (14.0, 11.0, 13.5),
+            (15.0, 12.0, 12.5),
+            (13.0, 10.0, 10.5),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, &(high, low, close))| CCIInput {
+            timestamp: Some(i as i64),
+            high,
+            low,
+            close,
+        })
+        .collect();
+
+        let config = CCIConfig::builder()
+            .period(3)
+            .bar_mode(BarMode::Tick)
+            .build()
+            .unwrap();
+        let mut ticked = CCI::with_config(config);
+        let mut reference = CCI::with_config(CCIConfig {
+            bar_mode: BarMode::Close,
+            ..config
+        });
+
+        for bar in &closed {
+            let mid = (bar.high + bar.low) / 2.0;
+            for tick in [mid, bar.low] {
+                ticked
+                    .calculate(CCIInput {
+                        high: tick.max(mid),
+                        low: tick,
+                        close: tick,
+                        ..*bar
+                    })
+                    .unwrap();
+            }
+            assert_eq!(
+                ticked.calculate(*bar).unwrap(),
+                reference.calculate(*bar).unwrap()
+            );
+        }
+        assert_eq!(
+            ticked.get_state().typical_prices,
+            reference.get_state().typical_prices
+        );
+
+        // In close mode the same ticks would each count as a bar
+        let mut per_tick = CCI::with_config(CCIConfig {
+            bar_mode: BarMode::Close,
+            ..config
+        });
+        for tick in [closed[0], closed[0], closed[0]] {
+            per_tick.calculate(tick).unwrap();
+        }
+        assert_eq!(per_tick.get_state().typical_prices.len(), 3);
+    }
 }
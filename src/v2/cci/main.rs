@@ -1,5 +1,9 @@
+use crate::common::precision::round_to;
+use crate::common::types::{BarMode, DenseBatch, DistanceUnits, GapAction};
+use crate::transform::price_source::main::typical_price;
 use crate::v2::cci::types::{
-    CCIConfig, CCIError, CCIInput, CCIMarketCondition, CCIOutput, CCIState,
+    CCIConfig, CCIError, CCIInput, CCIMarketCondition, CCIOutput, CCIRollback, CCIState,
+    CciDeviation,
 };
 
 /// Commodity Channel Index (CCI) Indicator
@@ -15,6 +19,9 @@ use crate::v2::cci::types::{
 ///
 /// The constant 0.015 ensures about 70-80% of CCI values fall between -100 and +100.
 ///
+/// CCI itself is unbounded and can spike on low-deviation windows; the optional
+/// `clamp` config bounds the emitted value and flags the bars where it applied.
+///
 /// Interpretation:
 /// - Above +100: Overbought, potential sell signal
 /// - Below -100: Oversold, potential buy signal
@@ -22,6 +29,8 @@ use crate::v2::cci::types::{
 /// - Below -200: Extremely oversold
 pub struct CCI {
     state: CCIState,
+    /// Configuration error found at construction
+    config_error: Option<CCIError>,
 }
 
 impl CCI {
@@ -51,24 +60,15 @@ impl CCI {
         extreme_overbought: f64,
         extreme_oversold: f64,
     ) -> Result<Self, CCIError> {
-        if period == 0 {
-            return Err(CCIError::InvalidPeriod);
-        }
-
-        if overbought <= oversold
-            || extreme_overbought <= overbought
-            || extreme_oversold >= oversold
-        {
-            return Err(CCIError::InvalidThresholds);
-        }
-
         let config = CCIConfig {
             period,
             overbought,
             oversold,
             extreme_overbought,
             extreme_oversold,
+            ..Default::default()
         };
+        config.validate()?;
         Ok(Self::with_config(config))
     }
 
@@ -76,49 +76,80 @@ impl CCI {
     pub fn with_config(config: CCIConfig) -> Self {
         Self {
             state: CCIState::new(config),
+            config_error: config.validate().err(),
         }
     }
 
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: CCIConfig) -> Result<Self, CCIError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
     /// Calculate CCI for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`. With
+    /// `BarMode::Tick`, an input with the latest bar's timestamp revises that bar.
     pub fn calculate(&mut self, input: CCIInput) -> Result<CCIOutput, CCIError> {
-        // Validate input
-        self.validate_input(&input)?;
-        self.validate_config()?;
-
-        // Calculate typical price
-        let typical_price = self.calculate_typical_price(&input);
-
-        // Update typical price history
-        self.update_typical_price_history(typical_price);
-
-        // Calculate CCI if we have enough data
-        let (cci, sma_tp, mean_deviation) = if self.state.has_sufficient_data {
-            self.calculate_cci_value(typical_price)?
-        } else {
-            (0.0, typical_price, 0.0) // Default values when insufficient data
-        };
-
-        // Determine market condition
-        let market_condition = self.determine_market_condition(cci);
+        let revise = self.is_revision(&input);
+        self.calculate_revising(input, revise)
+    }
 
-        // Calculate distance from zero
-        let distance_from_zero = cci.abs();
+    /// Replace the latest bar with a revised version and recalculate
+    ///
+    /// For live feeds that revise the current (unclosed) candle: call `calculate`
+    /// when a new bar opens and this method for each revision, so the bar is not
+    /// counted twice. The output matches feeding the revised bar in place of the
+    /// original. If there is no processed bar to replace (nothing fed yet, or the
+    /// latest bar was skipped or rejected), the input is simply calculated.
+    pub fn update_or_replace_last(&mut self, input: CCIInput) -> Result<CCIOutput, CCIError> {
+        self.calculate_revising(input, true)
+    }
 
-        Ok(CCIOutput {
-            cci,
-            typical_price,
-            sma_tp,
-            mean_deviation,
-            market_condition,
-            distance_from_zero,
-        })
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(&mut self, input: CCIInput) -> Result<Option<CCIOutput>, CCIError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
     }
 
     /// Calculate CCI for a batch of inputs
+    ///
+    /// The inputs are treated as one continuous series: they are fed in order to this
+    /// calculator, continuing from (and updating) its current state. Use
+    /// `calculate_independent` to evaluate each input on its own.
     pub fn calculate_batch(&mut self, inputs: &[CCIInput]) -> Result<Vec<CCIOutput>, CCIError> {
         inputs.iter().map(|input| self.calculate(*input)).collect()
     }
 
+    /// Evaluate each input independently on a fresh calculator with this configuration
+    ///
+    /// This calculator's state is neither read nor modified. Each output is what a
+    /// newly created calculator would return for that single bar (a warmup output
+    /// unless the period is 1).
+    pub fn calculate_independent(&self, inputs: &[CCIInput]) -> Result<Vec<CCIOutput>, CCIError> {
+        inputs
+            .iter()
+            .map(|input| Self::with_config(self.state.config).calculate(*input))
+            .collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[CCIInput],
+    ) -> Result<DenseBatch<CCIOutput>, CCIError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            CCIError::InvalidInput,
+        )
+    }
+
     /// Reset the calculator state
     pub fn reset(&mut self) {
         self.state = CCIState::new(self.state.config);
@@ -131,6 +162,7 @@ impl CCI {
 
     /// Restore state (for deserialization)
     pub fn set_state(&mut self, state: CCIState) {
+        self.config_error = state.config.validate().err();
         self.state = state;
     }
 
@@ -161,6 +193,124 @@ impl CCI {
 
     // Private helper methods
 
+    /// Calculate `input`, first replacing the latest bar when `revise` is set
+    ///
+    /// The gap policy and input validation run before the latest bar is undone,
+    /// so a skipped or rejected revision leaves that bar in place.
+    fn calculate_revising(&mut self, input: CCIInput, revise: bool) -> Result<CCIOutput, CCIError> {
+        // Only a bar that can still be rolled back is revised
+        let revise = revise && self.state.rollback.is_some();
+        if !revise {
+            self.state.rollback = None;
+        }
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| CCIOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(CCIError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(CCIError::InvalidPrice),
+        };
+
+        // Reject the bar before undoing anything, so a rejected revision keeps the original
+        self.validate_input(&input)?;
+        if revise {
+            self.rollback_last();
+        }
+        self.state.rollback = None;
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.cci = round_to(output.cci, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Undo the latest processed bar, if it can still be replaced
+    fn rollback_last(&mut self) {
+        if let Some(rollback) = self.state.rollback.take() {
+            self.state.typical_prices.undo_last();
+            self.state.ema_deviation = rollback.ema_deviation;
+            self.state.has_sufficient_data = rollback.has_sufficient_data;
+            self.state.last_valid_input = rollback.last_valid_input;
+            self.state.last_output = rollback.last_output;
+        }
+    }
+
+    /// Whether a tick-mode input revises the latest processed bar
+    fn is_revision(&self, input: &CCIInput) -> bool {
+        self.state.config.bar_mode == BarMode::Tick
+            && input.timestamp.is_some()
+            && self.state.rollback.is_some()
+            && self.state.last_output.and_then(|output| output.timestamp) == input.timestamp
+    }
+
+    fn calculate_bar(&mut self, input: CCIInput) -> Result<CCIOutput, CCIError> {
+        self.validate_config()?;
+
+        // Calculate typical price
+        let typical_price = self.calculate_typical_price(&input);
+
+        // Update typical price history
+        self.update_typical_price_history(typical_price);
+
+        // Calculate CCI if we have enough data
+        let (cci, sma_tp, mean_deviation) = if self.state.has_sufficient_data {
+            self.calculate_cci_value(typical_price)?
+        } else {
+            (0.0, typical_price, 0.0) // Default values when insufficient data
+        };
+
+        // Bound runaway readings (e.g. on near-zero deviation) if configured
+        let (cci, clamped) = match self.state.config.clamp {
+            Some((lower, upper)) if cci < lower || cci > upper => (cci.clamp(lower, upper), true),
+            _ => (cci, false),
+        };
+
+        // Determine market condition
+        let market_condition = self.determine_market_condition(cci);
+
+        // Calculate distance from zero
+        let config = &self.state.config;
+        let distance_from_zero = match config.distance_units {
+            DistanceUnits::Raw => cci.abs(),
+            // Half the band equals the threshold distance for symmetric thresholds (validated > 0)
+            DistanceUnits::Percent => {
+                cci.abs() / ((config.overbought - config.oversold) / 2.0) * 100.0
+            }
+        };
+
+        Ok(CCIOutput {
+            timestamp: None,
+            cci,
+            typical_price,
+            sma_tp,
+            mean_deviation,
+            market_condition,
+            distance_from_zero,
+            clamped,
+        })
+    }
+
+    fn is_finite_input(input: &CCIInput) -> bool {
+        input.high.is_finite() && input.low.is_finite() && input.close.is_finite()
+    }
+
     fn validate_input(&self, input: &CCIInput) -> Result<(), CCIError> {
         // Check for valid prices
         if !input.high.is_finite() || !input.low.is_finite() || !input.close.is_finite() {
@@ -180,52 +330,44 @@ impl CCI {
     }
 
     fn validate_config(&self) -> Result<(), CCIError> {
-        if self.state.config.period == 0 {
-            return Err(CCIError::InvalidPeriod);
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
         }
-
-        let config = &self.state.config;
-        if config.overbought <= config.oversold
-            || config.extreme_overbought <= config.overbought
-            || config.extreme_oversold >= config.oversold
-        {
-            return Err(CCIError::InvalidThresholds);
-        }
-
-        Ok(())
     }
 
     fn calculate_typical_price(&self, input: &CCIInput) -> f64 {
-        (input.high + input.low + input.close) / 3.0
+        typical_price(input.high, input.low, input.close)
     }
 
     fn update_typical_price_history(&mut self, typical_price: f64) {
-        // Remove oldest if at capacity
-        if self.state.typical_prices.len() >= self.state.config.period {
-            if let Some(oldest) = self.state.typical_prices.pop_front() {
-                self.state.tp_sum -= oldest;
-            }
-        }
+        self.state.rollback = Some(CCIRollback {
+            ema_deviation: self.state.ema_deviation,
+            has_sufficient_data: self.state.has_sufficient_data,
+            last_valid_input: self.state.last_valid_input,
+            last_output: self.state.last_output,
+        });
 
-        // Add new typical price
-        self.state.typical_prices.push_back(typical_price);
-        self.state.tp_sum += typical_price;
+        // Add new typical price, evicting the oldest if at capacity
+        self.state.typical_prices.push(typical_price);
 
         // Check if we have sufficient data
-        self.state.has_sufficient_data =
-            self.state.typical_prices.len() >= self.state.config.period;
+        self.state.has_sufficient_data = self.state.typical_prices.is_full();
     }
 
-    fn calculate_cci_value(&self, current_tp: f64) -> Result<(f64, f64, f64), CCIError> {
+    fn calculate_cci_value(&mut self, current_tp: f64) -> Result<(f64, f64, f64), CCIError> {
         if !self.state.has_sufficient_data {
             return Ok((0.0, current_tp, 0.0));
         }
 
         // Calculate SMA of typical prices
-        let sma_tp = self.state.tp_sum / self.state.config.period as f64;
+        let sma_tp = self.state.typical_prices.sum() / self.state.config.period as f64;
 
         // Calculate mean absolute deviation
-        let mean_deviation = self.calculate_mean_deviation(sma_tp);
+        let mean_deviation = match self.state.config.deviation {
+            CciDeviation::Simple => self.calculate_mean_deviation(sma_tp),
+            CciDeviation::Ema => self.update_ema_deviation(current_tp, sma_tp),
+        };
 
         // Calculate CCI
         if mean_deviation == 0.0 {
@@ -254,6 +396,18 @@ impl CCI {
         sum_deviations / self.state.config.period as f64
     }
 
+    fn update_ema_deviation(&mut self, current_tp: f64, sma_tp: f64) -> f64 {
+        let ema_deviation = match self.state.ema_deviation {
+            Some(previous) => {
+                let alpha = 2.0 / (self.state.config.period as f64 + 1.0);
+                alpha * (current_tp - sma_tp).abs() + (1.0 - alpha) * previous
+            }
+            None => self.calculate_mean_deviation(sma_tp),
+        };
+        self.state.ema_deviation = Some(ema_deviation);
+        ema_deviation
+    }
+
     fn determine_market_condition(&self, cci: f64) -> CCIMarketCondition {
         if !self.state.has_sufficient_data {
             CCIMarketCondition::Insufficient
@@ -295,17 +449,68 @@ pub fn calculate_cci_simple(
         return Ok(Vec::new());
     }
 
+    calculate_cci_iter(
+        highs
+            .iter()
+            .zip(lows)
+            .zip(closes)
+            .map(|((&high, &low), &close)| (high, low, close)),
+        period,
+    )
+}
+
+/// Like [`calculate_cci_simple`], but accepts any iterator of `(high, low, close)` bars
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_cci_iter(
+    bars: impl IntoIterator<Item = (f64, f64, f64)>,
+    period: usize,
+) -> Result<Vec<f64>, CCIError> {
+    let mut cci_calculator = CCI::with_period(period)?;
+
+    bars.into_iter()
+        .map(|(high, low, close)| {
+            let input = CCIInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
+            cci_calculator.calculate(input).map(|output| output.cci)
+        })
+        .collect()
+}
+
+/// Like [`calculate_cci_simple`], but warmup bars are `None` instead of placeholder values
+pub fn calculate_cci_simple_checked(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+) -> Result<Vec<Option<f64>>, CCIError> {
+    let len = highs.len();
+    if len != lows.len() || len != closes.len() {
+        return Err(CCIError::InvalidInput(
+            "All price arrays must have same length".to_string(),
+        ));
+    }
+
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
     let mut cci_calculator = CCI::with_period(period)?;
     let mut results = Vec::with_capacity(len);
 
     for i in 0..len {
         let input = CCIInput {
+            timestamp: None,
             high: highs[i],
             low: lows[i],
             close: closes[i],
         };
-        let output = cci_calculator.calculate(input)?;
-        results.push(output.cci);
+        let output = cci_calculator.calculate_checked(input)?;
+        results.push(output.map(|output| output.cci));
     }
 
     Ok(results)
@@ -1,5 +1,6 @@
+use crate::common::types::{BarMode, Candle, DistanceUnits, GapPolicy, Summation};
+use crate::util::rolling_sum::main::{RollingSum, StoredWindow};
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
 
 /// Configuration for CCI calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -14,6 +15,37 @@ pub struct CCIConfig {
     pub extreme_overbought: f64,
     /// Extreme oversold threshold (default: -200.0)
     pub extreme_oversold: f64,
+    /// How the mean deviation is averaged (default: Simple)
+    #[serde(default)]
+    pub deviation: CciDeviation,
+    /// Units `distance_from_zero` is reported in (default: DistanceUnits::Raw)
+    #[serde(default)]
+    pub distance_units: DistanceUnits,
+    /// Bounds `(lower, upper)` the emitted CCI is clamped to (default: None, unbounded)
+    #[serde(default)]
+    pub clamp: Option<(f64, f64)>,
+    /// Whether inputs sharing the latest bar's timestamp revise it (default: BarMode::Close)
+    #[serde(default)]
+    pub bar_mode: BarMode,
+    /// How the typical price sum is accumulated (default: Summation::Naive)
+    #[serde(default)]
+    pub summation: Summation,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    #[serde(default)]
+    pub gap_policy: GapPolicy,
+    /// Decimal places `cci` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+/// Averaging method for the CCI mean deviation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CciDeviation {
+    /// Mean absolute deviation of the window from its SMA (standard CCI)
+    #[default]
+    Simple,
+    /// Exponential average (α = 2 / (period + 1)) of each bar's absolute deviation,
+    /// seeded with the simple mean deviation of the first full window
+    Ema,
 }
 
 impl Default for CCIConfig {
@@ -24,13 +56,138 @@ impl Default for CCIConfig {
             oversold: -100.0,
             extreme_overbought: 200.0,
             extreme_oversold: -200.0,
+            deviation: CciDeviation::Simple,
+            distance_units: DistanceUnits::Raw,
+            clamp: None,
+            bar_mode: BarMode::Close,
+            summation: Summation::Naive,
+            gap_policy: GapPolicy::Error,
+            precision: None,
         }
     }
 }
 
+impl CCIConfig {
+    /// Start building a configuration from the defaults
+    pub fn builder() -> CCIConfigBuilder {
+        CCIConfigBuilder::default()
+    }
+
+    /// Check the invariants the CCI calculator relies on
+    pub fn validate(&self) -> Result<(), CCIError> {
+        if self.period == 0 {
+            return Err(CCIError::InvalidPeriod);
+        }
+
+        if self.overbought <= self.oversold
+            || self.extreme_overbought <= self.overbought
+            || self.extreme_oversold >= self.oversold
+        {
+            return Err(CCIError::InvalidThresholds);
+        }
+
+        if let Some((lower, upper)) = self.clamp {
+            if !lower.is_finite() || !upper.is_finite() || lower >= upper {
+                return Err(CCIError::InvalidThresholds);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for [`CCIConfig`] with chainable setters and a validating `build()`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CCIConfigBuilder {
+    config: CCIConfig,
+}
+
+impl CCIConfigBuilder {
+    /// Set the period for CCI calculation
+    pub fn period(mut self, period: usize) -> Self {
+        self.config.period = period;
+        self
+    }
+
+    /// Set the overbought threshold
+    pub fn overbought(mut self, overbought: f64) -> Self {
+        self.config.overbought = overbought;
+        self
+    }
+
+    /// Set the oversold threshold
+    pub fn oversold(mut self, oversold: f64) -> Self {
+        self.config.oversold = oversold;
+        self
+    }
+
+    /// Set the extreme overbought threshold
+    pub fn extreme_overbought(mut self, extreme_overbought: f64) -> Self {
+        self.config.extreme_overbought = extreme_overbought;
+        self
+    }
+
+    /// Set the extreme oversold threshold
+    pub fn extreme_oversold(mut self, extreme_oversold: f64) -> Self {
+        self.config.extreme_oversold = extreme_oversold;
+        self
+    }
+
+    /// Set how the mean deviation is averaged
+    pub fn deviation(mut self, deviation: CciDeviation) -> Self {
+        self.config.deviation = deviation;
+        self
+    }
+
+    /// Set the units `distance_from_zero` is reported in
+    pub fn distance_units(mut self, distance_units: DistanceUnits) -> Self {
+        self.config.distance_units = distance_units;
+        self
+    }
+
+    /// Clamp the emitted CCI to `[lower, upper]`
+    pub fn clamp(mut self, lower: f64, upper: f64) -> Self {
+        self.config.clamp = Some((lower, upper));
+        self
+    }
+
+    /// Set whether inputs sharing the latest bar's timestamp revise it
+    pub fn bar_mode(mut self, bar_mode: BarMode) -> Self {
+        self.config.bar_mode = bar_mode;
+        self
+    }
+
+    /// Set how the typical price sum is accumulated
+    pub fn summation(mut self, summation: Summation) -> Self {
+        self.config.summation = summation;
+        self
+    }
+
+    /// Set how non-finite inputs are handled
+    pub fn gap_policy(mut self, gap_policy: GapPolicy) -> Self {
+        self.config.gap_policy = gap_policy;
+        self
+    }
+
+    /// Set the decimal places `cci` is rounded to
+    pub fn precision(mut self, decimals: u8) -> Self {
+        self.config.precision = Some(decimals);
+        self
+    }
+
+    /// Validate and return the configuration
+    pub fn build(self) -> Result<CCIConfig, CCIError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
 /// Input data for CCI calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct CCIInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// High price
     pub high: f64,
     /// Low price
@@ -39,6 +196,18 @@ pub struct CCIInput {
     pub close: f64,
 }
 
+impl From<Candle> for CCIInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+        }
+    }
+}
+
 /// Market condition based on CCI value
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum CCIMarketCondition {
@@ -59,6 +228,9 @@ pub enum CCIMarketCondition {
 /// Output from CCI calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct CCIOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// Commodity Channel Index value
     pub cci: f64,
     /// Current typical price ((H+L+C)/3)
@@ -69,30 +241,94 @@ pub struct CCIOutput {
     pub mean_deviation: f64,
     /// Market condition based on thresholds
     pub market_condition: CCIMarketCondition,
-    /// Distance from zero (absolute CCI value)
+    /// Distance from zero (absolute CCI value); with `DistanceUnits::Percent`, a
+    /// percentage of half the overbought-oversold band (100% = at the threshold)
     pub distance_from_zero: f64,
+    /// Whether `cci` was limited by the configured `clamp` bounds on this bar
+    #[serde(default)]
+    pub clamped: bool,
 }
 
 /// CCI calculation state
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "StoredCCIState")]
 pub struct CCIState {
     /// Configuration
     pub config: CCIConfig,
-    /// History of typical prices
-    pub typical_prices: VecDeque<f64>,
-    /// Sum of typical prices (for SMA calculation)
-    pub tp_sum: f64,
+    /// Typical prices in the window and their running sum (for SMA calculation)
+    pub typical_prices: RollingSum,
+    /// Exponentially averaged deviation (CciDeviation::Ema only)
+    pub ema_deviation: Option<f64>,
     /// Whether we have enough data for calculation
     pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<CCIInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<CCIOutput>,
+    /// State before the latest processed bar (used by `update_or_replace_last`)
+    #[serde(default)]
+    pub rollback: Option<CCIRollback>,
+}
+
+/// Serialized form of [`CCIState`], also accepting states saved while the typical
+/// prices were a plain list next to a separate `tp_sum`
+#[derive(Deserialize)]
+struct StoredCCIState {
+    config: CCIConfig,
+    typical_prices: StoredWindow,
+    ema_deviation: Option<f64>,
+    has_sufficient_data: bool,
+    last_valid_input: Option<CCIInput>,
+    last_output: Option<CCIOutput>,
+    #[serde(default)]
+    rollback: Option<CCIRollback>,
+}
+
+impl From<StoredCCIState> for CCIState {
+    fn from(stored: StoredCCIState) -> Self {
+        // A legacy window cannot undo its latest push, so neither can the state
+        let rollback = match stored.typical_prices {
+            StoredWindow::Current(_) => stored.rollback,
+            StoredWindow::Legacy(_) => None,
+        };
+        let config = stored.config;
+        Self {
+            config,
+            typical_prices: stored
+                .typical_prices
+                .into_rolling_sum(config.period, config.summation),
+            ema_deviation: stored.ema_deviation,
+            has_sufficient_data: stored.has_sufficient_data,
+            last_valid_input: stored.last_valid_input,
+            last_output: stored.last_output,
+            rollback,
+        }
+    }
+}
+
+/// Values the latest processed bar overwrote, restored when that bar is replaced
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CCIRollback {
+    /// Exponentially averaged deviation before the bar
+    pub ema_deviation: Option<f64>,
+    /// Whether the window was full before the bar
+    pub has_sufficient_data: bool,
+    /// Last valid input before the bar
+    pub last_valid_input: Option<CCIInput>,
+    /// Last output before the bar
+    pub last_output: Option<CCIOutput>,
 }
 
 impl CCIState {
     pub fn new(config: CCIConfig) -> Self {
         Self {
             config,
-            typical_prices: VecDeque::with_capacity(config.period),
-            tp_sum: 0.0,
+            typical_prices: RollingSum::new(config.period).with_summation(config.summation),
+            ema_deviation: None,
             has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+            rollback: None,
         }
     }
 }
@@ -108,7 +344,7 @@ pub enum CCIError {
     InvalidPrice,
     /// Invalid period (must be > 0)
     InvalidPeriod,
-    /// Invalid threshold values
+    /// Invalid threshold values (including `clamp` bounds)
     InvalidThresholds,
     /// Division by zero in calculation
     DivisionByZero,
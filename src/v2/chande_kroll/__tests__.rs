@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::types::Candle;
+    use crate::v2::chande_kroll::{
+        main::{calculate_chande_kroll_simple, ChandeKrollStop},
+        types::{ChandeKrollError, ChandeKrollInput},
+    };
+
+    fn uptrend(bars: usize) -> Vec<ChandeKrollInput> {
+        (0..bars)
+            .map(|i| {
+                // Rising closes with a wiggle so the range varies
+                let close = 100.0 + i as f64 + (i % 3) as f64 * 0.3;
+                ChandeKrollInput {
+                    timestamp: Some(i as i64),
+                    high: close + 1.0,
+                    low: close - 1.5,
+                    close,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_long_stop_trails_rising_price() {
+        let inputs = uptrend(40);
+        let mut stop = ChandeKrollStop::with_params(5, 1.5, 4).unwrap();
+        let dense = stop.calculate_batch_dense(&inputs).unwrap();
+
+        // ATR needs 5 bars, the trailing window 4 more
+        assert_eq!(dense.start_index, 7);
+
+        let mut prev_long = f64::NEG_INFINITY;
+        for (output, input) in dense.outputs.iter().zip(&inputs[dense.start_index..]) {
+            assert!(output.long_stop < input.close);
+            assert!(output.long_stop >= prev_long);
+            assert_eq!(output.timestamp, input.timestamp);
+            prev_long = output.long_stop;
+        }
+        assert!(prev_long > dense.outputs[0].long_stop);
+    }
+
+    #[test]
+    fn test_known_values() {
+        // Constant bars: TR = 2, ATR = 2, HH = 11, LL = 9
+        let candles = vec![Candle::new(10.0, 11.0, 9.0, 10.0, 100.0); 4];
+        let mut stop = ChandeKrollStop::with_params(2, 1.0, 2).unwrap();
+
+        let outputs: Vec<_> = candles
+            .into_iter()
+            .map(|candle| stop.calculate_checked(candle.into()).unwrap())
+            .collect();
+        assert!(outputs[..2].iter().all(Option::is_none));
+        let output = outputs[3].unwrap();
+        assert_eq!(
+            (output.long_stop, output.short_stop, output.atr),
+            (9.0, 11.0, 2.0)
+        );
+
+        let simple =
+            calculate_chande_kroll_simple(&[11.0; 3], &[9.0; 3], &[10.0; 3], 2, 1.0, 2).unwrap();
+        assert_eq!(simple, vec![None, None, Some((9.0, 11.0))]);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert_eq!(
+            ChandeKrollStop::with_params(0, 1.0, 9).err(),
+            Some(ChandeKrollError::InvalidPeriod)
+        );
+        assert_eq!(
+            ChandeKrollStop::with_params(10, -1.0, 9).err(),
+            Some(ChandeKrollError::InvalidMultiplier)
+        );
+
+        let mut stop = ChandeKrollStop::new();
+        let bad = ChandeKrollInput {
+            timestamp: None,
+            high: 9.0,
+            low: 10.0,
+            close: 9.5,
+        };
+        assert_eq!(stop.calculate(bad), Err(ChandeKrollError::InvalidHLC));
+        assert_eq!(
+            stop.calculate(ChandeKrollInput {
+                high: f64::NAN,
+                ..bad
+            }),
+            Err(ChandeKrollError::InvalidPrice)
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::v2::chande_kroll::types::{
+    ChandeKrollConfig, ChandeKrollError, ChandeKrollInput, ChandeKrollOutput, ChandeKrollState,
+};
+
+/// Chande Kroll Stop Indicator
+///
+/// Tushar Chande and Stanley Kroll's volatility stop places trailing stops an
+/// ATR multiple away from recent extremes, then trails them over a second window
+/// so they only move in the direction of the trend.
+///
+/// Formula (p = atr_period, q = stop_period, x = atr_multiplier):
+/// 1. Preliminary long stop = Highest High(p) - x × ATR(p)
+/// 2. Preliminary short stop = Lowest Low(p) + x × ATR(p)
+/// 3. Long stop = Highest(preliminary long stop, q)
+/// 4. Short stop = Lowest(preliminary short stop, q)
+///
+/// Interpretation:
+/// - Longs are exited when price closes below the long stop
+/// - Shorts are exited when price closes above the short stop
+/// - While the trend continues the stop trails price and does not loosen
+pub struct ChandeKrollStop {
+    state: ChandeKrollState,
+    /// Configuration error found at construction
+    config_error: Option<ChandeKrollError>,
+}
+
+impl ChandeKrollStop {
+    /// Create a new Chande Kroll Stop calculator with default configuration (10, 1.0, 9)
+    pub fn new() -> Self {
+        Self::with_config(ChandeKrollConfig::default())
+    }
+
+    /// Create a new Chande Kroll Stop calculator with custom periods and multiplier
+    pub fn with_params(
+        atr_period: usize,
+        atr_multiplier: f64,
+        stop_period: usize,
+    ) -> Result<Self, ChandeKrollError> {
+        Self::try_with_config(ChandeKrollConfig {
+            atr_period,
+            atr_multiplier,
+            stop_period,
+            ..ChandeKrollConfig::default()
+        })
+    }
+
+    /// Create a new Chande Kroll Stop calculator with custom configuration
+    pub fn with_config(config: ChandeKrollConfig) -> Self {
+        Self {
+            state: ChandeKrollState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: ChandeKrollConfig) -> Result<Self, ChandeKrollError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Calculate the stop levels for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(
+        &mut self,
+        input: ChandeKrollInput,
+    ) -> Result<ChandeKrollOutput, ChandeKrollError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| ChandeKrollOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(ChandeKrollError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(ChandeKrollError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.long_stop = round_to(output.long_stop, decimals);
+            output.short_stop = round_to(output.short_stop, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(
+        &mut self,
+        input: ChandeKrollInput,
+    ) -> Result<Option<ChandeKrollOutput>, ChandeKrollError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Calculate the stop levels for a batch of inputs
+    pub fn calculate_batch(
+        &mut self,
+        inputs: &[ChandeKrollInput],
+    ) -> Result<Vec<ChandeKrollOutput>, ChandeKrollError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[ChandeKrollInput],
+    ) -> Result<DenseBatch<ChandeKrollOutput>, ChandeKrollError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            ChandeKrollError::InvalidInput,
+        )
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = ChandeKrollState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &ChandeKrollState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: ChandeKrollState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(
+        &mut self,
+        input: ChandeKrollInput,
+    ) -> Result<ChandeKrollOutput, ChandeKrollError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        let tr = self
+            .state
+            .true_range
+            .update(input.high, input.low, input.close);
+        let atr = self.state.atr.add_value(tr);
+        let (_, highest_high) = self.state.high_window.push(input.high);
+        let (lowest_low, _) = self.state.low_window.push(input.low);
+
+        let Some(atr) = atr else {
+            // Default values when insufficient data: no distance to price
+            return Ok(Self::placeholder_output(input.close));
+        };
+
+        let offset = self.state.config.atr_multiplier * atr;
+        let (_, long_stop) = self.state.long_window.push(highest_high - offset);
+        let (short_stop, _) = self.state.short_window.push(lowest_low + offset);
+
+        self.state.has_sufficient_data = self.state.long_window.is_full();
+        if !self.state.has_sufficient_data {
+            return Ok(Self::placeholder_output(input.close));
+        }
+
+        Ok(ChandeKrollOutput {
+            timestamp: None,
+            long_stop,
+            short_stop,
+            atr,
+        })
+    }
+
+    fn placeholder_output(close: f64) -> ChandeKrollOutput {
+        ChandeKrollOutput {
+            timestamp: None,
+            long_stop: close,
+            short_stop: close,
+            atr: 0.0,
+        }
+    }
+
+    fn is_finite_input(input: &ChandeKrollInput) -> bool {
+        input.high.is_finite() && input.low.is_finite() && input.close.is_finite()
+    }
+
+    fn validate_input(&self, input: &ChandeKrollInput) -> Result<(), ChandeKrollError> {
+        if !Self::is_finite_input(input) {
+            return Err(ChandeKrollError::InvalidPrice);
+        }
+
+        // Check HLC relationship
+        if input.high < input.low || input.close > input.high || input.close < input.low {
+            return Err(ChandeKrollError::InvalidHLC);
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), ChandeKrollError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for ChandeKrollStop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to calculate `(long_stop, short_stop)` for HLC data without maintaining state
+///
+/// Warmup bars are `None`.
+pub fn calculate_chande_kroll_simple(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    atr_period: usize,
+    atr_multiplier: f64,
+    stop_period: usize,
+) -> Result<Vec<Option<(f64, f64)>>, ChandeKrollError> {
+    if highs.len() != lows.len() || lows.len() != closes.len() {
+        return Err(ChandeKrollError::InvalidInput(
+            "All price arrays must have the same length".to_string(),
+        ));
+    }
+
+    let mut stop_calculator =
+        ChandeKrollStop::with_params(atr_period, atr_multiplier, stop_period)?;
+
+    (0..highs.len())
+        .map(|i| {
+            let input = ChandeKrollInput {
+                timestamp: None,
+                high: highs[i],
+                low: lows[i],
+                close: closes[i],
+            };
+            stop_calculator
+                .calculate_checked(input)
+                .map(|output| output.map(|output| (output.long_stop, output.short_stop)))
+        })
+        .collect()
+}
@@ -0,0 +1,146 @@
+use crate::common::types::{Candle, GapPolicy};
+use crate::util::rolling_extremes::main::RollingExtremes;
+use crate::util::TrueRange;
+use crate::v1::smma::main::SmoothedMovingAverage;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for Chande Kroll Stop calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChandeKrollConfig {
+    /// Period of the ATR and of the first highest high/lowest low window (default: 10)
+    pub atr_period: usize,
+    /// ATR multiplier subtracted from/added to the extremes (default: 1.0)
+    pub atr_multiplier: f64,
+    /// Window over which the preliminary stops are trailed (default: 9)
+    pub stop_period: usize,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+    /// Decimal places the stop levels are rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+impl Default for ChandeKrollConfig {
+    fn default() -> Self {
+        Self {
+            atr_period: 10,
+            atr_multiplier: 1.0,
+            stop_period: 9,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl ChandeKrollConfig {
+    /// Check the invariants the Chande Kroll Stop calculator relies on
+    pub fn validate(&self) -> Result<(), ChandeKrollError> {
+        if self.atr_period == 0 || self.stop_period == 0 {
+            return Err(ChandeKrollError::InvalidPeriod);
+        }
+
+        if !self.atr_multiplier.is_finite() || self.atr_multiplier < 0.0 {
+            return Err(ChandeKrollError::InvalidMultiplier);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for Chande Kroll Stop calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChandeKrollInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// High price
+    pub high: f64,
+    /// Low price
+    pub low: f64,
+    /// Close price
+    pub close: f64,
+}
+
+impl From<Candle> for ChandeKrollInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+        }
+    }
+}
+
+/// Output from Chande Kroll Stop calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChandeKrollOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Stop level for long positions (trails below price)
+    pub long_stop: f64,
+    /// Stop level for short positions (trails above price)
+    pub short_stop: f64,
+    /// Current Average True Range (Wilder)
+    pub atr: f64,
+}
+
+/// Chande Kroll Stop calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChandeKrollState {
+    /// Configuration
+    pub config: ChandeKrollConfig,
+    /// Highs of the last `atr_period` bars
+    pub high_window: RollingExtremes,
+    /// Lows of the last `atr_period` bars
+    pub low_window: RollingExtremes,
+    /// Preliminary long stops of the last `stop_period` bars
+    pub long_window: RollingExtremes,
+    /// Preliminary short stops of the last `stop_period` bars
+    pub short_window: RollingExtremes,
+    /// True range tracker holding the previous close
+    pub true_range: TrueRange,
+    /// Wilder-smoothed ATR, seeded with the mean of the first `atr_period` true ranges
+    pub atr: SmoothedMovingAverage,
+    /// Whether we have enough data for calculation
+    pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<ChandeKrollInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<ChandeKrollOutput>,
+}
+
+impl ChandeKrollState {
+    pub fn new(config: ChandeKrollConfig) -> Self {
+        Self {
+            config,
+            high_window: RollingExtremes::new(config.atr_period),
+            low_window: RollingExtremes::new(config.atr_period),
+            long_window: RollingExtremes::new(config.stop_period),
+            short_window: RollingExtremes::new(config.stop_period),
+            true_range: TrueRange::new(),
+            // A zero period is reported by `validate` before the ATR is ever fed
+            atr: SmoothedMovingAverage::new(config.atr_period.max(1))
+                .expect("ATR period is at least 1"),
+            has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Error types for Chande Kroll Stop calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChandeKrollError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid HLC relationship (high < low, or close outside the range)
+    InvalidHLC,
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid period (must be > 0)
+    InvalidPeriod,
+    /// Invalid ATR multiplier (must be finite and >= 0)
+    InvalidMultiplier,
+}
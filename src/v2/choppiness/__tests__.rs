@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::assert_dense_batch;
+    use crate::v2::choppiness::{
+        main::{calculate_choppiness_simple, ChoppinessIndex},
+        types::{ChoppinessCondition, ChoppinessError, ChoppinessInput},
+    };
+
+    #[test]
+    fn test_strong_trend_yields_low_value() {
+        let mut chop = ChoppinessIndex::new();
+        let mut last = None;
+
+        for i in 0..30 {
+            let low = 100.0 + i as f64;
+            last = Some(
+                chop.calculate(ChoppinessInput {
+                    timestamp: None,
+                    high: low + 1.0,
+                    low,
+                    close: low + 0.5,
+                })
+                .unwrap(),
+            );
+        }
+
+        let last = last.unwrap();
+        assert!(last.choppiness < 38.2, "choppiness {}", last.choppiness);
+        assert_eq!(last.condition, ChoppinessCondition::Trending);
+    }
+
+    #[test]
+    fn test_flat_range_yields_high_value() {
+        let mut chop = ChoppinessIndex::new();
+        let mut last = None;
+
+        for i in 0..30 {
+            let close = if i % 2 == 0 { 99.5 } else { 100.5 };
+            last = Some(
+                chop.calculate(ChoppinessInput {
+                    timestamp: None,
+                    high: 101.0,
+                    low: 99.0,
+                    close,
+                })
+                .unwrap(),
+            );
+        }
+
+        let last = last.unwrap();
+        assert!(last.choppiness > 61.8, "choppiness {}", last.choppiness);
+        assert!(last.choppiness <= 100.0);
+        assert_eq!(last.condition, ChoppinessCondition::Choppy);
+    }
+
+    #[test]
+    fn test_insufficient_data() {
+        let mut chop = ChoppinessIndex::with_period(5).unwrap();
+
+        for _ in 0..4 {
+            let output = chop
+                .calculate(ChoppinessInput {
+                    timestamp: None,
+                    high: 101.0,
+                    low: 99.0,
+                    close: 100.0,
+                })
+                .unwrap();
+            assert_eq!(output.choppiness, 50.0);
+            assert_eq!(output.condition, ChoppinessCondition::Insufficient);
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(matches!(
+            ChoppinessIndex::with_period(1),
+            Err(ChoppinessError::InvalidPeriod)
+        ));
+
+        let mut chop = ChoppinessIndex::new();
+        let result = chop.calculate(ChoppinessInput {
+            timestamp: None,
+            high: 99.0,
+            low: 101.0,
+            close: 100.0,
+        });
+        assert_eq!(result, Err(ChoppinessError::InvalidHLC));
+
+        let result = calculate_choppiness_simple(&[1.0], &[0.5, 0.4], &[0.7], 14);
+        assert!(matches!(result, Err(ChoppinessError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(ChoppinessIndex::new(), ChoppinessInput);
+    }
+}
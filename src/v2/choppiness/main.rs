@@ -0,0 +1,347 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::util::true_range;
+use crate::v2::choppiness::types::{
+    ChoppinessCondition, ChoppinessConfig, ChoppinessError, ChoppinessInput, ChoppinessOutput,
+    ChoppinessState,
+};
+
+/// Choppiness Index Indicator
+///
+/// The Choppiness Index (E.W. Dreiss) measures whether the market is trending
+/// or moving sideways. It compares the total distance travelled bar by bar
+/// (the sum of true ranges) against the net distance covered by the window.
+///
+/// Formula:
+/// CHOP = 100 × log10(Sum(TR, n) / (Highest High(n) - Lowest Low(n))) / log10(n)
+///
+/// Interpretation:
+/// - High values (above 61.8): choppy, ranging market
+/// - Low values (below 38.2): strong trend in either direction
+/// - The index says nothing about trend direction
+pub struct ChoppinessIndex {
+    state: ChoppinessState,
+    /// Configuration error found at construction
+    config_error: Option<ChoppinessError>,
+}
+
+impl ChoppinessIndex {
+    /// Create a new Choppiness Index calculator with default configuration (period=14)
+    pub fn new() -> Self {
+        Self::with_config(ChoppinessConfig::default())
+    }
+
+    /// Create a new Choppiness Index calculator with custom period
+    pub fn with_period(period: usize) -> Result<Self, ChoppinessError> {
+        if period < 2 {
+            return Err(ChoppinessError::InvalidPeriod);
+        }
+
+        Ok(Self::with_config(ChoppinessConfig {
+            period,
+            ..ChoppinessConfig::default()
+        }))
+    }
+
+    /// Create a new Choppiness Index calculator with custom configuration
+    pub fn with_config(config: ChoppinessConfig) -> Self {
+        Self {
+            state: ChoppinessState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: ChoppinessConfig) -> Result<Self, ChoppinessError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Calculate the Choppiness Index for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(
+        &mut self,
+        input: ChoppinessInput,
+    ) -> Result<ChoppinessOutput, ChoppinessError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| ChoppinessOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(ChoppinessError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(ChoppinessError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.choppiness = round_to(output.choppiness, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(
+        &mut self,
+        input: ChoppinessInput,
+    ) -> Result<Option<ChoppinessOutput>, ChoppinessError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Calculate the Choppiness Index for a batch of inputs
+    pub fn calculate_batch(
+        &mut self,
+        inputs: &[ChoppinessInput],
+    ) -> Result<Vec<ChoppinessOutput>, ChoppinessError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[ChoppinessInput],
+    ) -> Result<DenseBatch<ChoppinessOutput>, ChoppinessError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            ChoppinessError::InvalidInput,
+        )
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = ChoppinessState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &ChoppinessState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: ChoppinessState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(
+        &mut self,
+        input: ChoppinessInput,
+    ) -> Result<ChoppinessOutput, ChoppinessError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        let true_range = self.calculate_true_range(&input);
+        self.update_history(input.high, input.low, true_range);
+        self.state.previous_close = Some(input.close);
+
+        let highest_high = self
+            .state
+            .highs
+            .iter()
+            .fold(f64::NEG_INFINITY, |acc, &x| acc.max(x));
+        let lowest_low = self
+            .state
+            .lows
+            .iter()
+            .fold(f64::INFINITY, |acc, &x| acc.min(x));
+
+        let (choppiness, condition) = if self.state.has_sufficient_data {
+            let choppiness = self.calculate_choppiness(highest_high - lowest_low);
+            (choppiness, self.determine_condition(choppiness))
+        } else {
+            (50.0, ChoppinessCondition::Insufficient) // Default neutral value
+        };
+
+        Ok(ChoppinessOutput {
+            timestamp: None,
+            choppiness,
+            true_range,
+            highest_high,
+            lowest_low,
+            condition,
+        })
+    }
+
+    fn is_finite_input(input: &ChoppinessInput) -> bool {
+        input.high.is_finite() && input.low.is_finite() && input.close.is_finite()
+    }
+
+    fn validate_input(&self, input: &ChoppinessInput) -> Result<(), ChoppinessError> {
+        if !input.high.is_finite() || !input.low.is_finite() || !input.close.is_finite() {
+            return Err(ChoppinessError::InvalidPrice);
+        }
+
+        if input.high < input.low || input.close < input.low || input.close > input.high {
+            return Err(ChoppinessError::InvalidHLC);
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), ChoppinessError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn calculate_true_range(&self, input: &ChoppinessInput) -> f64 {
+        true_range(input.high, input.low, self.state.previous_close)
+    }
+
+    fn update_history(&mut self, high: f64, low: f64, true_range: f64) {
+        // Remove oldest values if at capacity
+        if self.state.highs.len() >= self.state.config.period {
+            self.state.highs.pop_front();
+            self.state.lows.pop_front();
+            if let Some(oldest) = self.state.true_ranges.pop_front() {
+                self.state.true_range_sum -= oldest;
+            }
+        }
+
+        self.state.highs.push_back(high);
+        self.state.lows.push_back(low);
+        self.state.true_ranges.push_back(true_range);
+        self.state.true_range_sum += true_range;
+
+        // Check if we have sufficient data
+        self.state.has_sufficient_data = self.state.highs.len() >= self.state.config.period;
+    }
+
+    fn calculate_choppiness(&self, range: f64) -> f64 {
+        if range <= 0.0 {
+            return 100.0; // No net movement at all - maximally choppy
+        }
+
+        let period = self.state.config.period as f64;
+        let choppiness = 100.0 * (self.state.true_range_sum / range).log10() / period.log10();
+
+        choppiness.clamp(0.0, 100.0)
+    }
+
+    fn determine_condition(&self, choppiness: f64) -> ChoppinessCondition {
+        if choppiness > self.state.config.choppy_threshold {
+            ChoppinessCondition::Choppy
+        } else if choppiness < self.state.config.trending_threshold {
+            ChoppinessCondition::Trending
+        } else {
+            ChoppinessCondition::Neutral
+        }
+    }
+}
+
+impl Default for ChoppinessIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to calculate the Choppiness Index for HLC data without maintaining state
+pub fn calculate_choppiness_simple(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+) -> Result<Vec<f64>, ChoppinessError> {
+    if highs.len() != lows.len() || highs.len() != closes.len() {
+        return Err(ChoppinessError::InvalidInput(
+            "All price arrays must have same length".to_string(),
+        ));
+    }
+
+    if highs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    calculate_choppiness_iter(
+        highs
+            .iter()
+            .zip(lows)
+            .zip(closes)
+            .map(|((&high, &low), &close)| (high, low, close)),
+        period,
+    )
+}
+
+/// Like [`calculate_choppiness_simple`], but accepts any iterator of `(high, low, close)` bars
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_choppiness_iter(
+    bars: impl IntoIterator<Item = (f64, f64, f64)>,
+    period: usize,
+) -> Result<Vec<f64>, ChoppinessError> {
+    let mut choppiness_calculator = ChoppinessIndex::with_period(period)?;
+
+    bars.into_iter()
+        .map(|(high, low, close)| {
+            let input = ChoppinessInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
+            choppiness_calculator
+                .calculate(input)
+                .map(|output| output.choppiness)
+        })
+        .collect()
+}
+
+/// Like [`calculate_choppiness_simple`], but warmup bars are `None` instead of placeholder values
+pub fn calculate_choppiness_simple_checked(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+) -> Result<Vec<Option<f64>>, ChoppinessError> {
+    if highs.len() != lows.len() || highs.len() != closes.len() {
+        return Err(ChoppinessError::InvalidInput(
+            "All price arrays must have same length".to_string(),
+        ));
+    }
+
+    if highs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut choppiness_calculator = ChoppinessIndex::with_period(period)?;
+    let mut results = Vec::with_capacity(highs.len());
+
+    for i in 0..highs.len() {
+        let input = ChoppinessInput {
+            timestamp: None,
+            high: highs[i],
+            low: lows[i],
+            close: closes[i],
+        };
+        let output = choppiness_calculator.calculate_checked(input)?;
+        results.push(output.map(|output| output.choppiness));
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,156 @@
+use crate::common::types::{Candle, GapPolicy};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Configuration for Choppiness Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChoppinessConfig {
+    /// Lookback period for the true range sum and high/low extremes (default: 14)
+    pub period: usize,
+    /// Level above which the market is considered choppy (default: 61.8)
+    pub choppy_threshold: f64,
+    /// Level below which the market is considered trending (default: 38.2)
+    pub trending_threshold: f64,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+    /// Decimal places `choppiness` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+impl Default for ChoppinessConfig {
+    fn default() -> Self {
+        Self {
+            period: 14,
+            choppy_threshold: 61.8,
+            trending_threshold: 38.2,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl ChoppinessConfig {
+    /// Check the invariants the Choppiness Index calculator relies on
+    pub fn validate(&self) -> Result<(), ChoppinessError> {
+        if self.period < 2 {
+            return Err(ChoppinessError::InvalidPeriod);
+        }
+
+        if self.trending_threshold >= self.choppy_threshold {
+            return Err(ChoppinessError::InvalidThresholds);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for Choppiness Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChoppinessInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// High price
+    pub high: f64,
+    /// Low price
+    pub low: f64,
+    /// Close price
+    pub close: f64,
+}
+
+impl From<Candle> for ChoppinessInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+        }
+    }
+}
+
+/// Output from Choppiness Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChoppinessOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Choppiness Index value (0 to 100)
+    pub choppiness: f64,
+    /// True range of the current bar
+    pub true_range: f64,
+    /// Highest high in the lookback period
+    pub highest_high: f64,
+    /// Lowest low in the lookback period
+    pub lowest_low: f64,
+    /// Market condition
+    pub condition: ChoppinessCondition,
+}
+
+/// Market conditions based on Choppiness Index
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ChoppinessCondition {
+    /// Choppiness below the trending threshold
+    Trending,
+    /// Choppiness between the thresholds
+    Neutral,
+    /// Choppiness above the choppy threshold (ranging market)
+    Choppy,
+    /// Insufficient data for calculation
+    Insufficient,
+}
+
+/// Choppiness Index calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChoppinessState {
+    /// Configuration
+    pub config: ChoppinessConfig,
+    /// History of high prices for period lookback
+    pub highs: VecDeque<f64>,
+    /// History of low prices for period lookback
+    pub lows: VecDeque<f64>,
+    /// History of true ranges for the rolling sum
+    pub true_ranges: VecDeque<f64>,
+    /// Running sum of the true ranges in the window
+    pub true_range_sum: f64,
+    /// Previous close price
+    pub previous_close: Option<f64>,
+    /// Whether we have enough data for calculation
+    pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<ChoppinessInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<ChoppinessOutput>,
+}
+
+impl ChoppinessState {
+    pub fn new(config: ChoppinessConfig) -> Self {
+        Self {
+            config,
+            highs: VecDeque::with_capacity(config.period),
+            lows: VecDeque::with_capacity(config.period),
+            true_ranges: VecDeque::with_capacity(config.period),
+            true_range_sum: 0.0,
+            previous_close: None,
+            has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Error types for Choppiness Index calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChoppinessError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid HLC relationship (e.g., high < low)
+    InvalidHLC,
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid period (must be > 1)
+    InvalidPeriod,
+    /// Invalid thresholds (trending must be below choppy)
+    InvalidThresholds,
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use crate::v2::drawdown::main::{max_drawdown_pct, Drawdown};
+
+    // Rises to 125, falls 20% to 100, recovers to a new high, then dips 10%
+    const EQUITY: [f64; 9] = [
+        100.0, 110.0, 125.0, 115.0, 100.0, 120.0, 130.0, 140.0, 126.0,
+    ];
+
+    #[test]
+    fn test_known_twenty_percent_drawdown() {
+        let mut drawdown = Drawdown::new();
+        let outputs: Vec<_> = EQUITY
+            .iter()
+            .map(|&e| drawdown.update(e).unwrap())
+            .collect();
+
+        let trough = outputs[4];
+        assert_eq!(trough.peak, 125.0);
+        assert!((trough.current_drawdown - 25.0).abs() < 1e-9);
+        assert!((trough.current_drawdown_pct - 20.0).abs() < 1e-9);
+
+        let last = outputs[8];
+        assert_eq!(last.peak, 140.0);
+        assert!((last.current_drawdown - 14.0).abs() < 1e-9);
+        assert!((last.current_drawdown_pct - 10.0).abs() < 1e-9);
+        assert!((last.max_drawdown - 25.0).abs() < 1e-9);
+        assert!((last.max_drawdown_pct - 20.0).abs() < 1e-9);
+
+        assert!((max_drawdown_pct(&EQUITY) - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_new_highs_have_no_drawdown() {
+        let mut drawdown = Drawdown::new();
+        for equity in [100.0, 101.0, 105.0] {
+            let output = drawdown.update(equity).unwrap();
+            assert_eq!(output.peak, equity);
+            assert_eq!(output.current_drawdown, 0.0);
+            assert_eq!(output.max_drawdown_pct, 0.0);
+        }
+        assert_eq!(max_drawdown_pct(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_reset_starts_new_period() {
+        let mut drawdown = Drawdown::new();
+        for &equity in &EQUITY[..5] {
+            drawdown.update(equity);
+        }
+        assert!(drawdown.update(f64::NAN).is_none());
+        assert!((drawdown.current().unwrap().max_drawdown_pct - 20.0).abs() < 1e-9);
+
+        drawdown.reset();
+        assert!(drawdown.current().is_none());
+
+        let output = drawdown.update(100.0).unwrap();
+        assert_eq!(output.peak, 100.0);
+        assert_eq!(output.max_drawdown, 0.0);
+        let output = drawdown.update(95.0).unwrap();
+        assert!((output.max_drawdown_pct - 5.0).abs() < 1e-9);
+    }
+}
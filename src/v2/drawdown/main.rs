@@ -0,0 +1,97 @@
+use crate::v2::drawdown::types::{DrawdownOutput, DrawdownState};
+
+/// Running drawdown tracker for equity curves
+///
+/// Keeps the running peak of the equity values it is fed and reports how far
+/// the latest value sits below it, along with the worst drawdown seen so far.
+///
+/// - current drawdown = peak - equity
+/// - current drawdown % = (peak - equity) / peak × 100
+///
+/// The absolute and percentage maxima are tracked independently, so they may
+/// come from different points on the curve. Percentages are reported as 0
+/// while the peak is not positive, since they are undefined there.
+///
+/// Call `reset()` at period boundaries (e.g. each month) for per-period figures.
+pub struct Drawdown {
+    state: DrawdownState,
+}
+
+impl Drawdown {
+    /// Create a new drawdown tracker
+    pub fn new() -> Self {
+        Self {
+            state: DrawdownState::new(),
+        }
+    }
+
+    /// Feed the next equity value
+    ///
+    /// Returns `None` (leaving the state untouched) for non-finite values.
+    pub fn update(&mut self, equity: f64) -> Option<DrawdownOutput> {
+        if !equity.is_finite() {
+            return None;
+        }
+
+        let peak = self.state.peak.map_or(equity, |peak| peak.max(equity));
+        let current_drawdown = peak - equity;
+        let current_drawdown_pct = if peak > f64::EPSILON {
+            current_drawdown / peak * 100.0
+        } else {
+            0.0
+        };
+
+        self.state.peak = Some(peak);
+        self.state.max_drawdown = self.state.max_drawdown.max(current_drawdown);
+        self.state.max_drawdown_pct = self.state.max_drawdown_pct.max(current_drawdown_pct);
+
+        let output = DrawdownOutput {
+            peak,
+            current_drawdown,
+            current_drawdown_pct,
+            max_drawdown: self.state.max_drawdown,
+            max_drawdown_pct: self.state.max_drawdown_pct,
+        };
+        self.state.last_output = Some(output);
+
+        Some(output)
+    }
+
+    /// Last reported output, if any value has been fed since the last reset
+    pub fn current(&self) -> Option<DrawdownOutput> {
+        self.state.last_output
+    }
+
+    /// Forget the peak and maxima, starting a new tracking period
+    pub fn reset(&mut self) {
+        self.state = DrawdownState::new();
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &DrawdownState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: DrawdownState) {
+        self.state = state;
+    }
+}
+
+impl Default for Drawdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Maximum percentage drawdown of an equity curve
+///
+/// Returns 0.0 for an empty or never-declining curve.
+pub fn max_drawdown_pct(equity: &[f64]) -> f64 {
+    let mut drawdown = Drawdown::new();
+    equity
+        .iter()
+        .filter_map(|&value| drawdown.update(value))
+        .last()
+        .map_or(0.0, |output| output.max_drawdown_pct)
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// Output from a drawdown update
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DrawdownOutput {
+    /// Highest equity seen since the last reset
+    pub peak: f64,
+    /// Distance of the current equity below the peak (>= 0)
+    pub current_drawdown: f64,
+    /// Current drawdown as a percentage of the peak (0-100)
+    pub current_drawdown_pct: f64,
+    /// Largest absolute drawdown since the last reset
+    pub max_drawdown: f64,
+    /// Largest percentage drawdown since the last reset
+    pub max_drawdown_pct: f64,
+}
+
+/// Drawdown tracking state
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct DrawdownState {
+    /// Running peak (None until the first value)
+    pub peak: Option<f64>,
+    /// Largest absolute drawdown so far
+    pub max_drawdown: f64,
+    /// Largest percentage drawdown so far
+    pub max_drawdown_pct: f64,
+    /// Last output
+    pub last_output: Option<DrawdownOutput>,
+}
+
+impl DrawdownState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
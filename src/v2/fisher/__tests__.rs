@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::assert_dense_batch;
+    use crate::v2::fisher::{
+        main::{calculate_fisher_simple, FisherTransform},
+        types::{FisherError, FisherInput},
+    };
+
+    #[test]
+    fn test_fisher_finite_at_window_extreme() {
+        let mut fisher = FisherTransform::with_period(5).unwrap();
+
+        // Every bar sets a new high, so the median keeps pinning the top of the window
+        for i in 0..200 {
+            let base = 100.0 + i as f64 * 5.0;
+            let input = FisherInput {
+                timestamp: None,
+                high: base + 1.0,
+                low: base - 1.0,
+            };
+            let result = fisher.calculate(input).unwrap();
+
+            assert!(result.fisher.is_finite(), "Fisher not finite at {}", i);
+            assert!(result.trigger.is_finite());
+            assert!(result.normalized.abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn test_fisher_trigger_lags_by_one_bar() {
+        let mut fisher = FisherTransform::with_period(3).unwrap();
+
+        let data = [
+            (10.0, 9.0),
+            (11.0, 10.0),
+            (12.0, 11.0),
+            (11.5, 10.5),
+            (10.0, 9.0),
+            (9.5, 8.5),
+        ];
+
+        let mut previous_fisher = 0.0;
+        for (high, low) in data {
+            let result = fisher
+                .calculate(FisherInput {
+                    timestamp: None,
+                    high,
+                    low,
+                })
+                .unwrap();
+            assert_eq!(result.trigger, previous_fisher);
+            previous_fisher = result.fisher;
+        }
+    }
+
+    #[test]
+    fn test_fisher_direction() {
+        let mut fisher = FisherTransform::with_period(5).unwrap();
+
+        // Rising prices push the Fisher value positive
+        let mut last = None;
+        for i in 0..10 {
+            let base = 100.0 + i as f64;
+            last = Some(
+                fisher
+                    .calculate(FisherInput {
+                        timestamp: None,
+                        high: base + 0.5,
+                        low: base - 0.5,
+                    })
+                    .unwrap(),
+            );
+        }
+        assert!(last.unwrap().fisher > 0.0);
+
+        // Falling prices eventually push it negative
+        for i in 0..10 {
+            let base = 110.0 - i as f64 * 2.0;
+            last = Some(
+                fisher
+                    .calculate(FisherInput {
+                        timestamp: None,
+                        high: base + 0.5,
+                        low: base - 0.5,
+                    })
+                    .unwrap(),
+            );
+        }
+        assert!(last.unwrap().fisher < 0.0);
+    }
+
+    #[test]
+    fn test_fisher_flat_prices() {
+        let mut fisher = FisherTransform::with_period(3).unwrap();
+
+        for _ in 0..10 {
+            let result = fisher
+                .calculate(FisherInput {
+                    timestamp: None,
+                    high: 100.0,
+                    low: 100.0,
+                })
+                .unwrap();
+            assert_eq!(result.fisher, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_fisher_error_handling() {
+        assert!(matches!(
+            FisherTransform::with_period(0),
+            Err(FisherError::InvalidPeriod)
+        ));
+
+        let mut fisher = FisherTransform::new();
+        assert!(matches!(
+            fisher.calculate(FisherInput {
+                timestamp: None,
+                high: 9.0,
+                low: 10.0
+            }),
+            Err(FisherError::InvalidHL)
+        ));
+        assert!(matches!(
+            fisher.calculate(FisherInput {
+                timestamp: None,
+                high: f64::NAN,
+                low: 10.0
+            }),
+            Err(FisherError::InvalidPrice)
+        ));
+    }
+
+    #[test]
+    fn test_fisher_simple_function() {
+        let highs = vec![10.0, 11.0, 12.0, 11.5, 10.0];
+        let lows = vec![9.0, 10.0, 11.0, 10.5, 9.0];
+
+        let result = calculate_fisher_simple(&highs, &lows, 3).unwrap();
+        assert_eq!(result.len(), 5);
+        assert!(result.iter().all(|value| value.is_finite()));
+
+        assert!(matches!(
+            calculate_fisher_simple(&highs, &lows[..4], 3),
+            Err(FisherError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(FisherTransform::new(), FisherInput);
+    }
+}
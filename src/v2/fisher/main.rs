@@ -0,0 +1,330 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::transform::price_source::main::median_price;
+use crate::v2::fisher::types::{FisherConfig, FisherError, FisherInput, FisherOutput, FisherState};
+
+/// Fisher Transform Indicator
+///
+/// The Fisher Transform (John Ehlers) converts prices into a Gaussian-like
+/// distribution so turning points stand out as sharp, clearly defined peaks.
+///
+/// Formula:
+/// 1. Median Price = (High + Low) / 2
+/// 2. x = 0.66 × ((Median - Lowest Low) / (Highest High - Lowest Low) - 0.5) + 0.67 × x[prev]
+/// 3. x is clamped to ±0.999 so the logarithm never reaches infinity
+/// 4. Fisher = 0.5 × ln((1 + x) / (1 - x)) + 0.5 × Fisher[prev]
+/// 5. Trigger = Fisher[prev]
+///
+/// Interpretation:
+/// - Fisher crossing above its trigger: potential bullish turn
+/// - Fisher crossing below its trigger: potential bearish turn
+/// - Extreme readings signal stretched prices likely to revert
+pub struct FisherTransform {
+    state: FisherState,
+    /// Configuration error found at construction
+    config_error: Option<FisherError>,
+}
+
+impl FisherTransform {
+    /// Clamp bound keeping the normalized value away from ±1
+    const CLAMP: f64 = 0.999;
+
+    /// Create a new Fisher Transform calculator with default configuration (period=10)
+    pub fn new() -> Self {
+        Self::with_config(FisherConfig::default())
+    }
+
+    /// Create a new Fisher Transform calculator with custom period
+    pub fn with_period(period: usize) -> Result<Self, FisherError> {
+        if period == 0 {
+            return Err(FisherError::InvalidPeriod);
+        }
+
+        Ok(Self::with_config(FisherConfig {
+            period,
+            ..Default::default()
+        }))
+    }
+
+    /// Create a new Fisher Transform calculator with custom configuration
+    pub fn with_config(config: FisherConfig) -> Self {
+        Self {
+            state: FisherState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: FisherConfig) -> Result<Self, FisherError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Calculate the Fisher Transform for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(&mut self, input: FisherInput) -> Result<FisherOutput, FisherError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| FisherOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(FisherError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(FisherError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.fisher = round_to(output.fisher, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(
+        &mut self,
+        input: FisherInput,
+    ) -> Result<Option<FisherOutput>, FisherError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Calculate the Fisher Transform for a batch of inputs
+    pub fn calculate_batch(
+        &mut self,
+        inputs: &[FisherInput],
+    ) -> Result<Vec<FisherOutput>, FisherError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[FisherInput],
+    ) -> Result<DenseBatch<FisherOutput>, FisherError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            FisherError::InvalidInput,
+        )
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = FisherState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &FisherState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: FisherState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(&mut self, input: FisherInput) -> Result<FisherOutput, FisherError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        // Update price history
+        self.update_price_history(input.high, input.low);
+
+        let trigger = self.state.previous_fisher;
+
+        // Calculate the transform if we have enough data
+        let (fisher, normalized) = if self.state.has_sufficient_data {
+            self.calculate_fisher_value(median_price(input.high, input.low))
+        } else {
+            (0.0, 0.0) // Default neutral values when insufficient data
+        };
+
+        if self.state.has_sufficient_data {
+            self.state.previous_normalized = normalized;
+            self.state.previous_fisher = fisher;
+        }
+
+        Ok(FisherOutput {
+            timestamp: None,
+            fisher,
+            trigger,
+            normalized,
+            highest_high: self.state.highest_high,
+            lowest_low: self.state.lowest_low,
+        })
+    }
+
+    fn is_finite_input(input: &FisherInput) -> bool {
+        input.high.is_finite() && input.low.is_finite()
+    }
+
+    fn validate_input(&self, input: &FisherInput) -> Result<(), FisherError> {
+        if !input.high.is_finite() || !input.low.is_finite() {
+            return Err(FisherError::InvalidPrice);
+        }
+
+        if input.high < input.low {
+            return Err(FisherError::InvalidHL);
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), FisherError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn update_price_history(&mut self, high: f64, low: f64) {
+        // Remove oldest prices if at capacity
+        if self.state.highs.len() >= self.state.config.period {
+            self.state.highs.pop_front();
+            self.state.lows.pop_front();
+        }
+
+        // Add new prices
+        self.state.highs.push_back(high);
+        self.state.lows.push_back(low);
+
+        // Find highest high and lowest low in the current period
+        self.state.highest_high = self
+            .state
+            .highs
+            .iter()
+            .fold(f64::NEG_INFINITY, |acc, &x| acc.max(x));
+        self.state.lowest_low = self
+            .state
+            .lows
+            .iter()
+            .fold(f64::INFINITY, |acc, &x| acc.min(x));
+
+        // Check if we have sufficient data
+        self.state.has_sufficient_data = self.state.highs.len() >= self.state.config.period;
+    }
+
+    fn calculate_fisher_value(&self, median_price: f64) -> (f64, f64) {
+        let price_range = self.state.highest_high - self.state.lowest_low;
+
+        // Position of the median price within the window, mapped to [-0.5, 0.5]
+        let position = if price_range == 0.0 {
+            0.0 // All prices are the same - use the middle
+        } else {
+            (median_price - self.state.lowest_low) / price_range - 0.5
+        };
+
+        let normalized = (0.66 * position + 0.67 * self.state.previous_normalized)
+            .clamp(-Self::CLAMP, Self::CLAMP);
+
+        let fisher =
+            0.5 * ((1.0 + normalized) / (1.0 - normalized)).ln() + 0.5 * self.state.previous_fisher;
+
+        (fisher, normalized)
+    }
+}
+
+impl Default for FisherTransform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to calculate the Fisher Transform for HL data without maintaining state
+pub fn calculate_fisher_simple(
+    highs: &[f64],
+    lows: &[f64],
+    period: usize,
+) -> Result<Vec<f64>, FisherError> {
+    if highs.len() != lows.len() {
+        return Err(FisherError::InvalidInput(
+            "Highs and lows must have same length".to_string(),
+        ));
+    }
+
+    if highs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    calculate_fisher_iter(highs.iter().copied().zip(lows.iter().copied()), period)
+}
+
+/// Like [`calculate_fisher_simple`], but accepts any iterator of `(high, low)` bars
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_fisher_iter(
+    bars: impl IntoIterator<Item = (f64, f64)>,
+    period: usize,
+) -> Result<Vec<f64>, FisherError> {
+    let mut fisher_calculator = FisherTransform::with_period(period)?;
+
+    bars.into_iter()
+        .map(|(high, low)| {
+            let input = FisherInput {
+                timestamp: None,
+                high,
+                low,
+            };
+            fisher_calculator
+                .calculate(input)
+                .map(|output| output.fisher)
+        })
+        .collect()
+}
+
+/// Like [`calculate_fisher_simple`], but warmup bars are `None` instead of placeholder values
+pub fn calculate_fisher_simple_checked(
+    highs: &[f64],
+    lows: &[f64],
+    period: usize,
+) -> Result<Vec<Option<f64>>, FisherError> {
+    if highs.len() != lows.len() {
+        return Err(FisherError::InvalidInput(
+            "Highs and lows must have same length".to_string(),
+        ));
+    }
+
+    if highs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut fisher_calculator = FisherTransform::with_period(period)?;
+    let mut results = Vec::with_capacity(highs.len());
+
+    for i in 0..highs.len() {
+        let input = FisherInput {
+            timestamp: None,
+            high: highs[i],
+            low: lows[i],
+        };
+        let output = fisher_calculator.calculate_checked(input)?;
+        results.push(output.map(|output| output.fisher));
+    }
+
+    Ok(results)
+}
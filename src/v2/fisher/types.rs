@@ -0,0 +1,131 @@
+use crate::common::types::{Candle, GapPolicy};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Configuration for Fisher Transform calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FisherConfig {
+    /// Lookback period for highest high and lowest low (default: 10)
+    pub period: usize,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+    /// Decimal places `fisher` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+impl Default for FisherConfig {
+    fn default() -> Self {
+        Self {
+            period: 10,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl FisherConfig {
+    /// Check the invariants the Fisher Transform calculator relies on
+    pub fn validate(&self) -> Result<(), FisherError> {
+        if self.period == 0 {
+            return Err(FisherError::InvalidPeriod);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for Fisher Transform calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FisherInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// High price
+    pub high: f64,
+    /// Low price
+    pub low: f64,
+}
+
+impl From<Candle> for FisherInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            high: candle.high,
+            low: candle.low,
+        }
+    }
+}
+
+/// Output from Fisher Transform calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FisherOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Fisher Transform value
+    pub fisher: f64,
+    /// Trigger line (previous bar's Fisher value)
+    pub trigger: f64,
+    /// Smoothed price position within the window, clamped to (-1, 1)
+    pub normalized: f64,
+    /// Highest high in the lookback period
+    pub highest_high: f64,
+    /// Lowest low in the lookback period
+    pub lowest_low: f64,
+}
+
+/// Fisher Transform calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FisherState {
+    /// Configuration
+    pub config: FisherConfig,
+    /// History of high prices for period lookback
+    pub highs: VecDeque<f64>,
+    /// History of low prices for period lookback
+    pub lows: VecDeque<f64>,
+    /// Current highest high in the period
+    pub highest_high: f64,
+    /// Current lowest low in the period
+    pub lowest_low: f64,
+    /// Previous smoothed normalized value
+    pub previous_normalized: f64,
+    /// Previous Fisher value (the trigger for the next bar)
+    pub previous_fisher: f64,
+    /// Whether we have enough data for calculation
+    pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<FisherInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<FisherOutput>,
+}
+
+impl FisherState {
+    pub fn new(config: FisherConfig) -> Self {
+        Self {
+            config,
+            highs: VecDeque::with_capacity(config.period),
+            lows: VecDeque::with_capacity(config.period),
+            highest_high: f64::NEG_INFINITY,
+            lowest_low: f64::INFINITY,
+            previous_normalized: 0.0,
+            previous_fisher: 0.0,
+            has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Error types for Fisher Transform calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FisherError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid HL relationship (high < low)
+    InvalidHL,
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid period (must be > 0)
+    InvalidPeriod,
+}
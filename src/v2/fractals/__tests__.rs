@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::types::GapPolicy;
+    use crate::v2::fractals::{
+        main::{calculate_fractals_simple, Fractals},
+        types::{FractalType, FractalsConfig, FractalsError, FractalsInput},
+    };
+
+    fn bar(high: f64, low: f64) -> FractalsInput {
+        FractalsInput {
+            timestamp: None,
+            high,
+            low,
+            close: (high + low) / 2.0,
+        }
+    }
+
+    #[test]
+    fn test_up_fractal_in_constructed_peak() {
+        let mut fractals = Fractals::new();
+        let highs = [10.0, 11.0, 13.0, 12.0, 11.5, 11.0];
+        let outputs: Vec<_> = highs
+            .iter()
+            .map(|&high| fractals.calculate(bar(high, high - 1.0)).unwrap())
+            .collect();
+
+        // The peak at bar 2 is confirmed two bars later, on bar 4
+        assert!(outputs[..4].iter().all(|o| o.fractal == FractalType::None));
+        assert_eq!(outputs[4].fractal, FractalType::Up);
+        assert_eq!(outputs[4].high, Some(13.0));
+        assert_eq!(outputs[4].low, None);
+        assert_eq!(outputs[5].fractal, FractalType::None);
+    }
+
+    #[test]
+    fn test_down_and_both_fractals() {
+        let mut fractals = Fractals::new();
+        let lows = [5.0, 4.0, 3.0, 4.5, 4.8];
+        let last = lows
+            .iter()
+            .map(|&low| fractals.calculate(bar(low + 1.0, low)).unwrap())
+            .last()
+            .unwrap();
+        assert_eq!(last.fractal, FractalType::Down);
+        assert_eq!(last.low, Some(3.0));
+
+        // An outside center bar is both
+        let mut fractals = Fractals::with_wing(1).unwrap();
+        fractals.calculate(bar(10.0, 9.0)).unwrap();
+        fractals.calculate(bar(11.0, 8.0)).unwrap();
+        let output = fractals.calculate(bar(10.5, 8.5)).unwrap();
+        assert_eq!(output.fractal, FractalType::Both);
+        assert_eq!((output.high, output.low), (Some(11.0), Some(8.0)));
+    }
+
+    #[test]
+    fn test_equal_highs_are_not_a_fractal() {
+        let highs = [10.0, 11.0, 12.0, 12.0, 11.0, 10.0];
+        let lows: Vec<f64> = highs.iter().map(|high| high - 0.5).collect();
+        let fractals = calculate_fractals_simple(&highs, &lows, &lows, 2).unwrap();
+
+        assert_eq!(fractals[..4], [None; 4]);
+        assert!(fractals[4..].iter().all(|f| *f == Some(FractalType::None)));
+    }
+
+    #[test]
+    fn test_checked_and_dense_warmup() {
+        let mut fractals = Fractals::with_wing(3).unwrap();
+        let inputs: Vec<_> = (0..10).map(|i| bar(10.0 + i as f64, 9.0)).collect();
+        for (i, input) in inputs.iter().enumerate() {
+            assert_eq!(
+                fractals.calculate_checked(*input).unwrap().is_some(),
+                i >= 6
+            );
+        }
+
+        fractals.reset();
+        let dense = fractals.calculate_batch_dense(&inputs).unwrap();
+        assert_eq!(dense.start_index, 6);
+        assert_eq!(dense.outputs.len(), 4);
+    }
+
+    #[test]
+    fn test_errors_and_gaps() {
+        assert_eq!(
+            Fractals::with_wing(0).err(),
+            Some(FractalsError::InvalidPeriod)
+        );
+        assert_eq!(
+            calculate_fractals_simple(&[1.0], &[], &[], 2),
+            Err(FractalsError::InvalidInput(
+                "All price arrays must have the same length".to_string()
+            ))
+        );
+
+        let mut fractals = Fractals::new();
+        assert_eq!(
+            fractals.calculate(bar(9.0, 10.0)),
+            Err(FractalsError::InvalidHLC)
+        );
+        assert_eq!(
+            fractals.calculate(bar(f64::NAN, 10.0)),
+            Err(FractalsError::InvalidPrice)
+        );
+
+        let mut skipping = Fractals::with_config(FractalsConfig {
+            gap_policy: GapPolicy::SkipBar,
+            ..FractalsConfig::default()
+        });
+        let first = skipping.calculate(bar(10.0, 9.0)).unwrap();
+        let state = skipping.get_state().clone();
+        assert_eq!(skipping.calculate(bar(f64::NAN, 9.0)).unwrap(), first);
+        assert_eq!(skipping.get_state().highs, state.highs);
+        assert_eq!(skipping.get_state().lows, state.lows);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::util::pivot::main::PivotKind;
+use crate::v2::fractals::types::{
+    FractalType, FractalsConfig, FractalsError, FractalsInput, FractalsOutput, FractalsState,
+};
+
+/// Williams Fractals Detector
+///
+/// A fractal marks a local turning point: the center bar of a `2 × wing + 1` bar
+/// window whose high (up fractal) or low (down fractal) is strictly beyond every
+/// other bar in the window. Fractal highs and lows are commonly used as
+/// resistance/support levels and breakout triggers.
+///
+/// Formula (wing = n, center bar c):
+/// - Up fractal: High_c > High_(c±1), ..., High_(c±n)
+/// - Down fractal: Low_c < Low_(c±1), ..., Low_(c±n)
+///
+/// Interpretation:
+/// - The pattern needs `wing` later bars, so each fractal is reported `wing` bars
+///   after the bar that formed it and is never repainted
+/// - Equal highs (or lows) inside the window do not form a fractal
+pub struct Fractals {
+    state: FractalsState,
+    /// Configuration error found at construction
+    config_error: Option<FractalsError>,
+}
+
+impl Fractals {
+    /// Create a new Fractals detector with default configuration (wing=2)
+    pub fn new() -> Self {
+        Self::with_config(FractalsConfig::default())
+    }
+
+    /// Create a new Fractals detector with a custom wing size
+    pub fn with_wing(wing: usize) -> Result<Self, FractalsError> {
+        Self::try_with_config(FractalsConfig {
+            wing,
+            ..FractalsConfig::default()
+        })
+    }
+
+    /// Create a new Fractals detector with custom configuration
+    pub fn with_config(config: FractalsConfig) -> Self {
+        Self {
+            state: FractalsState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new detector, rejecting an invalid configuration up front
+    pub fn try_with_config(config: FractalsConfig) -> Result<Self, FractalsError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Feed a bar and report the fractal it confirms, if any
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(&mut self, input: FractalsInput) -> Result<FractalsOutput, FractalsError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| FractalsOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(FractalsError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(FractalsError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.high = output.high.map(|value| round_to(value, decimals));
+            output.low = output.low.map(|value| round_to(value, decimals));
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Feed a bar, reporting warmup bars as `None`
+    ///
+    /// `calculate` reports `FractalType::None` while the first window fills; this
+    /// variant returns `Ok(None)` until bar `2 * wing + 1`.
+    pub fn calculate_checked(
+        &mut self,
+        input: FractalsInput,
+    ) -> Result<Option<FractalsOutput>, FractalsError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Feed a batch of bars
+    pub fn calculate_batch(
+        &mut self,
+        inputs: &[FractalsInput],
+    ) -> Result<Vec<FractalsOutput>, FractalsError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Feed a batch of bars, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[FractalsInput],
+    ) -> Result<DenseBatch<FractalsOutput>, FractalsError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            FractalsError::InvalidInput,
+        )
+    }
+
+    /// Reset the detector state
+    pub fn reset(&mut self) {
+        self.state = FractalsState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &FractalsState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: FractalsState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(&mut self, input: FractalsInput) -> Result<FractalsOutput, FractalsError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        // Both detectors see every bar; only their own direction forms a fractal
+        let high = self
+            .state
+            .highs
+            .update(input.high)
+            .filter(|pivot| pivot.kind == PivotKind::High)
+            .map(|pivot| pivot.value);
+        let low = self
+            .state
+            .lows
+            .update(input.low)
+            .filter(|pivot| pivot.kind == PivotKind::Low)
+            .map(|pivot| pivot.value);
+        self.state.has_sufficient_data = self.state.highs.is_full();
+
+        let fractal = match (high, low) {
+            (Some(_), Some(_)) => FractalType::Both,
+            (Some(_), None) => FractalType::Up,
+            (None, Some(_)) => FractalType::Down,
+            (None, None) => FractalType::None,
+        };
+
+        Ok(FractalsOutput {
+            timestamp: None,
+            fractal,
+            high,
+            low,
+        })
+    }
+
+    fn is_finite_input(input: &FractalsInput) -> bool {
+        input.high.is_finite() && input.low.is_finite() && input.close.is_finite()
+    }
+
+    fn validate_input(&self, input: &FractalsInput) -> Result<(), FractalsError> {
+        if !Self::is_finite_input(input) {
+            return Err(FractalsError::InvalidPrice);
+        }
+
+        // Check HLC relationship
+        if input.high < input.low || input.close < input.low || input.close > input.high {
+            return Err(FractalsError::InvalidHLC);
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), FractalsError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for Fractals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to detect fractals in HLC data without maintaining state
+///
+/// Each entry is the fractal confirmed on that bar, i.e. formed by the bar `wing`
+/// bars earlier; warmup bars are `None`.
+pub fn calculate_fractals_simple(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    wing: usize,
+) -> Result<Vec<Option<FractalType>>, FractalsError> {
+    if highs.len() != lows.len() || lows.len() != closes.len() {
+        return Err(FractalsError::InvalidInput(
+            "All price arrays must have the same length".to_string(),
+        ));
+    }
+
+    let mut fractals = Fractals::with_wing(wing)?;
+
+    (0..highs.len())
+        .map(|i| {
+            let input = FractalsInput {
+                timestamp: None,
+                high: highs[i],
+                low: lows[i],
+                close: closes[i],
+            };
+            fractals
+                .calculate_checked(input)
+                .map(|output| output.map(|output| output.fractal))
+        })
+        .collect()
+}
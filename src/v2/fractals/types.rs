@@ -0,0 +1,140 @@
+use crate::common::types::{Candle, GapPolicy};
+use crate::util::pivot::main::PivotDetector;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for Williams Fractals detection
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FractalsConfig {
+    /// Bars required on each side of the center bar (default: 2, the classic 5-bar pattern)
+    ///
+    /// This is also the confirmation delay: a fractal is reported `wing` bars after it forms.
+    pub wing: usize,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+    /// Decimal places `high` and `low` are rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+impl Default for FractalsConfig {
+    fn default() -> Self {
+        Self {
+            wing: 2,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl FractalsConfig {
+    /// Check the invariants the Fractals detector relies on
+    pub fn validate(&self) -> Result<(), FractalsError> {
+        if self.wing == 0 {
+            return Err(FractalsError::InvalidPeriod);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for Williams Fractals detection
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FractalsInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// High price
+    pub high: f64,
+    /// Low price
+    pub low: f64,
+    /// Close price
+    pub close: f64,
+}
+
+impl From<Candle> for FractalsInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+        }
+    }
+}
+
+/// Output from Williams Fractals detection
+///
+/// A fractal describes the bar `wing` bars before the input bar, which is the
+/// earliest point it can be confirmed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FractalsOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Fractal confirmed by this bar
+    pub fractal: FractalType,
+    /// High of the up fractal bar (Up or Both)
+    pub high: Option<f64>,
+    /// Low of the down fractal bar (Down or Both)
+    pub low: Option<f64>,
+}
+
+/// Kind of fractal formed by the center bar of the window
+///
+/// An outside bar can form an up and a down fractal at once. Such a bar is
+/// reported as `Both` rather than as one of the two, so no fractal is lost;
+/// the output's `high` and `low` carry both levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FractalType {
+    /// High strictly above the highs of the `wing` bars on each side
+    Up,
+    /// Low strictly below the lows of the `wing` bars on each side
+    Down,
+    /// Both at once, when the center bar engulfs its neighbours
+    Both,
+    /// No fractal
+    None,
+}
+
+/// Williams Fractals detection state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FractalsState {
+    /// Configuration
+    pub config: FractalsConfig,
+    /// Pivot highs of the bar highs, `wing` bars on each side (up fractals)
+    pub highs: PivotDetector,
+    /// Pivot lows of the bar lows, `wing` bars on each side (down fractals)
+    pub lows: PivotDetector,
+    /// Whether we have enough data for detection
+    pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<FractalsInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<FractalsOutput>,
+}
+
+impl FractalsState {
+    pub fn new(config: FractalsConfig) -> Self {
+        Self {
+            config,
+            highs: PivotDetector::new(config.wing, config.wing),
+            lows: PivotDetector::new(config.wing, config.wing),
+            has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Error types for Williams Fractals detection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FractalsError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid HLC relationship (high < low, or close outside the range)
+    InvalidHLC,
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid wing size (must be > 0)
+    InvalidPeriod,
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::assert_dense_batch;
+    use crate::v2::mass_index::{
+        main::{calculate_mass_index_simple, MassIndex},
+        types::{MassIndexConfig, MassIndexError, MassIndexInput},
+    };
+
+    fn bar(mid: f64, range: f64) -> MassIndexInput {
+        MassIndexInput {
+            timestamp: None,
+            high: mid + range / 2.0,
+            low: mid - range / 2.0,
+        }
+    }
+
+    #[test]
+    fn test_steady_range_sits_at_sum_period() {
+        let mut mass_index = MassIndex::new();
+        let mut last = None;
+
+        for _ in 0..60 {
+            last = Some(mass_index.calculate(bar(100.0, 2.0)).unwrap());
+        }
+
+        let last = last.unwrap();
+        assert!((last.mass_index - 25.0).abs() < 1e-9);
+        assert!((last.ratio - 1.0).abs() < 1e-12);
+        assert!(!last.reversal_bulge);
+    }
+
+    #[test]
+    fn test_mass_index_rises_on_expansion_then_falls_on_contraction() {
+        let mut mass_index = MassIndex::new();
+
+        for _ in 0..60 {
+            mass_index.calculate(bar(100.0, 2.0)).unwrap();
+        }
+        let baseline = mass_index.calculate(bar(100.0, 2.0)).unwrap().mass_index;
+
+        // Range expansion
+        let mut peak = baseline;
+        for i in 1..=15 {
+            let output = mass_index.calculate(bar(100.0, 2.0 + i as f64)).unwrap();
+            peak = peak.max(output.mass_index);
+        }
+        assert!(
+            peak > baseline + 1.0,
+            "peak {} vs baseline {}",
+            peak,
+            baseline
+        );
+
+        // Range contraction back to normal
+        let mut bulge_seen = false;
+        let mut last = peak;
+        for _ in 0..30 {
+            let output = mass_index.calculate(bar(100.0, 2.0)).unwrap();
+            bulge_seen |= output.reversal_bulge;
+            last = output.mass_index;
+        }
+        assert!(last < peak);
+        assert!(
+            bulge_seen,
+            "expected the expansion/contraction to complete a reversal bulge"
+        );
+    }
+
+    #[test]
+    fn test_insufficient_data_returns_zero() {
+        let mut mass_index = MassIndex::with_periods(3, 5).unwrap();
+
+        for i in 0..4 {
+            let output = mass_index.calculate(bar(100.0, 2.0)).unwrap();
+            assert_eq!(output.mass_index, 0.0, "bar {}", i);
+        }
+        let output = mass_index.calculate(bar(100.0, 2.0)).unwrap();
+        assert!((output.mass_index - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(matches!(
+            MassIndex::with_periods(0, 25),
+            Err(MassIndexError::InvalidPeriod)
+        ));
+
+        let mut mass_index = MassIndex::new();
+        let result = mass_index.calculate(MassIndexInput {
+            timestamp: None,
+            high: 99.0,
+            low: 101.0,
+        });
+        assert_eq!(result, Err(MassIndexError::InvalidHL));
+
+        let result = calculate_mass_index_simple(&[1.0, 2.0], &[0.5], 9, 25);
+        assert!(matches!(result, Err(MassIndexError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(MassIndex::new(), MassIndexInput);
+    }
+
+    #[test]
+    fn test_bulge_must_sit_above_trigger() {
+        for (bulge_threshold, trigger_threshold) in [(26.5, 26.5), (26.0, 26.5), (f64::NAN, 26.5)] {
+            let config = MassIndexConfig {
+                bulge_threshold,
+                trigger_threshold,
+                ..MassIndexConfig::default()
+            };
+            assert_eq!(config.validate(), Err(MassIndexError::InvalidThresholds));
+            assert!(MassIndex::try_with_config(config).is_err());
+        }
+
+        assert_eq!(MassIndexConfig::default().validate(), Ok(()));
+    }
+}
@@ -0,0 +1,320 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::v2::mass_index::types::{
+    MassIndexConfig, MassIndexError, MassIndexInput, MassIndexOutput, MassIndexState,
+};
+
+/// Mass Index Indicator
+///
+/// The Mass Index (Donald Dorsey) watches the high-low range for widening and
+/// narrowing. A range that expands and then contracts - the "reversal bulge" -
+/// often precedes a trend reversal, whichever way the trend is pointing.
+///
+/// Formula:
+/// 1. Range = High - Low
+/// 2. Single EMA = EMA(Range, 9)
+/// 3. Double EMA = EMA(Single EMA, 9)
+/// 4. Ratio = Single EMA / Double EMA
+/// 5. Mass Index = Sum(Ratio, 25)
+///
+/// Interpretation:
+/// - With a steady range every ratio is 1, so the index sits at `sum_period`
+/// - A rise above 27 followed by a drop below 26.5 completes a reversal bulge
+pub struct MassIndex {
+    state: MassIndexState,
+    /// Configuration error found at construction
+    config_error: Option<MassIndexError>,
+}
+
+impl MassIndex {
+    /// Create a new Mass Index calculator with default configuration (9/25)
+    pub fn new() -> Self {
+        Self::with_config(MassIndexConfig::default())
+    }
+
+    /// Create a new Mass Index calculator with custom EMA and sum periods
+    pub fn with_periods(ema_period: usize, sum_period: usize) -> Result<Self, MassIndexError> {
+        if ema_period == 0 || sum_period == 0 {
+            return Err(MassIndexError::InvalidPeriod);
+        }
+
+        Ok(Self::with_config(MassIndexConfig {
+            ema_period,
+            sum_period,
+            ..MassIndexConfig::default()
+        }))
+    }
+
+    /// Create a new Mass Index calculator with custom configuration
+    pub fn with_config(config: MassIndexConfig) -> Self {
+        Self {
+            state: MassIndexState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: MassIndexConfig) -> Result<Self, MassIndexError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Calculate the Mass Index for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(&mut self, input: MassIndexInput) -> Result<MassIndexOutput, MassIndexError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| MassIndexOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(MassIndexError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(MassIndexError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.mass_index = round_to(output.mass_index, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(
+        &mut self,
+        input: MassIndexInput,
+    ) -> Result<Option<MassIndexOutput>, MassIndexError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Calculate the Mass Index for a batch of inputs
+    pub fn calculate_batch(
+        &mut self,
+        inputs: &[MassIndexInput],
+    ) -> Result<Vec<MassIndexOutput>, MassIndexError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[MassIndexInput],
+    ) -> Result<DenseBatch<MassIndexOutput>, MassIndexError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            MassIndexError::InvalidInput,
+        )
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = MassIndexState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &MassIndexState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: MassIndexState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(&mut self, input: MassIndexInput) -> Result<MassIndexOutput, MassIndexError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        let double_ema = self.state.range_emas.update(input.high - input.low);
+        let single_ema = self.state.range_emas.level(0).unwrap_or(double_ema);
+
+        let ratio = if double_ema == 0.0 {
+            1.0 // No range at all - treat as neutral
+        } else {
+            single_ema / double_ema
+        };
+
+        self.update_ratio_sum(ratio);
+
+        let (mass_index, reversal_bulge) = if self.state.has_sufficient_data {
+            (
+                self.state.ratio_sum,
+                self.detect_reversal_bulge(self.state.ratio_sum),
+            )
+        } else {
+            (0.0, false) // Default value when insufficient data
+        };
+
+        Ok(MassIndexOutput {
+            timestamp: None,
+            mass_index,
+            ratio,
+            single_ema,
+            double_ema,
+            reversal_bulge,
+        })
+    }
+
+    fn is_finite_input(input: &MassIndexInput) -> bool {
+        input.high.is_finite() && input.low.is_finite()
+    }
+
+    fn validate_input(&self, input: &MassIndexInput) -> Result<(), MassIndexError> {
+        if !input.high.is_finite() || !input.low.is_finite() {
+            return Err(MassIndexError::InvalidPrice);
+        }
+
+        if input.high < input.low {
+            return Err(MassIndexError::InvalidHL);
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), MassIndexError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn update_ratio_sum(&mut self, ratio: f64) {
+        // Remove oldest ratio if at capacity
+        if self.state.ratios.len() >= self.state.config.sum_period {
+            if let Some(oldest) = self.state.ratios.pop_front() {
+                self.state.ratio_sum -= oldest;
+            }
+        }
+
+        self.state.ratios.push_back(ratio);
+        self.state.ratio_sum += ratio;
+
+        // Check if we have sufficient data
+        self.state.has_sufficient_data = self.state.ratios.len() >= self.state.config.sum_period;
+    }
+
+    fn detect_reversal_bulge(&mut self, mass_index: f64) -> bool {
+        if mass_index > self.state.config.bulge_threshold {
+            self.state.bulge_armed = true;
+            false
+        } else if self.state.bulge_armed && mass_index < self.state.config.trigger_threshold {
+            self.state.bulge_armed = false;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Default for MassIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to calculate the Mass Index for HL data without maintaining state
+pub fn calculate_mass_index_simple(
+    highs: &[f64],
+    lows: &[f64],
+    ema_period: usize,
+    sum_period: usize,
+) -> Result<Vec<f64>, MassIndexError> {
+    if highs.len() != lows.len() {
+        return Err(MassIndexError::InvalidInput(
+            "Highs and lows must have same length".to_string(),
+        ));
+    }
+
+    if highs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    calculate_mass_index_iter(
+        highs.iter().copied().zip(lows.iter().copied()),
+        ema_period,
+        sum_period,
+    )
+}
+
+/// Like [`calculate_mass_index_simple`], but accepts any iterator of `(high, low)` bars
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_mass_index_iter(
+    bars: impl IntoIterator<Item = (f64, f64)>,
+    ema_period: usize,
+    sum_period: usize,
+) -> Result<Vec<f64>, MassIndexError> {
+    let mut mass_index_calculator = MassIndex::with_periods(ema_period, sum_period)?;
+
+    bars.into_iter()
+        .map(|(high, low)| {
+            let input = MassIndexInput {
+                timestamp: None,
+                high,
+                low,
+            };
+            mass_index_calculator
+                .calculate(input)
+                .map(|output| output.mass_index)
+        })
+        .collect()
+}
+
+/// Like [`calculate_mass_index_simple`], but warmup bars are `None` instead of placeholder values
+pub fn calculate_mass_index_simple_checked(
+    highs: &[f64],
+    lows: &[f64],
+    ema_period: usize,
+    sum_period: usize,
+) -> Result<Vec<Option<f64>>, MassIndexError> {
+    if highs.len() != lows.len() {
+        return Err(MassIndexError::InvalidInput(
+            "Highs and lows must have same length".to_string(),
+        ));
+    }
+
+    if highs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut mass_index_calculator = MassIndex::with_periods(ema_period, sum_period)?;
+    let mut results = Vec::with_capacity(highs.len());
+
+    for i in 0..highs.len() {
+        let input = MassIndexInput {
+            timestamp: None,
+            high: highs[i],
+            low: lows[i],
+        };
+        let output = mass_index_calculator.calculate_checked(input)?;
+        results.push(output.map(|output| output.mass_index));
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,145 @@
+use crate::common::types::{Candle, GapPolicy};
+use crate::util::ema_chain::main::EmaChain;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Configuration for Mass Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MassIndexConfig {
+    /// Period of the single and double EMA of the high-low range (default: 9)
+    pub ema_period: usize,
+    /// Number of EMA ratios summed into the index (default: 25)
+    pub sum_period: usize,
+    /// Level the index must rise above to set up a reversal bulge (default: 27.0)
+    pub bulge_threshold: f64,
+    /// Level the index must then fall back below to complete the bulge (default: 26.5)
+    pub trigger_threshold: f64,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+    /// Decimal places `mass_index` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+impl Default for MassIndexConfig {
+    fn default() -> Self {
+        Self {
+            ema_period: 9,
+            sum_period: 25,
+            bulge_threshold: 27.0,
+            trigger_threshold: 26.5,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl MassIndexConfig {
+    /// Check the invariants the Mass Index calculator relies on
+    pub fn validate(&self) -> Result<(), MassIndexError> {
+        if self.ema_period == 0 || self.sum_period == 0 {
+            return Err(MassIndexError::InvalidPeriod);
+        }
+
+        // The reversal bulge needs the index to rise above the bulge and then fall back below
+        // the trigger, so the trigger must sit strictly below the bulge
+        if !(self.trigger_threshold.is_finite() && self.bulge_threshold.is_finite())
+            || self.bulge_threshold <= self.trigger_threshold
+        {
+            return Err(MassIndexError::InvalidThresholds);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for Mass Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MassIndexInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// High price
+    pub high: f64,
+    /// Low price
+    pub low: f64,
+}
+
+impl From<Candle> for MassIndexInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            high: candle.high,
+            low: candle.low,
+        }
+    }
+}
+
+/// Output from Mass Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MassIndexOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Mass Index value (sum of the last `sum_period` EMA ratios)
+    pub mass_index: f64,
+    /// Current single EMA / double EMA ratio
+    pub ratio: f64,
+    /// EMA of the high-low range
+    pub single_ema: f64,
+    /// EMA of the single EMA
+    pub double_ema: f64,
+    /// True on the bar that completes a reversal bulge
+    pub reversal_bulge: bool,
+}
+
+/// Mass Index calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MassIndexState {
+    /// Configuration
+    pub config: MassIndexConfig,
+    /// Single (level 0) and double (level 1) EMA of the high-low range
+    pub range_emas: EmaChain,
+    /// History of EMA ratios for the rolling sum
+    pub ratios: VecDeque<f64>,
+    /// Running sum of the ratios in the window
+    pub ratio_sum: f64,
+    /// Whether the index has risen above the bulge threshold and awaits the trigger
+    pub bulge_armed: bool,
+    /// Whether we have enough data for calculation
+    pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<MassIndexInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<MassIndexOutput>,
+}
+
+impl MassIndexState {
+    pub fn new(config: MassIndexConfig) -> Self {
+        Self {
+            config,
+            range_emas: EmaChain::new(config.ema_period, 2),
+            ratios: VecDeque::with_capacity(config.sum_period),
+            ratio_sum: 0.0,
+            bulge_armed: false,
+            has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Error types for Mass Index calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MassIndexError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid HL relationship (high < low)
+    InvalidHL,
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid period (must be > 0)
+    InvalidPeriod,
+    /// Invalid threshold values (the bulge must be above the trigger)
+    InvalidThresholds,
+}
@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod tests {
+    use crate::common::assert_dense_batch;
     use crate::v2::mfi::{
-        main::{calculate_mfi_simple, MFI},
-        types::{MFIConfig, MFIError, MFIInput, MFIMarketCondition},
+        main::{calculate_mfi_simple, calculate_mfi_simple_checked, MFI},
+        types::{MFIConfig, MFIError, MFIInput, MFIMarketCondition, MFIState, MfiSmoothing},
     };
 
     #[test]
@@ -19,6 +20,7 @@ mod tests {
 
         for (high, low, close, volume) in test_data {
             let input = MFIInput {
+                timestamp: None,
                 high,
                 low,
                 close,
@@ -41,11 +43,13 @@ mod tests {
             period: 2,
             overbought: 80.0,
             oversold: 20.0,
+            ..Default::default()
         };
         let mut mfi = MFI::with_config(config);
 
         // First input - insufficient data
         let input1 = MFIInput {
+            timestamp: None,
             high: 10.0,
             low: 8.0,
             close: 9.0,
@@ -56,6 +60,7 @@ mod tests {
 
         // Add more data to get actual MFI calculation
         let input2 = MFIInput {
+            timestamp: None,
             high: 15.0,
             low: 13.0,
             close: 14.0,
@@ -72,6 +77,7 @@ mod tests {
         let mut mfi = MFI::with_period(2).unwrap();
 
         let input1 = MFIInput {
+            timestamp: None,
             high: 10.0,
             low: 8.0,
             close: 9.0,
@@ -81,6 +87,7 @@ mod tests {
         assert_eq!(result1.flow_direction, 0.0); // First calculation
 
         let input2 = MFIInput {
+            timestamp: None,
             high: 12.0,
             low: 10.0,
             close: 11.0,
@@ -90,6 +97,7 @@ mod tests {
         assert_eq!(result2.flow_direction, 1.0); // TP increased
 
         let input3 = MFIInput {
+            timestamp: None,
             high: 10.0,
             low: 8.0,
             close: 9.0,
@@ -105,6 +113,7 @@ mod tests {
 
         // Test invalid OHLC (high < low)
         let input = MFIInput {
+            timestamp: None,
             high: 8.0,
             low: 10.0,
             close: 9.0,
@@ -114,6 +123,7 @@ mod tests {
 
         // Test negative volume
         let input = MFIInput {
+            timestamp: None,
             high: 10.0,
             low: 8.0,
             close: 9.0,
@@ -143,4 +153,408 @@ mod tests {
             assert!((0.0..=100.0).contains(&mfi_value));
         }
     }
+
+    #[test]
+    fn test_mfi_simple_checked_marks_warmup() {
+        let highs = vec![10.0, 11.0, 10.5, 12.0, 12.5];
+        let lows = vec![8.0, 9.0, 8.5, 10.0, 10.5];
+        let closes = vec![9.0, 10.0, 9.0, 11.0, 12.0];
+        let volumes = vec![1000.0, 1500.0, 1200.0, 1800.0, 1600.0];
+
+        let result = calculate_mfi_simple_checked(&highs, &lows, &closes, &volumes, 3).unwrap();
+        assert_eq!(result[..2], [None, None]);
+        assert!(result[2..].iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn test_prime_previous_typical_price_matches_uninterrupted_run() {
+        let inputs = [
+            MFIInput {
+                timestamp: None,
+                high: 12.0,
+                low: 10.0,
+                close: 11.0,
+                volume: 1000.0,
+            },
+            MFIInput {
+                timestamp: None,
+                high: 13.0,
+                low: 11.0,
+                close: 12.5,
+                volume: 1200.0,
+            },
+            MFIInput {
+                timestamp: None,
+                high: 12.5,
+                low: 10.5,
+                close: 11.0,
+                volume: 900.0,
+            },
+        ];
+
+        // Uninterrupted stream
+        let mut uninterrupted = MFI::with_period(2).unwrap();
+        let outputs = uninterrupted.calculate_batch(&inputs).unwrap();
+
+        // Restart before the last bar, priming only the previous typical price
+        let mut restarted = MFI::with_period(2).unwrap();
+        restarted.prime_previous_typical_price(outputs[1].typical_price);
+        let resumed = restarted.calculate(inputs[2]).unwrap();
+
+        assert_eq!(resumed.flow_direction, outputs[2].flow_direction);
+        assert_eq!(resumed.flow_direction, -1.0);
+
+        // Without priming the first bar is treated as neutral
+        let mut cold = MFI::with_period(2).unwrap();
+        assert_eq!(cold.calculate(inputs[2]).unwrap().flow_direction, 0.0);
+
+        // Non-finite values are ignored
+        cold.prime_previous_typical_price(f64::NAN);
+        assert_eq!(
+            cold.get_state().previous_typical_price,
+            Some(outputs[2].typical_price)
+        );
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let config = MFIConfig::builder()
+            .period(10)
+            .overbought(75.0)
+            .oversold(25.0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config,
+            MFIConfig {
+                period: 10,
+                overbought: 75.0,
+                oversold: 25.0,
+                ..Default::default()
+            }
+        );
+
+        // Same invariants as the constructors and `calculate`
+        assert_eq!(
+            MFIConfig::builder().period(0).build().err(),
+            MFI::with_period(0).err()
+        );
+        assert_eq!(
+            MFIConfig::builder().overbought(20.0).oversold(30.0).build(),
+            Err(MFIError::InvalidThresholds)
+        );
+        assert_eq!(
+            MFIConfig::builder().overbought(120.0).build(),
+            Err(MFIError::InvalidThresholds)
+        );
+        assert_eq!(
+            MFIConfig::builder().volume_scale(0.0).build(),
+            Err(MFIError::InvalidVolumeScale)
+        );
+    }
+
+    #[test]
+    fn test_volume_scale_does_not_change_mfi() {
+        let inputs: Vec<MFIInput> = (0..40)
+            .map(|i| {
+                let close = 60_000.0 + (i as f64 * 0.9).sin() * 1_500.0;
+                MFIInput {
+                    timestamp: None,
+                    high: close + 250.0,
+                    low: close - 250.0,
+                    close,
+                    volume: 3.0e12 + (i as f64 * 1.3).cos() * 1.0e12,
+                }
+            })
+            .collect();
+
+        let mut plain = MFI::with_period(14).unwrap();
+        let mut scaled = MFI::with_config(MFIConfig::builder().volume_scale(1e6).build().unwrap());
+
+        for input in inputs {
+            let expected = plain.calculate(input).unwrap();
+            let output = scaled.calculate(input).unwrap();
+            assert!((output.mfi - expected.mfi).abs() < 1e-9);
+            assert_eq!(output.market_condition, expected.market_condition);
+        }
+        assert!(scaled.positive_money_flow() < plain.positive_money_flow() / 1e5);
+    }
+
+    #[test]
+    fn test_calculate_checked_hides_warmup_output() {
+        let mut plain = MFI::with_period(3).unwrap();
+        let mut checked = MFI::with_period(3).unwrap();
+        let bars = [
+            (10.0, 8.0, 9.0),
+            (11.0, 9.0, 10.0),
+            (12.0, 10.0, 11.0),
+            (11.0, 9.0, 10.0),
+            (12.0, 10.0, 11.5),
+        ];
+
+        let mut results = Vec::new();
+        for (high, low, close) in bars {
+            let input = MFIInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+                volume: 1000.0,
+            };
+            let output = plain.calculate(input).unwrap();
+            let checked_output = checked.calculate_checked(input).unwrap();
+            if checked_output.is_none() {
+                // Warmup placeholder looks like a neutral reading
+                assert_eq!(output.mfi, 50.0);
+                assert_eq!(output.market_condition, MFIMarketCondition::Insufficient);
+            }
+            results.push(checked_output);
+        }
+
+        assert!(results[0].is_none());
+        let last = results.last().unwrap().unwrap();
+        assert_ne!(last.market_condition, MFIMarketCondition::Insufficient);
+    }
+
+    #[test]
+    fn test_wilder_smoothing_trajectory() {
+        let bars = [
+            (10.0, 8.0, 9.0, 100.0),
+            (11.0, 9.0, 10.0, 120.0),
+            (10.5, 8.5, 9.0, 90.0),
+            (12.0, 10.0, 11.0, 150.0),
+            (11.5, 9.5, 10.0, 110.0),
+            (13.0, 11.0, 12.0, 130.0),
+            (12.5, 10.5, 11.0, 80.0),
+        ];
+        let period = 3;
+        let run = |smoothing: MfiSmoothing| -> Vec<f64> {
+            let config = MFIConfig::builder()
+                .period(period)
+                .smoothing(smoothing)
+                .build()
+                .unwrap();
+            let mut mfi = MFI::with_config(config);
+            bars.iter()
+                .map(|&(high, low, close, volume)| {
+                    let input = MFIInput {
+                        timestamp: None,
+                        high,
+                        low,
+                        close,
+                        volume,
+                    };
+                    mfi.calculate(input).unwrap().mfi
+                })
+                .collect()
+        };
+
+        let simple = run(MfiSmoothing::Simple);
+        let wilder = run(MfiSmoothing::Wilder);
+
+        // Identical through the first full window
+        assert_eq!(simple[..period], wilder[..period]);
+
+        // Reference: flows are typical price × volume, signed by the typical price change
+        let typical: Vec<f64> = bars.iter().map(|&(h, l, c, _)| (h + l + c) / 3.0).collect();
+        let (mut positive, mut negative) = (0.0, 0.0);
+        for i in 1..period {
+            let flow = typical[i] * bars[i].3;
+            if typical[i] > typical[i - 1] {
+                positive += flow;
+            } else if typical[i] < typical[i - 1] {
+                negative += flow;
+            }
+        }
+        for i in period..bars.len() {
+            let flow = typical[i] * bars[i].3;
+            let (pos, neg) = if typical[i] > typical[i - 1] {
+                (flow, 0.0)
+            } else {
+                (0.0, flow)
+            };
+            positive += pos - positive / period as f64;
+            negative += neg - negative / period as f64;
+            let expected = 100.0 - 100.0 / (1.0 + positive / negative);
+            assert!(
+                (wilder[i] - expected).abs() < 1e-9,
+                "bar {i}: {} vs {expected}",
+                wilder[i]
+            );
+        }
+
+        // The windowed sums forget old flows entirely, so the trajectories diverge
+        assert!(simple[period..]
+            .iter()
+            .zip(&wilder[period..])
+            .any(|(s, w)| (s - w).abs() > 1e-6));
+    }
+
+    #[test]
+    fn test_smoothing_defaults_to_simple() {
+        assert_eq!(MFIConfig::default().smoothing, MfiSmoothing::Simple);
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(MFI::new(), MFIInput);
+    }
+
+    #[test]
+    fn test_calculate_batch_vs_independent() {
+        let inputs: Vec<MFIInput> = (0..6)
+            .map(|i| {
+                let base = 10.0 + (i % 3) as f64;
+                MFIInput {
+                    timestamp: Some(i),
+                    high: base + 1.0,
+                    low: base - 1.0,
+                    close: base + 0.5,
+                    volume: 1000.0 + 100.0 * i as f64,
+                }
+            })
+            .collect();
+
+        let mut calculator = MFI::with_period(3).unwrap();
+        calculator.calculate(inputs[0]).unwrap();
+        let state_before = calculator.get_state().clone();
+
+        // Independent evaluation neither reads nor touches the running state
+        let independent = calculator.calculate_independent(&inputs).unwrap();
+        assert_eq!(calculator.get_state(), &state_before);
+        for (input, output) in inputs.iter().zip(&independent) {
+            assert_eq!(
+                *output,
+                MFI::with_period(3).unwrap().calculate(*input).unwrap()
+            );
+        }
+
+        // The batch continues the series from the existing state
+        let mut fresh = MFI::with_period(3).unwrap();
+        let batch = fresh.calculate_batch(&inputs).unwrap();
+        assert_ne!(batch[2], independent[2]);
+        assert_ne!(
+            calculator.calculate_batch(&inputs[1..]).unwrap()[1],
+            independent[2]
+        );
+        assert_eq!(batch.len(), independent.len());
+    }
+
+    #[test]
+    fn test_hysteresis_keeps_condition() {
+        // Rising typical prices, then an up/down pattern keeping MFI within 70 +/- 10
+        let bars = [
+            (10.0, 3000.0),
+            (13.0, 3000.0),
+            (16.0, 3000.0),
+            (15.0, 1000.0),
+            (17.0, 2000.0),
+            (16.0, 1000.0),
+            (19.0, 3000.0),
+            (18.0, 1000.0),
+            (20.0, 2000.0),
+        ];
+        let inputs: Vec<MFIInput> = bars
+            .iter()
+            .map(|&(tp, volume)| MFIInput {
+                timestamp: None,
+                high: tp + 1.0,
+                low: tp - 1.0,
+                close: tp,
+                volume,
+            })
+            .collect();
+
+        let base = MFIConfig::builder()
+            .period(2)
+            .overbought(70.0)
+            .build()
+            .unwrap();
+        let sticky_config = MFIConfig::builder()
+            .period(2)
+            .overbought(70.0)
+            .hysteresis(10.0)
+            .build()
+            .unwrap();
+        let plain = MFI::with_config(base).calculate_batch(&inputs).unwrap();
+        let sticky = MFI::with_config(sticky_config)
+            .calculate_batch(&inputs)
+            .unwrap();
+
+        assert!(plain[2..]
+            .iter()
+            .all(|o| (o.mfi - 70.0).abs() < 10.0 || o.mfi >= 80.0));
+        assert!(plain[2..]
+            .iter()
+            .any(|o| o.market_condition == MFIMarketCondition::Normal));
+        assert!(sticky[2..]
+            .iter()
+            .all(|o| o.market_condition == MFIMarketCondition::Overbought));
+
+        assert_eq!(
+            MFIConfig::builder().hysteresis(-1.0).build(),
+            Err(MFIError::InvalidThresholds)
+        );
+    }
+
+    #[test]
+    fn test_empty_and_single_element_batches() {
+        assert!(calculate_mfi_simple(&[], &[], &[], &[], 14)
+            .unwrap()
+            .is_empty());
+        assert!(calculate_mfi_simple_checked(&[], &[], &[], &[], 14)
+            .unwrap()
+            .is_empty());
+
+        // One bar is always warmup: a placeholder, or None in the checked variant
+        assert_eq!(
+            calculate_mfi_simple(&[11.0], &[9.0], &[10.0], &[1000.0], 14).unwrap(),
+            vec![50.0]
+        );
+        assert_eq!(
+            calculate_mfi_simple_checked(&[11.0], &[9.0], &[10.0], &[1000.0], 14).unwrap(),
+            vec![None]
+        );
+    }
+
+    #[test]
+    fn test_config_without_volume_scale_deserializes() {
+        let json = r#"{"period":3,"overbought":80.0,"oversold":20.0,"smoothing":"Simple"}"#;
+        let config: MFIConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.volume_scale, 1.0);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_restores_baseline_state() {
+        // State saved by indexes-rs 1.0.1 with period 3
+        let json = r#"{"config":{"period":3,"overbought":80.0,"oversold":20.0},"money_flows":[{"typical_price":11.333333333333334,"raw_money_flow":13600.0,"flow_direction":1.0},{"typical_price":12.166666666666666,"raw_money_flow":21900.0,"flow_direction":1.0},{"typical_price":12.133333333333333,"raw_money_flow":10920.0,"flow_direction":-1.0}],"previous_typical_price":12.133333333333333,"positive_money_flow_sum":35500.0,"negative_money_flow_sum":10920.0,"has_sufficient_data":true}"#;
+        let state: MFIState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.config.smoothing, MfiSmoothing::Simple);
+        let mut restored = MFI::new();
+        restored.set_state(state);
+
+        let input = |(high, low, close, volume)| MFIInput {
+            timestamp: None,
+            high,
+            low,
+            close,
+            volume,
+        };
+        let mut fresh = MFI::with_period(3).unwrap();
+        for bar in [
+            (11.0, 9.0, 10.0, 1000.0),
+            (12.0, 10.0, 11.5, 1500.0),
+            (12.5, 10.5, 11.0, 1200.0),
+            (13.0, 11.0, 12.5, 1800.0),
+            (12.8, 11.6, 12.0, 900.0),
+        ] {
+            fresh.calculate(input(bar)).unwrap();
+        }
+
+        let next = (13.4, 12.1, 13.2, 1100.0);
+        let expected = fresh.calculate(input(next)).unwrap();
+        let actual = restored.calculate(input(next)).unwrap();
+        assert!((actual.mfi - expected.mfi).abs() < 1e-9);
+    }
 }
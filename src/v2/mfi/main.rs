@@ -1,5 +1,8 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::transform::price_source::main::typical_price;
 use crate::v2::mfi::types::{
-    MFIConfig, MFIError, MFIInput, MFIMarketCondition, MFIOutput, MFIState, MoneyFlow,
+    MFIConfig, MFIError, MFIInput, MFIMarketCondition, MFIOutput, MFIState, MfiSmoothing, MoneyFlow,
 };
 
 /// Money Flow Index (MFI) Indicator
@@ -15,6 +18,8 @@ use crate::v2::mfi::types::{
 /// 5. MFI = 100 - (100 / (1 + Money Ratio))
 pub struct MFI {
     state: MFIState,
+    /// Configuration error found at construction
+    config_error: Option<MFIError>,
 }
 
 impl MFI {
@@ -40,11 +45,134 @@ impl MFI {
     pub fn with_config(config: MFIConfig) -> Self {
         Self {
             state: MFIState::new(config),
+            config_error: config.validate().err(),
         }
     }
 
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: MFIConfig) -> Result<Self, MFIError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
     /// Calculate MFI for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
     pub fn calculate(&mut self, input: MFIInput) -> Result<MFIOutput, MFIError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| MFIOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(MFIError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(MFIError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.mfi = round_to(output.mfi, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(&mut self, input: MFIInput) -> Result<Option<MFIOutput>, MFIError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Calculate MFI for a batch of inputs
+    ///
+    /// The inputs are treated as one continuous series: they are fed in order to this
+    /// calculator, continuing from (and updating) its current state. Use
+    /// `calculate_independent` to evaluate each input on its own.
+    pub fn calculate_batch(&mut self, inputs: &[MFIInput]) -> Result<Vec<MFIOutput>, MFIError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Evaluate each input independently on a fresh calculator with this configuration
+    ///
+    /// This calculator's state is neither read nor modified. Each output is what a
+    /// newly created calculator would return for that single bar (a warmup output
+    /// unless the period is 1).
+    pub fn calculate_independent(&self, inputs: &[MFIInput]) -> Result<Vec<MFIOutput>, MFIError> {
+        inputs
+            .iter()
+            .map(|input| Self::with_config(self.state.config).calculate(*input))
+            .collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[MFIInput],
+    ) -> Result<DenseBatch<MFIOutput>, MFIError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            MFIError::InvalidInput,
+        )
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = MFIState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &MFIState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: MFIState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    /// Warm-start with the typical price of the last bar seen before a restart
+    ///
+    /// The next bar's money flow direction is then measured against `tp` instead of
+    /// being treated as the first bar. Non-finite values are ignored.
+    pub fn prime_previous_typical_price(&mut self, tp: f64) {
+        if tp.is_finite() {
+            self.state.previous_typical_price = Some(tp);
+        }
+    }
+
+    /// Get current positive money flow sum
+    pub fn positive_money_flow(&self) -> f64 {
+        self.state.positive_money_flow_sum
+    }
+
+    /// Get current negative money flow sum
+    pub fn negative_money_flow(&self) -> f64 {
+        self.state.negative_money_flow_sum
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(&mut self, input: MFIInput) -> Result<MFIOutput, MFIError> {
         // Validate input
         self.validate_input(&input)?;
         self.validate_config()?;
@@ -52,8 +180,8 @@ impl MFI {
         // Calculate typical price
         let typical_price = self.calculate_typical_price(&input);
 
-        // Calculate raw money flow
-        let raw_money_flow = typical_price * input.volume;
+        // Calculate raw money flow (volume scaled for numerical stability)
+        let raw_money_flow = typical_price * (input.volume / self.state.config.volume_scale);
 
         // Determine money flow direction
         let flow_direction = self.determine_flow_direction(typical_price);
@@ -82,6 +210,7 @@ impl MFI {
         self.state.previous_typical_price = Some(typical_price);
 
         Ok(MFIOutput {
+            timestamp: None,
             mfi,
             typical_price,
             raw_money_flow,
@@ -90,38 +219,13 @@ impl MFI {
         })
     }
 
-    /// Calculate MFI for a batch of inputs
-    pub fn calculate_batch(&mut self, inputs: &[MFIInput]) -> Result<Vec<MFIOutput>, MFIError> {
-        inputs.iter().map(|input| self.calculate(*input)).collect()
-    }
-
-    /// Reset the calculator state
-    pub fn reset(&mut self) {
-        self.state = MFIState::new(self.state.config);
+    fn is_finite_input(input: &MFIInput) -> bool {
+        input.high.is_finite()
+            && input.low.is_finite()
+            && input.close.is_finite()
+            && input.volume.is_finite()
     }
 
-    /// Get current state (for serialization/debugging)
-    pub fn get_state(&self) -> &MFIState {
-        &self.state
-    }
-
-    /// Restore state (for deserialization)
-    pub fn set_state(&mut self, state: MFIState) {
-        self.state = state;
-    }
-
-    /// Get current positive money flow sum
-    pub fn positive_money_flow(&self) -> f64 {
-        self.state.positive_money_flow_sum
-    }
-
-    /// Get current negative money flow sum
-    pub fn negative_money_flow(&self) -> f64 {
-        self.state.negative_money_flow_sum
-    }
-
-    // Private helper methods
-
     fn validate_input(&self, input: &MFIInput) -> Result<(), MFIError> {
         // Check for valid prices
         if !input.high.is_finite() || !input.low.is_finite() || !input.close.is_finite() {
@@ -146,23 +250,14 @@ impl MFI {
     }
 
     fn validate_config(&self) -> Result<(), MFIError> {
-        if self.state.config.period == 0 {
-            return Err(MFIError::InvalidPeriod);
-        }
-
-        if self.state.config.overbought <= self.state.config.oversold {
-            return Err(MFIError::InvalidThresholds);
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
         }
-
-        if self.state.config.overbought > 100.0 || self.state.config.oversold < 0.0 {
-            return Err(MFIError::InvalidThresholds);
-        }
-
-        Ok(())
     }
 
     fn calculate_typical_price(&self, input: &MFIInput) -> f64 {
-        (input.high + input.low + input.close) / 3.0
+        typical_price(input.high, input.low, input.close)
     }
 
     fn determine_flow_direction(&self, current_typical_price: f64) -> f64 {
@@ -181,24 +276,25 @@ impl MFI {
     }
 
     fn update_money_flow_history(&mut self, money_flow: MoneyFlow) {
+        if self.state.config.smoothing == MfiSmoothing::Wilder && self.state.has_sufficient_data {
+            self.update_wilder_sums(&money_flow);
+            self.state.money_flows.pop_front();
+            self.state.money_flows.push_back(money_flow);
+            return;
+        }
+
         // Remove oldest if at capacity
         if self.state.money_flows.len() >= self.state.config.period {
             if let Some(oldest) = self.state.money_flows.pop_front() {
                 // Remove from sums
-                if oldest.flow_direction > 0.0 {
-                    self.state.positive_money_flow_sum -= oldest.raw_money_flow;
-                } else if oldest.flow_direction < 0.0 {
-                    self.state.negative_money_flow_sum -= oldest.raw_money_flow;
-                }
+                let (positive, negative) = Self::split_money_flow(&oldest);
+                self.add_to_sums(-positive, -negative);
             }
         }
 
         // Add new money flow
-        if money_flow.flow_direction > 0.0 {
-            self.state.positive_money_flow_sum += money_flow.raw_money_flow;
-        } else if money_flow.flow_direction < 0.0 {
-            self.state.negative_money_flow_sum += money_flow.raw_money_flow;
-        }
+        let (positive, negative) = Self::split_money_flow(&money_flow);
+        self.add_to_sums(positive, negative);
 
         self.state.money_flows.push_back(money_flow);
 
@@ -206,6 +302,43 @@ impl MFI {
         self.state.has_sufficient_data = self.state.money_flows.len() >= self.state.config.period;
     }
 
+    /// Wilder's smoothing of the sums: Sum = Sum - Sum / n + Current
+    fn update_wilder_sums(&mut self, money_flow: &MoneyFlow) {
+        let period = self.state.config.period as f64;
+        let (positive, negative) = Self::split_money_flow(money_flow);
+
+        let positive_step = positive - self.state.positive_money_flow_sum / period;
+        let negative_step = negative - self.state.negative_money_flow_sum / period;
+        self.add_to_sums(positive_step, negative_step);
+    }
+
+    /// Split a money flow into its (positive, negative) contributions by direction
+    fn split_money_flow(money_flow: &MoneyFlow) -> (f64, f64) {
+        if money_flow.flow_direction > 0.0 {
+            (money_flow.raw_money_flow, 0.0)
+        } else if money_flow.flow_direction < 0.0 {
+            (0.0, money_flow.raw_money_flow)
+        } else {
+            (0.0, 0.0)
+        }
+    }
+
+    /// Add `positive` and `negative` to the positive and negative money flow sums
+    fn add_to_sums(&mut self, positive: f64, negative: f64) {
+        let summation = self.state.config.summation;
+        let state = &mut self.state;
+        summation.add(
+            &mut state.positive_money_flow_sum,
+            &mut state.positive_compensation,
+            positive,
+        );
+        summation.add(
+            &mut state.negative_money_flow_sum,
+            &mut state.negative_compensation,
+            negative,
+        );
+    }
+
     fn calculate_mfi_value(&self) -> Result<f64, MFIError> {
         if self.state.negative_money_flow_sum == 0.0 {
             // All positive money flow
@@ -228,11 +361,22 @@ impl MFI {
     }
 
     fn determine_market_condition(&self, mfi: f64) -> MFIMarketCondition {
+        let config = &self.state.config;
+        let previous = self.state.last_output.map(|output| output.market_condition);
+
         if !self.state.has_sufficient_data {
             MFIMarketCondition::Insufficient
-        } else if mfi >= self.state.config.overbought {
+        } else if previous == Some(MFIMarketCondition::Overbought)
+            && mfi > config.overbought - config.hysteresis
+        {
+            MFIMarketCondition::Overbought // Sticky until MFI drops below the band
+        } else if previous == Some(MFIMarketCondition::Oversold)
+            && mfi < config.oversold + config.hysteresis
+        {
+            MFIMarketCondition::Oversold
+        } else if mfi >= config.overbought + config.hysteresis {
             MFIMarketCondition::Overbought
-        } else if mfi <= self.state.config.oversold {
+        } else if mfi <= config.oversold - config.hysteresis {
             MFIMarketCondition::Oversold
         } else {
             MFIMarketCondition::Normal
@@ -265,18 +409,72 @@ pub fn calculate_mfi_simple(
         return Ok(Vec::new());
     }
 
+    calculate_mfi_iter(
+        highs
+            .iter()
+            .zip(lows)
+            .zip(closes)
+            .zip(volumes)
+            .map(|(((&high, &low), &close), &volume)| (high, low, close, volume)),
+        period,
+    )
+}
+
+/// Like [`calculate_mfi_simple`], but accepts any iterator of `(high, low, close, volume)` bars
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_mfi_iter(
+    bars: impl IntoIterator<Item = (f64, f64, f64, f64)>,
+    period: usize,
+) -> Result<Vec<f64>, MFIError> {
+    let mut mfi_calculator = MFI::with_period(period)?;
+
+    bars.into_iter()
+        .map(|(high, low, close, volume)| {
+            let input = MFIInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+                volume,
+            };
+            mfi_calculator.calculate(input).map(|output| output.mfi)
+        })
+        .collect()
+}
+
+/// Like [`calculate_mfi_simple`], but warmup bars are `None` instead of placeholder values
+pub fn calculate_mfi_simple_checked(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    volumes: &[f64],
+    period: usize,
+) -> Result<Vec<Option<f64>>, MFIError> {
+    let len = highs.len();
+    if len != lows.len() || len != closes.len() || len != volumes.len() {
+        return Err(MFIError::InvalidInput(
+            "All price and volume arrays must have same length".to_string(),
+        ));
+    }
+
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
     let mut mfi_calculator = MFI::with_period(period)?;
     let mut results = Vec::with_capacity(len);
 
     for i in 0..len {
         let input = MFIInput {
+            timestamp: None,
             high: highs[i],
             low: lows[i],
             close: closes[i],
             volume: volumes[i],
         };
-        let output = mfi_calculator.calculate(input)?;
-        results.push(output.mfi);
+        let output = mfi_calculator.calculate_checked(input)?;
+        results.push(output.map(|output| output.mfi));
     }
 
     Ok(results)
@@ -1,3 +1,4 @@
+use crate::common::types::{Candle, GapPolicy, Summation};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
@@ -10,6 +11,42 @@ pub struct MFIConfig {
     pub overbought: f64,
     /// Oversold threshold (default: 20.0)
     pub oversold: f64,
+    /// Divisor applied to volume before money flow is accumulated (default: 1.0)
+    ///
+    /// The scale cancels out in the money flow ratio, so it does not change the MFI value.
+    /// It keeps the positive/negative sums small for pairs with huge volumes, where
+    /// `typical_price * volume` would otherwise lose float precision. Raw money flows
+    /// and the sums are reported in scaled units.
+    #[serde(default = "default_volume_scale")]
+    pub volume_scale: f64,
+    /// How the positive/negative money flow sums are maintained (default: Simple)
+    #[serde(default)]
+    pub smoothing: MfiSmoothing,
+    /// Hysteresis band around the thresholds (default: 0.0, none)
+    ///
+    /// Entering overbought requires MFI >= overbought + hysteresis and leaving it
+    /// MFI <= overbought - hysteresis (mirrored for oversold), so a value hovering
+    /// at a threshold keeps its condition.
+    #[serde(default)]
+    pub hysteresis: f64,
+    /// How the money flow sums are accumulated (default: Summation::Naive)
+    #[serde(default)]
+    pub summation: Summation,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    #[serde(default)]
+    pub gap_policy: GapPolicy,
+    /// Decimal places `mfi` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+/// Smoothing method for the MFI money flow sums
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MfiSmoothing {
+    /// Sums over a sliding window of `period` bars (standard MFI)
+    #[default]
+    Simple,
+    /// Wilder's smoothing once the first window is full: Sum = Sum - Sum / n + Current
+    Wilder,
 }
 
 impl Default for MFIConfig {
@@ -18,13 +55,126 @@ impl Default for MFIConfig {
             period: 14,
             overbought: 80.0,
             oversold: 20.0,
+            volume_scale: 1.0,
+            smoothing: MfiSmoothing::Simple,
+            hysteresis: 0.0,
+            summation: Summation::Naive,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+fn default_volume_scale() -> f64 {
+    1.0
+}
+
+impl MFIConfig {
+    /// Start building a configuration from the defaults
+    pub fn builder() -> MFIConfigBuilder {
+        MFIConfigBuilder::default()
+    }
+
+    /// Check the invariants the MFI calculator relies on
+    pub fn validate(&self) -> Result<(), MFIError> {
+        if self.period == 0 {
+            return Err(MFIError::InvalidPeriod);
+        }
+
+        if self.overbought <= self.oversold {
+            return Err(MFIError::InvalidThresholds);
         }
+
+        if self.overbought > 100.0 || self.oversold < 0.0 {
+            return Err(MFIError::InvalidThresholds);
+        }
+
+        if !self.hysteresis.is_finite() || self.hysteresis < 0.0 {
+            return Err(MFIError::InvalidThresholds);
+        }
+
+        if !self.volume_scale.is_finite() || self.volume_scale <= 0.0 {
+            return Err(MFIError::InvalidVolumeScale);
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for [`MFIConfig`] with chainable setters and a validating `build()`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MFIConfigBuilder {
+    config: MFIConfig,
+}
+
+impl MFIConfigBuilder {
+    /// Set the period for MFI calculation
+    pub fn period(mut self, period: usize) -> Self {
+        self.config.period = period;
+        self
+    }
+
+    /// Set the overbought threshold
+    pub fn overbought(mut self, overbought: f64) -> Self {
+        self.config.overbought = overbought;
+        self
+    }
+
+    /// Set the oversold threshold
+    pub fn oversold(mut self, oversold: f64) -> Self {
+        self.config.oversold = oversold;
+        self
+    }
+
+    /// Set the divisor applied to volume before accumulation
+    pub fn volume_scale(mut self, volume_scale: f64) -> Self {
+        self.config.volume_scale = volume_scale;
+        self
+    }
+
+    /// Set how the money flow sums are maintained
+    pub fn smoothing(mut self, smoothing: MfiSmoothing) -> Self {
+        self.config.smoothing = smoothing;
+        self
+    }
+
+    /// Set the hysteresis band around the overbought/oversold thresholds
+    pub fn hysteresis(mut self, hysteresis: f64) -> Self {
+        self.config.hysteresis = hysteresis;
+        self
+    }
+
+    /// Set how the money flow sums are accumulated
+    pub fn summation(mut self, summation: Summation) -> Self {
+        self.config.summation = summation;
+        self
+    }
+
+    /// Set how non-finite inputs are handled
+    pub fn gap_policy(mut self, gap_policy: GapPolicy) -> Self {
+        self.config.gap_policy = gap_policy;
+        self
+    }
+
+    /// Set the decimal places `mfi` is rounded to
+    pub fn precision(mut self, decimals: u8) -> Self {
+        self.config.precision = Some(decimals);
+        self
+    }
+
+    /// Validate and return the configuration
+    pub fn build(self) -> Result<MFIConfig, MFIError> {
+        self.config.validate()?;
+        Ok(self.config)
     }
 }
 
 /// Input data for MFI calculation (OHLCV)
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct MFIInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// High price
     pub high: f64,
     /// Low price
@@ -35,6 +185,19 @@ pub struct MFIInput {
     pub volume: f64,
 }
 
+impl From<Candle> for MFIInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            volume: candle.volume,
+        }
+    }
+}
+
 /// Raw Money Flow data point
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct MoneyFlow {
@@ -49,6 +212,9 @@ pub struct MoneyFlow {
 /// Output from MFI calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct MFIOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// Money Flow Index value (0-100)
     pub mfi: f64,
     /// Current typical price
@@ -83,12 +249,22 @@ pub struct MFIState {
     pub money_flows: VecDeque<MoneyFlow>,
     /// Previous typical price for comparison
     pub previous_typical_price: Option<f64>,
-    /// Sum of positive money flows in current period
+    /// Sum of positive money flows in current period (Wilder-smoothed in `MfiSmoothing::Wilder`)
     pub positive_money_flow_sum: f64,
-    /// Sum of negative money flows in current period
+    /// Sum of negative money flows in current period (Wilder-smoothed in `MfiSmoothing::Wilder`)
     pub negative_money_flow_sum: f64,
+    /// Rounding error carried for the positive sum by `Summation::Kahan`
+    #[serde(default)]
+    pub positive_compensation: f64,
+    /// Rounding error carried for the negative sum by `Summation::Kahan`
+    #[serde(default)]
+    pub negative_compensation: f64,
     /// Whether we have enough data for calculation
     pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<MFIInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<MFIOutput>,
 }
 
 impl MFIState {
@@ -99,7 +275,11 @@ impl MFIState {
             previous_typical_price: None,
             positive_money_flow_sum: 0.0,
             negative_money_flow_sum: 0.0,
+            positive_compensation: 0.0,
+            negative_compensation: 0.0,
             has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
         }
     }
 }
@@ -119,6 +299,8 @@ pub enum MFIError {
     InvalidPeriod,
     /// Invalid threshold values
     InvalidThresholds,
+    /// Invalid volume scale (must be positive and finite)
+    InvalidVolumeScale,
     /// Division by zero in calculation
     DivisionByZero,
 }
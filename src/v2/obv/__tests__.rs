@@ -1,8 +1,11 @@
 #[cfg(test)]
 mod tests {
+    use crate::common::assert_dense_batch;
+    use crate::common::types::Summation;
+    use crate::testutil::{main::ohlcv_series, types::OhlcvParams};
     use crate::v2::obv::{
-        main::{calculate_obv_simple, OBV},
-        types::{OBVError, OBVInput},
+        main::{calculate_obv_iter, calculate_obv_simple, calculate_obv_simple_checked, OBV},
+        types::{OBVConfig, OBVError, OBVInput, OBVNormalization, OBVPriceSource, OBVState},
     };
 
     #[test]
@@ -28,8 +31,11 @@ mod tests {
 
         for (i, (close, volume)) in test_cases.iter().enumerate() {
             let input = OBVInput {
+                timestamp: None,
                 close: *close,
                 volume: *volume,
+                high: None,
+                low: None,
             };
             let result = obv.calculate(input).unwrap();
             assert_eq!(result.obv, expected_obv[i], "Failed at index {}", i);
@@ -41,29 +47,41 @@ mod tests {
         let mut obv = OBV::new();
 
         let input1 = OBVInput {
+            timestamp: None,
             close: 100.0,
             volume: 1000.0,
+            high: None,
+            low: None,
         };
         let result1 = obv.calculate(input1).unwrap();
         assert_eq!(result1.flow_direction, 0.0); // First calculation
 
         let input2 = OBVInput {
+            timestamp: None,
             close: 105.0,
             volume: 1500.0,
+            high: None,
+            low: None,
         };
         let result2 = obv.calculate(input2).unwrap();
         assert_eq!(result2.flow_direction, 1.0); // Up
 
         let input3 = OBVInput {
+            timestamp: None,
             close: 103.0,
             volume: 1200.0,
+            high: None,
+            low: None,
         };
         let result3 = obv.calculate(input3).unwrap();
         assert_eq!(result3.flow_direction, -1.0); // Down
 
         let input4 = OBVInput {
+            timestamp: None,
             close: 103.0,
             volume: 800.0,
+            high: None,
+            low: None,
         };
         let result4 = obv.calculate(input4).unwrap();
         assert_eq!(result4.flow_direction, 0.0); // Unchanged
@@ -80,14 +98,29 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_obv_iter_matches_slice_version() {
+        let closes = vec![100.0, 105.0, 103.0, 103.0, 107.0];
+        let volumes = vec![1000.0, 1500.0, 1200.0, 800.0, 2000.0];
+
+        let from_slices = calculate_obv_simple(&closes, &volumes).unwrap();
+        let from_iter =
+            calculate_obv_iter(closes.iter().copied().zip(volumes.iter().copied())).unwrap();
+
+        assert_eq!(from_iter, from_slices);
+    }
+
     #[test]
     fn test_obv_error_handling() {
         let mut obv = OBV::new();
 
         // Test negative volume
         let input = OBVInput {
+            timestamp: None,
             close: 100.0,
             volume: -1000.0,
+            high: None,
+            low: None,
         };
         assert!(matches!(
             obv.calculate(input),
@@ -96,9 +129,301 @@ mod tests {
 
         // Test invalid price
         let input = OBVInput {
+            timestamp: None,
             close: f64::NAN,
             volume: 1000.0,
+            high: None,
+            low: None,
         };
         assert!(matches!(obv.calculate(input), Err(OBVError::InvalidPrice)));
     }
+
+    fn hlc_bar(high: f64, low: f64, close: f64, volume: f64) -> OBVInput {
+        OBVInput {
+            timestamp: None,
+            close,
+            volume,
+            high: Some(high),
+            low: Some(low),
+        }
+    }
+
+    #[test]
+    fn test_obv_price_source() {
+        // Bar 2 closes up but with a long lower wick, so its typical price falls
+        let bars = [
+            hlc_bar(101.0, 99.0, 100.0, 1000.0),
+            hlc_bar(106.0, 80.0, 105.0, 1500.0),
+            hlc_bar(104.0, 102.0, 103.0, 1200.0),
+        ];
+
+        // Close-based OBV ignores high/low and matches the close-only series
+        let mut by_close = OBV::new();
+        let close_obv: Vec<f64> = bars
+            .iter()
+            .map(|bar| by_close.calculate(*bar).unwrap().obv)
+            .collect();
+        let expected =
+            calculate_obv_simple(&[100.0, 105.0, 103.0], &[1000.0, 1500.0, 1200.0]).unwrap();
+        assert_eq!(close_obv, expected);
+
+        let mut by_typical = OBV::with_config(OBVConfig {
+            price_source: OBVPriceSource::TypicalPrice,
+            ..Default::default()
+        });
+        let typical: Vec<_> = bars
+            .iter()
+            .map(|bar| by_typical.calculate(*bar).unwrap())
+            .collect();
+
+        // Typical price: 100.0 -> 97.0 -> 103.0
+        assert_eq!(typical[1].flow_direction, -1.0);
+        assert_eq!(typical[1].obv, -500.0);
+        assert_ne!(typical[1].obv, close_obv[1]);
+        assert_eq!(typical[2].flow_direction, 1.0);
+        assert_eq!(typical[2].obv, 700.0);
+    }
+
+    #[test]
+    fn test_obv_typical_price_requires_high_low() {
+        let mut obv = OBV::with_config(OBVConfig {
+            price_source: OBVPriceSource::TypicalPrice,
+            ..Default::default()
+        });
+        let missing = OBVInput {
+            timestamp: None,
+            close: 100.0,
+            volume: 1000.0,
+            high: None,
+            low: None,
+        };
+        assert_eq!(obv.calculate(missing), Err(OBVError::InvalidHighLow));
+        assert_eq!(
+            obv.calculate(hlc_bar(99.0, 101.0, 100.0, 1000.0)),
+            Err(OBVError::InvalidHighLow)
+        );
+    }
+
+    #[test]
+    fn test_current_matches_last_output() {
+        let mut obv = OBV::new();
+        assert_eq!(obv.current(), 0.0);
+        assert_eq!(obv.previous_close(), None);
+
+        let mut last = None;
+        for (close, volume) in [(100.0, 1000.0), (105.0, 1500.0), (103.0, 1200.0)] {
+            last = Some(
+                obv.calculate(OBVInput {
+                    timestamp: None,
+                    close,
+                    volume,
+                    high: None,
+                    low: None,
+                })
+                .unwrap(),
+            );
+        }
+        assert_eq!(obv.current(), last.unwrap().obv);
+        assert_eq!(obv.previous_close(), Some(103.0));
+
+        // Accessors also work on restored state
+        let mut restored = OBV::new();
+        restored.set_state(obv.get_state().clone());
+        assert_eq!(restored.current(), 1300.0);
+        assert_eq!(restored.previous_close(), Some(103.0));
+    }
+
+    #[test]
+    fn test_empty_and_single_element_batches() {
+        assert!(calculate_obv_simple(&[], &[]).unwrap().is_empty());
+        assert!(calculate_obv_simple_checked(&[], &[]).unwrap().is_empty());
+
+        // A single bar only seeds OBV
+        assert_eq!(
+            calculate_obv_simple(&[10.0], &[500.0]).unwrap(),
+            vec![500.0]
+        );
+        assert_eq!(
+            calculate_obv_simple_checked(&[10.0], &[500.0]).unwrap(),
+            vec![None]
+        );
+
+        assert_eq!(
+            calculate_obv_simple_checked(&[10.0, 11.0], &[500.0, 200.0]).unwrap(),
+            vec![None, Some(700.0)]
+        );
+    }
+
+    #[test]
+    fn test_kahan_summation_over_long_series() {
+        let run = |summation: Summation| {
+            let mut obv = OBV::with_config(OBVConfig {
+                summation,
+                ..OBVConfig::default()
+            });
+            // Rising closes add every volume, alternating large and small
+            let mut last = 0.0;
+            for i in 0..1_000_000 {
+                last = obv
+                    .calculate(OBVInput {
+                        timestamp: None,
+                        close: 1.0 + i as f64,
+                        volume: if i % 2 == 0 { 1e9 } else { 0.1 },
+                        high: None,
+                        low: None,
+                    })
+                    .unwrap()
+                    .obv;
+            }
+            last
+        };
+        let reference = 500_000.0 * 1e9 + 500_000.0 * 0.1;
+
+        let naive_error = (run(Summation::Naive) - reference).abs();
+        let kahan_error = (run(Summation::Kahan) - reference).abs();
+        assert!(kahan_error < 1.0);
+        assert!(kahan_error < naive_error);
+    }
+
+    fn normalized_series(normalization: OBVNormalization, volume_factor: f64) -> Vec<Option<f64>> {
+        let mut obv = OBV::with_config(OBVConfig {
+            normalization,
+            ..OBVConfig::default()
+        });
+        ohlcv_series(7, 200, OhlcvParams::default())
+            .into_iter()
+            .map(|candle| {
+                let input = OBVInput {
+                    volume: candle.volume * volume_factor,
+                    ..OBVInput::from(candle)
+                };
+                obv.calculate(input).unwrap().normalized
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_normalized_obv_is_scale_invariant() {
+        for normalization in [
+            OBVNormalization::VolumeAverage { lookback: 20 },
+            OBVNormalization::ZScore { lookback: 20 },
+        ] {
+            let base = normalized_series(normalization, 1.0);
+            let scaled = normalized_series(normalization, 1e6);
+
+            // Warmup until the lookback is full
+            assert!(base[..19].iter().all(Option::is_none));
+            for (a, b) in base[19..].iter().zip(&scaled[19..]) {
+                let (a, b) = (a.unwrap(), b.unwrap());
+                assert!((a - b).abs() <= 1e-9 * a.abs().max(1.0));
+            }
+        }
+
+        assert!(normalized_series(OBVNormalization::None, 1.0)
+            .iter()
+            .all(Option::is_none));
+    }
+
+    #[test]
+    fn test_normalized_obv_values() {
+        let mut obv = OBV::with_config(OBVConfig {
+            normalization: OBVNormalization::VolumeAverage { lookback: 2 },
+            ..OBVConfig::default()
+        });
+        let outputs = calculate_with(&mut obv, &[(10.0, 100.0), (11.0, 300.0), (10.0, 200.0)]);
+        assert_eq!(outputs[0], None);
+        // OBV 400 over average volume 200
+        assert_eq!(outputs[1], Some(2.0));
+        // OBV 200 over average volume 250
+        assert_eq!(outputs[2], Some(0.8));
+
+        let mut obv = OBV::with_config(OBVConfig {
+            normalization: OBVNormalization::ZScore { lookback: 2 },
+            ..OBVConfig::default()
+        });
+        let outputs = calculate_with(&mut obv, &[(10.0, 100.0), (10.0, 50.0), (11.0, 100.0)]);
+        // Flat OBV has no spread
+        assert_eq!(outputs[1], None);
+        // OBV 200 against window [100, 200]: one standard deviation above the mean
+        assert_eq!(outputs[2], Some(1.0));
+
+        assert_eq!(
+            OBV::try_with_config(OBVConfig {
+                normalization: OBVNormalization::ZScore { lookback: 0 },
+                ..OBVConfig::default()
+            })
+            .err(),
+            Some(OBVError::InvalidLookback)
+        );
+    }
+
+    fn calculate_with(obv: &mut OBV, bars: &[(f64, f64)]) -> Vec<Option<f64>> {
+        bars.iter()
+            .map(|&(close, volume)| {
+                obv.calculate(OBVInput {
+                    timestamp: None,
+                    close,
+                    volume,
+                    high: None,
+                    low: None,
+                })
+                .unwrap()
+                .normalized
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_restores_baseline_state() {
+        // State saved by indexes-rs 1.0.1, when the previous price was `previous_close`
+        let json = r#"{"previous_close":12.0,"cumulative_obv":2200.0,"config":{"cumulative":true},"is_first":false}"#;
+        let state: OBVState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.previous_price, Some(12.0));
+        assert_eq!(state.config.price_source, OBVPriceSource::Close);
+
+        let mut restored = OBV::new();
+        restored.set_state(state);
+        let output = restored
+            .calculate(OBVInput {
+                timestamp: None,
+                close: 12.5,
+                volume: 500.0,
+                high: None,
+                low: None,
+            })
+            .unwrap();
+        assert_eq!(output.obv, 2700.0);
+    }
+
+    #[test]
+    fn test_calculate_checked_skips_seed_bar() {
+        let mut obv = OBV::new();
+        assert_eq!(
+            obv.calculate_checked(OBVInput {
+                timestamp: None,
+                close: 10.0,
+                volume: 1000.0,
+                high: None,
+                low: None,
+            })
+            .unwrap(),
+            None
+        );
+
+        let output = obv
+            .calculate_checked(OBVInput {
+                timestamp: None,
+                close: 11.0,
+                volume: 1500.0,
+                high: None,
+                low: None,
+            })
+            .unwrap();
+        assert_eq!(output.map(|output| output.obv), Some(2500.0));
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(OBV::new(), OBVInput);
+    }
 }
@@ -1,4 +1,9 @@
-use crate::v2::obv::types::{OBVConfig, OBVError, OBVInput, OBVOutput, OBVState};
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::transform::price_source::main::typical_price;
+use crate::v2::obv::types::{
+    OBVConfig, OBVError, OBVInput, OBVNormalization, OBVOutput, OBVPriceSource, OBVState,
+};
 
 /// On Balance Volume (OBV) Indicator
 ///
@@ -9,6 +14,9 @@ use crate::v2::obv::types::{OBVConfig, OBVError, OBVInput, OBVOutput, OBVState};
 /// - If Close > Previous Close: OBV = Previous OBV + Volume
 /// - If Close < Previous Close: OBV = Previous OBV - Volume
 /// - If Close = Previous Close: OBV = Previous OBV
+///
+/// With `OBVPriceSource::TypicalPrice` the comparison uses (H+L+C)/3 instead of the close.
+/// `OBVConfig::normalization` adds a scale-free `normalized` output for comparing assets.
 #[derive(Default)]
 pub struct OBV {
     state: OBVState,
@@ -27,68 +35,189 @@ impl OBV {
         }
     }
 
+    /// Create a new OBV calculator, rejecting an invalid configuration
+    pub fn try_with_config(config: OBVConfig) -> Result<Self, OBVError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
     /// Calculate OBV for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
     pub fn calculate(&mut self, input: OBVInput) -> Result<OBVOutput, OBVError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| OBVOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(OBVError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(OBVError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.obv = round_to(output.obv, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// The first bar has no previous price to compare against, so `calculate`
+    /// seeds OBV with its volume; this variant returns `Ok(None)` for it instead.
+    pub fn calculate_checked(&mut self, input: OBVInput) -> Result<Option<OBVOutput>, OBVError> {
+        let seeding = self.state.is_first;
+        let output = self.calculate(input)?;
+        Ok((!seeding).then_some(output))
+    }
+
+    /// Calculate OBV for a batch of inputs
+    pub fn calculate_batch(&mut self, inputs: &[OBVInput]) -> Result<Vec<OBVOutput>, OBVError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[OBVInput],
+    ) -> Result<DenseBatch<OBVOutput>, OBVError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            OBVError::InvalidInput,
+        )
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = OBVState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &OBVState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: OBVState) {
+        self.state = state;
+    }
+
+    /// Get the current cumulative OBV without feeding a new bar
+    pub fn current(&self) -> f64 {
+        self.state.cumulative_obv
+    }
+
+    /// Get the previous bar's comparison price
+    ///
+    /// This is the close, or the typical price with `OBVPriceSource::TypicalPrice`.
+    pub fn previous_close(&self) -> Option<f64> {
+        self.state.previous_price
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(&mut self, input: OBVInput) -> Result<OBVOutput, OBVError> {
         // Validate input
         self.validate_input(&input)?;
+        let price = self.source_price(&input)?;
 
         let flow_direction = if self.state.is_first {
             // First calculation - no direction yet
-            self.state.previous_close = Some(input.close);
+            self.state.previous_price = Some(price);
             self.state.cumulative_obv = input.volume;
+            self.state.compensation = 0.0;
             self.state.is_first = false;
             0.0
         } else {
-            let prev_close = self.state.previous_close.unwrap();
-            let direction = self.determine_flow_direction(input.close, prev_close);
+            let prev_price = self.state.previous_price.unwrap();
+            let direction = self.determine_flow_direction(price, prev_price);
 
             // Update OBV based on price direction
+            let summation = self.state.config.summation;
             match direction {
                 d if d > 0.0 => {
                     // Price went up - add volume
-                    self.state.cumulative_obv += input.volume;
+                    summation.add(
+                        &mut self.state.cumulative_obv,
+                        &mut self.state.compensation,
+                        input.volume,
+                    );
                 }
                 d if d < 0.0 => {
                     // Price went down - subtract volume
-                    self.state.cumulative_obv -= input.volume;
+                    summation.add(
+                        &mut self.state.cumulative_obv,
+                        &mut self.state.compensation,
+                        -input.volume,
+                    );
                 }
                 _ => {
                     // Price unchanged - OBV stays the same
                 }
             }
 
-            self.state.previous_close = Some(input.close);
+            self.state.previous_price = Some(price);
             direction
         };
 
         Ok(OBVOutput {
+            timestamp: None,
             obv: self.state.cumulative_obv,
             flow_direction,
+            normalized: self.update_normalization(input.volume),
         })
     }
 
-    /// Calculate OBV for a batch of inputs
-    pub fn calculate_batch(&mut self, inputs: &[OBVInput]) -> Result<Vec<OBVOutput>, OBVError> {
-        inputs.iter().map(|input| self.calculate(*input)).collect()
-    }
+    /// Feed the bar to the normalization window and return the normalized OBV
+    fn update_normalization(&mut self, volume: f64) -> Option<f64> {
+        let obv = self.state.cumulative_obv;
+        let normalization = self.state.config.normalization;
+        let window = self.state.normalization_window.as_mut()?;
 
-    /// Reset the calculator state
-    pub fn reset(&mut self) {
-        self.state = OBVState::new(self.state.config);
+        match normalization {
+            OBVNormalization::None => None,
+            OBVNormalization::VolumeAverage { .. } => {
+                window.push(volume.abs());
+                let average = window.mean().filter(|_| window.is_full())?;
+                (average > 0.0).then(|| obv / average)
+            }
+            OBVNormalization::ZScore { .. } => {
+                window.push(obv);
+                let mean = window.mean().filter(|_| window.is_full())?;
+                let variance =
+                    window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / window.len() as f64;
+                let std_dev = variance.sqrt();
+                // A flat window has no spread to scale by
+                (std_dev > f64::EPSILON * mean.abs().max(1.0)).then(|| (obv - mean) / std_dev)
+            }
+        }
     }
 
-    /// Get current state (for serialization/debugging)
-    pub fn get_state(&self) -> &OBVState {
-        &self.state
+    fn is_finite_input(input: &OBVInput) -> bool {
+        input.close.is_finite()
+            && input.volume.is_finite()
+            && input.high.is_none_or(f64::is_finite)
+            && input.low.is_none_or(f64::is_finite)
     }
 
-    /// Restore state (for deserialization)
-    pub fn set_state(&mut self, state: OBVState) {
-        self.state = state;
-    }
-
-    // Private helper methods
-
     fn validate_input(&self, input: &OBVInput) -> Result<(), OBVError> {
         if input.volume < 0.0 {
             return Err(OBVError::NegativeVolume);
@@ -101,6 +230,16 @@ impl OBV {
         Ok(())
     }
 
+    fn source_price(&self, input: &OBVInput) -> Result<f64, OBVError> {
+        match self.state.config.price_source {
+            OBVPriceSource::Close => Ok(input.close),
+            OBVPriceSource::TypicalPrice => match (input.high, input.low) {
+                (Some(high), Some(low)) if high >= low => Ok(typical_price(high, low, input.close)),
+                _ => Err(OBVError::InvalidHighLow),
+            },
+        }
+    }
+
     fn determine_flow_direction(&self, current_close: f64, previous_close: f64) -> f64 {
         if current_close > previous_close {
             1.0 // Up
@@ -112,7 +251,10 @@ impl OBV {
     }
 }
 
-/// Convenience function to calculate OBV for a single input without maintaining state
+/// Convenience function to calculate OBV for a series of bars without maintaining state
+///
+/// The first bar has no price change yet; its OBV is the seed (its own volume). Use
+/// [`calculate_obv_simple_checked`] to get `None` for that bar instead.
 pub fn calculate_obv_simple(close_prices: &[f64], volumes: &[f64]) -> Result<Vec<f64>, OBVError> {
     if close_prices.len() != volumes.len() {
         return Err(OBVError::InvalidInput(
@@ -124,17 +266,46 @@ pub fn calculate_obv_simple(close_prices: &[f64], volumes: &[f64]) -> Result<Vec
         return Ok(Vec::new());
     }
 
-    let mut obv_calculator = OBV::new();
-    let mut results = Vec::with_capacity(close_prices.len());
+    calculate_obv_iter(close_prices.iter().copied().zip(volumes.iter().copied()))
+}
 
-    for (close, volume) in close_prices.iter().zip(volumes.iter()) {
-        let input = OBVInput {
-            close: *close,
-            volume: *volume,
-        };
-        let output = obv_calculator.calculate(input)?;
-        results.push(output.obv);
+/// Like [`calculate_obv_simple`], but the first bar, which has no price change yet, is `None`
+///
+/// An empty series gives an empty vector and a single bar gives `[None]`, matching the
+/// `_checked` variants of the other indicators.
+pub fn calculate_obv_simple_checked(
+    close_prices: &[f64],
+    volumes: &[f64],
+) -> Result<Vec<Option<f64>>, OBVError> {
+    let mut values = calculate_obv_simple(close_prices, volumes)?
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+    if let Some(first) = values.first_mut() {
+        *first = None;
     }
 
-    Ok(results)
+    Ok(values)
+}
+
+/// Like [`calculate_obv_simple`], but accepts any iterator of `(close, volume)` pairs
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_obv_iter(
+    bars: impl IntoIterator<Item = (f64, f64)>,
+) -> Result<Vec<f64>, OBVError> {
+    let mut obv_calculator = OBV::new();
+
+    bars.into_iter()
+        .map(|(close, volume)| {
+            let input = OBVInput {
+                timestamp: None,
+                close,
+                volume,
+                high: None,
+                low: None,
+            };
+            obv_calculator.calculate(input).map(|output| output.obv)
+        })
+        .collect()
 }
@@ -1,3 +1,5 @@
+use crate::common::types::{Candle, GapPolicy, Summation};
+use crate::util::rolling_sum::main::RollingSum;
 use serde::{Deserialize, Serialize};
 
 /// Configuration for OBV calculation
@@ -5,52 +7,170 @@ use serde::{Deserialize, Serialize};
 pub struct OBVConfig {
     /// Whether to use cumulative calculation (default: true)
     pub cumulative: bool,
+    /// Price compared bar to bar to decide the volume direction (default: Close)
+    #[serde(default)]
+    pub price_source: OBVPriceSource,
+    /// How the cumulative OBV is accumulated (default: Summation::Naive)
+    #[serde(default)]
+    pub summation: Summation,
+    /// Scale-free `normalized` output reported next to the raw OBV (default: None)
+    #[serde(default)]
+    pub normalization: OBVNormalization,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    #[serde(default)]
+    pub gap_policy: GapPolicy,
+    /// Decimal places `obv` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
 }
 
 impl Default for OBVConfig {
     fn default() -> Self {
-        Self { cumulative: true }
+        Self {
+            cumulative: true,
+            price_source: OBVPriceSource::Close,
+            summation: Summation::Naive,
+            normalization: OBVNormalization::None,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl OBVConfig {
+    /// Check the invariants the OBV calculator relies on
+    pub fn validate(&self) -> Result<(), OBVError> {
+        if self.normalization.lookback() == Some(0) {
+            return Err(OBVError::InvalidLookback);
+        }
+
+        Ok(())
+    }
+}
+
+/// How OBV is made comparable across assets
+///
+/// Raw OBV is a running total of volume, so its magnitude depends on how much the
+/// asset trades. Both normalizations divide that out: multiplying every volume by a
+/// constant leaves the `normalized` output unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum OBVNormalization {
+    /// No normalized output (default)
+    #[default]
+    None,
+    /// OBV divided by the average volume of the last `lookback` bars
+    ///
+    /// The result reads as "net bars of average volume" accumulated so far.
+    VolumeAverage { lookback: usize },
+    /// Z-score of OBV against its own mean and population standard deviation over
+    /// the last `lookback` bars
+    ZScore { lookback: usize },
+}
+
+impl OBVNormalization {
+    /// Number of bars the normalization looks back over, if any
+    pub fn lookback(&self) -> Option<usize> {
+        match *self {
+            OBVNormalization::None => None,
+            OBVNormalization::VolumeAverage { lookback }
+            | OBVNormalization::ZScore { lookback } => Some(lookback),
+        }
     }
 }
 
+/// Price used to determine whether a bar is an up or down bar
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum OBVPriceSource {
+    /// Closing price (traditional OBV)
+    #[default]
+    Close,
+    /// Typical price ((H+L+C)/3); requires `high` and `low` on every input
+    TypicalPrice,
+}
+
 /// Input data for OBV calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct OBVInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// Current closing price
     pub close: f64,
     /// Current volume
     pub volume: f64,
+    /// High price (only needed for `OBVPriceSource::TypicalPrice`)
+    #[serde(default)]
+    pub high: Option<f64>,
+    /// Low price (only needed for `OBVPriceSource::TypicalPrice`)
+    #[serde(default)]
+    pub low: Option<f64>,
+}
+
+impl From<Candle> for OBVInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            close: candle.close,
+            volume: candle.volume,
+            high: Some(candle.high),
+            low: Some(candle.low),
+        }
+    }
 }
 
 /// Output from OBV calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct OBVOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// On Balance Volume value
     pub obv: f64,
     /// Optional: Volume flow direction (1.0 = up, -1.0 = down, 0.0 = unchanged)
     pub flow_direction: f64,
+    /// OBV scaled as configured by `OBVConfig::normalization`
+    ///
+    /// `None` without a normalization, until `lookback` bars have been seen, or when
+    /// the scale is zero (no volume, or a flat OBV for `ZScore`).
+    #[serde(default)]
+    pub normalized: Option<f64>,
 }
 
 /// OBV calculation state
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OBVState {
-    /// Previous closing price
-    pub previous_close: Option<f64>,
+    /// Previous source price (close or typical price)
+    #[serde(alias = "previous_close")]
+    pub previous_price: Option<f64>,
     /// Current cumulative OBV value
     pub cumulative_obv: f64,
+    /// Rounding error carried by `Summation::Kahan`
+    #[serde(default)]
+    pub compensation: f64,
     /// Configuration
     pub config: OBVConfig,
     /// Whether this is the first calculation
     pub is_first: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<OBVInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<OBVOutput>,
+    /// Recent volumes (`VolumeAverage`) or OBV values (`ZScore`) for the normalization
+    #[serde(default)]
+    pub normalization_window: Option<RollingSum>,
 }
 
 impl OBVState {
     pub fn new(config: OBVConfig) -> Self {
         Self {
-            previous_close: None,
+            previous_price: None,
             cumulative_obv: 0.0,
+            compensation: 0.0,
             config,
             is_first: true,
+            last_valid_input: None,
+            last_output: None,
+            normalization_window: config.normalization.lookback().map(RollingSum::new),
         }
     }
 }
@@ -64,4 +184,8 @@ pub enum OBVError {
     NegativeVolume,
     /// Invalid price (NaN or infinite)
     InvalidPrice,
+    /// High/low missing or inconsistent while using `OBVPriceSource::TypicalPrice`
+    InvalidHighLow,
+    /// Normalization lookback of zero
+    InvalidLookback,
 }
@@ -1,8 +1,13 @@
 #[cfg(test)]
 mod tests {
+    use crate::common::assert_dense_batch;
+    use crate::common::types::{TiePolicy, TrendDirection};
     use crate::v2::parabolic_sar::{
         main::{calculate_parabolic_sar_simple, ParabolicSAR},
-        types::{ParabolicSARError, ParabolicSARInput, TrendDirection},
+        types::{
+            AfResetPolicy, ParabolicSARConfig, ParabolicSARError, ParabolicSARInput,
+            ParabolicSARState, SARDirection,
+        },
     };
 
     #[test]
@@ -21,6 +26,7 @@ mod tests {
         let mut results = Vec::new();
         for (high, low) in &test_data {
             let input = ParabolicSARInput {
+                timestamp: None,
                 high: *high,
                 low: *low,
                 close: None,
@@ -34,7 +40,7 @@ mod tests {
 
         // In an uptrend, SAR should generally be below the lows
         for (i, result) in results.iter().enumerate().skip(2) {
-            if matches!(result.trend, TrendDirection::Up) {
+            if matches!(result.trend, SARDirection::Up) {
                 // SAR should be below current low in uptrend
                 assert!(
                     result.sar <= test_data[i].1,
@@ -64,6 +70,7 @@ mod tests {
         let mut results = Vec::new();
         for (i, (high, low)) in test_data.iter().enumerate() {
             let input = ParabolicSARInput {
+                timestamp: None,
                 high: *high,
                 low: *low,
                 close: None,
@@ -88,7 +95,7 @@ mod tests {
         for (i, result) in results.iter().enumerate() {
             if result.trend_reversal {
                 println!("Reversal detected at period {}: {:?}", i, result);
-                assert_eq!(result.trend, TrendDirection::Down);
+                assert_eq!(result.trend, SARDirection::Down);
                 break;
             }
         }
@@ -109,6 +116,7 @@ mod tests {
         let mut prev_af = 0.0;
         for (i, (high, low)) in test_data.iter().enumerate() {
             let input = ParabolicSARInput {
+                timestamp: None,
                 high: *high,
                 low: *low,
                 close: None,
@@ -131,6 +139,7 @@ mod tests {
 
         // Test invalid HL (high < low)
         let input = ParabolicSARInput {
+            timestamp: None,
             high: 8.0,
             low: 10.0,
             close: None,
@@ -186,6 +195,7 @@ mod tests {
 
         for (high, low) in uptrend_data {
             let input = ParabolicSARInput {
+                timestamp: None,
                 high,
                 low,
                 close: None,
@@ -194,8 +204,283 @@ mod tests {
 
             // After the first two setup periods, should be in uptrend
             if sar.state.trend_periods > 2 && !result.trend_reversal {
-                assert_eq!(result.trend, TrendDirection::Up);
+                assert_eq!(result.trend, SARDirection::Up);
             }
         }
     }
+
+    #[test]
+    fn test_sar_direction_conversions() {
+        for direction in [SARDirection::Up, SARDirection::Down] {
+            let shared: TrendDirection = direction.into();
+            assert_eq!(SARDirection::try_from(shared), Ok(direction));
+        }
+
+        assert_eq!(TrendDirection::from(SARDirection::Up), TrendDirection::Up);
+        assert!(matches!(
+            SARDirection::try_from(TrendDirection::Sideways),
+            Err(ParabolicSARError::InvalidInput(_))
+        ));
+    }
+
+    /// Run a steady uptrend followed by a sharp drop, returning the AF before the
+    /// reversal, on the reversal bar, and on the bar after it
+    fn af_around_reversal(af_reset: AfResetPolicy) -> (f64, f64, f64) {
+        let mut sar = ParabolicSAR::with_config(ParabolicSARConfig {
+            af_reset,
+            ..Default::default()
+        });
+        let bar = |high: f64, low: f64| ParabolicSARInput {
+            timestamp: None,
+            high,
+            low,
+            close: None,
+        };
+
+        let mut before = 0.0;
+        for i in 0..6 {
+            let base = 10.0 + i as f64;
+            let output = sar.calculate(bar(base + 1.0, base - 1.0)).unwrap();
+            assert!(!output.trend_reversal);
+            before = output.acceleration_factor;
+        }
+
+        let reversal = sar.calculate(bar(8.0, 5.0)).unwrap();
+        assert!(reversal.trend_reversal);
+        assert_eq!(reversal.trend, SARDirection::Down);
+
+        // New low extends the downtrend, so the AF is incremented once
+        let after = sar.calculate(bar(6.0, 4.0)).unwrap();
+        assert!(!after.trend_reversal);
+
+        (
+            before,
+            reversal.acceleration_factor,
+            after.acceleration_factor,
+        )
+    }
+
+    #[test]
+    fn test_af_reset_policies_on_reversal() {
+        let (before, reversal, after) = af_around_reversal(AfResetPolicy::Full);
+        assert!((before - 0.10).abs() < 1e-10, "before {}", before);
+        assert!((reversal - 0.02).abs() < 1e-10);
+        assert!((after - 0.04).abs() < 1e-10);
+
+        let (_, reversal, after) = af_around_reversal(AfResetPolicy::Half);
+        assert!((reversal - 0.05).abs() < 1e-10);
+        assert!((after - 0.07).abs() < 1e-10);
+
+        let (_, reversal, after) = af_around_reversal(AfResetPolicy::Keep);
+        assert!((reversal - 0.10).abs() < 1e-10);
+        assert!((after - 0.12).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_invalid_config_rejected_at_construction() {
+        let config = ParabolicSARConfig {
+            acceleration_start: 0.3,
+            acceleration_maximum: 0.2,
+            ..Default::default()
+        };
+        assert_eq!(
+            ParabolicSAR::try_with_config(config).err(),
+            Some(ParabolicSARError::InvalidAcceleration)
+        );
+
+        // The infallible constructor reports the same error on every bar
+        let mut psar = ParabolicSAR::with_config(config);
+        let input = ParabolicSARInput {
+            timestamp: None,
+            high: 10.0,
+            low: 9.0,
+            close: None,
+        };
+        assert_eq!(
+            psar.calculate(input),
+            Err(ParabolicSARError::InvalidAcceleration)
+        );
+    }
+
+    #[test]
+    fn test_valid_config_is_not_revalidated_per_bar() {
+        let mut psar = ParabolicSAR::try_with_config(ParabolicSARConfig::default()).unwrap();
+        let input = ParabolicSARInput {
+            timestamp: None,
+            high: 10.0,
+            low: 9.0,
+            close: None,
+        };
+        psar.calculate(input).unwrap();
+
+        // Rules are checked at construction only, not re-run on each bar
+        psar.state.config.acceleration_maximum = 0.0;
+        assert!(psar.calculate(input).is_ok());
+
+        // Restoring a state validates its config once
+        let state = psar.get_state().clone();
+        psar.set_state(state);
+        assert_eq!(
+            psar.calculate(input),
+            Err(ParabolicSARError::InvalidAcceleration)
+        );
+    }
+
+    #[test]
+    fn test_tie_policy_on_flat_series() {
+        let flat = ParabolicSARInput {
+            timestamp: None,
+            high: 11.0,
+            low: 9.0,
+            close: Some(10.0),
+        };
+
+        let run = |tie_policy: TiePolicy| {
+            let mut sar = ParabolicSAR::with_config(ParabolicSARConfig {
+                tie_policy,
+                ..ParabolicSARConfig::default()
+            });
+            sar.calculate_batch(&[flat; 10]).unwrap()
+        };
+
+        // Neutral: each flat bar touches the SAR and flips the trend
+        let neutral = run(TiePolicy::Neutral);
+        assert!(neutral[2..].iter().all(|o| o.trend_reversal));
+
+        // CarryForward: the initial downtrend is kept
+        let carried = run(TiePolicy::CarryForward);
+        assert!(carried[1..]
+            .iter()
+            .all(|o| o.trend == SARDirection::Down && !o.trend_reversal));
+        assert_eq!(carried.last().unwrap().trend_periods, 9);
+    }
+
+    #[test]
+    fn test_trend_periods_across_reversal() {
+        let bar = |high: f64, low: f64| ParabolicSARInput {
+            timestamp: None,
+            high,
+            low,
+            close: None,
+        };
+        let mut sar = ParabolicSAR::new();
+        assert_eq!(sar.trend_periods(), 0);
+
+        // Rising bars: uptrend starts on the second bar and counts up
+        let rising = [
+            bar(10.0, 9.0),
+            bar(11.0, 10.0),
+            bar(12.0, 11.0),
+            bar(13.0, 12.0),
+        ];
+        let mut counts = Vec::new();
+        for input in rising {
+            let output = sar.calculate(input).unwrap();
+            assert_eq!(output.trend_periods, sar.trend_periods());
+            counts.push(sar.trend_periods());
+        }
+        assert_eq!(counts, vec![1, 1, 2, 3]);
+        assert_eq!(sar.current_trend(), Some(SARDirection::Up));
+
+        // A bar that crashes through the SAR reverses and restarts the count
+        let output = sar.calculate(bar(9.0, 5.0)).unwrap();
+        assert!(output.trend_reversal);
+        assert_eq!(output.trend, SARDirection::Down);
+        assert_eq!(sar.trend_periods(), 1);
+
+        sar.calculate(bar(8.0, 4.0)).unwrap();
+        assert_eq!(sar.trend_periods(), 2);
+
+        sar.reset();
+        assert_eq!(sar.trend_periods(), 0);
+    }
+
+    #[test]
+    fn test_af_at_max_in_sustained_trend() {
+        let mut sar = ParabolicSAR::new();
+        let inputs: Vec<_> = (0..20)
+            .map(|i| ParabolicSARInput {
+                timestamp: None,
+                high: 11.0 + i as f64,
+                low: 9.0 + i as f64,
+                close: None,
+            })
+            .collect();
+        let outputs = sar.calculate_batch(&inputs).unwrap();
+
+        // Every bar makes a new high, so the AF climbs 0.02 per bar until the 0.2 cap
+        assert!(!outputs[2].af_at_max);
+        let last = outputs.last().unwrap();
+        assert_eq!(last.acceleration_factor, 0.2);
+        assert!(last.af_at_max);
+        for output in &outputs {
+            assert_eq!(output.af_at_max, output.acceleration_factor == 0.2);
+        }
+        // Once capped, the flag stays set for the rest of the trend
+        let first_capped = outputs.iter().position(|o| o.af_at_max).unwrap();
+        assert!(outputs[first_capped..].iter().all(|o| o.af_at_max));
+    }
+
+    #[test]
+    fn test_restores_baseline_state() {
+        // State saved by indexes-rs 1.0.1 with the default acceleration settings
+        let json = r#"{"config":{"acceleration_start":0.02,"acceleration_increment":0.02,"acceleration_maximum":0.2},"trend":"Up","current_sar":9.589304,"acceleration_factor":0.06,"extreme_point":13.0,"previous_high":12.8,"previous_low":11.6,"previous_close":12.0,"trend_periods":4,"is_first":false,"is_second":false}"#;
+        let state: ParabolicSARState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.config.af_reset, AfResetPolicy::Full);
+        let mut restored = ParabolicSAR::new();
+        restored.set_state(state);
+
+        let input = |(high, low, close)| ParabolicSARInput {
+            timestamp: None,
+            high,
+            low,
+            close: Some(close),
+        };
+        let mut fresh = ParabolicSAR::new();
+        for bar in [
+            (11.0, 9.0, 10.0),
+            (12.0, 10.0, 11.5),
+            (12.5, 10.5, 11.0),
+            (13.0, 11.0, 12.5),
+            (12.8, 11.6, 12.0),
+        ] {
+            fresh.calculate(input(bar)).unwrap();
+        }
+
+        let next = (13.4, 12.1, 13.2);
+        let expected = fresh.calculate(input(next)).unwrap();
+        let actual = restored.calculate(input(next)).unwrap();
+        assert!((actual.sar - expected.sar).abs() < 1e-9);
+        assert_eq!(actual.trend, expected.trend);
+        assert_eq!(actual.acceleration_factor, expected.acceleration_factor);
+    }
+
+    #[test]
+    fn test_calculate_checked_skips_placeholder_bar() {
+        let mut sar = ParabolicSAR::new();
+        let first = sar
+            .calculate_checked(ParabolicSARInput {
+                timestamp: None,
+                high: 11.0,
+                low: 9.0,
+                close: None,
+            })
+            .unwrap();
+        assert_eq!(first, None);
+
+        let second = sar
+            .calculate_checked(ParabolicSARInput {
+                timestamp: None,
+                high: 12.0,
+                low: 10.0,
+                close: None,
+            })
+            .unwrap();
+        assert!(second.is_some());
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(ParabolicSAR::new(), ParabolicSARInput);
+    }
 }
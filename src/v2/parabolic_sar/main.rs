@@ -1,6 +1,8 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction, TiePolicy};
 use crate::v2::parabolic_sar::types::{
-    ParabolicSARConfig, ParabolicSARError, ParabolicSARInput, ParabolicSAROutput,
-    ParabolicSARState, TrendDirection,
+    AfResetPolicy, ParabolicSARConfig, ParabolicSARError, ParabolicSARInput, ParabolicSAROutput,
+    ParabolicSARState, SARDirection,
 };
 
 /// Parabolic SAR (Stop and Reverse) Indicator
@@ -17,6 +19,8 @@ use crate::v2::parabolic_sar::types::{
 /// - EP = Extreme Point (highest high in uptrend, lowest low in downtrend)
 pub struct ParabolicSAR {
     pub state: ParabolicSARState,
+    /// Configuration error found at construction
+    config_error: Option<ParabolicSARError>,
 }
 
 impl ParabolicSAR {
@@ -35,6 +39,7 @@ impl ParabolicSAR {
             acceleration_start: start,
             acceleration_increment: increment,
             acceleration_maximum: maximum,
+            ..Default::default()
         };
 
         // Validate configuration
@@ -49,30 +54,64 @@ impl ParabolicSAR {
     pub fn with_config(config: ParabolicSARConfig) -> Self {
         Self {
             state: ParabolicSARState::new(config),
+            config_error: config.validate().err(),
         }
     }
 
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: ParabolicSARConfig) -> Result<Self, ParabolicSARError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
     /// Calculate Parabolic SAR for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
     pub fn calculate(
         &mut self,
         input: ParabolicSARInput,
     ) -> Result<ParabolicSAROutput, ParabolicSARError> {
-        // Validate input
-        self.validate_input(&input)?;
-        self.validate_config()?;
-
-        let result = if self.state.is_first {
-            self.handle_first_calculation(input)
-        } else if self.state.is_second {
-            self.handle_second_calculation(input)
-        } else {
-            self.handle_normal_calculation(input)
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| ParabolicSAROutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(ParabolicSARError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(ParabolicSARError::InvalidPrice),
         };
 
-        // Update state for next calculation
-        self.update_state_after_calculation(input);
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.sar = round_to(output.sar, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
 
-        result
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// The first bar only seeds the extreme point; `calculate` reports its low as a
+    /// placeholder SAR with an up trend, while this variant returns `Ok(None)`.
+    pub fn calculate_checked(
+        &mut self,
+        input: ParabolicSARInput,
+    ) -> Result<Option<ParabolicSAROutput>, ParabolicSARError> {
+        let output = self.calculate(input)?;
+        Ok((!self.state.is_second).then_some(output))
     }
 
     /// Calculate Parabolic SAR for a batch of inputs
@@ -83,6 +122,20 @@ impl ParabolicSAR {
         inputs.iter().map(|input| self.calculate(*input)).collect()
     }
 
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[ParabolicSARInput],
+    ) -> Result<DenseBatch<ParabolicSAROutput>, ParabolicSARError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            ParabolicSARError::InvalidInput,
+        )
+    }
+
     /// Reset the calculator state
     pub fn reset(&mut self) {
         self.state = ParabolicSARState::new(self.state.config);
@@ -95,11 +148,12 @@ impl ParabolicSAR {
 
     /// Restore state (for deserialization)
     pub fn set_state(&mut self, state: ParabolicSARState) {
+        self.config_error = state.config.validate().err();
         self.state = state;
     }
 
     /// Get current trend direction
-    pub fn current_trend(&self) -> Option<TrendDirection> {
+    pub fn current_trend(&self) -> Option<SARDirection> {
         self.state.trend
     }
 
@@ -108,8 +162,39 @@ impl ParabolicSAR {
         self.state.acceleration_factor
     }
 
+    /// Get the number of periods the current trend has lasted (1 on the bar a trend starts)
+    pub fn trend_periods(&self) -> usize {
+        self.state.trend_periods
+    }
+
     // Private helper methods
 
+    fn calculate_bar(
+        &mut self,
+        input: ParabolicSARInput,
+    ) -> Result<ParabolicSAROutput, ParabolicSARError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        let result = if self.state.is_first {
+            self.handle_first_calculation(input)
+        } else if self.state.is_second {
+            self.handle_second_calculation(input)
+        } else {
+            self.handle_normal_calculation(input)
+        };
+
+        // Update state for next calculation
+        self.update_state_after_calculation(input);
+
+        result
+    }
+
+    fn is_finite_input(input: &ParabolicSARInput) -> bool {
+        input.high.is_finite() && input.low.is_finite()
+    }
+
     fn validate_input(&self, input: &ParabolicSARInput) -> Result<(), ParabolicSARError> {
         // Check for valid prices
         if !input.high.is_finite() || !input.low.is_finite() {
@@ -135,16 +220,10 @@ impl ParabolicSAR {
     }
 
     fn validate_config(&self) -> Result<(), ParabolicSARError> {
-        let config = &self.state.config;
-
-        if config.acceleration_start <= 0.0
-            || config.acceleration_increment <= 0.0
-            || config.acceleration_maximum <= config.acceleration_start
-        {
-            return Err(ParabolicSARError::InvalidAcceleration);
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
         }
-
-        Ok(())
     }
 
     fn handle_first_calculation(
@@ -155,14 +234,18 @@ impl ParabolicSAR {
         // Initial trend determination will happen on second calculation
         self.state.is_first = false;
         self.state.is_second = true;
+        self.state.trend_periods = 1;
 
         Ok(ParabolicSAROutput {
-            sar: input.low,            // Placeholder - will be properly calculated next period
-            trend: TrendDirection::Up, // Placeholder
+            timestamp: None,
+            sar: input.low, // Placeholder - will be properly calculated next period
+            trend: SARDirection::Up, // Placeholder
             acceleration_factor: self.state.config.acceleration_start,
+            af_at_max: self.state.config.acceleration_start
+                >= self.state.config.acceleration_maximum,
             extreme_point: input.high,
             trend_reversal: false,
-            trend_periods: 1,
+            trend_periods: self.state.trend_periods,
         })
     }
 
@@ -176,28 +259,27 @@ impl ParabolicSAR {
         let prev_high = self.state.previous_high.unwrap();
         let prev_low = self.state.previous_low.unwrap();
 
-        // Determine initial trend direction
+        // Determine initial trend direction (a tie has no previous direction to keep)
         let trend = if input.high > prev_high {
-            TrendDirection::Up
+            SARDirection::Up
         } else {
-            TrendDirection::Down
+            SARDirection::Down
         };
 
         // Set initial SAR and extreme point
         let (sar, extreme_point) = match trend {
-            TrendDirection::Up => (prev_low, input.high.max(prev_high)),
-            TrendDirection::Down => (prev_high, input.low.min(prev_low)),
+            SARDirection::Up => (prev_low, input.high.max(prev_high)),
+            SARDirection::Down => (prev_high, input.low.min(prev_low)),
         };
 
-        self.state.trend = Some(trend);
-        self.state.current_sar = Some(sar);
-        self.state.extreme_point = Some(extreme_point);
-        self.state.trend_periods = 1;
+        self.begin_trend(trend, sar, extreme_point);
 
         Ok(ParabolicSAROutput {
+            timestamp: None,
             sar,
             trend,
             acceleration_factor: self.state.acceleration_factor,
+            af_at_max: self.af_at_max(),
             extreme_point,
             trend_reversal: false,
             trend_periods: self.state.trend_periods,
@@ -212,10 +294,12 @@ impl ParabolicSAR {
         let current_sar = self.state.current_sar.unwrap();
         let current_ep = self.state.extreme_point.unwrap();
 
-        // Check for trend reversal
-        let trend_reversal = match current_trend {
-            TrendDirection::Up => input.low <= current_sar,
-            TrendDirection::Down => input.high >= current_sar,
+        // Check for trend reversal; touching the SAR exactly is a tie
+        let trend_reversal = match (current_trend, self.state.config.tie_policy) {
+            (SARDirection::Up, TiePolicy::Neutral) => input.low <= current_sar,
+            (SARDirection::Up, TiePolicy::CarryForward) => input.low < current_sar,
+            (SARDirection::Down, TiePolicy::Neutral) => input.high >= current_sar,
+            (SARDirection::Down, TiePolicy::CarryForward) => input.high > current_sar,
         };
 
         if trend_reversal {
@@ -228,13 +312,13 @@ impl ParabolicSAR {
     fn handle_trend_reversal(
         &mut self,
         input: ParabolicSARInput,
-        old_trend: TrendDirection,
+        old_trend: SARDirection,
         old_ep: f64,
     ) -> Result<ParabolicSAROutput, ParabolicSARError> {
         // Trend reversal - flip direction
         let new_trend = match old_trend {
-            TrendDirection::Up => TrendDirection::Down,
-            TrendDirection::Down => TrendDirection::Up,
+            SARDirection::Up => SARDirection::Down,
+            SARDirection::Down => SARDirection::Up,
         };
 
         // New SAR is the old extreme point
@@ -242,44 +326,63 @@ impl ParabolicSAR {
 
         // New extreme point
         let new_ep = match new_trend {
-            TrendDirection::Up => input.high,
-            TrendDirection::Down => input.low,
+            SARDirection::Up => input.high,
+            SARDirection::Down => input.low,
         };
 
-        // Reset acceleration factor
-        self.state.acceleration_factor = self.state.config.acceleration_start;
-        self.state.trend = Some(new_trend);
-        self.state.current_sar = Some(new_sar);
-        self.state.extreme_point = Some(new_ep);
-        self.state.trend_periods = 1;
+        // Reset acceleration factor according to the configured policy
+        let config = &self.state.config;
+        self.state.acceleration_factor = match config.af_reset {
+            AfResetPolicy::Full => config.acceleration_start,
+            AfResetPolicy::Half => {
+                (self.state.acceleration_factor / 2.0).max(config.acceleration_start)
+            }
+            AfResetPolicy::Keep => self.state.acceleration_factor,
+        };
+        self.begin_trend(new_trend, new_sar, new_ep);
 
         Ok(ParabolicSAROutput {
+            timestamp: None,
             sar: new_sar,
             trend: new_trend,
             acceleration_factor: self.state.acceleration_factor,
+            af_at_max: self.af_at_max(),
             extreme_point: new_ep,
             trend_reversal: true,
             trend_periods: self.state.trend_periods,
         })
     }
 
+    /// Whether the acceleration factor has been capped at its configured maximum
+    fn af_at_max(&self) -> bool {
+        self.state.acceleration_factor >= self.state.config.acceleration_maximum
+    }
+
+    /// Start a new trend; the single place where the trend period count resets
+    fn begin_trend(&mut self, trend: SARDirection, sar: f64, extreme_point: f64) {
+        self.state.trend = Some(trend);
+        self.state.current_sar = Some(sar);
+        self.state.extreme_point = Some(extreme_point);
+        self.state.trend_periods = 1;
+    }
+
     fn handle_trend_continuation(
         &mut self,
         input: ParabolicSARInput,
-        trend: TrendDirection,
+        trend: SARDirection,
         current_sar: f64,
         current_ep: f64,
     ) -> Result<ParabolicSAROutput, ParabolicSARError> {
         // Check if we have a new extreme point
         let (new_ep, ep_updated) = match trend {
-            TrendDirection::Up => {
+            SARDirection::Up => {
                 if input.high > current_ep {
                     (input.high, true)
                 } else {
                     (current_ep, false)
                 }
             }
-            TrendDirection::Down => {
+            SARDirection::Down => {
                 if input.low < current_ep {
                     (input.low, true)
                 } else {
@@ -300,12 +403,12 @@ impl ParabolicSAR {
 
         // Apply SAR rules to prevent SAR from moving into the price range
         new_sar = match trend {
-            TrendDirection::Up => {
+            SARDirection::Up => {
                 // In uptrend, SAR cannot be above the low of current or previous period
                 let prev_low = self.state.previous_low.unwrap_or(input.low);
                 new_sar.min(input.low).min(prev_low)
             }
-            TrendDirection::Down => {
+            SARDirection::Down => {
                 // In downtrend, SAR cannot be below the high of current or previous period
                 let prev_high = self.state.previous_high.unwrap_or(input.high);
                 new_sar.max(input.high).max(prev_high)
@@ -317,9 +420,11 @@ impl ParabolicSAR {
         self.state.trend_periods += 1;
 
         Ok(ParabolicSAROutput {
+            timestamp: None,
             sar: new_sar,
             trend,
             acceleration_factor: self.state.acceleration_factor,
+            af_at_max: self.af_at_max(),
             extreme_point: new_ep,
             trend_reversal: false,
             trend_periods: self.state.trend_periods,
@@ -359,24 +464,41 @@ pub fn calculate_parabolic_sar_simple(
         return Ok(Vec::new());
     }
 
+    calculate_parabolic_sar_iter(
+        highs.iter().copied().zip(lows.iter().copied()),
+        acceleration_start,
+        acceleration_increment,
+        acceleration_maximum,
+    )
+}
+
+/// Like [`calculate_parabolic_sar_simple`], but accepts any iterator of `(high, low)` bars
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_parabolic_sar_iter(
+    bars: impl IntoIterator<Item = (f64, f64)>,
+    acceleration_start: Option<f64>,
+    acceleration_increment: Option<f64>,
+    acceleration_maximum: Option<f64>,
+) -> Result<Vec<f64>, ParabolicSARError> {
     let config = ParabolicSARConfig {
         acceleration_start: acceleration_start.unwrap_or(0.02),
         acceleration_increment: acceleration_increment.unwrap_or(0.02),
         acceleration_maximum: acceleration_maximum.unwrap_or(0.20),
+        ..Default::default()
     };
 
     let mut sar_calculator = ParabolicSAR::with_config(config);
-    let mut results = Vec::with_capacity(highs.len());
 
-    for i in 0..highs.len() {
-        let input = ParabolicSARInput {
-            high: highs[i],
-            low: lows[i],
-            close: None,
-        };
-        let output = sar_calculator.calculate(input)?;
-        results.push(output.sar);
-    }
-
-    Ok(results)
+    bars.into_iter()
+        .map(|(high, low)| {
+            let input = ParabolicSARInput {
+                timestamp: None,
+                high,
+                low,
+                close: None,
+            };
+            sar_calculator.calculate(input).map(|output| output.sar)
+        })
+        .collect()
 }
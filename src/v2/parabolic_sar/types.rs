@@ -1,3 +1,4 @@
+use crate::common::types::{self as common, Candle, GapPolicy, TiePolicy};
 use serde::{Deserialize, Serialize};
 
 /// Configuration for Parabolic SAR calculation
@@ -9,6 +10,17 @@ pub struct ParabolicSARConfig {
     pub acceleration_increment: f64,
     /// Maximum acceleration factor (default: 0.20)
     pub acceleration_maximum: f64,
+    /// What the acceleration factor becomes on a trend reversal (default: Full)
+    #[serde(default)]
+    pub af_reset: AfResetPolicy,
+    /// Whether a bar that only touches the SAR reverses the trend (default: TiePolicy::Neutral)
+    #[serde(default)]
+    pub tie_policy: TiePolicy,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    #[serde(default)]
+    pub gap_policy: GapPolicy,
+    /// Decimal places `sar` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
 }
 
 impl Default for ParabolicSARConfig {
@@ -17,13 +29,46 @@ impl Default for ParabolicSARConfig {
             acceleration_start: 0.02,
             acceleration_increment: 0.02,
             acceleration_maximum: 0.20,
+            af_reset: AfResetPolicy::Full,
+            tie_policy: TiePolicy::Neutral,
+            gap_policy: GapPolicy::Error,
+            precision: None,
         }
     }
 }
 
+impl ParabolicSARConfig {
+    /// Check the invariants the Parabolic SAR calculator relies on
+    pub fn validate(&self) -> Result<(), ParabolicSARError> {
+        if self.acceleration_start <= 0.0
+            || self.acceleration_increment <= 0.0
+            || self.acceleration_maximum <= self.acceleration_start
+        {
+            return Err(ParabolicSARError::InvalidAcceleration);
+        }
+
+        Ok(())
+    }
+}
+
+/// Acceleration factor handling on a trend reversal
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum AfResetPolicy {
+    /// Reset to `acceleration_start` (standard Wilder behavior)
+    #[default]
+    Full,
+    /// Halve the current factor, never going below `acceleration_start`
+    Half,
+    /// Carry the current factor over into the new trend
+    Keep,
+}
+
 /// Input data for Parabolic SAR calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ParabolicSARInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// High price
     pub high: f64,
     /// Low price
@@ -32,24 +77,72 @@ pub struct ParabolicSARInput {
     pub close: Option<f64>,
 }
 
+impl From<Candle> for ParabolicSARInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            high: candle.high,
+            low: candle.low,
+            close: Some(candle.close),
+        }
+    }
+}
+
 /// Current trend direction
+///
+/// Parabolic SAR is always in either an uptrend or a downtrend, so unlike the shared
+/// `TrendDirection` there is no `Sideways` variant. Convert with `From`/`TryFrom`.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub enum TrendDirection {
+pub enum SARDirection {
     /// Uptrend (SAR below price)
     Up,
     /// Downtrend (SAR above price)
     Down,
 }
 
+/// Former name of [`SARDirection`], kept so existing imports keep compiling
+#[deprecated(note = "renamed to `SARDirection`")]
+pub type TrendDirection = SARDirection;
+
+impl From<SARDirection> for common::TrendDirection {
+    fn from(direction: SARDirection) -> Self {
+        match direction {
+            SARDirection::Up => common::TrendDirection::Up,
+            SARDirection::Down => common::TrendDirection::Down,
+        }
+    }
+}
+
+impl TryFrom<common::TrendDirection> for SARDirection {
+    type Error = ParabolicSARError;
+
+    fn try_from(direction: common::TrendDirection) -> Result<Self, Self::Error> {
+        match direction {
+            common::TrendDirection::Up => Ok(SARDirection::Up),
+            common::TrendDirection::Down => Ok(SARDirection::Down),
+            common::TrendDirection::Sideways => Err(ParabolicSARError::InvalidInput(
+                "Parabolic SAR has no sideways trend".to_string(),
+            )),
+        }
+    }
+}
+
 /// Output from Parabolic SAR calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ParabolicSAROutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// Parabolic SAR value
     pub sar: f64,
     /// Current trend direction
-    pub trend: TrendDirection,
+    pub trend: SARDirection,
     /// Current acceleration factor
     pub acceleration_factor: f64,
+    /// Whether the acceleration factor has reached `acceleration_maximum` (trend fully accelerated)
+    #[serde(default)]
+    pub af_at_max: bool,
     /// Extreme point (highest high in uptrend, lowest low in downtrend)
     pub extreme_point: f64,
     /// Whether a trend reversal occurred
@@ -64,7 +157,7 @@ pub struct ParabolicSARState {
     /// Configuration
     pub config: ParabolicSARConfig,
     /// Current trend direction
-    pub trend: Option<TrendDirection>,
+    pub trend: Option<SARDirection>,
     /// Current SAR value
     pub current_sar: Option<f64>,
     /// Current acceleration factor
@@ -83,6 +176,10 @@ pub struct ParabolicSARState {
     pub is_first: bool,
     /// Whether this is the second calculation
     pub is_second: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<ParabolicSARInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<ParabolicSAROutput>,
 }
 
 impl ParabolicSARState {
@@ -99,6 +196,8 @@ impl ParabolicSARState {
             trend_periods: 0,
             is_first: true,
             is_second: false,
+            last_valid_input: None,
+            last_output: None,
         }
     }
 }
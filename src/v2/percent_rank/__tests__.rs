@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::assert_dense_batch;
+    use crate::common::types::GapPolicy;
+    use crate::v2::percent_rank::{
+        main::{calculate_percent_rank_simple, PercentRank},
+        types::{PercentRankCondition, PercentRankConfig, PercentRankError, PercentRankInput},
+    };
+
+    fn input(close: f64) -> PercentRankInput {
+        PercentRankInput {
+            timestamp: None,
+            close,
+        }
+    }
+
+    #[test]
+    fn test_window_maximum_and_minimum() {
+        let mut percent_rank = PercentRank::with_period(10).unwrap();
+        for i in 0..9 {
+            let output = percent_rank.calculate(input(100.0 + i as f64)).unwrap();
+            assert_eq!(output.condition, PercentRankCondition::Insufficient);
+        }
+
+        // New high ranks 100 and is flagged overbought
+        let high = percent_rank.calculate(input(120.0)).unwrap();
+        assert_eq!(high.percent_rank, 100.0);
+        assert_eq!(high.condition, PercentRankCondition::Overbought);
+
+        // New low ranks 0 and is flagged oversold
+        let low = percent_rank.calculate(input(90.0)).unwrap();
+        assert!(low.percent_rank < 1e-9);
+        assert_eq!(low.condition, PercentRankCondition::Oversold);
+
+        // Middle of the window is neutral
+        let middle = percent_rank.calculate(input(104.5)).unwrap();
+        assert_eq!(middle.condition, PercentRankCondition::Neutral);
+    }
+
+    #[test]
+    fn test_configurable_percentiles() {
+        let mut percent_rank = PercentRank::with_config(PercentRankConfig {
+            period: 5,
+            overbought: 95.0,
+            oversold: 5.0,
+            ..PercentRankConfig::default()
+        });
+        for close in [1.0, 2.0, 3.0, 4.0] {
+            percent_rank.calculate(input(close)).unwrap();
+        }
+        // 3.5 is above three of the other four closes: 75, neutral at 95/5
+        let output = percent_rank.calculate(input(3.5)).unwrap();
+        assert_eq!(output.percent_rank, 75.0);
+        assert_eq!(output.condition, PercentRankCondition::Neutral);
+    }
+
+    #[test]
+    fn test_calculate_checked_and_gap_policy() {
+        let mut percent_rank = PercentRank::with_config(PercentRankConfig {
+            period: 3,
+            gap_policy: GapPolicy::SkipBar,
+            ..PercentRankConfig::default()
+        });
+        assert_eq!(percent_rank.calculate_checked(input(1.0)).unwrap(), None);
+        assert_eq!(percent_rank.calculate_checked(input(2.0)).unwrap(), None);
+        let ready = percent_rank.calculate_checked(input(3.0)).unwrap().unwrap();
+
+        // Skipped bars repeat the last output
+        let skipped = percent_rank.calculate(input(f64::NAN)).unwrap();
+        assert_eq!(skipped, ready);
+    }
+
+    #[test]
+    fn test_percent_rank_validation() {
+        assert_eq!(
+            PercentRank::with_period(1).err(),
+            Some(PercentRankError::InvalidPeriod)
+        );
+
+        let mut percent_rank = PercentRank::with_config(PercentRankConfig {
+            overbought: 20.0,
+            oversold: 80.0,
+            ..PercentRankConfig::default()
+        });
+        assert_eq!(
+            percent_rank.calculate(input(1.0)),
+            Err(PercentRankError::InvalidThresholds)
+        );
+
+        let mut percent_rank = PercentRank::new();
+        assert_eq!(
+            percent_rank.calculate(input(f64::INFINITY)),
+            Err(PercentRankError::InvalidPrice)
+        );
+    }
+
+    #[test]
+    fn test_percent_rank_simple_function() {
+        let result = calculate_percent_rank_simple(&[3.0, 1.0, 2.0, 5.0], 3).unwrap();
+        assert_eq!(result, vec![50.0, 50.0, 50.0, 100.0]);
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(PercentRank::new(), PercentRankInput);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::v2::percent_rank::types::{
+    PercentRankCondition, PercentRankConfig, PercentRankError, PercentRankInput, PercentRankOutput,
+    PercentRankState,
+};
+
+/// Percent Rank Indicator
+///
+/// Percent Rank tells where the current close sits within the last `period`
+/// closes: the share of the other closes in the window at or below it, scaled
+/// to 0-100. It is a simple mean-reversion gauge.
+///
+/// Interpretation:
+/// - 100: the close is the highest in the window
+/// - 0: the close is the lowest in the window
+/// - Above the overbought percentile: stretched to the upside
+/// - Below the oversold percentile: stretched to the downside
+pub struct PercentRank {
+    state: PercentRankState,
+    /// Configuration error found at construction
+    config_error: Option<PercentRankError>,
+}
+
+impl PercentRank {
+    /// Create a new Percent Rank calculator with default configuration (period=20)
+    pub fn new() -> Self {
+        Self::with_config(PercentRankConfig::default())
+    }
+
+    /// Create a new Percent Rank calculator with custom period
+    pub fn with_period(period: usize) -> Result<Self, PercentRankError> {
+        if period < 2 {
+            return Err(PercentRankError::InvalidPeriod);
+        }
+
+        Ok(Self::with_config(PercentRankConfig {
+            period,
+            ..PercentRankConfig::default()
+        }))
+    }
+
+    /// Create a new Percent Rank calculator with custom configuration
+    pub fn with_config(config: PercentRankConfig) -> Self {
+        Self {
+            state: PercentRankState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: PercentRankConfig) -> Result<Self, PercentRankError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Calculate Percent Rank for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(
+        &mut self,
+        input: PercentRankInput,
+    ) -> Result<PercentRankOutput, PercentRankError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| PercentRankOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(PercentRankError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(PercentRankError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.percent_rank = round_to(output.percent_rank, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(
+        &mut self,
+        input: PercentRankInput,
+    ) -> Result<Option<PercentRankOutput>, PercentRankError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Calculate Percent Rank for a batch of inputs
+    pub fn calculate_batch(
+        &mut self,
+        inputs: &[PercentRankInput],
+    ) -> Result<Vec<PercentRankOutput>, PercentRankError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[PercentRankInput],
+    ) -> Result<DenseBatch<PercentRankOutput>, PercentRankError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            PercentRankError::InvalidInput,
+        )
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = PercentRankState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &PercentRankState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: PercentRankState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(
+        &mut self,
+        input: PercentRankInput,
+    ) -> Result<PercentRankOutput, PercentRankError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        let (percent_rank, condition) = match self.state.ranker.push(input.close) {
+            Some(rank) => {
+                self.state.has_sufficient_data = true;
+                (rank, self.determine_condition(rank))
+            }
+            None => (50.0, PercentRankCondition::Insufficient), // Default neutral value
+        };
+
+        Ok(PercentRankOutput {
+            timestamp: None,
+            percent_rank,
+            condition,
+        })
+    }
+
+    fn is_finite_input(input: &PercentRankInput) -> bool {
+        input.close.is_finite()
+    }
+
+    fn validate_input(&self, input: &PercentRankInput) -> Result<(), PercentRankError> {
+        if !input.close.is_finite() {
+            return Err(PercentRankError::InvalidPrice);
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), PercentRankError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn determine_condition(&self, percent_rank: f64) -> PercentRankCondition {
+        if percent_rank > self.state.config.overbought {
+            PercentRankCondition::Overbought
+        } else if percent_rank < self.state.config.oversold {
+            PercentRankCondition::Oversold
+        } else {
+            PercentRankCondition::Neutral
+        }
+    }
+}
+
+impl Default for PercentRank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to calculate Percent Rank for close prices without maintaining state
+pub fn calculate_percent_rank_simple(
+    closes: &[f64],
+    period: usize,
+) -> Result<Vec<f64>, PercentRankError> {
+    if closes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    calculate_percent_rank_iter(closes.iter().copied(), period)
+}
+
+/// Like [`calculate_percent_rank_simple`], but accepts any iterator of closes
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_percent_rank_iter(
+    closes: impl IntoIterator<Item = f64>,
+    period: usize,
+) -> Result<Vec<f64>, PercentRankError> {
+    let mut percent_rank_calculator = PercentRank::with_period(period)?;
+
+    closes
+        .into_iter()
+        .map(|close| {
+            let input = PercentRankInput {
+                timestamp: None,
+                close,
+            };
+            percent_rank_calculator
+                .calculate(input)
+                .map(|output| output.percent_rank)
+        })
+        .collect()
+}
@@ -0,0 +1,130 @@
+use crate::common::types::{Candle, GapPolicy};
+use crate::util::percent_rank::main::RollingPercentRank;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for Percent Rank calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PercentRankConfig {
+    /// Number of closes in the ranking window (default: 20)
+    pub period: usize,
+    /// Rank above which the close is flagged overbought (default: 80.0)
+    pub overbought: f64,
+    /// Rank below which the close is flagged oversold (default: 20.0)
+    pub oversold: f64,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+    /// Decimal places `percent_rank` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+impl Default for PercentRankConfig {
+    fn default() -> Self {
+        Self {
+            period: 20,
+            overbought: 80.0,
+            oversold: 20.0,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl PercentRankConfig {
+    /// Check the invariants the Percent Rank calculator relies on
+    pub fn validate(&self) -> Result<(), PercentRankError> {
+        if self.period < 2 {
+            return Err(PercentRankError::InvalidPeriod);
+        }
+
+        if self.oversold >= self.overbought || self.oversold < 0.0 || self.overbought > 100.0 {
+            return Err(PercentRankError::InvalidThresholds);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for Percent Rank calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PercentRankInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Closing price
+    pub close: f64,
+}
+
+impl From<Candle> for PercentRankInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            close: candle.close,
+        }
+    }
+}
+
+/// Output from Percent Rank calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PercentRankOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Share of the other closes in the window at or below the current close (0-100)
+    pub percent_rank: f64,
+    /// Market condition based on the overbought/oversold percentiles
+    pub condition: PercentRankCondition,
+}
+
+/// Market condition classification for Percent Rank
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PercentRankCondition {
+    /// Rank above the overbought percentile
+    Overbought,
+    /// Rank below the oversold percentile
+    Oversold,
+    /// Rank between the percentiles
+    Neutral,
+    /// Not enough data yet
+    Insufficient,
+}
+
+/// Percent Rank calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PercentRankState {
+    /// Configuration
+    pub config: PercentRankConfig,
+    /// Rolling window of closes
+    pub ranker: RollingPercentRank,
+    /// Whether we have enough data for calculation
+    pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<PercentRankInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<PercentRankOutput>,
+}
+
+impl PercentRankState {
+    pub fn new(config: PercentRankConfig) -> Self {
+        Self {
+            config,
+            ranker: RollingPercentRank::new(config.period),
+            has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Error types for Percent Rank calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PercentRankError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid period (must be >= 2)
+    InvalidPeriod,
+    /// Invalid threshold values
+    InvalidThresholds,
+}
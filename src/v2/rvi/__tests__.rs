@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use crate::v2::rvi::{
+        main::{calculate_rvi_simple, RVI},
+        types::{RVICrossover, RVIError, RVIInput},
+    };
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> RVIInput {
+        RVIInput {
+            timestamp: None,
+            open,
+            high,
+            low,
+            close,
+        }
+    }
+
+    /// A bar that opens at its low and closes at its high (or the reverse)
+    fn trend_bar(base: f64, up: bool) -> RVIInput {
+        if up {
+            bar(base, base + 1.0, base, base + 1.0)
+        } else {
+            bar(base + 1.0, base + 1.0, base, base)
+        }
+    }
+
+    #[test]
+    fn test_warmup_and_full_vigor() {
+        let mut rvi = RVI::with_period(4).unwrap();
+        let inputs: Vec<_> = (0..10).map(|i| trend_bar(100.0 + i as f64, true)).collect();
+        let outputs = rvi.calculate_batch(&inputs).unwrap();
+
+        // 3 bars fill the weighted window, then 4 values fill the SMA
+        assert!(outputs[..6]
+            .iter()
+            .all(|o| o.rvi == 0.0 && o.signal.is_none()));
+        // Every bar closes at its high: full bullish vigor
+        for output in &outputs[6..] {
+            assert!((output.rvi - 1.0).abs() < 1e-12);
+        }
+        // The signal line needs 4 RVI values
+        assert!(outputs[8].signal.is_none());
+        assert!((outputs[9].signal.unwrap() - 1.0).abs() < 1e-12);
+
+        rvi.reset();
+        assert_eq!(
+            rvi.calculate_checked(inputs[0]).unwrap(),
+            None,
+            "reset restarts the warmup"
+        );
+    }
+
+    #[test]
+    fn test_zero_range_bars_read_zero() {
+        let mut rvi = RVI::with_period(3).unwrap();
+        let outputs = rvi.calculate_batch(&[bar(5.0, 5.0, 5.0, 5.0); 12]).unwrap();
+        assert!(outputs.iter().all(|o| o.rvi == 0.0));
+        assert!(outputs.iter().all(|o| o.crossover == RVICrossover::None));
+    }
+
+    #[test]
+    fn test_rvi_and_signal_cross_on_reversal() {
+        let mut rvi = RVI::with_period(4).unwrap();
+        let mut inputs: Vec<_> = (0..12).map(|i| trend_bar(100.0 + i as f64, true)).collect();
+        inputs.extend((0..8).map(|i| trend_bar(111.0 - i as f64, false)));
+        inputs.extend((0..8).map(|i| trend_bar(104.0 + i as f64, true)));
+        let outputs = rvi.calculate_batch(&inputs).unwrap();
+
+        let crosses: Vec<_> = outputs
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| o.crossover != RVICrossover::None)
+            .map(|(i, o)| (i, o.crossover))
+            .collect();
+
+        // The RVI turns first and the weighted signal lags behind it: the first down
+        // bar pulls the RVI under the signal, and the first up bar lifts it back over
+        assert_eq!(
+            crosses,
+            vec![(12, RVICrossover::Bearish), (20, RVICrossover::Bullish)]
+        );
+        assert!(outputs[12].rvi < outputs[12].signal.unwrap());
+        assert!(outputs[20].rvi > outputs[20].signal.unwrap());
+    }
+
+    #[test]
+    fn test_invalid_inputs_and_simple() {
+        let mut rvi = RVI::new();
+        assert_eq!(
+            rvi.calculate(bar(10.0, 9.0, 11.0, 10.0)),
+            Err(RVIError::InvalidOHLC)
+        );
+        assert_eq!(
+            rvi.calculate(bar(12.0, 11.0, 9.0, 10.0)),
+            Err(RVIError::InvalidOHLC)
+        );
+        assert_eq!(
+            rvi.calculate(bar(f64::NAN, 11.0, 9.0, 10.0)),
+            Err(RVIError::InvalidPrice)
+        );
+        assert!(RVI::with_period(0).is_err());
+
+        let opens = [10.0, 10.5, 11.0, 11.5, 12.0];
+        let highs = [11.0, 11.5, 12.0, 12.5, 13.0];
+        let lows = [9.5, 10.0, 10.5, 11.0, 11.5];
+        let closes = [10.8, 11.2, 11.9, 12.1, 12.8];
+        let values = calculate_rvi_simple(&opens, &highs, &lows, &closes, 2).unwrap();
+        assert_eq!(values[..4], [None, None, None, None]);
+        assert!(values[4].unwrap() > 0.0);
+        assert!(calculate_rvi_simple(&opens[..2], &highs, &lows, &closes, 2).is_err());
+    }
+}
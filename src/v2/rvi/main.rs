@@ -0,0 +1,320 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::v2::rvi::types::{RVIConfig, RVICrossover, RVIError, RVIInput, RVIOutput, RVIState};
+use std::collections::VecDeque;
+
+/// Relative Vigor Index (RVI) Indicator
+///
+/// The RVI measures the conviction of a move by comparing where bars close
+/// relative to their open against their full range: in rising markets prices
+/// tend to close above the open, in falling markets below it.
+///
+/// Formula:
+/// 1. Numerator = [(C-O) + 2×(C-O)₋₁ + 2×(C-O)₋₂ + (C-O)₋₃] / 6
+/// 2. Denominator = [(H-L) + 2×(H-L)₋₁ + 2×(H-L)₋₂ + (H-L)₋₃] / 6
+/// 3. RVI = SMA(Numerator, period) / SMA(Denominator, period)
+/// 4. Signal = [RVI + 2×RVI₋₁ + 2×RVI₋₂ + RVI₋₃] / 6
+///
+/// Interpretation:
+/// - Positive RVI: bars close above their opens (bullish vigor)
+/// - Crosses of the RVI over its signal line flag momentum reversals
+/// - A window of zero-range bars carries no information and reads 0
+pub struct RVI {
+    state: RVIState,
+    /// Configuration error found at construction
+    config_error: Option<RVIError>,
+}
+
+/// Weights of the symmetric 4-bar smoothing, oldest first
+const WEIGHTS: [f64; 4] = [1.0, 2.0, 2.0, 1.0];
+
+impl RVI {
+    /// Create a new RVI calculator with default configuration (period=10)
+    pub fn new() -> Self {
+        Self::with_config(RVIConfig::default())
+    }
+
+    /// Create a new RVI calculator with a custom SMA period
+    pub fn with_period(period: usize) -> Result<Self, RVIError> {
+        Self::try_with_config(RVIConfig {
+            period,
+            ..RVIConfig::default()
+        })
+    }
+
+    /// Create a new RVI calculator with custom configuration
+    pub fn with_config(config: RVIConfig) -> Self {
+        Self {
+            state: RVIState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: RVIConfig) -> Result<Self, RVIError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Calculate RVI for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(&mut self, input: RVIInput) -> Result<RVIOutput, RVIError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| RVIOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(RVIError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(RVIError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.rvi = round_to(output.rvi, decimals);
+            output.signal = output.signal.map(|value| round_to(value, decimals));
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` until the first RVI value (bar `period + 3`).
+    pub fn calculate_checked(&mut self, input: RVIInput) -> Result<Option<RVIOutput>, RVIError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Calculate RVI for a batch of inputs
+    pub fn calculate_batch(&mut self, inputs: &[RVIInput]) -> Result<Vec<RVIOutput>, RVIError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[RVIInput],
+    ) -> Result<DenseBatch<RVIOutput>, RVIError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            RVIError::InvalidInput,
+        )
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = RVIState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &RVIState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: RVIState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(&mut self, input: RVIInput) -> Result<RVIOutput, RVIError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        push_capped(
+            &mut self.state.bars,
+            (input.close - input.open, input.high - input.low),
+            4,
+        );
+        if self.state.bars.len() < 4 {
+            return Ok(Self::warmup_output());
+        }
+
+        let (numerator, denominator) = self
+            .state
+            .bars
+            .iter()
+            .zip(WEIGHTS)
+            .fold((0.0, 0.0), |(num, den), (&(body, range), weight)| {
+                (num + weight * body, den + weight * range)
+            });
+        let (numerator, denominator) = (numerator / 6.0, denominator / 6.0);
+
+        let rvi = match self.update_averages(numerator, denominator) {
+            Some(rvi) => rvi,
+            None => return Ok(Self::warmup_output()),
+        };
+
+        push_capped(&mut self.state.rvi_values, rvi, 4);
+        let signal = (self.state.rvi_values.len() == 4).then(|| {
+            self.state
+                .rvi_values
+                .iter()
+                .zip(WEIGHTS)
+                .map(|(value, weight)| weight * value)
+                .sum::<f64>()
+                / 6.0
+        });
+        let crossover = match signal {
+            Some(signal) => self.detect_crossover(rvi - signal),
+            None => RVICrossover::None,
+        };
+
+        Ok(RVIOutput {
+            timestamp: None,
+            rvi,
+            signal,
+            crossover,
+        })
+    }
+
+    fn warmup_output() -> RVIOutput {
+        RVIOutput {
+            timestamp: None,
+            rvi: 0.0,
+            signal: None,
+            crossover: RVICrossover::None,
+        }
+    }
+
+    /// Add the weighted values to the SMA windows and return the RVI once they are full
+    fn update_averages(&mut self, numerator: f64, denominator: f64) -> Option<f64> {
+        let period = self.state.config.period;
+        let state = &mut self.state;
+
+        if state.numerators.len() >= period {
+            state.numerator_sum -= state.numerators.pop_front().unwrap_or(0.0);
+            state.denominator_sum -= state.denominators.pop_front().unwrap_or(0.0);
+        }
+        state.numerators.push_back(numerator);
+        state.denominators.push_back(denominator);
+        state.numerator_sum += numerator;
+        state.denominator_sum += denominator;
+
+        state.has_sufficient_data = state.numerators.len() >= period;
+        if !state.has_sufficient_data {
+            return None;
+        }
+
+        // The period divisor cancels; a window of zero-range bars (up to the
+        // rounding residue of the running sums) reads 0
+        Some(if state.denominator_sum <= f64::EPSILON {
+            0.0
+        } else {
+            (state.numerator_sum / state.denominator_sum).clamp(-1.0, 1.0)
+        })
+    }
+
+    fn detect_crossover(&mut self, histogram: f64) -> RVICrossover {
+        let crossover = match self.state.prev_histogram {
+            Some(prev) if prev <= 0.0 && histogram > 0.0 => RVICrossover::Bullish,
+            Some(prev) if prev >= 0.0 && histogram < 0.0 => RVICrossover::Bearish,
+            _ => RVICrossover::None,
+        };
+        self.state.prev_histogram = Some(histogram);
+        crossover
+    }
+
+    fn is_finite_input(input: &RVIInput) -> bool {
+        input.open.is_finite()
+            && input.high.is_finite()
+            && input.low.is_finite()
+            && input.close.is_finite()
+    }
+
+    fn validate_input(&self, input: &RVIInput) -> Result<(), RVIError> {
+        if !Self::is_finite_input(input) {
+            return Err(RVIError::InvalidPrice);
+        }
+
+        // Check OHLC relationship
+        if input.high < input.low {
+            return Err(RVIError::InvalidOHLC);
+        }
+
+        for price in [input.open, input.close] {
+            if price < input.low || price > input.high {
+                return Err(RVIError::InvalidOHLC);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), RVIError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Append a value, dropping the oldest once the deque holds `capacity` values
+fn push_capped<T>(values: &mut VecDeque<T>, value: T, capacity: usize) {
+    if values.len() >= capacity {
+        values.pop_front();
+    }
+    values.push_back(value);
+}
+
+impl Default for RVI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to calculate RVI for OHLC data without maintaining state
+///
+/// Warmup bars are `None`.
+pub fn calculate_rvi_simple(
+    opens: &[f64],
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+) -> Result<Vec<Option<f64>>, RVIError> {
+    if opens.len() != highs.len() || highs.len() != lows.len() || lows.len() != closes.len() {
+        return Err(RVIError::InvalidInput(
+            "All price arrays must have the same length".to_string(),
+        ));
+    }
+
+    let mut rvi_calculator = RVI::with_period(period)?;
+
+    (0..opens.len())
+        .map(|i| {
+            let input = RVIInput {
+                timestamp: None,
+                open: opens[i],
+                high: highs[i],
+                low: lows[i],
+                close: closes[i],
+            };
+            rvi_calculator
+                .calculate_checked(input)
+                .map(|output| output.map(|output| output.rvi))
+        })
+        .collect()
+}
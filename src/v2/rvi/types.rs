@@ -0,0 +1,147 @@
+use crate::common::types::{Candle, GapPolicy};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Configuration for Relative Vigor Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RVIConfig {
+    /// Period of the SMAs of the weighted numerator and denominator (default: 10)
+    pub period: usize,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+    /// Decimal places `rvi` and `signal` are rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+impl Default for RVIConfig {
+    fn default() -> Self {
+        Self {
+            period: 10,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl RVIConfig {
+    /// Check the invariants the RVI calculator relies on
+    pub fn validate(&self) -> Result<(), RVIError> {
+        if self.period == 0 {
+            return Err(RVIError::InvalidPeriod);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for Relative Vigor Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RVIInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Open price
+    pub open: f64,
+    /// High price
+    pub high: f64,
+    /// Low price
+    pub low: f64,
+    /// Close price
+    pub close: f64,
+}
+
+impl From<Candle> for RVIInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+        }
+    }
+}
+
+/// Output from Relative Vigor Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RVIOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// RVI value, within [-1, 1] (0 during warmup or over zero-range windows)
+    pub rvi: f64,
+    /// Signal line (4-bar weighted average of the RVI); None until it has 4 RVI values
+    pub signal: Option<f64>,
+    /// Cross of the RVI over its signal line on this bar
+    pub crossover: RVICrossover,
+}
+
+/// Cross of the RVI over its signal line
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RVICrossover {
+    /// RVI crossed above the signal line
+    Bullish,
+    /// RVI crossed below the signal line
+    Bearish,
+    /// No cross on this bar
+    None,
+}
+
+/// Relative Vigor Index calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RVIState {
+    /// Configuration
+    pub config: RVIConfig,
+    /// Last 4 bars as (close - open, high - low), oldest first
+    pub bars: VecDeque<(f64, f64)>,
+    /// Recent weighted numerators for the SMA
+    pub numerators: VecDeque<f64>,
+    /// Recent weighted denominators for the SMA
+    pub denominators: VecDeque<f64>,
+    /// Running sum of the numerators in the window
+    pub numerator_sum: f64,
+    /// Running sum of the denominators in the window
+    pub denominator_sum: f64,
+    /// Last 4 RVI values for the signal line, oldest first
+    pub rvi_values: VecDeque<f64>,
+    /// Previous RVI minus signal, for crossover detection
+    pub prev_histogram: Option<f64>,
+    /// Whether we have enough data for calculation
+    pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<RVIInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<RVIOutput>,
+}
+
+impl RVIState {
+    pub fn new(config: RVIConfig) -> Self {
+        Self {
+            config,
+            bars: VecDeque::with_capacity(4),
+            numerators: VecDeque::with_capacity(config.period),
+            denominators: VecDeque::with_capacity(config.period),
+            numerator_sum: 0.0,
+            denominator_sum: 0.0,
+            rvi_values: VecDeque::with_capacity(4),
+            prev_histogram: None,
+            has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Error types for Relative Vigor Index calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RVIError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid OHLC relationship (high < low, or open/close outside the range)
+    InvalidOHLC,
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid period (must be > 0)
+    InvalidPeriod,
+}
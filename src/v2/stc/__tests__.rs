@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::assert_dense_batch;
+    use crate::v2::stc::{
+        main::{calculate_stc_simple, calculate_stc_simple_checked, STC},
+        types::{STCCondition, STCConfig, STCError, STCInput},
+    };
+
+    fn sine_closes(len: usize, cycle: f64) -> Vec<f64> {
+        (0..len)
+            .map(|i| 100.0 + 10.0 * (i as f64 * 2.0 * std::f64::consts::PI / cycle).sin())
+            .collect()
+    }
+
+    fn input(close: f64) -> STCInput {
+        STCInput {
+            timestamp: None,
+            close,
+        }
+    }
+
+    #[test]
+    fn test_stc_stays_in_range() {
+        let mut stc = STC::with_periods(5, 10, 5).unwrap();
+        let mut closes = sine_closes(150, 40.0);
+        // Add a trending leg so both passes see non-periodic data
+        closes.extend((0..50).map(|i| 100.0 + i as f64 * 0.8));
+
+        for close in closes {
+            let output = stc.calculate(input(close)).unwrap();
+            assert!((0.0..=100.0).contains(&output.stc));
+            assert!((0.0..=100.0).contains(&output.stoch_macd));
+        }
+    }
+
+    #[test]
+    fn test_stc_warmup() {
+        let closes = sine_closes(30, 40.0);
+        let result = calculate_stc_simple_checked(&closes, 5, 10, 5).unwrap();
+
+        // MACD ready at bar 10, first pass full at bar 14, second pass full at bar 18
+        let warmup = 17;
+        assert!(result[..warmup].iter().all(Option::is_none));
+        assert!(result[warmup..].iter().all(Option::is_some));
+
+        let mut stc = STC::with_periods(5, 10, 5).unwrap();
+        let output = stc.calculate(input(100.0)).unwrap();
+        assert_eq!(output.stc, 50.0);
+        assert_eq!(output.condition, STCCondition::Insufficient);
+    }
+
+    #[test]
+    fn test_stc_leads_macd_on_turns() {
+        let closes = sine_closes(200, 40.0);
+        let mut stc = STC::with_periods(5, 10, 5).unwrap();
+        let outputs = stc
+            .calculate_batch(&closes.iter().map(|&c| input(c)).collect::<Vec<_>>())
+            .unwrap();
+
+        // After warmup, every downward MACD zero-cross is preceded by STC
+        // dropping out of the overbought zone
+        let mut checked_turns = 0;
+        for i in 60..outputs.len() {
+            if outputs[i - 1].macd > 0.0 && outputs[i].macd <= 0.0 {
+                let peak = (i - 15..i)
+                    .rev()
+                    .find(|&j| outputs[j].stc > 75.0)
+                    .expect("STC should have been overbought before the top");
+                assert!(outputs[peak + 1..=i].iter().any(|o| o.stc < 75.0));
+                checked_turns += 1;
+            }
+        }
+        assert!(checked_turns >= 2);
+    }
+
+    #[test]
+    fn test_stc_validation() {
+        assert_eq!(
+            STC::with_periods(10, 5, 5).err(),
+            Some(STCError::InvalidPeriod)
+        );
+        assert_eq!(
+            STC::with_periods(5, 10, 0).err(),
+            Some(STCError::InvalidPeriod)
+        );
+
+        let mut stc = STC::with_config(STCConfig {
+            factor: 0.0,
+            ..STCConfig::default()
+        });
+        assert_eq!(stc.calculate(input(1.0)), Err(STCError::InvalidFactor));
+
+        let mut stc = STC::new();
+        assert_eq!(stc.calculate(input(f64::NAN)), Err(STCError::InvalidPrice));
+    }
+
+    #[test]
+    fn test_stc_simple_function() {
+        let closes = sine_closes(40, 20.0);
+        let result = calculate_stc_simple(&closes, 5, 10, 5).unwrap();
+        assert_eq!(result.len(), 40);
+        assert!(result.iter().all(|v| (0.0..=100.0).contains(v)));
+        assert!(calculate_stc_simple(&[], 5, 10, 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_config_validate() {
+        assert!(STCConfig::default().validate().is_ok());
+
+        let config = STCConfig {
+            factor: 1.5,
+            ..STCConfig::default()
+        };
+        assert_eq!(config.validate(), Err(STCError::InvalidFactor));
+        assert_eq!(
+            STC::try_with_config(config).err(),
+            Some(STCError::InvalidFactor)
+        );
+
+        let mut stc = STC::with_config(config);
+        assert_eq!(stc.calculate(input(100.0)), Err(STCError::InvalidFactor));
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(STC::new(), STCInput);
+    }
+}
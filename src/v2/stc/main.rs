@@ -0,0 +1,300 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::v1::ema::main::ExponentialMovingAverage;
+use crate::v1::stochastic::main::percent_k;
+use crate::v2::stc::types::{STCCondition, STCConfig, STCError, STCInput, STCOutput, STCState};
+
+/// Schaff Trend Cycle (STC) Indicator
+///
+/// STC (Doug Schaff) runs the MACD line through two stochastic passes, giving
+/// a fast, smooth 0-100 oscillator that turns earlier than MACD itself.
+///
+/// Steps:
+/// 1. MACD = EMA(close, fast) - EMA(close, slow)
+/// 2. %K1 = 100 × (MACD - Lowest(MACD, cycle)) / (Highest(MACD, cycle) - Lowest(MACD, cycle)),
+///    smoothed: PF = PF_prev + factor × (%K1 - PF_prev)
+/// 3. %K2 = the same stochastic of PF, smoothed the same way into the STC
+///
+/// When a window is flat the previous stochastic value is carried forward.
+/// Output is available once the MACD line and both cycle windows are full.
+pub struct STC {
+    state: STCState,
+    /// Configuration error found at construction
+    config_error: Option<STCError>,
+}
+
+impl STC {
+    /// Create a new STC calculator with default configuration (23/50/10)
+    pub fn new() -> Self {
+        Self::with_config(STCConfig::default())
+    }
+
+    /// Create a new STC calculator with custom MACD and cycle periods
+    pub fn with_periods(
+        fast_period: usize,
+        slow_period: usize,
+        cycle_period: usize,
+    ) -> Result<Self, STCError> {
+        let stc = Self::with_config(STCConfig {
+            fast_period,
+            slow_period,
+            cycle_period,
+            ..STCConfig::default()
+        });
+        stc.validate_config()?;
+        Ok(stc)
+    }
+
+    /// Create a new STC calculator with custom configuration
+    pub fn with_config(config: STCConfig) -> Self {
+        Self {
+            state: STCState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: STCConfig) -> Result<Self, STCError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Calculate STC for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(&mut self, input: STCInput) -> Result<STCOutput, STCError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| STCOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(STCError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(STCError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.stc = round_to(output.stc, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(&mut self, input: STCInput) -> Result<Option<STCOutput>, STCError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Calculate STC for a batch of inputs
+    pub fn calculate_batch(&mut self, inputs: &[STCInput]) -> Result<Vec<STCOutput>, STCError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[STCInput],
+    ) -> Result<DenseBatch<STCOutput>, STCError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            STCError::InvalidInput,
+        )
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = STCState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &STCState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: STCState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(&mut self, input: STCInput) -> Result<STCOutput, STCError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        let macd = self.update_macd(input.close);
+        self.state.count += 1;
+
+        // Both stochastic passes start once the slow EMA has seen a full period
+        if self.state.count >= self.state.config.slow_period {
+            let (lowest, highest) = self.state.macd_extremes.push(macd);
+            if self.state.macd_extremes.is_full() {
+                let stoch_macd =
+                    Self::smoothed_stochastic(&mut self.state.stoch_macd, macd, lowest, highest);
+
+                let (lowest, highest) = self.state.stoch_extremes.push(stoch_macd);
+                if self.state.stoch_extremes.is_full() {
+                    Self::smoothed_stochastic(&mut self.state.stc, stoch_macd, lowest, highest);
+                    self.state.has_sufficient_data = true;
+                }
+            }
+        }
+
+        let (stc, condition) = match self.state.stc.get_current_value() {
+            Some(stc) if self.state.has_sufficient_data => (stc, self.determine_condition(stc)),
+            _ => (50.0, STCCondition::Insufficient), // Default neutral value
+        };
+
+        Ok(STCOutput {
+            timestamp: None,
+            stc,
+            macd,
+            stoch_macd: self.state.stoch_macd.get_current_value().unwrap_or(50.0),
+            condition,
+        })
+    }
+
+    fn is_finite_input(input: &STCInput) -> bool {
+        input.close.is_finite()
+    }
+
+    fn validate_input(&self, input: &STCInput) -> Result<(), STCError> {
+        if !input.close.is_finite() {
+            return Err(STCError::InvalidPrice);
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), STCError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn update_macd(&mut self, close: f64) -> f64 {
+        self.state.fast_ema.update(close) - self.state.slow_ema.update(close)
+    }
+
+    /// Stochastic of `value` over the window, smoothed by `ema`
+    fn smoothed_stochastic(
+        ema: &mut ExponentialMovingAverage,
+        value: f64,
+        lowest: f64,
+        highest: f64,
+    ) -> f64 {
+        // Flat window: carry the previous value forward
+        let stochastic = percent_k(value, lowest, highest)
+            .or(ema.get_current_value())
+            .unwrap_or(50.0);
+        ema.add_value(stochastic).unwrap_or(stochastic)
+    }
+
+    fn determine_condition(&self, stc: f64) -> STCCondition {
+        if stc > self.state.config.overbought {
+            STCCondition::Overbought
+        } else if stc < self.state.config.oversold {
+            STCCondition::Oversold
+        } else {
+            STCCondition::Neutral
+        }
+    }
+}
+
+impl Default for STC {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to calculate STC for close prices without maintaining state
+pub fn calculate_stc_simple(
+    closes: &[f64],
+    fast_period: usize,
+    slow_period: usize,
+    cycle_period: usize,
+) -> Result<Vec<f64>, STCError> {
+    if closes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    calculate_stc_iter(
+        closes.iter().copied(),
+        fast_period,
+        slow_period,
+        cycle_period,
+    )
+}
+
+/// Like [`calculate_stc_simple`], but accepts any iterator of closes
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_stc_iter(
+    closes: impl IntoIterator<Item = f64>,
+    fast_period: usize,
+    slow_period: usize,
+    cycle_period: usize,
+) -> Result<Vec<f64>, STCError> {
+    let mut stc_calculator = STC::with_periods(fast_period, slow_period, cycle_period)?;
+
+    closes
+        .into_iter()
+        .map(|close| {
+            let input = STCInput {
+                timestamp: None,
+                close,
+            };
+            stc_calculator.calculate(input).map(|output| output.stc)
+        })
+        .collect()
+}
+
+/// Like [`calculate_stc_simple`], but warmup bars are `None` instead of placeholder values
+pub fn calculate_stc_simple_checked(
+    closes: &[f64],
+    fast_period: usize,
+    slow_period: usize,
+    cycle_period: usize,
+) -> Result<Vec<Option<f64>>, STCError> {
+    if closes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut stc_calculator = STC::with_periods(fast_period, slow_period, cycle_period)?;
+    let mut results = Vec::with_capacity(closes.len());
+
+    for &close in closes {
+        let input = STCInput {
+            timestamp: None,
+            close,
+        };
+        let output = stc_calculator.calculate_checked(input)?;
+        results.push(output.map(|output| output.stc));
+    }
+
+    Ok(results)
+}
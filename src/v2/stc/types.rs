@@ -0,0 +1,176 @@
+use crate::common::types::{Candle, GapPolicy};
+use crate::util::ema_chain::main::EmaChain;
+use crate::util::rolling_extremes::main::RollingExtremes;
+use crate::v1::ema::main::ExponentialMovingAverage;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for Schaff Trend Cycle calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct STCConfig {
+    /// Fast EMA period of the MACD line (default: 23)
+    pub fast_period: usize,
+    /// Slow EMA period of the MACD line (default: 50)
+    pub slow_period: usize,
+    /// Window of both stochastic passes (default: 10)
+    pub cycle_period: usize,
+    /// Smoothing factor applied after each stochastic pass, in (0, 1] (default: 0.5)
+    pub factor: f64,
+    /// Overbought threshold (default: 75.0)
+    pub overbought: f64,
+    /// Oversold threshold (default: 25.0)
+    pub oversold: f64,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+    /// Decimal places `stc` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+impl Default for STCConfig {
+    fn default() -> Self {
+        Self {
+            fast_period: 23,
+            slow_period: 50,
+            cycle_period: 10,
+            factor: 0.5,
+            overbought: 75.0,
+            oversold: 25.0,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl STCConfig {
+    /// Check the invariants the STC calculator relies on
+    pub fn validate(&self) -> Result<(), STCError> {
+        if self.fast_period == 0 || self.cycle_period == 0 || self.fast_period >= self.slow_period {
+            return Err(STCError::InvalidPeriod);
+        }
+
+        if !(self.factor > 0.0 && self.factor <= 1.0) {
+            return Err(STCError::InvalidFactor);
+        }
+
+        if self.oversold >= self.overbought {
+            return Err(STCError::InvalidThresholds);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for Schaff Trend Cycle calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct STCInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Closing price
+    pub close: f64,
+}
+
+impl From<Candle> for STCInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            close: candle.close,
+        }
+    }
+}
+
+/// Output from Schaff Trend Cycle calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct STCOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Schaff Trend Cycle value (0-100)
+    pub stc: f64,
+    /// MACD line (fast EMA - slow EMA)
+    pub macd: f64,
+    /// Smoothed first stochastic pass (stochastic of the MACD line, 0-100)
+    pub stoch_macd: f64,
+    /// Market condition based on the overbought/oversold thresholds
+    pub condition: STCCondition,
+}
+
+/// Market condition classification for STC
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum STCCondition {
+    /// STC above the overbought threshold
+    Overbought,
+    /// STC below the oversold threshold
+    Oversold,
+    /// STC between the thresholds
+    Neutral,
+    /// Not enough data yet
+    Insufficient,
+}
+
+/// Schaff Trend Cycle calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct STCState {
+    /// Configuration
+    pub config: STCConfig,
+    /// Fast EMA of the close
+    pub fast_ema: EmaChain,
+    /// Slow EMA of the close
+    pub slow_ema: EmaChain,
+    /// Number of closes seen (the MACD line is ready after `slow_period`)
+    pub count: usize,
+    /// Rolling min/max of the MACD line over the cycle window
+    pub macd_extremes: RollingExtremes,
+    /// Smoothed first stochastic pass
+    pub stoch_macd: ExponentialMovingAverage,
+    /// Rolling min/max of the first pass over the cycle window
+    pub stoch_extremes: RollingExtremes,
+    /// Smoothed second stochastic pass (the STC)
+    pub stc: ExponentialMovingAverage,
+    /// Whether we have enough data for calculation
+    pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<STCInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<STCOutput>,
+}
+
+impl STCState {
+    pub fn new(config: STCConfig) -> Self {
+        Self {
+            config,
+            fast_ema: EmaChain::new(config.fast_period, 1),
+            slow_ema: EmaChain::new(config.slow_period, 1),
+            count: 0,
+            macd_extremes: RollingExtremes::new(config.cycle_period),
+            stoch_macd: Self::factor_ema(&config),
+            stoch_extremes: RollingExtremes::new(config.cycle_period),
+            stc: Self::factor_ema(&config),
+            has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+
+    /// EMA smoothing a stochastic pass, with `factor` as its alpha
+    fn factor_ema(config: &STCConfig) -> ExponentialMovingAverage {
+        // An invalid factor is reported by `validate`, so no bar is ever smoothed with it
+        ExponentialMovingAverage::from_alpha(config.factor)
+            .unwrap_or_else(|_| ExponentialMovingAverage::new(1))
+    }
+}
+
+/// Error types for Schaff Trend Cycle calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum STCError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid period (must be > 0, fast < slow)
+    InvalidPeriod,
+    /// Invalid smoothing factor (must be in (0, 1])
+    InvalidFactor,
+    /// Invalid threshold values
+    InvalidThresholds,
+}
@@ -1,12 +1,27 @@
 #[cfg(test)]
 mod tests {
+    use crate::common::assert_dense_batch;
+    use crate::common::types::GapPolicy;
     use crate::v2::std_dev::{
         main::{
-            calculate_standard_deviation_simple, rolling_standard_deviation, StandardDeviation,
+            calculate_standard_deviation_iter, calculate_standard_deviation_simple,
+            calculate_standard_deviation_simple_checked, rolling_standard_deviation,
+            rolling_standard_deviation_filled, StandardDeviation,
+        },
+        types::{
+            RunningStats, StandardDeviationConfig, StandardDeviationError, StandardDeviationInput,
+            StandardDeviationOutput, StandardDeviationState, StdDevMode, VolatilityLevel,
         },
-        types::{StandardDeviationError, StandardDeviationInput},
     };
 
+    fn with_gap_policy(gap_policy: GapPolicy) -> StandardDeviation {
+        StandardDeviation::with_config(StandardDeviationConfig {
+            period: 3,
+            gap_policy,
+            ..Default::default()
+        })
+    }
+
     #[test]
     fn test_std_dev_basic_calculation() {
         let mut std_dev = StandardDeviation::with_period(3).unwrap();
@@ -19,7 +34,10 @@ mod tests {
 
         let mut results = Vec::new();
         for value in test_values {
-            let input = StandardDeviationInput { value };
+            let input = StandardDeviationInput {
+                timestamp: None,
+                value,
+            };
             let result = std_dev.calculate(input).unwrap();
             results.push(result);
         }
@@ -42,17 +60,26 @@ mod tests {
         let mut sample_std_dev = StandardDeviation::sample(3).unwrap();
 
         for value in values {
-            let input = StandardDeviationInput { value };
+            let input = StandardDeviationInput {
+                timestamp: None,
+                value,
+            };
             let _ = pop_std_dev.calculate(input).unwrap();
             let _ = sample_std_dev.calculate(input).unwrap();
         }
 
         // Sample std dev should be larger than population std dev
         let pop_result = pop_std_dev
-            .calculate(StandardDeviationInput { value: 5.0 })
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 5.0,
+            })
             .unwrap();
         let sample_result = sample_std_dev
-            .calculate(StandardDeviationInput { value: 5.0 })
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 5.0,
+            })
             .unwrap();
 
         assert!(sample_result.std_dev > pop_result.std_dev);
@@ -67,7 +94,10 @@ mod tests {
         let values = vec![10.0, 12.0, 14.0, 16.0, 18.0];
 
         for value in values {
-            let input = StandardDeviationInput { value };
+            let input = StandardDeviationInput {
+                timestamp: None,
+                value,
+            };
             let _ = std_dev.calculate(input).unwrap();
         }
 
@@ -75,7 +105,10 @@ mod tests {
         let baseline_state = std_dev.get_state().clone();
 
         // Test z-score calculation with a normal value first
-        let normal_input = StandardDeviationInput { value: 14.0 }; // Should be close to mean
+        let normal_input = StandardDeviationInput {
+            timestamp: None,
+            value: 14.0,
+        }; // Should be close to mean
         let normal_result = std_dev.calculate(normal_input).unwrap();
 
         // Z-score for the mean should be close to 0
@@ -85,7 +118,10 @@ mod tests {
         std_dev.set_state(baseline_state);
 
         // Test with a value that's definitely an outlier based on the original data
-        let outlier_input = StandardDeviationInput { value: 30.0 }; // Much higher
+        let outlier_input = StandardDeviationInput {
+            timestamp: None,
+            value: 30.0,
+        }; // Much higher
         let outlier_result = std_dev.calculate(outlier_input).unwrap();
 
         // Z-score should be positive and significant
@@ -108,7 +144,10 @@ mod tests {
         let mut final_result = None;
 
         for value in values {
-            let input = StandardDeviationInput { value };
+            let input = StandardDeviationInput {
+                timestamp: None,
+                value,
+            };
             final_result = Some(std_dev.calculate(input).unwrap());
         }
 
@@ -116,7 +155,7 @@ mod tests {
 
         // CV should be (std_dev / mean) * 100
         let expected_cv = (result.std_dev / result.mean.abs()) * 100.0;
-        assert!((result.coefficient_of_variation - expected_cv).abs() < 1e-10);
+        assert!((result.coefficient_of_variation.unwrap() - expected_cv).abs() < 1e-10);
     }
 
     #[test]
@@ -127,26 +166,41 @@ mod tests {
         // Low volatility data (tight range)
         let low_vol_values = vec![100.0, 100.1, 99.9];
         for value in low_vol_values {
-            let input = StandardDeviationInput { value };
+            let input = StandardDeviationInput {
+                timestamp: None,
+                value,
+            };
             let _ = low_vol.calculate(input).unwrap();
         }
 
         // High volatility data (wide range)
         let high_vol_values = vec![100.0, 150.0, 50.0];
         for value in high_vol_values {
-            let input = StandardDeviationInput { value };
+            let input = StandardDeviationInput {
+                timestamp: None,
+                value,
+            };
             let _ = high_vol.calculate(input).unwrap();
         }
 
         let low_result = low_vol
-            .calculate(StandardDeviationInput { value: 100.0 })
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 100.0,
+            })
             .unwrap();
         let high_result = high_vol
-            .calculate(StandardDeviationInput { value: 100.0 })
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 100.0,
+            })
             .unwrap();
 
         // High volatility should have higher coefficient of variation
-        assert!(high_result.coefficient_of_variation > low_result.coefficient_of_variation);
+        assert!(
+            high_result.coefficient_of_variation.unwrap()
+                > low_result.coefficient_of_variation.unwrap()
+        );
     }
 
     #[test]
@@ -158,7 +212,10 @@ mod tests {
         let mut final_result = None;
 
         for value in values {
-            let input = StandardDeviationInput { value };
+            let input = StandardDeviationInput {
+                timestamp: None,
+                value,
+            };
             final_result = Some(std_dev.calculate(input).unwrap());
         }
 
@@ -184,7 +241,10 @@ mod tests {
 
         // Test invalid value
         let mut std_dev = StandardDeviation::new();
-        let invalid_input = StandardDeviationInput { value: f64::NAN };
+        let invalid_input = StandardDeviationInput {
+            timestamp: None,
+            value: f64::NAN,
+        };
         assert!(matches!(
             std_dev.calculate(invalid_input),
             Err(StandardDeviationError::InvalidValue)
@@ -205,11 +265,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_std_dev_iter_matches_slice_version() {
+        let values = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+
+        let from_slice = calculate_standard_deviation_simple(&values, 4, false).unwrap();
+        let from_iter =
+            calculate_standard_deviation_iter(values.iter().copied(), 4, false).unwrap();
+
+        assert_eq!(from_iter, from_slice);
+    }
+
+    #[test]
+    fn test_std_dev_simple_checked_marks_warmup() {
+        let values = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        let result = calculate_standard_deviation_simple_checked(&values, 3, false).unwrap();
+        assert_eq!(result.len(), 5);
+        assert_eq!(result[..2], [None, None]);
+        assert!(result[2..].iter().all(Option::is_some));
+    }
+
     #[test]
     fn test_rolling_standard_deviation() {
         let values = vec![1.0, 2.0, 3.0, 4.0, 5.0];
 
-        let result = rolling_standard_deviation(&values, 3, false).unwrap();
+        let result = rolling_standard_deviation_filled(&values, 3, false).unwrap();
         assert_eq!(result.len(), 5);
 
         // Check that we get reasonable results
@@ -219,13 +300,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rolling_standard_deviation_min_periods() {
+        let values = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        // Full windows only
+        let result = rolling_standard_deviation(&values, 3, 3, false).unwrap();
+        assert_eq!(result[..2], [None, None]);
+        assert!(result[2..]
+            .iter()
+            .all(|v| (v.unwrap() - (2.0f64 / 3.0).sqrt()).abs() < 1e-12));
+
+        // Partial windows from two values on
+        let result = rolling_standard_deviation(&values, 3, 2, false).unwrap();
+        assert_eq!(result[0], None);
+        assert_eq!(result[1], Some(0.5));
+
+        // Sample std dev needs two values even with min_periods = 1
+        let result = rolling_standard_deviation(&values, 3, 1, true).unwrap();
+        assert_eq!(result[0], None);
+        assert!(result[1].is_some());
+
+        // The legacy form fills with 0.0
+        assert_eq!(
+            rolling_standard_deviation_filled(&values[..2], 3, false).unwrap(),
+            vec![0.0, 0.0]
+        );
+
+        assert_eq!(
+            rolling_standard_deviation(&values, 3, 4, false),
+            Err(StandardDeviationError::InvalidPeriod)
+        );
+        assert_eq!(
+            rolling_standard_deviation(&values, 0, 0, false),
+            Err(StandardDeviationError::InvalidPeriod)
+        );
+    }
+
     #[test]
     fn test_std_dev_state_management() {
         let mut std_dev = StandardDeviation::with_period(3).unwrap();
 
         // Add some data
         for i in 1..=5 {
-            let input = StandardDeviationInput { value: i as f64 };
+            let input = StandardDeviationInput {
+                timestamp: None,
+                value: i as f64,
+            };
             let _ = std_dev.calculate(input).unwrap();
         }
 
@@ -240,4 +361,421 @@ mod tests {
         std_dev.set_state(state);
         assert!(std_dev.get_state().has_sufficient_data);
     }
+
+    #[test]
+    fn test_gap_policy_error_rejects_nan_bar() {
+        let mut std_dev = with_gap_policy(GapPolicy::Error);
+        std_dev
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 1.0,
+            })
+            .unwrap();
+
+        let result = std_dev.calculate(StandardDeviationInput {
+            timestamp: None,
+            value: f64::NAN,
+        });
+        assert_eq!(result, Err(StandardDeviationError::InvalidValue));
+        assert_eq!(std_dev.get_state().values.len(), 1);
+    }
+
+    #[test]
+    fn test_gap_policy_skip_bar_ignores_nan_bar() {
+        let mut std_dev = with_gap_policy(GapPolicy::SkipBar);
+        std_dev
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 1.0,
+            })
+            .unwrap();
+        let before = std_dev
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 2.0,
+            })
+            .unwrap();
+
+        // The NaN bar repeats the previous output and leaves the window untouched
+        let skipped = std_dev
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: f64::NAN,
+            })
+            .unwrap();
+        assert_eq!(skipped, before);
+        assert_eq!(std_dev.get_state().values.len(), 2);
+
+        // The next valid bar completes the window as if the gap never happened
+        let result = std_dev
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 3.0,
+            })
+            .unwrap();
+        assert!((result.mean - 2.0).abs() < 1e-10);
+        assert!((result.std_dev - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_gap_policy_forward_fill_repeats_last_value() {
+        let mut std_dev = with_gap_policy(GapPolicy::ForwardFill);
+
+        // With no previous value there is nothing to forward-fill
+        let result = std_dev.calculate(StandardDeviationInput {
+            timestamp: None,
+            value: f64::NAN,
+        });
+        assert_eq!(result, Err(StandardDeviationError::InvalidValue));
+
+        std_dev
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 1.0,
+            })
+            .unwrap();
+        std_dev
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 4.0,
+            })
+            .unwrap();
+
+        // The NaN bar is processed as a repeat of 4.0: window [1, 4, 4]
+        let result = std_dev
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: f64::INFINITY,
+            })
+            .unwrap();
+        assert_eq!(result.current_value, 4.0);
+        assert!((result.mean - 3.0).abs() < 1e-10);
+        assert_eq!(std_dev.get_state().values.len(), 3);
+    }
+
+    #[test]
+    fn test_median_and_mad() {
+        let mut std_dev = StandardDeviation::population(5).unwrap();
+        let mut last = None;
+        for value in [3.0, 1.0, 4.0, 1.0, 5.0] {
+            last = Some(
+                std_dev
+                    .calculate(StandardDeviationInput {
+                        timestamp: None,
+                        value,
+                    })
+                    .unwrap(),
+            );
+        }
+
+        // Sorted window [1, 1, 3, 4, 5]: median 3, deviations [2, 2, 0, 1, 2] -> MAD 2
+        let last = last.unwrap();
+        assert_eq!(last.median, 3.0);
+        assert_eq!(last.mad, 2.0);
+
+        // Even window: median averages the middle pair
+        let mut even = StandardDeviation::population(4).unwrap();
+        let mut last = None;
+        for value in [1.0, 2.0, 3.0, 10.0] {
+            last = Some(
+                even.calculate(StandardDeviationInput {
+                    timestamp: None,
+                    value,
+                })
+                .unwrap(),
+            );
+        }
+        assert_eq!(last.unwrap().median, 2.5);
+    }
+
+    #[test]
+    fn test_outlier_inflates_std_dev_more_than_mad() {
+        let calm = [10.0, 10.2, 9.8, 10.1, 9.9, 10.0, 10.2, 9.8, 10.1, 9.9];
+        let mut spiked = calm;
+        spiked[9] = 100.0;
+
+        let run = |values: &[f64]| {
+            let mut std_dev = StandardDeviation::population(values.len()).unwrap();
+            let mut last = None;
+            for &value in values {
+                last = Some(
+                    std_dev
+                        .calculate(StandardDeviationInput {
+                            timestamp: None,
+                            value,
+                        })
+                        .unwrap(),
+                );
+            }
+            last.unwrap()
+        };
+
+        let before = run(&calm);
+        let after = run(&spiked);
+
+        let std_dev_growth = after.std_dev / before.std_dev;
+        let mad_growth = after.mad / before.mad;
+
+        assert!(std_dev_growth > 100.0, "std dev grew {}x", std_dev_growth);
+        assert!(mad_growth < 1.5, "MAD grew {}x", mad_growth);
+        assert!((after.median - before.median).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_timestamp_round_trips_through_gap_policies() {
+        let mut std_dev = with_gap_policy(GapPolicy::SkipBar);
+        let bar = |timestamp, value| StandardDeviationInput {
+            timestamp: Some(timestamp),
+            value,
+        };
+
+        assert_eq!(
+            std_dev.calculate(bar(1_000, 1.0)).unwrap().timestamp,
+            Some(1_000)
+        );
+        assert_eq!(
+            std_dev.calculate(bar(2_000, 2.0)).unwrap().timestamp,
+            Some(2_000)
+        );
+        // A skipped bar repeats the previous values but keeps its own timestamp
+        let skipped = std_dev.calculate(bar(3_000, f64::NAN)).unwrap();
+        assert_eq!(skipped.timestamp, Some(3_000));
+
+        let mut filled = with_gap_policy(GapPolicy::ForwardFill);
+        filled.calculate(bar(1_000, 1.0)).unwrap();
+        let output = filled.calculate(bar(2_000, f64::NAN)).unwrap();
+        assert_eq!(output.timestamp, Some(2_000));
+
+        let untimed = filled
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 3.0,
+            })
+            .unwrap();
+        assert_eq!(untimed.timestamp, None);
+    }
+
+    #[test]
+    fn test_running_stats_matches_two_pass() {
+        // A million samples around a large offset, where sum-of-squares would lose precision
+        let values: Vec<f64> = (0..1_000_000u64)
+            .map(|i| 1.0e9 + ((i * 7919) % 1000) as f64 * 0.001)
+            .collect();
+
+        let mut stats = RunningStats::new();
+        for &value in &values {
+            stats.push(value);
+        }
+
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+
+        assert_eq!(stats.count(), 1_000_000);
+        assert!((stats.mean() - mean).abs() < 1e-6);
+        assert!((stats.variance() - variance).abs() < 1e-9);
+        assert!((stats.std_dev() - variance.sqrt()).abs() < 1e-9);
+        assert!((stats.sample_variance() - variance * n / (n - 1.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_running_stats_small_and_edge_cases() {
+        let mut stats = RunningStats::new();
+        assert_eq!(
+            (stats.count(), stats.mean(), stats.variance()),
+            (0, 0.0, 0.0)
+        );
+
+        for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            stats.push(value);
+        }
+        stats.push(f64::NAN);
+        assert_eq!(stats.count(), 8);
+        assert!((stats.mean() - 5.0).abs() < 1e-12);
+        assert!((stats.std_dev() - 2.0).abs() < 1e-12);
+
+        stats.reset();
+        assert_eq!(stats, RunningStats::default());
+    }
+
+    #[test]
+    fn test_calculate_checked_separates_warmup_from_zero() {
+        let mut plain = StandardDeviation::with_period(3).unwrap();
+        let mut checked = StandardDeviation::with_period(3).unwrap();
+        let input = StandardDeviationInput {
+            timestamp: None,
+            value: 5.0,
+        };
+
+        // During warmup `calculate` reports 0.0, the same as a genuine flat window
+        assert_eq!(plain.calculate(input).unwrap().std_dev, 0.0);
+        assert_eq!(checked.calculate_checked(input).unwrap(), None);
+        assert_eq!(checked.calculate_checked(input).unwrap(), None);
+
+        let genuine = checked.calculate_checked(input).unwrap().unwrap();
+        assert_eq!(genuine.std_dev, 0.0);
+    }
+
+    #[test]
+    fn test_std_dev_negative_values() {
+        let mut negative = StandardDeviation::with_period(3).unwrap();
+        let mut positive = StandardDeviation::with_period(3).unwrap();
+
+        let mut result = None;
+        let mut shifted = None;
+        for value in [-2.0, -4.0, -6.0] {
+            result = Some(
+                negative
+                    .calculate(StandardDeviationInput {
+                        timestamp: None,
+                        value,
+                    })
+                    .unwrap(),
+            );
+            shifted = Some(
+                positive
+                    .calculate(StandardDeviationInput {
+                        timestamp: None,
+                        value: value + 10.0,
+                    })
+                    .unwrap(),
+            );
+        }
+        let result = result.unwrap();
+        let shifted = shifted.unwrap();
+
+        // Dispersion does not depend on the sign of the values
+        assert!((result.std_dev - shifted.std_dev).abs() < 1e-12);
+        assert!((result.z_score - shifted.z_score).abs() < 1e-12);
+        assert_eq!(result.mean, -4.0);
+
+        // A ratio to a negative mean is meaningless
+        assert_eq!(result.coefficient_of_variation, None);
+        assert_eq!(result.volatility_level, VolatilityLevel::Undefined);
+        assert!(shifted.coefficient_of_variation.is_some());
+    }
+
+    #[test]
+    fn test_std_dev_zero_mean_has_no_cv() {
+        let mut std_dev = StandardDeviation::with_period(2).unwrap();
+        std_dev
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: -1.0,
+            })
+            .unwrap();
+        let result = std_dev
+            .calculate(StandardDeviationInput {
+                timestamp: None,
+                value: 1.0,
+            })
+            .unwrap();
+
+        assert_eq!(result.mean, 0.0);
+        assert!(result.std_dev > 0.0);
+        assert_eq!(result.coefficient_of_variation, None);
+    }
+
+    #[test]
+    fn test_try_with_config_rejects_invalid_config() {
+        let config = StandardDeviationConfig {
+            period: 1,
+            use_sample: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            StandardDeviation::try_with_config(config).err(),
+            Some(StandardDeviationError::InvalidPeriod)
+        );
+        assert!(StandardDeviation::try_with_config(StandardDeviationConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(StandardDeviation::new(), StandardDeviationInput);
+    }
+
+    #[test]
+    fn test_cumulative_mode_matches_two_pass() {
+        let values: Vec<f64> = (0..200)
+            .map(|i| 1_000_000.0 + ((i * 37) % 11) as f64 * 0.5)
+            .collect();
+
+        for use_sample in [true, false] {
+            let config = StandardDeviationConfig {
+                period: 5,
+                use_sample,
+                mode: StdDevMode::Cumulative,
+                ..Default::default()
+            };
+            let mut std_dev = StandardDeviation::with_config(config);
+            let outputs: Vec<_> = values
+                .iter()
+                .map(|&value| {
+                    std_dev
+                        .calculate(StandardDeviationInput {
+                            timestamp: None,
+                            value,
+                        })
+                        .unwrap()
+                })
+                .collect();
+
+            // Two-pass reference over every value so far
+            let n = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / n;
+            let squares: f64 = values.iter().map(|v| (v - mean).powi(2)).sum();
+            let variance = if use_sample {
+                squares / (n - 1.0)
+            } else {
+                squares / n
+            };
+
+            let last = outputs.last().unwrap();
+            assert!((last.mean - mean).abs() < 1e-9);
+            assert!((last.variance - variance).abs() < 1e-9);
+            assert!((last.std_dev - variance.sqrt()).abs() < 1e-9);
+            assert_eq!(std_dev.get_state().running.count(), values.len() as u64);
+
+            // Median and MAD stay on the last `period` values, bounding the history
+            assert_eq!(std_dev.get_state().values.len(), 5);
+            let mut recent = values[values.len() - 5..].to_vec();
+            recent.sort_by(f64::total_cmp);
+            assert_eq!(last.median, recent[2]);
+        }
+    }
+
+    #[test]
+    fn test_restores_baseline_state() {
+        // State saved by indexes-rs 1.0.1 after the values 10.0, 11.5, 11.0, 12.5, 12.0
+        let json = r#"{"config":{"period":3,"use_sample":true},"values":[11.0,12.5,12.0],"sum":35.5,"sum_squared":421.25,"has_sufficient_data":true,"current_mean":11.833333333333334}"#;
+        let state: StandardDeviationState = serde_json::from_str(json).unwrap();
+        let mut restored = StandardDeviation::new();
+        restored.set_state(state);
+
+        let input = |value| StandardDeviationInput {
+            timestamp: None,
+            value,
+        };
+        let mut fresh = StandardDeviation::with_period(3).unwrap();
+        for value in [10.0, 11.5, 11.0, 12.5, 12.0] {
+            fresh.calculate(input(value)).unwrap();
+        }
+
+        let expected = fresh.calculate(input(13.0)).unwrap();
+        let actual = restored.calculate(input(13.0)).unwrap();
+        assert!((actual.mean - expected.mean).abs() < 1e-9);
+        assert!((actual.std_dev - expected.std_dev).abs() < 1e-9);
+        assert_eq!(actual.median, expected.median);
+    }
+
+    #[test]
+    fn test_output_without_median_and_mad_deserializes() {
+        // Output saved by indexes-rs 1.0.1, before median and MAD were reported
+        let json = r#"{"std_dev":0.7637626158259734,"variance":0.5833333333333334,"mean":11.833333333333334,"current_value":12.0,"z_score":0.21821789023599236,"coefficient_of_variation":6.454332,"volatility_level":"VeryHigh"}"#;
+        let output: StandardDeviationOutput = serde_json::from_str(json).unwrap();
+        assert_eq!(output.median, 0.0);
+        assert_eq!(output.mad, 0.0);
+        assert_eq!(output.coefficient_of_variation, Some(6.454332));
+    }
 }
@@ -1,6 +1,8 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
 use crate::v2::std_dev::types::{
     StandardDeviationConfig, StandardDeviationError, StandardDeviationInput,
-    StandardDeviationOutput, StandardDeviationState, VolatilityLevel,
+    StandardDeviationOutput, StandardDeviationState, StdDevMode, VolatilityLevel,
 };
 
 /// Standard Deviation Indicator
@@ -25,6 +27,8 @@ use crate::v2::std_dev::types::{
 /// - Z-score calculations
 pub struct StandardDeviation {
     state: StandardDeviationState,
+    /// Configuration error found at construction
+    config_error: Option<StandardDeviationError>,
 }
 
 impl StandardDeviation {
@@ -55,6 +59,7 @@ impl StandardDeviation {
         let config = StandardDeviationConfig {
             period,
             use_sample: false,
+            ..Default::default()
         };
         Ok(Self::with_config(config))
     }
@@ -68,6 +73,7 @@ impl StandardDeviation {
         let config = StandardDeviationConfig {
             period,
             use_sample: true,
+            ..Default::default()
         };
         Ok(Self::with_config(config))
     }
@@ -76,53 +82,66 @@ impl StandardDeviation {
     pub fn with_config(config: StandardDeviationConfig) -> Self {
         Self {
             state: StandardDeviationState::new(config),
+            config_error: config.validate().err(),
         }
     }
 
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(
+        config: StandardDeviationConfig,
+    ) -> Result<Self, StandardDeviationError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
     /// Calculate Standard Deviation for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
     pub fn calculate(
         &mut self,
         input: StandardDeviationInput,
     ) -> Result<StandardDeviationOutput, StandardDeviationError> {
-        // Validate input
-        self.validate_input(&input)?;
-        self.validate_config()?;
-
-        // Update value history
-        self.update_value_history(input.value);
-
-        // Calculate standard deviation if we have enough data
-        let (std_dev, variance, mean) = if self.state.has_sufficient_data {
-            self.calculate_standard_deviation()?
-        } else {
-            (0.0, 0.0, input.value) // Default values when insufficient data
-        };
-
-        // Calculate derived metrics
-        let z_score = if std_dev != 0.0 {
-            (input.value - mean) / std_dev
-        } else {
-            0.0
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| StandardDeviationOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(StandardDeviationError::InvalidValue)
+            }
+            GapAction::Reject => return Err(StandardDeviationError::InvalidValue),
         };
 
-        let coefficient_of_variation = if mean != 0.0 {
-            (std_dev / mean.abs()) * 100.0
-        } else {
-            0.0
-        };
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.std_dev = round_to(output.std_dev, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
 
-        // Classify volatility level
-        let volatility_level = self.classify_volatility(std_dev, mean);
+        Ok(output)
+    }
 
-        Ok(StandardDeviationOutput {
-            std_dev,
-            variance,
-            mean,
-            current_value: input.value,
-            z_score,
-            coefficient_of_variation,
-            volatility_level,
-        })
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(
+        &mut self,
+        input: StandardDeviationInput,
+    ) -> Result<Option<StandardDeviationOutput>, StandardDeviationError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
     }
 
     /// Calculate Standard Deviation for a batch of inputs
@@ -133,6 +152,20 @@ impl StandardDeviation {
         inputs.iter().map(|input| self.calculate(*input)).collect()
     }
 
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[StandardDeviationInput],
+    ) -> Result<DenseBatch<StandardDeviationOutput>, StandardDeviationError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            StandardDeviationError::InvalidInput,
+        )
+    }
+
     /// Reset the calculator state
     pub fn reset(&mut self) {
         self.state = StandardDeviationState::new(self.state.config);
@@ -145,6 +178,7 @@ impl StandardDeviation {
 
     /// Restore state (for deserialization)
     pub fn set_state(&mut self, state: StandardDeviationState) {
+        self.config_error = state.config.validate().err();
         self.state = state;
     }
 
@@ -186,6 +220,61 @@ impl StandardDeviation {
 
     // Private helper methods
 
+    fn calculate_bar(
+        &mut self,
+        input: StandardDeviationInput,
+    ) -> Result<StandardDeviationOutput, StandardDeviationError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        // Update value history
+        self.update_value_history(input.value);
+
+        // Calculate standard deviation if we have enough data
+        let (std_dev, variance, mean) = if self.state.has_sufficient_data {
+            self.calculate_standard_deviation()?
+        } else {
+            (0.0, 0.0, input.value) // Default values when insufficient data
+        };
+
+        // Calculate derived metrics
+        let z_score = if std_dev != 0.0 {
+            (input.value - mean) / std_dev
+        } else {
+            0.0
+        };
+
+        let coefficient_of_variation = coefficient_of_variation(std_dev, mean);
+
+        // Classify volatility level
+        let volatility_level = self.classify_volatility(coefficient_of_variation);
+
+        // Robust spread: median and median absolute deviation
+        let (median, mad) = if self.state.has_sufficient_data {
+            self.calculate_median_and_mad()
+        } else {
+            (input.value, 0.0) // Default values when insufficient data
+        };
+
+        Ok(StandardDeviationOutput {
+            timestamp: None,
+            std_dev,
+            variance,
+            mean,
+            current_value: input.value,
+            z_score,
+            coefficient_of_variation,
+            volatility_level,
+            median,
+            mad,
+        })
+    }
+
+    fn is_finite_input(input: &StandardDeviationInput) -> bool {
+        input.value.is_finite()
+    }
+
     fn validate_input(&self, input: &StandardDeviationInput) -> Result<(), StandardDeviationError> {
         if !input.value.is_finite() {
             return Err(StandardDeviationError::InvalidValue);
@@ -194,34 +283,26 @@ impl StandardDeviation {
     }
 
     fn validate_config(&self) -> Result<(), StandardDeviationError> {
-        if self.state.config.period == 0 {
-            return Err(StandardDeviationError::InvalidPeriod);
-        }
-
-        if self.state.config.use_sample && self.state.config.period <= 1 {
-            return Err(StandardDeviationError::InvalidPeriod);
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
         }
-
-        Ok(())
     }
 
     fn update_value_history(&mut self, value: f64) {
-        // Remove oldest if at capacity
-        if self.state.values.len() >= self.state.config.period {
-            if let Some(oldest) = self.state.values.pop_front() {
-                self.state.sum -= oldest;
-                self.state.sum_squared -= oldest * oldest;
-            }
+        let cumulative = self.state.config.mode == StdDevMode::Cumulative;
+        if cumulative {
+            self.state.running.push(value);
         }
 
-        // Add new value
-        self.state.values.push_back(value);
-        self.state.sum += value;
-        self.state.sum_squared += value * value;
+        // Add new value, evicting the oldest if at capacity
+        self.state.values.push(value);
 
         // Update mean
-        if !self.state.values.is_empty() {
-            self.state.current_mean = self.state.sum / self.state.values.len() as f64;
+        if cumulative {
+            self.state.current_mean = self.state.running.mean();
+        } else if let Some(mean) = self.state.values.mean() {
+            self.state.current_mean = mean;
         }
 
         // Check if we have sufficient data
@@ -235,12 +316,25 @@ impl StandardDeviation {
             return Ok((0.0, 0.0, self.state.current_mean));
         }
 
-        let n = self.state.values.len() as f64;
-        let mean = self.state.sum / n;
+        if self.state.config.mode == StdDevMode::Cumulative {
+            return self.cumulative_standard_deviation();
+        }
 
-        // Calculate variance using the computational formula
-        // Var = (Σx²)/n - (Σx/n)²
-        let variance_raw = (self.state.sum_squared / n) - (mean * mean);
+        let n = self.state.values.len() as f64;
+        let mean = self.state.values.sum() / n;
+
+        // Two-pass variance over the window. The shortcut Σx²/n - mean² cancels
+        // catastrophically when the spread is tiny next to the level (prices around
+        // 60000 moving by cents); the second sum corrects the rounding of `mean`.
+        let (squares, deviations) =
+            self.state
+                .values
+                .iter()
+                .fold((0.0, 0.0), |(squares, deviations), &value| {
+                    let deviation = value - mean;
+                    (squares + deviation * deviation, deviations + deviation)
+                });
+        let variance_raw = (squares - deviations * deviations / n) / n;
 
         // Apply sample vs population correction
         let variance = if self.state.config.use_sample && n > 1.0 {
@@ -263,16 +357,43 @@ impl StandardDeviation {
         Ok((std_dev, variance, mean))
     }
 
-    fn classify_volatility(&self, std_dev: f64, mean: f64) -> VolatilityLevel {
+    fn cumulative_standard_deviation(&self) -> Result<(f64, f64, f64), StandardDeviationError> {
+        // Welford's accumulator stays accurate however many values have been seen
+        let running = &self.state.running;
+        let variance = if self.state.config.use_sample {
+            running.sample_variance()
+        } else {
+            running.variance()
+        };
+
+        let std_dev = variance.sqrt();
+        if !std_dev.is_finite() {
+            return Err(StandardDeviationError::DivisionByZero);
+        }
+
+        Ok((std_dev, variance, running.mean()))
+    }
+
+    fn calculate_median_and_mad(&self) -> (f64, f64) {
+        // The window holds at most `period` values in both modes, so sorting a copy
+        // per bar is cheap enough
+        let mut sorted: Vec<f64> = self.state.values.iter().copied().collect();
+        let median = median_of(&mut sorted);
+
+        let mut deviations: Vec<f64> = sorted.iter().map(|v| (v - median).abs()).collect();
+        let mad = median_of(&mut deviations);
+
+        (median, mad)
+    }
+
+    fn classify_volatility(&self, coefficient_of_variation: Option<f64>) -> VolatilityLevel {
         if !self.state.has_sufficient_data {
             return VolatilityLevel::Insufficient;
         }
 
         // Use coefficient of variation for relative volatility measurement
-        let cv = if mean != 0.0 {
-            (std_dev / mean.abs()) * 100.0
-        } else {
-            0.0
+        let Some(cv) = coefficient_of_variation else {
+            return VolatilityLevel::Undefined;
         };
 
         // Classification based on coefficient of variation
@@ -286,6 +407,28 @@ impl StandardDeviation {
     }
 }
 
+/// Coefficient of variation in percent, defined only for a positive mean
+///
+/// Zero or negative means (spreads, funding rates) would give an infinite or
+/// sign-flipped ratio, so they yield `None`.
+fn coefficient_of_variation(std_dev: f64, mean: f64) -> Option<f64> {
+    if mean <= f64::EPSILON {
+        return None;
+    }
+    Some(std_dev / mean * 100.0)
+}
+
+/// Median of a non-empty slice (sorts the slice in place)
+fn median_of(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
 impl Default for StandardDeviation {
     fn default() -> Self {
         Self::new()
@@ -302,22 +445,118 @@ pub fn calculate_standard_deviation_simple(
         return Ok(Vec::new());
     }
 
-    let config = StandardDeviationConfig { period, use_sample };
+    calculate_standard_deviation_iter(values.iter().copied(), period, use_sample)
+}
+
+/// Like [`calculate_standard_deviation_simple`], but accepts any iterator of values
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_standard_deviation_iter(
+    values: impl IntoIterator<Item = f64>,
+    period: usize,
+    use_sample: bool,
+) -> Result<Vec<f64>, StandardDeviationError> {
+    let config = StandardDeviationConfig {
+        period,
+        use_sample,
+        ..Default::default()
+    };
+
+    let mut std_dev_calculator = StandardDeviation::with_config(config);
+
+    values
+        .into_iter()
+        .map(|value| {
+            let input = StandardDeviationInput {
+                timestamp: None,
+                value,
+            };
+            std_dev_calculator
+                .calculate(input)
+                .map(|output| output.std_dev)
+        })
+        .collect()
+}
+
+/// Like [`calculate_standard_deviation_simple`], but warmup bars are `None` instead of placeholder values
+pub fn calculate_standard_deviation_simple_checked(
+    values: &[f64],
+    period: usize,
+    use_sample: bool,
+) -> Result<Vec<Option<f64>>, StandardDeviationError> {
+    if values.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let config = StandardDeviationConfig {
+        period,
+        use_sample,
+        ..Default::default()
+    };
 
     let mut std_dev_calculator = StandardDeviation::with_config(config);
     let mut results = Vec::with_capacity(values.len());
 
     for &value in values {
-        let input = StandardDeviationInput { value };
-        let output = std_dev_calculator.calculate(input)?;
-        results.push(output.std_dev);
+        let input = StandardDeviationInput {
+            timestamp: None,
+            value,
+        };
+        let output = std_dev_calculator.calculate_checked(input)?;
+        results.push(output.map(|output| output.std_dev));
     }
 
     Ok(results)
 }
 
 /// Calculate rolling standard deviation over a window
+///
+/// Windows holding fewer than `min_periods` values (the leading `min_periods - 1`
+/// bars) are `None`; longer partial windows are computed over the values available.
+/// Use `min_periods == window` for full windows only. A sample standard deviation
+/// needs at least two values, so those windows are `None` regardless.
+///
+/// Returns `StandardDeviationError::InvalidPeriod` if `window` is zero or
+/// `min_periods` exceeds it.
 pub fn rolling_standard_deviation(
+    values: &[f64],
+    window: usize,
+    min_periods: usize,
+    use_sample: bool,
+) -> Result<Vec<Option<f64>>, StandardDeviationError> {
+    if window == 0 || min_periods > window {
+        return Err(StandardDeviationError::InvalidPeriod);
+    }
+
+    let min_required = min_periods.max(if use_sample { 2 } else { 1 });
+
+    let results = (0..values.len())
+        .map(|i| {
+            let window_values = &values[(i + 1).saturating_sub(window)..=i];
+            if window_values.len() < min_required {
+                return None;
+            }
+
+            let n = window_values.len() as f64;
+            let mean = window_values.iter().sum::<f64>() / n;
+            let squares = window_values
+                .iter()
+                .map(|x| (x - mean).powi(2))
+                .sum::<f64>();
+            let divisor = if use_sample { n - 1.0 } else { n };
+            Some((squares / divisor).sqrt())
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Calculate rolling standard deviation over a window, filling unavailable values with 0.0
+///
+/// Legacy form of [`rolling_standard_deviation`]: if there are fewer values than `window`
+/// every result is 0.0, otherwise leading windows are computed over the values available.
+/// Prefer `rolling_standard_deviation`, which reports short windows as `None`.
+pub fn rolling_standard_deviation_filled(
     values: &[f64],
     window: usize,
     use_sample: bool,
@@ -333,7 +572,7 @@ pub fn rolling_standard_deviation(
     let mut results = Vec::with_capacity(values.len());
 
     for i in 0..values.len() {
-        let start = if i + 1 >= window { i + 1 - window } else { 0 };
+        let start = (i + 1).saturating_sub(window);
         let end = i + 1;
         let window_values = &values[start..end];
 
@@ -1,13 +1,41 @@
+use crate::common::types::{Candle, GapPolicy, Summation};
+use crate::util::rolling_sum::main::{RollingSum, StoredWindow};
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
 
 /// Configuration for Standard Deviation calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct StandardDeviationConfig {
     /// Period for standard deviation calculation (default: 20)
+    ///
+    /// In `StdDevMode::Cumulative` this is only the number of values required
+    /// before the first output.
     pub period: usize,
     /// Whether to use sample standard deviation (n-1) or population (n) (default: sample)
     pub use_sample: bool,
+    /// Whether statistics cover the last `period` values or all values so far (default: Windowed)
+    #[serde(default)]
+    pub mode: StdDevMode,
+    /// How the running sum behind the mean is accumulated (default: Summation::Naive)
+    #[serde(default)]
+    pub summation: Summation,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    #[serde(default)]
+    pub gap_policy: GapPolicy,
+    /// Decimal places `std_dev` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+/// Which values the Standard Deviation statistics are computed over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StdDevMode {
+    /// The last `period` values (rolling window)
+    #[default]
+    Windowed,
+    /// Every value since creation or the last reset, e.g. for whole-session statistics
+    ///
+    /// Mean and variance use Welford's algorithm over every value. Median and MAD
+    /// still cover the last `period` values, so memory stays bounded.
+    Cumulative,
 }
 
 impl Default for StandardDeviationConfig {
@@ -15,17 +43,49 @@ impl Default for StandardDeviationConfig {
         Self {
             period: 20,
             use_sample: true,
+            mode: StdDevMode::Windowed,
+            summation: Summation::Naive,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl StandardDeviationConfig {
+    /// Check the invariants the Standard Deviation calculator relies on
+    pub fn validate(&self) -> Result<(), StandardDeviationError> {
+        if self.period == 0 {
+            return Err(StandardDeviationError::InvalidPeriod);
         }
+
+        if self.use_sample && self.period <= 1 {
+            return Err(StandardDeviationError::InvalidPeriod);
+        }
+
+        Ok(())
     }
 }
 
 /// Input data for Standard Deviation calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct StandardDeviationInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// Value to calculate standard deviation for
     pub value: f64,
 }
 
+impl From<Candle> for StandardDeviationInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            value: candle.close,
+        }
+    }
+}
+
 /// Volatility classification based on standard deviation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum VolatilityLevel {
@@ -39,6 +99,8 @@ pub enum VolatilityLevel {
     High,
     /// Very high volatility
     VeryHigh,
+    /// Relative volatility is undefined because the mean is not positive
+    Undefined,
     /// Not enough data yet
     Insufficient,
 }
@@ -46,6 +108,9 @@ pub enum VolatilityLevel {
 /// Output from Standard Deviation calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct StandardDeviationOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// Standard deviation value
     pub std_dev: f64,
     /// Variance (std_dev squared)
@@ -56,42 +121,163 @@ pub struct StandardDeviationOutput {
     pub current_value: f64,
     /// Z-score of current value (how many std devs from mean)
     pub z_score: f64,
-    /// Coefficient of variation (std_dev / mean) - relative volatility
-    pub coefficient_of_variation: f64,
+    /// Coefficient of variation (std_dev / mean × 100) - relative volatility
+    ///
+    /// `None` when the mean is zero or negative, where a ratio to the mean is meaningless.
+    pub coefficient_of_variation: Option<f64>,
     /// Volatility level classification
     pub volatility_level: VolatilityLevel,
+    /// Median of the last `period` values (in either mode)
+    #[serde(default)]
+    pub median: f64,
+    /// Median absolute deviation from the median (robust to outliers)
+    #[serde(default)]
+    pub mad: f64,
 }
 
 /// Standard Deviation calculation state
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "StoredStandardDeviationState")]
 pub struct StandardDeviationState {
     /// Configuration
     pub config: StandardDeviationConfig,
-    /// History of values for period calculation
-    pub values: VecDeque<f64>,
-    /// Sum of values (for mean calculation)
-    pub sum: f64,
-    /// Sum of squared values (for variance calculation)
-    pub sum_squared: f64,
+    /// The last `period` values and their running sum (for mean calculation)
+    pub values: RollingSum,
+    /// Welford accumulator over all values (used in `StdDevMode::Cumulative`)
+    #[serde(default)]
+    pub running: RunningStats,
     /// Whether we have enough data for calculation
     pub has_sufficient_data: bool,
     /// Current mean value
     pub current_mean: f64,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<StandardDeviationInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<StandardDeviationOutput>,
 }
 
 impl StandardDeviationState {
     pub fn new(config: StandardDeviationConfig) -> Self {
         Self {
             config,
-            values: VecDeque::with_capacity(config.period),
-            sum: 0.0,
-            sum_squared: 0.0,
+            values: RollingSum::new(config.period).with_summation(config.summation),
+            running: RunningStats::new(),
             has_sufficient_data: false,
             current_mean: 0.0,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Serialized form of [`StandardDeviationState`], also accepting states saved while the
+/// values were a plain list next to a separate `sum`
+#[derive(Deserialize)]
+struct StoredStandardDeviationState {
+    config: StandardDeviationConfig,
+    values: StoredWindow,
+    #[serde(default)]
+    running: RunningStats,
+    has_sufficient_data: bool,
+    current_mean: f64,
+    last_valid_input: Option<StandardDeviationInput>,
+    last_output: Option<StandardDeviationOutput>,
+}
+
+impl From<StoredStandardDeviationState> for StandardDeviationState {
+    fn from(stored: StoredStandardDeviationState) -> Self {
+        let config = stored.config;
+        Self {
+            config,
+            values: stored
+                .values
+                .into_rolling_sum(config.period, config.summation),
+            running: stored.running,
+            has_sufficient_data: stored.has_sufficient_data,
+            current_mean: stored.current_mean,
+            last_valid_input: stored.last_valid_input,
+            last_output: stored.last_output,
         }
     }
 }
 
+/// All-time running mean and variance (Welford's online algorithm)
+///
+/// Unlike `StandardDeviation` this is not windowed: every value pushed since creation
+/// (or the last `reset`) contributes. Welford's update avoids the cancellation error of
+/// the naive sum-of-squares formula, so precision holds over millions of samples.
+///
+/// Non-finite values are ignored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunningStats {
+    count: u64,
+    mean: f64,
+    /// Sum of squared deviations from the current mean
+    m2: f64,
+}
+
+impl RunningStats {
+    /// Create empty running statistics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a value
+    pub fn push(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Number of values pushed
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Mean of all values (0.0 when empty)
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Population variance (0.0 when empty)
+    pub fn variance(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.m2 / self.count as f64
+        }
+    }
+
+    /// Sample variance (0.0 with fewer than two values)
+    pub fn sample_variance(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            self.m2 / (self.count - 1) as f64
+        }
+    }
+
+    /// Population standard deviation
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Sample standard deviation
+    pub fn sample_std_dev(&self) -> f64 {
+        self.sample_variance().sqrt()
+    }
+
+    /// Clear all accumulated values
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
 /// Error types for Standard Deviation calculation
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum StandardDeviationError {
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use crate::v1::ema::main::ExponentialMovingAverage;
+    use crate::v2::tsi::{
+        main::{calculate_tsi_simple, calculate_tsi_simple_checked, TSI},
+        types::{TSIConfig, TSICrossover, TSIError, TSIInput},
+    };
+
+    fn input(close: f64) -> TSIInput {
+        TSIInput {
+            timestamp: None,
+            close,
+        }
+    }
+
+    #[test]
+    fn test_tsi_positive_in_steady_uptrend() {
+        let mut tsi = TSI::with_periods(10, 5, 3).unwrap();
+        let mut last = None;
+        for i in 0..40 {
+            // Rising with small pullbacks every few bars
+            let close = 100.0 + i as f64 - if i % 4 == 3 { 1.5 } else { 0.0 };
+            last = tsi.calculate_checked(input(close)).unwrap();
+        }
+
+        let output = last.unwrap();
+        assert!(output.tsi > 0.0);
+        assert!(output.tsi <= 100.0);
+
+        // A perfectly steady uptrend has no down momentum at all
+        let values = calculate_tsi_simple(
+            &(0..40).map(|i| 100.0 + i as f64).collect::<Vec<_>>(),
+            10,
+            5,
+        )
+        .unwrap();
+        assert!((values.last().unwrap() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tsi_crosses_signal_on_reversals() {
+        let mut tsi = TSI::with_periods(6, 3, 3).unwrap();
+        let closes: Vec<f64> = (0..20)
+            .map(|i| 100.0 + i as f64)
+            .chain((0..15).map(|i| 119.0 - i as f64 * 1.5))
+            .chain((0..15).map(|i| 97.0 + i as f64 * 2.0))
+            .collect();
+
+        let crosses: Vec<(usize, TSICrossover)> = closes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &close)| {
+                let output = tsi.calculate_checked(input(close)).unwrap()?;
+                (output.crossover != TSICrossover::None).then_some((i, output.crossover))
+            })
+            .collect();
+
+        let bearish = crosses
+            .iter()
+            .find(|(_, cross)| *cross == TSICrossover::Bearish)
+            .expect("bearish cross after the top");
+        assert!((20..35).contains(&bearish.0));
+        let bullish = crosses
+            .iter()
+            .find(|(i, cross)| *cross == TSICrossover::Bullish && *i > bearish.0)
+            .expect("bullish cross after the bottom");
+        assert!(bullish.0 >= 35);
+    }
+
+    #[test]
+    fn test_matches_nested_emas() {
+        let closes: Vec<f64> = (0..30)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 4.0 + i as f64 * 0.3)
+            .collect();
+        let mut tsi = TSI::with_periods(8, 4, 3).unwrap();
+        let mut emas: Vec<ExponentialMovingAverage> = [8, 4, 8, 4, 3]
+            .into_iter()
+            .map(ExponentialMovingAverage::new)
+            .collect();
+
+        // The first close only seeds the momentum term
+        tsi.calculate(input(closes[0])).unwrap();
+        for window in closes.windows(2) {
+            let momentum = window[1] - window[0];
+            let long = emas[0].add_value(momentum).unwrap();
+            let smoothed = emas[1].add_value(long).unwrap();
+            let abs_long = emas[2].add_value(momentum.abs()).unwrap();
+            let smoothed_abs = emas[3].add_value(abs_long).unwrap();
+
+            let Some(output) = tsi.calculate_checked(input(window[1])).unwrap() else {
+                continue;
+            };
+            let expected = 100.0 * smoothed / smoothed_abs;
+            assert!((output.tsi - expected).abs() < 1e-9);
+            assert!((output.signal - emas[4].add_value(expected).unwrap()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_flat_prices_read_zero() {
+        let values = calculate_tsi_simple_checked(&[50.0; 20], 5, 3).unwrap();
+
+        // First bar has no momentum; ready after long + short - 1 momentum values
+        assert!(values[..7].iter().all(Option::is_none));
+        assert!(values[7..].iter().all(|value| *value == Some(0.0)));
+    }
+
+    #[test]
+    fn test_invalid_config_and_input() {
+        assert_eq!(
+            TSI::with_periods(0, 13, 7).err(),
+            Some(TSIError::InvalidPeriod)
+        );
+        assert!(TSI::try_with_config(TSIConfig {
+            signal_period: 0,
+            ..TSIConfig::default()
+        })
+        .is_err());
+
+        let mut tsi = TSI::new();
+        assert_eq!(tsi.calculate(input(f64::NAN)), Err(TSIError::InvalidPrice));
+    }
+}
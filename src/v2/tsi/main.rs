@@ -0,0 +1,273 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::v2::tsi::types::{TSIConfig, TSICrossover, TSIError, TSIInput, TSIOutput, TSIState};
+
+/// True Strength Index (TSI) Indicator
+///
+/// The TSI (William Blau) double-smooths price momentum with two EMAs and
+/// normalizes it by the double-smoothed absolute momentum, giving a bounded
+/// oscillator with little lag for its smoothness.
+///
+/// Formula:
+/// 1. Momentum = Close - Previous Close
+/// 2. Double-smoothed momentum = EMA(EMA(Momentum, long), short)
+/// 3. Double-smoothed |momentum| = EMA(EMA(|Momentum|, long), short)
+/// 4. TSI = 100 × (2) / (3)
+/// 5. Signal = EMA(TSI, signal)
+///
+/// Interpretation:
+/// - TSI ranges from -100 to +100; positive readings mean upward momentum
+/// - Crosses of the TSI over its signal line flag momentum reversals
+/// - Flat prices have no momentum at all and read 0
+pub struct TSI {
+    state: TSIState,
+    /// Configuration error found at construction
+    config_error: Option<TSIError>,
+}
+
+impl TSI {
+    /// Create a new TSI calculator with default configuration (25/13, signal 7)
+    pub fn new() -> Self {
+        Self::with_config(TSIConfig::default())
+    }
+
+    /// Create a new TSI calculator with custom smoothing and signal periods
+    pub fn with_periods(
+        long_period: usize,
+        short_period: usize,
+        signal_period: usize,
+    ) -> Result<Self, TSIError> {
+        Self::try_with_config(TSIConfig {
+            long_period,
+            short_period,
+            signal_period,
+            ..TSIConfig::default()
+        })
+    }
+
+    /// Create a new TSI calculator with custom configuration
+    pub fn with_config(config: TSIConfig) -> Self {
+        Self {
+            state: TSIState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: TSIConfig) -> Result<Self, TSIError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Calculate the TSI for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(&mut self, input: TSIInput) -> Result<TSIOutput, TSIError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| TSIOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(TSIError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(TSIError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.tsi = round_to(output.tsi, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(&mut self, input: TSIInput) -> Result<Option<TSIOutput>, TSIError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Calculate the TSI for a batch of inputs
+    pub fn calculate_batch(&mut self, inputs: &[TSIInput]) -> Result<Vec<TSIOutput>, TSIError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[TSIInput],
+    ) -> Result<DenseBatch<TSIOutput>, TSIError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            TSIError::InvalidInput,
+        )
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = TSIState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &TSIState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: TSIState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(&mut self, input: TSIInput) -> Result<TSIOutput, TSIError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        let prev_close = self.state.prev_close.replace(input.close);
+        let Some(prev_close) = prev_close else {
+            // No momentum on the first bar
+            return Ok(Self::placeholder_output());
+        };
+
+        let momentum = input.close - prev_close;
+        let smoothed = self.state.momentum.update(momentum);
+        let smoothed_abs = self.state.abs_momentum.update(momentum.abs());
+        self.state.momentum_count += 1;
+        self.state.has_sufficient_data = self.state.momentum_count
+            >= self.state.config.long_period + self.state.config.short_period - 1;
+        if !self.state.has_sufficient_data {
+            return Ok(Self::placeholder_output());
+        }
+
+        let tsi = if smoothed_abs <= f64::EPSILON {
+            0.0 // No momentum at all - neither strength nor weakness
+        } else {
+            (100.0 * smoothed / smoothed_abs).clamp(-100.0, 100.0)
+        };
+
+        let signal = self.state.signal_ema.add_value(tsi).unwrap_or(tsi);
+
+        let histogram = tsi - signal;
+        let crossover = self.detect_crossover(histogram);
+
+        Ok(TSIOutput {
+            timestamp: None,
+            tsi,
+            signal,
+            histogram,
+            crossover,
+        })
+    }
+
+    fn placeholder_output() -> TSIOutput {
+        // Default neutral values when insufficient data
+        TSIOutput {
+            timestamp: None,
+            tsi: 0.0,
+            signal: 0.0,
+            histogram: 0.0,
+            crossover: TSICrossover::None,
+        }
+    }
+
+    fn is_finite_input(input: &TSIInput) -> bool {
+        input.close.is_finite()
+    }
+
+    fn validate_input(&self, input: &TSIInput) -> Result<(), TSIError> {
+        if !input.close.is_finite() {
+            return Err(TSIError::InvalidPrice);
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), TSIError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn detect_crossover(&mut self, histogram: f64) -> TSICrossover {
+        let crossover = match self.state.prev_histogram {
+            Some(prev) if prev <= 0.0 && histogram > 0.0 => TSICrossover::Bullish,
+            Some(prev) if prev >= 0.0 && histogram < 0.0 => TSICrossover::Bearish,
+            _ => TSICrossover::None,
+        };
+        self.state.prev_histogram = Some(histogram);
+        crossover
+    }
+}
+
+impl Default for TSI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to calculate the TSI for close prices without maintaining state
+pub fn calculate_tsi_simple(
+    closes: &[f64],
+    long_period: usize,
+    short_period: usize,
+) -> Result<Vec<f64>, TSIError> {
+    calculate_tsi_simple_checked(closes, long_period, short_period).map(|values| {
+        values
+            .into_iter()
+            .map(|value| value.unwrap_or(0.0))
+            .collect()
+    })
+}
+
+/// Like [`calculate_tsi_simple`], but warmup bars are `None` instead of placeholder values
+pub fn calculate_tsi_simple_checked(
+    closes: &[f64],
+    long_period: usize,
+    short_period: usize,
+) -> Result<Vec<Option<f64>>, TSIError> {
+    if closes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut tsi_calculator = TSI::try_with_config(TSIConfig {
+        long_period,
+        short_period,
+        ..TSIConfig::default()
+    })?;
+
+    closes
+        .iter()
+        .map(|&close| {
+            let input = TSIInput {
+                timestamp: None,
+                close,
+            };
+            tsi_calculator
+                .calculate_checked(input)
+                .map(|output| output.map(|output| output.tsi))
+        })
+        .collect()
+}
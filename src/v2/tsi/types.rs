@@ -0,0 +1,142 @@
+use crate::common::types::{Candle, GapPolicy};
+use crate::util::ema_chain::main::EmaChain;
+use crate::v1::ema::main::ExponentialMovingAverage;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for True Strength Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TSIConfig {
+    /// Period of the first (long) momentum EMA (default: 25)
+    pub long_period: usize,
+    /// Period of the second (short) momentum EMA (default: 13)
+    pub short_period: usize,
+    /// Period of the signal line EMA of the TSI (default: 7)
+    pub signal_period: usize,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+    /// Decimal places `tsi` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+impl Default for TSIConfig {
+    fn default() -> Self {
+        Self {
+            long_period: 25,
+            short_period: 13,
+            signal_period: 7,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl TSIConfig {
+    /// Check the invariants the TSI calculator relies on
+    pub fn validate(&self) -> Result<(), TSIError> {
+        if self.long_period == 0 || self.short_period == 0 || self.signal_period == 0 {
+            return Err(TSIError::InvalidPeriod);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for True Strength Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TSIInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Closing price
+    pub close: f64,
+}
+
+impl From<Candle> for TSIInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            close: candle.close,
+        }
+    }
+}
+
+/// Output from True Strength Index calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TSIOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// TSI value, within [-100, 100]
+    pub tsi: f64,
+    /// Signal line (EMA of the TSI)
+    pub signal: f64,
+    /// TSI minus signal line
+    pub histogram: f64,
+    /// Cross of the TSI over its signal line on this bar
+    pub crossover: TSICrossover,
+}
+
+/// Cross of the TSI over its signal line
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TSICrossover {
+    /// TSI crossed above the signal line
+    Bullish,
+    /// TSI crossed below the signal line
+    Bearish,
+    /// No cross on this bar
+    None,
+}
+
+/// True Strength Index calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TSIState {
+    /// Configuration
+    pub config: TSIConfig,
+    /// Previous close, for the momentum term
+    pub prev_close: Option<f64>,
+    /// Double-smoothed momentum, EMA(EMA(momentum, long), short)
+    pub momentum: EmaChain,
+    /// Double-smoothed |momentum|, EMA(EMA(|momentum|, long), short)
+    pub abs_momentum: EmaChain,
+    /// Number of momentum values seen
+    pub momentum_count: usize,
+    /// Signal line (EMA of the TSI)
+    pub signal_ema: ExponentialMovingAverage,
+    /// Previous TSI minus signal, for crossover detection
+    pub prev_histogram: Option<f64>,
+    /// Whether we have enough data for calculation
+    pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<TSIInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<TSIOutput>,
+}
+
+impl TSIState {
+    pub fn new(config: TSIConfig) -> Self {
+        Self {
+            config,
+            prev_close: None,
+            momentum: EmaChain::with_periods(&[config.long_period, config.short_period]),
+            abs_momentum: EmaChain::with_periods(&[config.long_period, config.short_period]),
+            momentum_count: 0,
+            signal_ema: ExponentialMovingAverage::new(config.signal_period),
+            prev_histogram: None,
+            has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Error types for True Strength Index calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TSIError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Invalid period (must be > 0)
+    InvalidPeriod,
+}
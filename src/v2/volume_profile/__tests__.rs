@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use crate::v2::volume_profile::{
+        main::{calculate_volume_profile_simple, VolumeProfile},
+        types::{VolumeProfileConfig, VolumeProfileError, VolumeProfileInput},
+    };
+
+    fn bar(price: f64, volume: f64) -> VolumeProfileInput {
+        VolumeProfileInput {
+            timestamp: None,
+            price,
+            volume,
+        }
+    }
+
+    #[test]
+    fn test_poc_matches_heaviest_price_level() {
+        let mut profile = VolumeProfile::new(1.0, 50).unwrap();
+        // Most volume trades around 102.x
+        let bars = [
+            bar(100.2, 100.0),
+            bar(101.5, 200.0),
+            bar(102.1, 500.0),
+            bar(102.9, 400.0),
+            bar(103.4, 150.0),
+            bar(104.8, 50.0),
+        ];
+
+        let output = *profile.calculate_batch(&bars).unwrap().last().unwrap();
+        assert_eq!(output.poc_price, 102.5);
+        assert_eq!(output.poc_volume, 900.0);
+        assert_eq!(output.total_volume, 1400.0);
+
+        // Value area: 102 (900) -> +101 (200) = 1100 >= 980
+        assert_eq!(output.value_area_low, 101.0);
+        assert_eq!(output.value_area_high, 103.0);
+        assert_eq!(output.value_area_volume, 1100.0);
+
+        assert_eq!(
+            profile.profile(),
+            vec![
+                (100.0, 100.0),
+                (101.0, 200.0),
+                (102.0, 900.0),
+                (103.0, 150.0),
+                (104.0, 50.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_window_evicts_old_bars() {
+        let mut profile = VolumeProfile::new(0.5, 3).unwrap();
+        profile.calculate(bar(10.1, 1000.0)).unwrap();
+        profile.calculate(bar(12.2, 300.0)).unwrap();
+        let before = profile.calculate(bar(12.3, 300.0)).unwrap();
+        assert_eq!(before.poc_price, 10.25);
+
+        // The heavy bar at 10.1 leaves the window
+        let after = profile.calculate(bar(12.4, 100.0)).unwrap();
+        assert_eq!(after.poc_price, 12.25);
+        assert_eq!(after.total_volume, 700.0);
+        assert_eq!(profile.profile(), vec![(12.0, 700.0)]);
+        assert_eq!(profile.get_state().bars.len(), 3);
+    }
+
+    #[test]
+    fn test_volume_profile_validation() {
+        assert_eq!(
+            VolumeProfile::new(0.0, 10).err(),
+            Some(VolumeProfileError::InvalidBucketSize)
+        );
+        assert_eq!(
+            VolumeProfile::new(1.0, 0).err(),
+            Some(VolumeProfileError::InvalidPeriod)
+        );
+
+        let mut bad_area = VolumeProfile::with_config(VolumeProfileConfig {
+            value_area_pct: 1.5,
+            ..Default::default()
+        });
+        assert_eq!(
+            bad_area.calculate(bar(1.0, 1.0)),
+            Err(VolumeProfileError::InvalidValueArea)
+        );
+
+        let mut profile = VolumeProfile::default();
+        assert_eq!(
+            profile.calculate(bar(1.0, -1.0)),
+            Err(VolumeProfileError::NegativeVolume)
+        );
+        assert_eq!(
+            profile.calculate(bar(f64::NAN, 1.0)),
+            Err(VolumeProfileError::InvalidPrice)
+        );
+    }
+
+    #[test]
+    fn test_volume_profile_simple_function() {
+        let pocs =
+            calculate_volume_profile_simple(&[5.2, 7.7, 7.1], &[10.0, 20.0, 5.0], 1.0, 10).unwrap();
+        assert_eq!(pocs, vec![5.5, 7.5, 7.5]);
+
+        assert!(matches!(
+            calculate_volume_profile_simple(&[1.0], &[], 1.0, 10),
+            Err(VolumeProfileError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::common::types::GapAction;
+use crate::v2::volume_profile::types::{
+    VolumeProfileConfig, VolumeProfileError, VolumeProfileInput, VolumeProfileOutput,
+    VolumeProfileState,
+};
+
+/// Volume Profile (Price-by-Volume) Indicator
+///
+/// Volume Profile distributes the volume of a rolling window of bars over
+/// fixed-width price buckets, showing where trading activity concentrated.
+///
+/// Outputs:
+/// - Point of Control (POC): the bucket with the most volume
+/// - Value Area: the range around the POC holding `value_area_pct` of the
+///   window's volume, grown one bucket at a time towards the heavier neighbour
+///
+/// Bucket index = floor(price / bucket_size). Ties for the POC resolve to the
+/// lowest price. Bars older than `window` are evicted as new bars arrive.
+pub struct VolumeProfile {
+    state: VolumeProfileState,
+    /// Configuration error found at construction
+    config_error: Option<VolumeProfileError>,
+}
+
+impl VolumeProfile {
+    /// Create a new Volume Profile with the given bucket size and window length
+    pub fn new(bucket_size: f64, window: usize) -> Result<Self, VolumeProfileError> {
+        let config = VolumeProfileConfig {
+            bucket_size,
+            window,
+            ..Default::default()
+        };
+
+        let profile = Self::with_config(config);
+        profile.validate_config()?;
+        Ok(profile)
+    }
+
+    /// Create a new Volume Profile with custom configuration
+    pub fn with_config(config: VolumeProfileConfig) -> Self {
+        Self {
+            state: VolumeProfileState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: VolumeProfileConfig) -> Result<Self, VolumeProfileError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Add a bar and return the profile of the current window
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(
+        &mut self,
+        input: VolumeProfileInput,
+    ) -> Result<VolumeProfileOutput, VolumeProfileError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| VolumeProfileOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(VolumeProfileError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(VolumeProfileError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Add a batch of bars
+    pub fn calculate_batch(
+        &mut self,
+        inputs: &[VolumeProfileInput],
+    ) -> Result<Vec<VolumeProfileOutput>, VolumeProfileError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Current histogram as `(bucket lower price, volume)` pairs in ascending price order
+    pub fn profile(&self) -> Vec<(f64, f64)> {
+        let bucket_size = self.state.config.bucket_size;
+        self.state
+            .buckets
+            .iter()
+            .map(|(&index, &(volume, _))| (index as f64 * bucket_size, volume))
+            .collect()
+    }
+
+    /// Reset the calculator state
+    pub fn reset(&mut self) {
+        self.state = VolumeProfileState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &VolumeProfileState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: VolumeProfileState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(
+        &mut self,
+        input: VolumeProfileInput,
+    ) -> Result<VolumeProfileOutput, VolumeProfileError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        let index = (input.price / self.state.config.bucket_size).floor() as i64;
+        self.add_bar(index, input.volume);
+
+        Ok(self.summarize())
+    }
+
+    fn is_finite_input(input: &VolumeProfileInput) -> bool {
+        input.price.is_finite() && input.volume.is_finite()
+    }
+
+    fn validate_input(&self, input: &VolumeProfileInput) -> Result<(), VolumeProfileError> {
+        if !input.price.is_finite() || !input.volume.is_finite() {
+            return Err(VolumeProfileError::InvalidPrice);
+        }
+
+        if input.volume < 0.0 {
+            return Err(VolumeProfileError::NegativeVolume);
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), VolumeProfileError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn add_bar(&mut self, index: i64, volume: f64) {
+        // Evict the oldest bar once the window is full
+        if self.state.bars.len() >= self.state.config.window {
+            if let Some((old_index, old_volume)) = self.state.bars.pop_front() {
+                self.state.total_volume -= old_volume;
+                if let Some(bucket) = self.state.buckets.get_mut(&old_index) {
+                    bucket.0 -= old_volume;
+                    bucket.1 -= 1;
+                    if bucket.1 == 0 {
+                        self.state.buckets.remove(&old_index);
+                    }
+                }
+            }
+        }
+
+        self.state.bars.push_back((index, volume));
+        self.state.total_volume += volume;
+        let bucket = self.state.buckets.entry(index).or_insert((0.0, 0));
+        bucket.0 += volume;
+        bucket.1 += 1;
+    }
+
+    fn summarize(&self) -> VolumeProfileOutput {
+        let buckets: Vec<(i64, f64)> = self
+            .state
+            .buckets
+            .iter()
+            .map(|(&index, &(volume, _))| (index, volume))
+            .collect();
+
+        // Highest-volume bucket; the first (lowest price) wins ties
+        let mut poc = 0;
+        for (i, &(_, volume)) in buckets.iter().enumerate() {
+            if volume > buckets[poc].1 {
+                poc = i;
+            }
+        }
+
+        // Grow the value area from the POC towards the heavier neighbour
+        let target = self.state.total_volume * self.state.config.value_area_pct;
+        let (mut low, mut high) = (poc, poc);
+        let mut value_area_volume = buckets[poc].1;
+        while value_area_volume < target && (low > 0 || high + 1 < buckets.len()) {
+            let below = low.checked_sub(1).map(|i| buckets[i].1);
+            let above = buckets.get(high + 1).map(|bucket| bucket.1);
+
+            match (below, above) {
+                (Some(below), Some(above)) if below > above => {
+                    low -= 1;
+                    value_area_volume += below;
+                }
+                (_, Some(above)) => {
+                    high += 1;
+                    value_area_volume += above;
+                }
+                (Some(below), None) => {
+                    low -= 1;
+                    value_area_volume += below;
+                }
+                (None, None) => break,
+            }
+        }
+
+        let bucket_size = self.state.config.bucket_size;
+        VolumeProfileOutput {
+            timestamp: None,
+            poc_price: (buckets[poc].0 as f64 + 0.5) * bucket_size,
+            poc_volume: buckets[poc].1,
+            value_area_high: (buckets[high].0 + 1) as f64 * bucket_size,
+            value_area_low: buckets[low].0 as f64 * bucket_size,
+            value_area_volume,
+            total_volume: self.state.total_volume,
+        }
+    }
+}
+
+impl Default for VolumeProfile {
+    fn default() -> Self {
+        Self::with_config(VolumeProfileConfig::default())
+    }
+}
+
+/// Convenience function returning the POC price after each bar for price/volume data
+pub fn calculate_volume_profile_simple(
+    prices: &[f64],
+    volumes: &[f64],
+    bucket_size: f64,
+    window: usize,
+) -> Result<Vec<f64>, VolumeProfileError> {
+    if prices.len() != volumes.len() {
+        return Err(VolumeProfileError::InvalidInput(
+            "Prices and volumes must have same length".to_string(),
+        ));
+    }
+
+    calculate_volume_profile_iter(
+        prices.iter().copied().zip(volumes.iter().copied()),
+        bucket_size,
+        window,
+    )
+}
+
+/// Like [`calculate_volume_profile_simple`], but accepts any iterator of `(price, volume)` pairs
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_volume_profile_iter(
+    bars: impl IntoIterator<Item = (f64, f64)>,
+    bucket_size: f64,
+    window: usize,
+) -> Result<Vec<f64>, VolumeProfileError> {
+    let mut profile = VolumeProfile::new(bucket_size, window)?;
+
+    bars.into_iter()
+        .map(|(price, volume)| {
+            let input = VolumeProfileInput {
+                timestamp: None,
+                price,
+                volume,
+            };
+            profile.calculate(input).map(|output| output.poc_price)
+        })
+        .collect()
+}
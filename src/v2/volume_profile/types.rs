@@ -0,0 +1,136 @@
+use crate::common::types::{Candle, GapPolicy};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Configuration for Volume Profile calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VolumeProfileConfig {
+    /// Price width of each bucket (default: 1.0)
+    pub bucket_size: f64,
+    /// Number of bars in the rolling window (default: 100)
+    pub window: usize,
+    /// Share of the window's volume the value area must contain (default: 0.70)
+    pub value_area_pct: f64,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+}
+
+impl Default for VolumeProfileConfig {
+    fn default() -> Self {
+        Self {
+            bucket_size: 1.0,
+            window: 100,
+            value_area_pct: 0.70,
+            gap_policy: GapPolicy::Error,
+        }
+    }
+}
+
+impl VolumeProfileConfig {
+    /// Check the invariants the Volume Profile calculator relies on
+    pub fn validate(&self) -> Result<(), VolumeProfileError> {
+        if !self.bucket_size.is_finite() || self.bucket_size <= 0.0 {
+            return Err(VolumeProfileError::InvalidBucketSize);
+        }
+
+        if self.window == 0 {
+            return Err(VolumeProfileError::InvalidPeriod);
+        }
+
+        if !(self.value_area_pct > 0.0 && self.value_area_pct <= 1.0) {
+            return Err(VolumeProfileError::InvalidValueArea);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for Volume Profile calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VolumeProfileInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Price the bar's volume is attributed to
+    pub price: f64,
+    /// Bar volume
+    pub volume: f64,
+}
+
+impl From<Candle> for VolumeProfileInput {
+    /// Build an input from a candle, attributing its volume to the typical price
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            price: (candle.high + candle.low + candle.close) / 3.0,
+            volume: candle.volume,
+        }
+    }
+}
+
+/// Output from Volume Profile calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VolumeProfileOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Center price of the point-of-control (highest-volume) bucket
+    pub poc_price: f64,
+    /// Volume traded in the point-of-control bucket
+    pub poc_volume: f64,
+    /// Upper edge of the value area
+    pub value_area_high: f64,
+    /// Lower edge of the value area
+    pub value_area_low: f64,
+    /// Volume contained in the value area
+    pub value_area_volume: f64,
+    /// Total volume in the window
+    pub total_volume: f64,
+}
+
+/// Volume Profile calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolumeProfileState {
+    /// Configuration
+    pub config: VolumeProfileConfig,
+    /// Bucket index and volume of each bar in the window (oldest first)
+    pub bars: VecDeque<(i64, f64)>,
+    /// Volume and number of window bars per bucket index
+    pub buckets: BTreeMap<i64, (f64, usize)>,
+    /// Total volume in the window
+    pub total_volume: f64,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<VolumeProfileInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<VolumeProfileOutput>,
+}
+
+impl VolumeProfileState {
+    pub fn new(config: VolumeProfileConfig) -> Self {
+        Self {
+            config,
+            bars: VecDeque::with_capacity(config.window),
+            buckets: BTreeMap::new(),
+            total_volume: 0.0,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Error types for Volume Profile calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum VolumeProfileError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Negative volume provided
+    NegativeVolume,
+    /// Invalid bucket size (must be positive and finite)
+    InvalidBucketSize,
+    /// Invalid window (must be > 0)
+    InvalidPeriod,
+    /// Invalid value area share (must be in (0, 1])
+    InvalidValueArea,
+}
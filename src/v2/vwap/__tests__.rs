@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use crate::common::types::Candle;
+    use crate::v2::vwap::{
+        main::{calculate_vwap_simple, VwapBands},
+        types::{VwapError, VwapInput, VwapZone},
+    };
+
+    fn bar(price: f64, volume: f64) -> VwapInput {
+        VwapInput {
+            timestamp: None,
+            high: price,
+            low: price,
+            close: price,
+            volume,
+        }
+    }
+
+    #[test]
+    fn test_known_values() {
+        let mut vwap = VwapBands::with_std_dev(1.0).unwrap();
+        vwap.calculate(bar(10.0, 100.0)).unwrap();
+        let output = vwap.calculate(bar(20.0, 300.0)).unwrap();
+
+        // VWAP = (10×100 + 20×300) / 400 = 17.5; variance = (100×100 + 400×300) / 400 - 17.5²
+        assert!((output.vwap - 17.5).abs() < 1e-12);
+        assert!((output.std_dev - 18.75f64.sqrt()).abs() < 1e-9);
+        assert!((output.upper_band - (17.5 + 18.75f64.sqrt())).abs() < 1e-9);
+        assert!((output.lower_band - (17.5 - 18.75f64.sqrt())).abs() < 1e-9);
+
+        let from_candles =
+            calculate_vwap_simple(&[11.0, 21.0], &[9.0, 19.0], &[10.0, 20.0], &[100.0, 300.0])
+                .unwrap();
+        assert!((from_candles[1].unwrap() - 17.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_std_dev_holds_at_high_price_level() {
+        let bars: Vec<(f64, f64)> = (0..2000)
+            .map(|i| {
+                let price = 60_000.0 + ((i * 7919) % 13) as f64 * 0.01;
+                (price, 1.0 + (i % 5) as f64 * 250.0)
+            })
+            .collect();
+
+        let mut vwap = VwapBands::new();
+        let mut output = None;
+        for &(price, volume) in &bars {
+            output = Some(vwap.calculate(bar(price, volume)).unwrap());
+        }
+        let output = output.unwrap();
+
+        // Two-pass reference over the whole session
+        let volume: f64 = bars.iter().map(|(_, v)| v).sum();
+        let mean = bars.iter().map(|(p, v)| p * v).sum::<f64>() / volume;
+        let variance = bars
+            .iter()
+            .map(|(p, v)| v * (p - mean) * (p - mean))
+            .sum::<f64>()
+            / volume;
+        assert!((output.vwap - mean).abs() < 1e-9);
+        assert!((output.std_dev - variance.sqrt()).abs() < 1e-9 * variance.sqrt().max(1.0));
+    }
+
+    #[test]
+    fn test_price_above_vwap_sits_in_upper_region() {
+        let mut vwap = VwapBands::new();
+        let mut zones = Vec::new();
+
+        // A session opening low and then trading steadily higher
+        vwap.calculate(bar(95.0, 5000.0)).unwrap();
+        for i in 0..20 {
+            let output = vwap.calculate(bar(100.0 + i as f64 * 0.1, 1000.0)).unwrap();
+            assert!(output.typical_price > output.vwap);
+            zones.push(output.zone);
+        }
+
+        assert!(zones
+            .iter()
+            .all(|zone| matches!(zone, VwapZone::Upper | VwapZone::AboveUpper)));
+    }
+
+    #[test]
+    fn test_zero_volume_and_reset() {
+        let mut vwap = VwapBands::new();
+
+        // No volume yet: no VWAP
+        assert_eq!(vwap.calculate_checked(bar(10.0, 0.0)).unwrap(), None);
+        let output = vwap.calculate(bar(12.0, 0.0)).unwrap();
+        assert_eq!(output.zone, VwapZone::Insufficient);
+        assert_eq!(output.vwap, 12.0);
+
+        // Zero-volume bars carry no weight once volume has traded
+        vwap.calculate(bar(20.0, 100.0)).unwrap();
+        let output = vwap.calculate(bar(30.0, 0.0)).unwrap();
+        assert_eq!((output.vwap, output.std_dev), (20.0, 0.0));
+        assert_eq!(output.zone, VwapZone::AboveUpper);
+
+        // Re-anchoring starts a new session
+        vwap.reset();
+        let candle = Candle::new(50.0, 51.0, 49.0, 50.0, 10.0).with_timestamp(7);
+        let output = vwap.calculate(candle.into()).unwrap();
+        assert_eq!((output.vwap, output.timestamp), (50.0, Some(7)));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert_eq!(
+            VwapBands::with_std_dev(-1.0).err(),
+            Some(VwapError::InvalidMultiplier)
+        );
+
+        let mut vwap = VwapBands::new();
+        assert_eq!(
+            vwap.calculate(bar(10.0, -1.0)),
+            Err(VwapError::NegativeVolume)
+        );
+        assert_eq!(
+            vwap.calculate(bar(f64::NAN, 1.0)),
+            Err(VwapError::InvalidPrice)
+        );
+        assert_eq!(
+            vwap.calculate(VwapInput {
+                high: 9.0,
+                low: 10.0,
+                ..bar(9.5, 1.0)
+            }),
+            Err(VwapError::InvalidHLC)
+        );
+        assert!(calculate_vwap_simple(&[1.0], &[], &[], &[]).is_err());
+    }
+}
@@ -0,0 +1,274 @@
+use crate::common::precision::round_to;
+use crate::common::types::{DenseBatch, GapAction};
+use crate::transform::price_source::main::typical_price;
+use crate::v2::vwap::types::{
+    VwapBandsConfig, VwapError, VwapInput, VwapOutput, VwapState, VwapZone,
+};
+
+/// Anchored VWAP with Standard Deviation Bands
+///
+/// The Volume Weighted Average Price of every bar since the anchor (creation or
+/// the last `reset`, e.g. the session open), with bands a multiple of the
+/// volume-weighted standard deviation of price away from it. Everything is kept
+/// as running weighted sums, so each bar is O(1).
+///
+/// Formula (P = typical price, V = volume, sums since the anchor):
+/// - VWAP = Σ(P × V) / ΣV
+/// - Variance = Σ(P² × V) / ΣV - VWAP²
+/// - Bands = VWAP ± num_std_dev × √Variance
+///
+/// Interpretation:
+/// - Price holding above VWAP means buyers have paid up through the session
+/// - The outer bands mark stretched prices relative to the volume-weighted mean
+/// - Until any volume trades (e.g. a pre-market of zero-volume bars) there is no
+///   VWAP; the bar's own price is reported as a placeholder
+pub struct VwapBands {
+    state: VwapState,
+    /// Configuration error found at construction
+    config_error: Option<VwapError>,
+}
+
+impl VwapBands {
+    /// Create a new VWAP band calculator with default configuration (±2 standard deviations)
+    pub fn new() -> Self {
+        Self::with_config(VwapBandsConfig::default())
+    }
+
+    /// Create a new VWAP band calculator with bands `num_std_dev` standard deviations wide
+    pub fn with_std_dev(num_std_dev: f64) -> Result<Self, VwapError> {
+        Self::try_with_config(VwapBandsConfig {
+            num_std_dev,
+            ..VwapBandsConfig::default()
+        })
+    }
+
+    /// Create a new VWAP band calculator with custom configuration
+    pub fn with_config(config: VwapBandsConfig) -> Self {
+        Self {
+            state: VwapState::new(config),
+            config_error: config.validate().err(),
+        }
+    }
+
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: VwapBandsConfig) -> Result<Self, VwapError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Calculate VWAP and its bands for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`.
+    pub fn calculate(&mut self, input: VwapInput) -> Result<VwapOutput, VwapError> {
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| VwapOutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(VwapError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(VwapError::InvalidPrice),
+        };
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.vwap = round_to(output.vwap, decimals);
+            output.upper_band = round_to(output.upper_band, decimals);
+            output.lower_band = round_to(output.lower_band, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Calculate for the given input, reporting bars before any volume traded as `None`
+    pub fn calculate_checked(&mut self, input: VwapInput) -> Result<Option<VwapOutput>, VwapError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
+    }
+
+    /// Calculate VWAP and its bands for a batch of inputs
+    pub fn calculate_batch(&mut self, inputs: &[VwapInput]) -> Result<Vec<VwapOutput>, VwapError> {
+        inputs.iter().map(|input| self.calculate(*input)).collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the outputs after volume first traded
+    ///
+    /// Unlike `calculate_batch`, placeholders are dropped; `start_index` tells which
+    /// input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[VwapInput],
+    ) -> Result<DenseBatch<VwapOutput>, VwapError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            VwapError::InvalidInput,
+        )
+    }
+
+    /// Re-anchor: forget every bar so far, e.g. at the start of a new session
+    pub fn reset(&mut self) {
+        self.state = VwapState::new(self.state.config);
+    }
+
+    /// Get current state (for serialization/debugging)
+    pub fn get_state(&self) -> &VwapState {
+        &self.state
+    }
+
+    /// Restore state (for deserialization)
+    pub fn set_state(&mut self, state: VwapState) {
+        self.config_error = state.config.validate().err();
+        self.state = state;
+    }
+
+    // Private helper methods
+
+    fn calculate_bar(&mut self, input: VwapInput) -> Result<VwapOutput, VwapError> {
+        // Validate input
+        self.validate_input(&input)?;
+        self.validate_config()?;
+
+        let price = typical_price(input.high, input.low, input.close);
+        self.update_moments(price, input.volume);
+
+        self.state.has_sufficient_data = self.state.volume_sum > 0.0;
+        if !self.state.has_sufficient_data {
+            // Zero-volume session so far - no weights to average with
+            return Ok(VwapOutput {
+                timestamp: None,
+                vwap: price,
+                upper_band: price,
+                lower_band: price,
+                std_dev: 0.0,
+                typical_price: price,
+                zone: VwapZone::Insufficient,
+            });
+        }
+
+        let vwap = self.state.vwap;
+        let std_dev = (self.state.squared_deviation_sum / self.state.volume_sum).sqrt();
+
+        let width = self.state.config.num_std_dev * std_dev;
+        let upper_band = vwap + width;
+        let lower_band = vwap - width;
+
+        let zone = if price > upper_band {
+            VwapZone::AboveUpper
+        } else if price >= vwap {
+            VwapZone::Upper
+        } else if price >= lower_band {
+            VwapZone::Lower
+        } else {
+            VwapZone::BelowLower
+        };
+
+        Ok(VwapOutput {
+            timestamp: None,
+            vwap,
+            upper_band,
+            lower_band,
+            std_dev,
+            typical_price: price,
+            zone,
+        })
+    }
+
+    /// Fold one weighted price into the VWAP and its squared deviations (West, 1979)
+    fn update_moments(&mut self, price: f64, volume: f64) {
+        let previous_volume = self.state.volume_sum;
+        self.state.volume_sum += volume;
+        if volume <= 0.0 {
+            return;
+        }
+
+        let delta = price - self.state.vwap;
+        self.state.vwap += delta * volume / self.state.volume_sum;
+        // Every factor is non-negative, so the sum never dips below zero
+        self.state.squared_deviation_sum +=
+            volume * delta * delta * previous_volume / self.state.volume_sum;
+    }
+
+    fn is_finite_input(input: &VwapInput) -> bool {
+        input.high.is_finite()
+            && input.low.is_finite()
+            && input.close.is_finite()
+            && input.volume.is_finite()
+    }
+
+    fn validate_input(&self, input: &VwapInput) -> Result<(), VwapError> {
+        if !Self::is_finite_input(input) {
+            return Err(VwapError::InvalidPrice);
+        }
+
+        if input.volume < 0.0 {
+            return Err(VwapError::NegativeVolume);
+        }
+
+        // Check HLC relationship
+        if input.high < input.low || input.close > input.high || input.close < input.low {
+            return Err(VwapError::InvalidHLC);
+        }
+
+        Ok(())
+    }
+
+    fn validate_config(&self) -> Result<(), VwapError> {
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for VwapBands {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to calculate anchored VWAP for HLCV data without maintaining state
+///
+/// Bars before any volume traded are `None`.
+pub fn calculate_vwap_simple(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    volumes: &[f64],
+) -> Result<Vec<Option<f64>>, VwapError> {
+    let len = highs.len();
+    if len != lows.len() || len != closes.len() || len != volumes.len() {
+        return Err(VwapError::InvalidInput(
+            "All price and volume arrays must have same length".to_string(),
+        ));
+    }
+
+    let mut vwap_calculator = VwapBands::new();
+
+    (0..len)
+        .map(|i| {
+            let input = VwapInput {
+                timestamp: None,
+                high: highs[i],
+                low: lows[i],
+                close: closes[i],
+                volume: volumes[i],
+            };
+            vwap_calculator
+                .calculate_checked(input)
+                .map(|output| output.map(|output| output.vwap))
+        })
+        .collect()
+}
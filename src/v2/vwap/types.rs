@@ -0,0 +1,146 @@
+use crate::common::types::{Candle, GapPolicy};
+use serde::{Deserialize, Serialize};
+
+/// Configuration for VWAP band calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VwapBandsConfig {
+    /// Number of volume-weighted standard deviations between VWAP and each band (default: 2.0)
+    pub num_std_dev: f64,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    pub gap_policy: GapPolicy,
+    /// Decimal places `vwap` and the bands are rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
+}
+
+impl Default for VwapBandsConfig {
+    fn default() -> Self {
+        Self {
+            num_std_dev: 2.0,
+            gap_policy: GapPolicy::Error,
+            precision: None,
+        }
+    }
+}
+
+impl VwapBandsConfig {
+    /// Check the invariants the VWAP band calculator relies on
+    pub fn validate(&self) -> Result<(), VwapError> {
+        if !self.num_std_dev.is_finite() || self.num_std_dev < 0.0 {
+            return Err(VwapError::InvalidMultiplier);
+        }
+
+        Ok(())
+    }
+}
+
+/// Input data for VWAP band calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VwapInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// High price
+    pub high: f64,
+    /// Low price
+    pub low: f64,
+    /// Close price
+    pub close: f64,
+    /// Volume
+    pub volume: f64,
+}
+
+impl From<Candle> for VwapInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            volume: candle.volume,
+        }
+    }
+}
+
+/// Where the bar's typical price sits relative to VWAP and its bands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VwapZone {
+    /// Above the upper band
+    AboveUpper,
+    /// Between VWAP and the upper band
+    Upper,
+    /// Between the lower band and VWAP
+    Lower,
+    /// Below the lower band
+    BelowLower,
+    /// No volume traded since the anchor yet
+    Insufficient,
+}
+
+/// Output from VWAP band calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VwapOutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Volume-weighted average typical price since the anchor
+    pub vwap: f64,
+    /// VWAP + num_std_dev × standard deviation
+    pub upper_band: f64,
+    /// VWAP - num_std_dev × standard deviation
+    pub lower_band: f64,
+    /// Volume-weighted standard deviation of the typical price around VWAP
+    pub std_dev: f64,
+    /// Typical price of this bar ((H+L+C)/3)
+    pub typical_price: f64,
+    /// Position of the typical price relative to VWAP and the bands
+    pub zone: VwapZone,
+}
+
+/// VWAP band calculation state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VwapState {
+    /// Configuration
+    pub config: VwapBandsConfig,
+    /// Σ volume since the anchor
+    pub volume_sum: f64,
+    /// Volume-weighted mean typical price since the anchor
+    pub vwap: f64,
+    /// Σ volume × (price - VWAP)² since the anchor, kept with West's weighted update
+    pub squared_deviation_sum: f64,
+    /// Whether any volume has traded since the anchor
+    pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<VwapInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<VwapOutput>,
+}
+
+impl VwapState {
+    pub fn new(config: VwapBandsConfig) -> Self {
+        Self {
+            config,
+            volume_sum: 0.0,
+            vwap: 0.0,
+            squared_deviation_sum: 0.0,
+            has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+        }
+    }
+}
+
+/// Error types for VWAP band calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum VwapError {
+    /// Invalid input data
+    InvalidInput(String),
+    /// Invalid HLC relationship (high < low, or close outside the range)
+    InvalidHLC,
+    /// Invalid price (NaN or infinite)
+    InvalidPrice,
+    /// Negative volume
+    NegativeVolume,
+    /// Invalid band multiplier (must be finite and >= 0)
+    InvalidMultiplier,
+}
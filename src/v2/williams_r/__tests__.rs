@@ -1,8 +1,13 @@
 #[cfg(test)]
 mod tests {
+    use crate::common::assert_dense_batch;
+    use crate::common::types::{BarMode, DistanceUnits, GapPolicy};
     use crate::v2::williams_r::{
-        main::{calculate_williams_r_simple, WilliamsR},
-        types::{WilliamsRConfig, WilliamsRError, WilliamsRInput, WilliamsRMarketCondition},
+        main::{calculate_williams_r_simple, calculate_williams_r_simple_checked, WilliamsR},
+        types::{
+            WilliamsRConfig, WilliamsRCrossover, WilliamsRError, WilliamsRInput,
+            WilliamsRMarketCondition, WilliamsRState,
+        },
     };
 
     #[test]
@@ -20,6 +25,7 @@ mod tests {
 
         for (i, (high, low, close)) in test_data.iter().enumerate() {
             let input = WilliamsRInput {
+                timestamp: None,
                 high: *high,
                 low: *low,
                 close: *close,
@@ -51,13 +57,19 @@ mod tests {
         let setup_data = vec![(10.0, 8.0, 9.0), (12.0, 9.0, 10.0), (11.0, 8.0, 9.5)];
 
         for (high, low, close) in setup_data {
-            let input = WilliamsRInput { high, low, close };
+            let input = WilliamsRInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
             let _ = williams_r.calculate(input).unwrap();
         }
 
         // Test overbought condition (close near highest high)
         // HH=12, LL=8, Close=11.8 should give Williams %R near 0 (overbought)
         let overbought_input = WilliamsRInput {
+            timestamp: None,
             high: 12.0,
             low: 10.0,
             close: 11.8,
@@ -71,6 +83,7 @@ mod tests {
         // Test oversold condition (close near lowest low)
         // Using a close near the lowest low should give Williams %R near -100 (oversold)
         let oversold_input = WilliamsRInput {
+            timestamp: None,
             high: 9.0,
             low: 7.0,
             close: 7.2,
@@ -90,12 +103,18 @@ mod tests {
         let setup_data = vec![(15.0, 10.0, 12.0), (16.0, 11.0, 13.0), (14.0, 9.0, 11.0)];
 
         for (high, low, close) in setup_data {
-            let input = WilliamsRInput { high, low, close };
+            let input = WilliamsRInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
             let _ = williams_r.calculate(input).unwrap();
         }
 
         // Test with known values: HH=16, LL=9, Close=12
         let test_input = WilliamsRInput {
+            timestamp: None,
             high: 13.0,
             low: 11.0,
             close: 12.0,
@@ -120,6 +139,7 @@ mod tests {
             oversold: -80.0,
             extreme_overbought: -10.0,
             extreme_oversold: -90.0,
+            ..Default::default()
         };
         let mut williams_r = WilliamsR::with_config(config);
 
@@ -127,7 +147,12 @@ mod tests {
         let setup_data = vec![(10.0, 8.0, 9.0), (12.0, 9.0, 10.0), (11.0, 8.0, 9.5)];
 
         for (high, low, close) in setup_data {
-            let input = WilliamsRInput { high, low, close };
+            let input = WilliamsRInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
             let _ = williams_r.calculate(input).unwrap();
         }
 
@@ -159,7 +184,12 @@ mod tests {
         let identical_data = vec![(10.0, 10.0, 10.0), (10.0, 10.0, 10.0), (10.0, 10.0, 10.0)];
 
         for (high, low, close) in identical_data {
-            let input = WilliamsRInput { high, low, close };
+            let input = WilliamsRInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
             let result = williams_r.calculate(input).unwrap();
 
             if result.market_condition != WilliamsRMarketCondition::Insufficient {
@@ -176,6 +206,7 @@ mod tests {
 
         // Test invalid HLC (high < low)
         let input = WilliamsRInput {
+            timestamp: None,
             high: 8.0,
             low: 10.0,
             close: 9.0,
@@ -187,6 +218,7 @@ mod tests {
 
         // Test close out of range
         let input = WilliamsRInput {
+            timestamp: None,
             high: 10.0,
             low: 8.0,
             close: 12.0,
@@ -220,16 +252,32 @@ mod tests {
 
         // All Williams %R values should be between 0 and -100
         for wr_value in result {
-            assert!(wr_value >= -100.0 && wr_value <= 0.0);
+            assert!((-100.0..=0.0).contains(&wr_value));
         }
     }
 
+    #[test]
+    fn test_williams_r_simple_checked_marks_warmup() {
+        let highs = vec![10.0, 12.0, 11.0, 13.0, 9.0];
+        let lows = vec![8.0, 9.0, 8.5, 10.0, 7.0];
+        let closes = vec![9.0, 11.0, 10.0, 12.5, 7.5];
+
+        let plain = calculate_williams_r_simple(&highs, &lows, &closes, 3).unwrap();
+        let checked = calculate_williams_r_simple_checked(&highs, &lows, &closes, 3).unwrap();
+        assert_eq!(checked[..2], [None, None]);
+        assert_eq!(
+            checked[2..],
+            plain[2..].iter().map(|&v| Some(v)).collect::<Vec<_>>()[..]
+        );
+    }
+
     #[test]
     fn test_williams_r_market_conditions() {
         let mut williams_r = WilliamsR::with_period(2).unwrap();
 
         // Test insufficient data
         let input1 = WilliamsRInput {
+            timestamp: None,
             high: 10.0,
             low: 8.0,
             close: 9.0,
@@ -242,6 +290,7 @@ mod tests {
 
         // Add more data to get sufficient data
         let input2 = WilliamsRInput {
+            timestamp: None,
             high: 12.0,
             low: 10.0,
             close: 11.0,
@@ -252,4 +301,524 @@ mod tests {
             WilliamsRMarketCondition::Insufficient
         );
     }
+
+    #[test]
+    fn test_set_thresholds_keeps_price_window() {
+        let mut williams_r = WilliamsR::with_period(3).unwrap();
+
+        let bars = [(10.0, 8.0, 9.0), (12.0, 9.0, 11.0), (11.0, 8.5, 10.5)];
+        let mut last = None;
+        for (high, low, close) in bars {
+            last = Some(
+                williams_r
+                    .calculate(WilliamsRInput {
+                        timestamp: None,
+                        high,
+                        low,
+                        close,
+                    })
+                    .unwrap(),
+            );
+        }
+
+        // Close 10.5 in a 8.0-12.0 range: %R = -37.5, Normal with default thresholds
+        let last = last.unwrap();
+        assert!((last.williams_r + 37.5).abs() < 1e-10);
+        assert_eq!(last.market_condition, WilliamsRMarketCondition::Normal);
+
+        // Tighten the overbought levels mid-stream
+        williams_r
+            .set_thresholds(-40.0, -80.0, -30.0, -90.0)
+            .unwrap();
+        assert_eq!(williams_r.get_state().high_window.len(), 3);
+        assert!(williams_r.get_state().has_sufficient_data);
+
+        // The window still holds the earlier bars, so the next value is immediate
+        let result = williams_r
+            .calculate(WilliamsRInput {
+                timestamp: None,
+                high: 12.0,
+                low: 8.0,
+                close: 10.5,
+            })
+            .unwrap();
+        assert!((result.williams_r + 37.5).abs() < 1e-10);
+        assert_eq!(
+            result.market_condition,
+            WilliamsRMarketCondition::Overbought
+        );
+        assert_eq!(result.distance_from_overbought, result.williams_r + 40.0);
+    }
+
+    #[test]
+    fn test_set_thresholds_rejects_bad_ordering() {
+        let mut williams_r = WilliamsR::new();
+        let before = williams_r.get_state().config;
+
+        let result = williams_r.set_thresholds(-80.0, -20.0, -10.0, -90.0);
+        assert_eq!(result, Err(WilliamsRError::InvalidThresholds));
+        assert_eq!(williams_r.get_state().config, before);
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let config = WilliamsRConfig::builder()
+            .period(10)
+            .overbought(-25.0)
+            .oversold(-75.0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config,
+            WilliamsRConfig {
+                period: 10,
+                overbought: -25.0,
+                oversold: -75.0,
+                ..Default::default()
+            }
+        );
+
+        // Same invariants as the constructors
+        assert_eq!(
+            WilliamsRConfig::builder().period(0).build().err(),
+            WilliamsR::with_period(0).err()
+        );
+        assert_eq!(
+            WilliamsRConfig::builder().overbought(20.0).build().err(),
+            WilliamsR::with_thresholds(14, 20.0, -80.0, -10.0, -90.0).err()
+        );
+        assert_eq!(
+            WilliamsRConfig::builder().extreme_oversold(-70.0).build(),
+            Err(WilliamsRError::InvalidThresholds)
+        );
+    }
+
+    #[test]
+    fn test_precision_rounds_williams_r() {
+        let config = WilliamsRConfig::builder()
+            .period(3)
+            .precision(2)
+            .build()
+            .unwrap();
+        let mut rounded = WilliamsR::with_config(config);
+        let mut raw = WilliamsR::with_period(3).unwrap();
+
+        for (high, low, close) in [
+            (10.0, 8.0, 9.0),
+            (11.0, 9.0, 10.0),
+            (12.0, 9.5, 10.3),
+            (11.5, 9.0, 9.7),
+        ] {
+            let input = WilliamsRInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
+            let a = raw.calculate(input).unwrap();
+            let b = rounded.calculate(input).unwrap();
+            assert_eq!(b.williams_r, (a.williams_r * 100.0).round() / 100.0);
+        }
+    }
+
+    #[test]
+    fn test_update_or_replace_last() {
+        let bars = [
+            (10.0, 8.0, 9.0),
+            (12.0, 9.0, 11.0),
+            (11.0, 8.5, 10.0),
+            (13.0, 10.0, 12.5),
+            (9.0, 7.0, 7.5),
+            (10.0, 8.0, 9.5),
+        ];
+        let mut live = WilliamsR::with_period(3).unwrap();
+        let mut reference = WilliamsR::with_period(3).unwrap();
+
+        for &(high, low, close) in &bars {
+            let input = WilliamsRInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
+            // The bar first prints a wider range, then is revised to its final values
+            let provisional = WilliamsRInput {
+                high: high + 3.0,
+                low: low - 3.0,
+                ..input
+            };
+            live.calculate(provisional).unwrap();
+            let revised = live.update_or_replace_last(input).unwrap();
+
+            assert_eq!(revised, reference.calculate(input).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_rejected_revision_keeps_latest_bar() {
+        let bars = [
+            (10.0, 8.0, 9.0),
+            (12.0, 9.0, 11.0),
+            (11.0, 8.5, 10.0),
+            (13.0, 10.0, 12.5),
+        ];
+        let input = |(high, low, close): (f64, f64, f64)| WilliamsRInput {
+            timestamp: None,
+            high,
+            low,
+            close,
+        };
+        let mut live = WilliamsR::with_period(3).unwrap();
+        let mut reference = WilliamsR::with_period(3).unwrap();
+        for &bar in &bars[..3] {
+            live.calculate(input(bar)).unwrap();
+            reference.calculate(input(bar)).unwrap();
+        }
+
+        assert_eq!(
+            live.update_or_replace_last(input((f64::NAN, 8.5, 10.0))),
+            Err(WilliamsRError::InvalidPrice)
+        );
+        assert_eq!(
+            live.update_or_replace_last(input((8.0, 8.5, 8.2))),
+            Err(WilliamsRError::InvalidHLC)
+        );
+        assert_eq!(
+            live.calculate(input(bars[3])),
+            reference.calculate(input(bars[3]))
+        );
+    }
+
+    #[test]
+    fn test_calculate_batch_dense() {
+        assert_dense_batch!(WilliamsR::new(), WilliamsRInput);
+    }
+
+    #[test]
+    fn test_percent_distances_are_scale_invariant() {
+        let config = WilliamsRConfig::builder()
+            .period(5)
+            .distance_units(DistanceUnits::Percent)
+            .build()
+            .unwrap();
+        let bars = [
+            (10.0, 8.0, 9.0),
+            (12.0, 9.0, 11.0),
+            (11.0, 8.5, 10.0),
+            (13.0, 10.0, 12.5),
+            (9.0, 7.0, 7.5),
+            (11.0, 8.0, 10.5),
+            (12.5, 10.0, 12.0),
+        ];
+
+        let run = |scale: f64| {
+            let mut indicator = WilliamsR::with_config(config);
+            bars.iter()
+                .map(|&(high, low, close)| {
+                    let input = WilliamsRInput {
+                        timestamp: None,
+                        high: high * scale,
+                        low: low * scale,
+                        close: close * scale,
+                    };
+                    indicator.calculate(input).unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let (base, scaled) = (run(1.0), run(250.0));
+        for (a, b) in base.iter().zip(&scaled) {
+            assert!((a.distance_from_overbought - b.distance_from_overbought).abs() < 1e-9);
+        }
+
+        // Default band (-20 to -80) is 60 points wide
+        let last = base.last().unwrap();
+        let expected = (last.williams_r + 20.0) / 60.0 * 100.0;
+        assert!((last.distance_from_overbought - expected).abs() < 1e-9);
+        assert!((last.distance_from_oversold - (expected + 100.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_calculate_batch_vs_independent() {
+        let inputs: Vec<WilliamsRInput> = (0..6)
+            .map(|i| {
+                let base = 10.0 + (i % 3) as f64;
+                WilliamsRInput {
+                    timestamp: Some(i),
+                    high: base + 1.0,
+                    low: base - 1.0,
+                    close: base + 0.5,
+                }
+            })
+            .collect();
+
+        let mut calculator = WilliamsR::with_period(3).unwrap();
+        calculator.calculate(inputs[0]).unwrap();
+        let state_before = calculator.get_state().clone();
+
+        // Independent evaluation neither reads nor touches the running state
+        let independent = calculator.calculate_independent(&inputs).unwrap();
+        assert_eq!(calculator.get_state(), &state_before);
+        for (input, output) in inputs.iter().zip(&independent) {
+            assert_eq!(
+                *output,
+                WilliamsR::with_period(3)
+                    .unwrap()
+                    .calculate(*input)
+                    .unwrap()
+            );
+        }
+
+        // The batch continues the series from the existing state
+        let mut fresh = WilliamsR::with_period(3).unwrap();
+        let batch = fresh.calculate_batch(&inputs).unwrap();
+        assert_ne!(batch[2], independent[2]);
+        assert_ne!(
+            calculator.calculate_batch(&inputs[1..]).unwrap()[1],
+            independent[2]
+        );
+        assert_eq!(batch.len(), independent.len());
+    }
+
+    /// A period-1 bar on a 0..10 range whose %R is exactly `williams_r`
+    fn bar_with_r(williams_r: f64) -> WilliamsRInput {
+        WilliamsRInput {
+            timestamp: None,
+            high: 10.0,
+            low: 0.0,
+            close: (williams_r + 100.0) / 10.0,
+        }
+    }
+
+    fn with_signal(period: usize, signal_period: usize) -> WilliamsR {
+        let config = WilliamsRConfig::builder()
+            .period(period)
+            .signal_period(signal_period)
+            .build()
+            .unwrap();
+        WilliamsR::with_config(config)
+    }
+
+    #[test]
+    fn test_signal_line_lags_williams_r() {
+        let mut calculator = with_signal(1, 3);
+        let rising = [-90.0, -80.0, -70.0, -60.0, -50.0];
+        let outputs = calculator.calculate_batch(&rising.map(bar_with_r)).unwrap();
+
+        assert_eq!(outputs[0].signal, None);
+        assert_eq!(outputs[1].signal, None);
+        for i in 2..outputs.len() {
+            let signal = outputs[i].signal.unwrap();
+            let mean = rising[i - 2..=i].iter().sum::<f64>() / 3.0;
+            assert!((signal - mean).abs() < 1e-9);
+            // On a rising %R the average trails behind
+            assert!(signal < outputs[i].williams_r);
+        }
+
+        // Without a signal period there is no line and never a cross
+        let plain = WilliamsR::with_period(1)
+            .unwrap()
+            .calculate_batch(&rising.map(bar_with_r))
+            .unwrap();
+        assert!(plain
+            .iter()
+            .all(|o| o.signal.is_none() && o.crossover == WilliamsRCrossover::None));
+        assert_eq!(
+            WilliamsRConfig::builder().signal_period(0).build(),
+            Err(WilliamsRError::InvalidPeriod)
+        );
+    }
+
+    #[test]
+    fn test_signal_crossovers_fire_on_expected_bars() {
+        // %R:     -80, -60, -40, -90, -90, -20
+        // signal:   -, -70, -50, -65, -90, -55
+        // %R - signal: 10, 10, -25, 0, 35
+        let mut calculator = with_signal(1, 2);
+        let inputs = [-80.0, -60.0, -40.0, -90.0, -90.0, -20.0].map(bar_with_r);
+        let crossovers: Vec<_> = calculator
+            .calculate_batch(&inputs)
+            .unwrap()
+            .iter()
+            .map(|o| o.crossover)
+            .collect();
+
+        use WilliamsRCrossover::{Bearish, Bullish, None};
+        assert_eq!(crossovers, vec![None, None, None, Bearish, None, Bullish]);
+
+        // Revising the last bar rolls back its signal window update
+        let mut revised = with_signal(1, 2);
+        revised.calculate_batch(&inputs[..5]).unwrap();
+        revised.calculate(bar_with_r(-95.0)).unwrap();
+        let output = revised.update_or_replace_last(inputs[5]).unwrap();
+        assert_eq!(output.crossover, Bullish);
+        assert!((output.signal.unwrap() + 55.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_crossover_compares_unrounded_values() {
+        // %R - signal: -0.2, then +5.2; the rounded -50 would read +0.2 and hide the cross
+        let config = WilliamsRConfig::builder()
+            .period(1)
+            .signal_period(2)
+            .precision(0)
+            .build()
+            .unwrap();
+        let outputs = WilliamsR::with_config(config)
+            .calculate_batch(&[-50.0, -50.4, -40.0].map(bar_with_r))
+            .unwrap();
+
+        assert_eq!(outputs[1].williams_r, -50.0);
+        assert_eq!(outputs[2].crossover, WilliamsRCrossover::Bullish);
+    }
+
+    #[test]
+    fn test_tick_mode_skipped_tick_keeps_forming_bar() {
+        let bars: Vec<WilliamsRInput> = [
+            (10.0, 8.0, 9.0),
+            (12.0, 9.0, 11.0),
+            (11.0, 8.5, 10.0),
+            (13.0, 10.0, 12.5),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, &(high, low, close))| WilliamsRInput {
+            timestamp: Some(i as i64),
+            high,
+            low,
+            close,
+        })
+        .collect();
+
+        let config = WilliamsRConfig::builder()
+            .period(3)
+            .bar_mode(BarMode::Tick)
+            .gap_policy(GapPolicy::SkipBar)
+            .build()
+            .unwrap();
+        let mut ticked = WilliamsR::with_config(config);
+        let mut reference = WilliamsR::with_config(config);
+        for bar in &bars[..3] {
+            ticked.calculate(*bar).unwrap();
+            reference.calculate(*bar).unwrap();
+        }
+
+        // A NaN tick of the forming bar is skipped, an inconsistent one rejected;
+        // neither erases the bar
+        let skipped = ticked
+            .calculate(WilliamsRInput {
+                close: f64::NAN,
+                ..bars[2]
+            })
+            .unwrap();
+        assert_eq!(skipped, reference.get_state().last_output.unwrap());
+        assert_eq!(
+            ticked.calculate(WilliamsRInput {
+                high: bars[2].low - 1.0,
+                ..bars[2]
+            }),
+            Err(WilliamsRError::InvalidHLC)
+        );
+
+        // The bar can still be revised, and the next bar is unaffected
+        assert_eq!(ticked.calculate(bars[2]), reference.calculate(bars[2]));
+        assert_eq!(ticked.calculate(bars[3]), reference.calculate(bars[3]));
+    }
+
+    #[test]
+    fn test_tick_mode_matches_closed_bars() {
+        // Closed bars, each preceded by two intrabar ticks with the same timestamp
+        let closed: Vec<WilliamsRInput> = [
+            (12.0, 9.0, 11.0),
+            (13.0, 10.0, 12.5),
+            (12.5, 10.5, 11.0),
+            (14.0, 11.0, 13.5),
+            (15.0, 12.0, 12.5),
+            (13.0, 10.0, 10.5),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, &(high, low, close))| WilliamsRInput {
+            timestamp: Some(i as i64),
+            high,
+            low,
+            close,
+        })
+        .collect();
+
+        let config = WilliamsRConfig::builder()
+            .period(3)
+            .signal_period(2)
+            .bar_mode(BarMode::Tick)
+            .build()
+            .unwrap();
+        let mut ticked = WilliamsR::with_config(config);
+        let mut reference = WilliamsR::with_config(WilliamsRConfig {
+            bar_mode: BarMode::Close,
+            ..config
+        });
+
+        for bar in &closed {
+            let mid = (bar.high + bar.low) / 2.0;
+            for tick in [mid, bar.low] {
+                ticked
+                    .calculate(WilliamsRInput {
+                        high: tick.max(mid),
+                        low: tick,
+                        close: tick,
+                        ..*bar
+                    })
+                    .unwrap();
+            }
+            assert_eq!(
+                ticked.calculate(*bar).unwrap(),
+                reference.calculate(*bar).unwrap()
+            );
+        }
+
+        // Inputs without a timestamp are always new bars, even in tick mode
+        let untimed = WilliamsRInput {
+            timestamp: None,
+            ..closed[0]
+        };
+        let mut no_timestamps = WilliamsR::with_config(config);
+        for _ in 0..3 {
+            no_timestamps.calculate(untimed).unwrap();
+        }
+        assert!(no_timestamps.calculate_checked(untimed).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_restores_baseline_state() {
+        // State saved by indexes-rs 1.0.1, when the window was plain `highs`/`lows` lists
+        let json = r#"{"config":{"period":3,"overbought":-20.0,"oversold":-80.0,"extreme_overbought":-10.0,"extreme_oversold":-90.0},"highs":[12.5,13.0,12.8],"lows":[10.5,11.0,11.6],"highest_high":13.0,"lowest_low":10.5,"has_sufficient_data":true}"#;
+        let state: WilliamsRState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.high_window.len(), 3);
+        let mut restored = WilliamsR::new();
+        restored.set_state(state);
+
+        let input = |(high, low, close)| WilliamsRInput {
+            timestamp: None,
+            high,
+            low,
+            close,
+        };
+        let mut fresh = WilliamsR::with_period(3).unwrap();
+        for bar in [
+            (11.0, 9.0, 10.0),
+            (12.0, 10.0, 11.5),
+            (12.5, 10.5, 11.0),
+            (13.0, 11.0, 12.5),
+            (12.8, 11.6, 12.0),
+        ] {
+            fresh.calculate(input(bar)).unwrap();
+        }
+
+        // Enough bars to evict every restored high and low
+        for bar in [(13.4, 12.1, 13.2), (13.1, 11.9, 12.2), (12.6, 11.8, 12.4)] {
+            let expected = fresh.calculate(input(bar)).unwrap();
+            let actual = restored.calculate(input(bar)).unwrap();
+            assert_eq!(actual.williams_r, expected.williams_r);
+        }
+    }
 }
@@ -1,6 +1,8 @@
+use crate::common::precision::round_to;
+use crate::common::types::{BarMode, DenseBatch, DistanceUnits, GapAction};
 use crate::v2::williams_r::types::{
-    WilliamsRConfig, WilliamsRError, WilliamsRInput, WilliamsRMarketCondition, WilliamsROutput,
-    WilliamsRState,
+    WilliamsRConfig, WilliamsRCrossover, WilliamsRError, WilliamsRInput, WilliamsRMarketCondition,
+    WilliamsROutput, WilliamsRRollback, WilliamsRState,
 };
 
 /// Williams %R Indicator
@@ -23,21 +25,23 @@ use crate::v2::williams_r::types::{
 /// - Above -10: Extremely overbought
 /// - Below -90: Extremely oversold
 ///
+/// An optional SMA signal line of %R (`signal_period`) smooths it like the
+/// Stochastic's %D; crosses of %R over the line are flagged in the output.
+///
 /// Williams %R is particularly useful for:
 /// - Identifying extreme price conditions
 /// - Timing entry/exit points
 /// - Confirming trend reversals
 pub struct WilliamsR {
     state: WilliamsRState,
+    /// Configuration error found at construction
+    config_error: Option<WilliamsRError>,
 }
 
 impl WilliamsR {
     /// Create a new Williams %R calculator with default configuration (period=14)
     pub fn new() -> Self {
-        // Use default config directly to avoid validation issues during testing
-        Self {
-            state: WilliamsRState::new(WilliamsRConfig::default()),
-        }
+        Self::with_config(WilliamsRConfig::default())
     }
 
     /// Create a new Williams %R calculator with custom period
@@ -65,19 +69,7 @@ impl WilliamsR {
             return Err(WilliamsRError::InvalidPeriod);
         }
 
-        // Williams %R thresholds should be negative and in descending order (towards more negative)
-        // Scale: 0 (most overbought) to -100 (most oversold)
-        // Valid order: extreme_overbought > overbought > oversold > extreme_oversold
-        // Example: -10 > -20 > -80 > -90
-        if overbought >= 0.0
-            || oversold >= overbought          // Want: -80 < -20 → -80 >= -20 is false ✅
-            || extreme_overbought >= 0.0
-            || extreme_overbought <= overbought // Want: -10 > -20 → -10 <= -20 is false ✅
-            || extreme_oversold >= oversold
-        {
-            // Want: -90 < -80 → -90 >= -80 is false ✅
-            return Err(WilliamsRError::InvalidThresholds);
-        }
+        Self::validate_thresholds(overbought, oversold, extreme_overbought, extreme_oversold)?;
 
         let config = WilliamsRConfig {
             period,
@@ -85,6 +77,7 @@ impl WilliamsR {
             oversold,
             extreme_overbought,
             extreme_oversold,
+            ..Default::default()
         };
         Ok(Self::with_config(config))
     }
@@ -93,48 +86,56 @@ impl WilliamsR {
     pub fn with_config(config: WilliamsRConfig) -> Self {
         Self {
             state: WilliamsRState::new(config),
+            config_error: config.validate().err(),
         }
     }
 
+    /// Create a new calculator, rejecting an invalid configuration up front
+    pub fn try_with_config(config: WilliamsRConfig) -> Result<Self, WilliamsRError> {
+        config.validate()?;
+        Ok(Self::with_config(config))
+    }
+
     /// Calculate Williams %R for the given input
+    ///
+    /// Non-finite inputs are handled according to the configured `GapPolicy`. With
+    /// `BarMode::Tick`, an input with the latest bar's timestamp revises that bar.
     pub fn calculate(&mut self, input: WilliamsRInput) -> Result<WilliamsROutput, WilliamsRError> {
-        // Validate input
-        self.validate_input(&input)?;
-        self.validate_config()?;
-
-        // Update price history
-        self.update_price_history(input.high, input.low);
-
-        // Calculate Williams %R if we have enough data
-        let williams_r = if self.state.has_sufficient_data {
-            self.calculate_williams_r_value(input.close)?
-        } else {
-            -50.0 // Default middle value when insufficient data
-        };
-
-        // Determine market condition
-        let market_condition = self.determine_market_condition(williams_r);
-
-        // Calculate distances from key levels
-        let distance_from_overbought = williams_r - self.state.config.overbought;
-        let distance_from_oversold = williams_r - self.state.config.oversold;
+        let revise = self.is_revision(&input);
+        self.calculate_revising(input, revise)
+    }
 
-        // Calculate price range
-        let price_range = self.state.highest_high - self.state.lowest_low;
+    /// Replace the latest bar with a revised version and recalculate
+    ///
+    /// For live feeds that revise the current (unclosed) candle: call `calculate`
+    /// when a new bar opens and this method for each revision, so the bar is not
+    /// counted twice. The output matches feeding the revised bar in place of the
+    /// original. If there is no processed bar to replace (nothing fed yet, or the
+    /// latest bar was skipped or rejected), the input is simply calculated.
+    pub fn update_or_replace_last(
+        &mut self,
+        input: WilliamsRInput,
+    ) -> Result<WilliamsROutput, WilliamsRError> {
+        self.calculate_revising(input, true)
+    }
 
-        Ok(WilliamsROutput {
-            williams_r,
-            highest_high: self.state.highest_high,
-            lowest_low: self.state.lowest_low,
-            close: input.close,
-            price_range,
-            market_condition,
-            distance_from_overbought,
-            distance_from_oversold,
-        })
+    /// Calculate for the given input, reporting warmup bars as `None`
+    ///
+    /// `calculate` returns placeholder values while the indicator warms up; this
+    /// variant returns `Ok(None)` instead so warmup cannot be mistaken for a reading.
+    pub fn calculate_checked(
+        &mut self,
+        input: WilliamsRInput,
+    ) -> Result<Option<WilliamsROutput>, WilliamsRError> {
+        let output = self.calculate(input)?;
+        Ok(self.state.has_sufficient_data.then_some(output))
     }
 
     /// Calculate Williams %R for a batch of inputs
+    ///
+    /// The inputs are treated as one continuous series: they are fed in order to this
+    /// calculator, continuing from (and updating) its current state. Use
+    /// `calculate_independent` to evaluate each input on its own.
     pub fn calculate_batch(
         &mut self,
         inputs: &[WilliamsRInput],
@@ -142,6 +143,35 @@ impl WilliamsR {
         inputs.iter().map(|input| self.calculate(*input)).collect()
     }
 
+    /// Evaluate each input independently on a fresh calculator with this configuration
+    ///
+    /// This calculator's state is neither read nor modified. Each output is what a
+    /// newly created calculator would return for that single bar (a warmup output
+    /// unless the period is 1).
+    pub fn calculate_independent(
+        &self,
+        inputs: &[WilliamsRInput],
+    ) -> Result<Vec<WilliamsROutput>, WilliamsRError> {
+        inputs
+            .iter()
+            .map(|input| Self::with_config(self.state.config).calculate(*input))
+            .collect()
+    }
+
+    /// Calculate for a batch of inputs, keeping only the post-warmup outputs
+    ///
+    /// Unlike `calculate_batch`, warmup placeholders are dropped; `start_index`
+    /// tells which input the first returned output belongs to.
+    pub fn calculate_batch_dense(
+        &mut self,
+        inputs: &[WilliamsRInput],
+    ) -> Result<DenseBatch<WilliamsROutput>, WilliamsRError> {
+        DenseBatch::try_collect(
+            inputs.iter().map(|input| self.calculate_checked(*input)),
+            WilliamsRError::InvalidInput,
+        )
+    }
+
     /// Reset the calculator state
     pub fn reset(&mut self) {
         self.state = WilliamsRState::new(self.state.config);
@@ -154,9 +184,30 @@ impl WilliamsR {
 
     /// Restore state (for deserialization)
     pub fn set_state(&mut self, state: WilliamsRState) {
+        self.config_error = state.config.validate().err();
         self.state = state;
     }
 
+    /// Update the thresholds in place, keeping the period and price history
+    pub fn set_thresholds(
+        &mut self,
+        overbought: f64,
+        oversold: f64,
+        extreme_overbought: f64,
+        extreme_oversold: f64,
+    ) -> Result<(), WilliamsRError> {
+        Self::validate_thresholds(overbought, oversold, extreme_overbought, extreme_oversold)?;
+
+        let config = &mut self.state.config;
+        config.overbought = overbought;
+        config.oversold = oversold;
+        config.extreme_overbought = extreme_overbought;
+        config.extreme_oversold = extreme_oversold;
+        self.config_error = self.state.config.validate().err();
+
+        Ok(())
+    }
+
     /// Check if currently overbought
     pub fn is_overbought(&self, williams_r: f64) -> bool {
         williams_r >= self.state.config.overbought
@@ -183,6 +234,147 @@ impl WilliamsR {
 
     // Private helper methods
 
+    /// Calculate `input`, first replacing the latest bar when `revise` is set
+    ///
+    /// The gap policy and input validation run before the latest bar is undone,
+    /// so a skipped or rejected revision leaves that bar in place.
+    fn calculate_revising(
+        &mut self,
+        input: WilliamsRInput,
+        revise: bool,
+    ) -> Result<WilliamsROutput, WilliamsRError> {
+        // Only a bar that can still be rolled back is revised
+        let revise = revise && self.state.rollback.is_some();
+        if !revise {
+            self.state.rollback = None;
+        }
+        let timestamp = input.timestamp;
+        let input = match self.state.config.gap_policy.apply(
+            input,
+            Self::is_finite_input(&input),
+            self.state.last_valid_input,
+        ) {
+            GapAction::Process(input) => input,
+            GapAction::Skip => {
+                return self
+                    .state
+                    .last_output
+                    .map(|output| WilliamsROutput {
+                        timestamp,
+                        ..output
+                    })
+                    .ok_or(WilliamsRError::InvalidPrice)
+            }
+            GapAction::Reject => return Err(WilliamsRError::InvalidPrice),
+        };
+
+        // Reject the bar before undoing anything, so a rejected revision keeps the original
+        self.validate_input(&input)?;
+        if revise {
+            self.rollback_last();
+        }
+        self.state.rollback = None;
+
+        let mut output = self.calculate_bar(input)?;
+        output.timestamp = timestamp;
+        if let Some(decimals) = self.state.config.precision {
+            output.williams_r = round_to(output.williams_r, decimals);
+        }
+        self.state.last_valid_input = Some(input);
+        self.state.last_output = Some(output);
+
+        Ok(output)
+    }
+
+    /// Undo the latest processed bar, if it can still be replaced
+    fn rollback_last(&mut self) {
+        if let Some(rollback) = self.state.rollback.take() {
+            self.state.high_window.undo_last();
+            self.state.low_window.undo_last();
+            self.state.highest_high = rollback.highest_high;
+            self.state.lowest_low = rollback.lowest_low;
+            self.state.has_sufficient_data = rollback.has_sufficient_data;
+            self.state.last_valid_input = rollback.last_valid_input;
+            self.state.last_output = rollback.last_output;
+            if rollback.signal_pushed {
+                self.state.signal_window.pop_back();
+                if let Some(evicted) = rollback.signal_evicted {
+                    self.state.signal_window.push_front(evicted);
+                }
+            }
+        }
+    }
+
+    /// Whether a tick-mode input revises the latest processed bar
+    fn is_revision(&self, input: &WilliamsRInput) -> bool {
+        self.state.config.bar_mode == BarMode::Tick
+            && input.timestamp.is_some()
+            && self.state.rollback.is_some()
+            && self.state.last_output.and_then(|output| output.timestamp) == input.timestamp
+    }
+
+    fn calculate_bar(&mut self, input: WilliamsRInput) -> Result<WilliamsROutput, WilliamsRError> {
+        self.validate_config()?;
+
+        // Update price history, remembering what the bar overwrites
+        self.state.rollback = Some(WilliamsRRollback {
+            highest_high: self.state.highest_high,
+            lowest_low: self.state.lowest_low,
+            has_sufficient_data: self.state.has_sufficient_data,
+            last_valid_input: self.state.last_valid_input,
+            last_output: self.state.last_output,
+            signal_pushed: false,
+            signal_evicted: None,
+        });
+        self.update_price_history(input.high, input.low);
+
+        // Calculate Williams %R if we have enough data
+        let williams_r = if self.state.has_sufficient_data {
+            self.calculate_williams_r_value(input.close)?
+        } else {
+            -50.0 // Default middle value when insufficient data
+        };
+
+        // Update the signal line and check for a cross over it
+        let signal = self.update_signal(williams_r);
+        let crossover = self.detect_crossover(williams_r, signal);
+
+        // Determine market condition
+        let market_condition = self.determine_market_condition(williams_r);
+
+        // Calculate distances from key levels
+        let config = &self.state.config;
+        let mut distance_from_overbought = williams_r - config.overbought;
+        let mut distance_from_oversold = williams_r - config.oversold;
+        if config.distance_units == DistanceUnits::Percent {
+            // Express as a share of the overbought-oversold band (validated to be > 0)
+            let band = config.overbought - config.oversold;
+            distance_from_overbought = distance_from_overbought / band * 100.0;
+            distance_from_oversold = distance_from_oversold / band * 100.0;
+        }
+
+        // Calculate price range
+        let price_range = self.state.highest_high - self.state.lowest_low;
+
+        Ok(WilliamsROutput {
+            timestamp: None,
+            williams_r,
+            highest_high: self.state.highest_high,
+            lowest_low: self.state.lowest_low,
+            close: input.close,
+            price_range,
+            market_condition,
+            distance_from_overbought,
+            distance_from_oversold,
+            signal,
+            crossover,
+        })
+    }
+
+    fn is_finite_input(input: &WilliamsRInput) -> bool {
+        input.high.is_finite() && input.low.is_finite() && input.close.is_finite()
+    }
+
     fn validate_input(&self, input: &WilliamsRInput) -> Result<(), WilliamsRError> {
         // Check for valid prices
         if !input.high.is_finite() || !input.low.is_finite() || !input.close.is_finite() {
@@ -202,61 +394,35 @@ impl WilliamsR {
     }
 
     fn validate_config(&self) -> Result<(), WilliamsRError> {
-        if self.state.config.period == 0 {
-            return Err(WilliamsRError::InvalidPeriod);
+        match &self.config_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
         }
+    }
 
-        let config = &self.state.config;
-
-        // Williams %R thresholds validation:
-        // Scale: 0 (most overbought) to -100 (most oversold)
-        // We need: extreme_overbought > overbought > oversold > extreme_oversold
-        if config.overbought >= 0.0
-            || config.oversold >= config.overbought
-            || config.extreme_overbought >= 0.0
-            || config.extreme_overbought <= config.overbought
-            || config.extreme_oversold >= config.oversold
-        {
-            return Err(WilliamsRError::InvalidThresholds);
+    fn validate_thresholds(
+        overbought: f64,
+        oversold: f64,
+        extreme_overbought: f64,
+        extreme_oversold: f64,
+    ) -> Result<(), WilliamsRError> {
+        WilliamsRConfig {
+            overbought,
+            oversold,
+            extreme_overbought,
+            extreme_oversold,
+            ..Default::default()
         }
-
-        Ok(())
+        .validate()
     }
 
     fn update_price_history(&mut self, high: f64, low: f64) {
-        // Remove oldest prices if at capacity
-        if self.state.highs.len() >= self.state.config.period {
-            self.state.highs.pop_front();
-            self.state.lows.pop_front();
-        }
-
-        // Add new prices
-        self.state.highs.push_back(high);
-        self.state.lows.push_back(low);
-
-        // Update highest/lowest values
-        self.update_extremes();
+        // Update highest/lowest values over the rolling window
+        self.state.highest_high = self.state.high_window.push(high).1;
+        self.state.lowest_low = self.state.low_window.push(low).0;
 
         // Check if we have sufficient data
-        self.state.has_sufficient_data = self.state.highs.len() >= self.state.config.period;
-    }
-
-    fn update_extremes(&mut self) {
-        if self.state.highs.is_empty() {
-            return;
-        }
-
-        // Find highest high and lowest low in the current period
-        self.state.highest_high = self
-            .state
-            .highs
-            .iter()
-            .fold(f64::NEG_INFINITY, |acc, &x| acc.max(x));
-        self.state.lowest_low = self
-            .state
-            .lows
-            .iter()
-            .fold(f64::INFINITY, |acc, &x| acc.min(x));
+        self.state.has_sufficient_data = self.state.high_window.is_full();
     }
 
     fn calculate_williams_r_value(&self, close: f64) -> Result<f64, WilliamsRError> {
@@ -279,7 +445,47 @@ impl WilliamsR {
         }
 
         // Clamp to valid range (0 to -100)
-        Ok(williams_r.max(-100.0).min(0.0))
+        Ok(williams_r.clamp(-100.0, 0.0))
+    }
+
+    fn update_signal(&mut self, williams_r: f64) -> Option<f64> {
+        let period = self.state.config.signal_period?;
+        if !self.state.has_sufficient_data {
+            return None;
+        }
+
+        let window = &mut self.state.signal_window;
+        window.push_back(williams_r);
+        let evicted = if window.len() > period {
+            window.pop_front()
+        } else {
+            None
+        };
+        if let Some(rollback) = self.state.rollback.as_mut() {
+            rollback.signal_pushed = true;
+            rollback.signal_evicted = evicted;
+        }
+
+        (window.len() == period).then(|| window.iter().sum::<f64>() / period as f64)
+    }
+
+    fn detect_crossover(&self, williams_r: f64, signal: Option<f64>) -> WilliamsRCrossover {
+        // The previous raw %R sits just before this bar's in the signal window;
+        // `last_output.williams_r` may be rounded to the configured precision
+        let window = &self.state.signal_window;
+        let previous_r = window.len().checked_sub(2).map(|index| window[index]);
+        let previous = self
+            .state
+            .last_output
+            .and_then(|output| output.signal)
+            .zip(previous_r)
+            .map(|(signal, williams_r)| williams_r - signal);
+
+        match (previous, signal.map(|signal| williams_r - signal)) {
+            (Some(prev), Some(diff)) if prev <= 0.0 && diff > 0.0 => WilliamsRCrossover::Bullish,
+            (Some(prev), Some(diff)) if prev >= 0.0 && diff < 0.0 => WilliamsRCrossover::Bearish,
+            _ => WilliamsRCrossover::None,
+        }
     }
 
     fn determine_market_condition(&self, williams_r: f64) -> WilliamsRMarketCondition {
@@ -323,17 +529,70 @@ pub fn calculate_williams_r_simple(
         return Ok(Vec::new());
     }
 
+    calculate_williams_r_iter(
+        highs
+            .iter()
+            .zip(lows)
+            .zip(closes)
+            .map(|((&high, &low), &close)| (high, low, close)),
+        period,
+    )
+}
+
+/// Like [`calculate_williams_r_simple`], but accepts any iterator of `(high, low, close)` bars
+///
+/// Streaming sources can be fed directly without collecting into slices first.
+pub fn calculate_williams_r_iter(
+    bars: impl IntoIterator<Item = (f64, f64, f64)>,
+    period: usize,
+) -> Result<Vec<f64>, WilliamsRError> {
+    let mut williams_r_calculator = WilliamsR::with_period(period)?;
+
+    bars.into_iter()
+        .map(|(high, low, close)| {
+            let input = WilliamsRInput {
+                timestamp: None,
+                high,
+                low,
+                close,
+            };
+            williams_r_calculator
+                .calculate(input)
+                .map(|output| output.williams_r)
+        })
+        .collect()
+}
+
+/// Like [`calculate_williams_r_simple`], but warmup bars are `None` instead of placeholder values
+pub fn calculate_williams_r_simple_checked(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+) -> Result<Vec<Option<f64>>, WilliamsRError> {
+    let len = highs.len();
+    if len != lows.len() || len != closes.len() {
+        return Err(WilliamsRError::InvalidInput(
+            "All price arrays must have same length".to_string(),
+        ));
+    }
+
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
     let mut williams_r_calculator = WilliamsR::with_period(period)?;
     let mut results = Vec::with_capacity(len);
 
     for i in 0..len {
         let input = WilliamsRInput {
+            timestamp: None,
             high: highs[i],
             low: lows[i],
             close: closes[i],
         };
-        let output = williams_r_calculator.calculate(input)?;
-        results.push(output.williams_r);
+        let output = williams_r_calculator.calculate_checked(input)?;
+        results.push(output.map(|output| output.williams_r));
     }
 
     Ok(results)
@@ -1,3 +1,5 @@
+use crate::common::types::{BarMode, Candle, DistanceUnits, GapPolicy};
+use crate::util::rolling_extremes::main::RollingExtremes;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
@@ -14,6 +16,20 @@ pub struct WilliamsRConfig {
     pub extreme_overbought: f64,
     /// Extreme oversold threshold (default: -90.0)
     pub extreme_oversold: f64,
+    /// Units the distances from overbought/oversold are reported in (default: DistanceUnits::Raw)
+    #[serde(default)]
+    pub distance_units: DistanceUnits,
+    /// Period of the SMA signal line of %R (default: None, no signal line)
+    #[serde(default)]
+    pub signal_period: Option<usize>,
+    /// Whether inputs sharing the latest bar's timestamp revise it (default: BarMode::Close)
+    #[serde(default)]
+    pub bar_mode: BarMode,
+    /// How non-finite inputs are handled (default: GapPolicy::Error)
+    #[serde(default)]
+    pub gap_policy: GapPolicy,
+    /// Decimal places `williams_r` is rounded to (default: None, unrounded)
+    pub precision: Option<u8>,
 }
 
 impl Default for WilliamsRConfig {
@@ -24,13 +40,124 @@ impl Default for WilliamsRConfig {
             oversold: -80.0,
             extreme_overbought: -10.0,
             extreme_oversold: -90.0,
+            distance_units: DistanceUnits::Raw,
+            signal_period: None,
+            bar_mode: BarMode::Close,
+            gap_policy: GapPolicy::Error,
+            precision: None,
         }
     }
 }
 
+impl WilliamsRConfig {
+    /// Start building a configuration from the defaults
+    pub fn builder() -> WilliamsRConfigBuilder {
+        WilliamsRConfigBuilder::default()
+    }
+
+    /// Check the invariants the Williams %R calculator relies on
+    pub fn validate(&self) -> Result<(), WilliamsRError> {
+        if self.period == 0 || self.signal_period == Some(0) {
+            return Err(WilliamsRError::InvalidPeriod);
+        }
+
+        // Williams %R thresholds should be negative and in descending order (towards more negative)
+        // Scale: 0 (most overbought) to -100 (most oversold)
+        // Valid order: extreme_overbought > overbought > oversold > extreme_oversold
+        // Example: -10 > -20 > -80 > -90
+        if self.overbought >= 0.0
+            || self.oversold >= self.overbought
+            || self.extreme_overbought >= 0.0
+            || self.extreme_overbought <= self.overbought
+            || self.extreme_oversold >= self.oversold
+        {
+            return Err(WilliamsRError::InvalidThresholds);
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for [`WilliamsRConfig`] with chainable setters and a validating `build()`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WilliamsRConfigBuilder {
+    config: WilliamsRConfig,
+}
+
+impl WilliamsRConfigBuilder {
+    /// Set the lookback period for highest high and lowest low
+    pub fn period(mut self, period: usize) -> Self {
+        self.config.period = period;
+        self
+    }
+
+    /// Set the overbought threshold
+    pub fn overbought(mut self, overbought: f64) -> Self {
+        self.config.overbought = overbought;
+        self
+    }
+
+    /// Set the oversold threshold
+    pub fn oversold(mut self, oversold: f64) -> Self {
+        self.config.oversold = oversold;
+        self
+    }
+
+    /// Set the extreme overbought threshold
+    pub fn extreme_overbought(mut self, extreme_overbought: f64) -> Self {
+        self.config.extreme_overbought = extreme_overbought;
+        self
+    }
+
+    /// Set the extreme oversold threshold
+    pub fn extreme_oversold(mut self, extreme_oversold: f64) -> Self {
+        self.config.extreme_oversold = extreme_oversold;
+        self
+    }
+
+    /// Set the units `distance_from_overbought`/`distance_from_oversold` are reported in
+    pub fn distance_units(mut self, distance_units: DistanceUnits) -> Self {
+        self.config.distance_units = distance_units;
+        self
+    }
+
+    /// Enable an SMA signal line of %R over `period` bars
+    pub fn signal_period(mut self, period: usize) -> Self {
+        self.config.signal_period = Some(period);
+        self
+    }
+
+    /// Set whether inputs sharing the latest bar's timestamp revise it
+    pub fn bar_mode(mut self, bar_mode: BarMode) -> Self {
+        self.config.bar_mode = bar_mode;
+        self
+    }
+
+    /// Set how non-finite inputs are handled
+    pub fn gap_policy(mut self, gap_policy: GapPolicy) -> Self {
+        self.config.gap_policy = gap_policy;
+        self
+    }
+
+    /// Set the decimal places `williams_r` is rounded to
+    pub fn precision(mut self, decimals: u8) -> Self {
+        self.config.precision = Some(decimals);
+        self
+    }
+
+    /// Validate and return the configuration
+    pub fn build(self) -> Result<WilliamsRConfig, WilliamsRError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
 /// Input data for Williams %R calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct WilliamsRInput {
+    /// Bar timestamp (e.g. Unix milliseconds), echoed in the output
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// High price
     pub high: f64,
     /// Low price
@@ -39,6 +166,18 @@ pub struct WilliamsRInput {
     pub close: f64,
 }
 
+impl From<Candle> for WilliamsRInput {
+    /// Build an input from a candle, carrying its timestamp through
+    fn from(candle: Candle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+        }
+    }
+}
+
 /// Market condition based on Williams %R value
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum WilliamsRMarketCondition {
@@ -59,6 +198,9 @@ pub enum WilliamsRMarketCondition {
 /// Output from Williams %R calculation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct WilliamsROutput {
+    /// Timestamp of the input bar, if it carried one
+    #[serde(default)]
+    pub timestamp: Option<i64>,
     /// Williams %R value (ranges from 0 to -100)
     pub williams_r: f64,
     /// Highest high in the lookback period
@@ -71,38 +213,146 @@ pub struct WilliamsROutput {
     pub price_range: f64,
     /// Market condition based on thresholds
     pub market_condition: WilliamsRMarketCondition,
-    /// Distance from overbought level (useful for momentum analysis)
+    /// Distance from overbought level (useful for momentum analysis); in %R points,
+    /// or a percentage of the overbought-oversold band with `DistanceUnits::Percent`
     pub distance_from_overbought: f64,
-    /// Distance from oversold level (useful for momentum analysis)
+    /// Distance from oversold level (useful for momentum analysis), in the same units
     pub distance_from_oversold: f64,
+    /// SMA signal line of %R (None without `signal_period` or until it has a full window)
+    #[serde(default)]
+    pub signal: Option<f64>,
+    /// Cross of %R over its signal line on this bar
+    #[serde(default)]
+    pub crossover: WilliamsRCrossover,
+}
+
+/// Cross of Williams %R over its signal line
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum WilliamsRCrossover {
+    /// %R crossed above the signal line
+    Bullish,
+    /// %R crossed below the signal line
+    Bearish,
+    /// No cross on this bar (or no signal line)
+    #[default]
+    None,
 }
 
 /// Williams %R calculation state
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "StoredWilliamsRState")]
 pub struct WilliamsRState {
     /// Configuration
     pub config: WilliamsRConfig,
-    /// History of high prices for period lookback
-    pub highs: VecDeque<f64>,
-    /// History of low prices for period lookback
-    pub lows: VecDeque<f64>,
+    /// Rolling window of high prices for period lookback
+    pub high_window: RollingExtremes,
+    /// Rolling window of low prices for period lookback
+    pub low_window: RollingExtremes,
     /// Current highest high in the period
     pub highest_high: f64,
     /// Current lowest low in the period
     pub lowest_low: f64,
     /// Whether we have enough data for calculation
     pub has_sufficient_data: bool,
+    /// Last valid input (repeated by GapPolicy::ForwardFill)
+    pub last_valid_input: Option<WilliamsRInput>,
+    /// Last output (repeated by GapPolicy::SkipBar)
+    pub last_output: Option<WilliamsROutput>,
+    /// Recent %R values averaged into the signal line
+    #[serde(default)]
+    pub signal_window: VecDeque<f64>,
+    /// State before the latest processed bar (used by `update_or_replace_last`)
+    #[serde(default)]
+    pub rollback: Option<WilliamsRRollback>,
+}
+
+/// Serialized form of [`WilliamsRState`], also accepting states saved while the
+/// window was kept as plain `highs`/`lows` lists
+#[derive(Deserialize)]
+struct StoredWilliamsRState {
+    config: WilliamsRConfig,
+    #[serde(default)]
+    high_window: Option<RollingExtremes>,
+    #[serde(default)]
+    low_window: Option<RollingExtremes>,
+    #[serde(default)]
+    highs: VecDeque<f64>,
+    #[serde(default)]
+    lows: VecDeque<f64>,
+    highest_high: f64,
+    lowest_low: f64,
+    has_sufficient_data: bool,
+    last_valid_input: Option<WilliamsRInput>,
+    last_output: Option<WilliamsROutput>,
+    #[serde(default)]
+    signal_window: VecDeque<f64>,
+    #[serde(default)]
+    rollback: Option<WilliamsRRollback>,
+}
+
+impl From<StoredWilliamsRState> for WilliamsRState {
+    fn from(stored: StoredWilliamsRState) -> Self {
+        let period = stored.config.period;
+        let rebuild = |values: VecDeque<f64>| {
+            let mut window = RollingExtremes::new(period);
+            values.into_iter().for_each(|value| {
+                window.push(value);
+            });
+            window
+        };
+        let (high_window, low_window) = match (stored.high_window, stored.low_window) {
+            (Some(high_window), Some(low_window)) => (high_window, low_window),
+            _ => (rebuild(stored.highs), rebuild(stored.lows)),
+        };
+        Self {
+            config: stored.config,
+            high_window,
+            low_window,
+            highest_high: stored.highest_high,
+            lowest_low: stored.lowest_low,
+            has_sufficient_data: stored.has_sufficient_data,
+            last_valid_input: stored.last_valid_input,
+            last_output: stored.last_output,
+            signal_window: stored.signal_window,
+            rollback: stored.rollback,
+        }
+    }
+}
+
+/// Values the latest processed bar overwrote, restored when that bar is replaced
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WilliamsRRollback {
+    /// Highest high before the bar
+    pub highest_high: f64,
+    /// Lowest low before the bar
+    pub lowest_low: f64,
+    /// Whether the window was full before the bar
+    pub has_sufficient_data: bool,
+    /// Last valid input before the bar
+    pub last_valid_input: Option<WilliamsRInput>,
+    /// Last output before the bar
+    pub last_output: Option<WilliamsROutput>,
+    /// Whether the bar pushed a %R value into the signal window
+    #[serde(default)]
+    pub signal_pushed: bool,
+    /// Signal window value the bar evicted
+    #[serde(default)]
+    pub signal_evicted: Option<f64>,
 }
 
 impl WilliamsRState {
     pub fn new(config: WilliamsRConfig) -> Self {
         Self {
             config,
-            highs: VecDeque::with_capacity(config.period),
-            lows: VecDeque::with_capacity(config.period),
+            high_window: RollingExtremes::new(config.period),
+            low_window: RollingExtremes::new(config.period),
             highest_high: f64::NEG_INFINITY,
             lowest_low: f64::INFINITY,
             has_sufficient_data: false,
+            last_valid_input: None,
+            last_output: None,
+            signal_window: VecDeque::new(),
+            rollback: None,
         }
     }
 }
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use crate::wasm::main::{bollinger, macd, rsi};
+
+    fn prices() -> Vec<f64> {
+        (0..40)
+            .map(|i| 100.0 + (i as f64 * 0.4).sin() * 5.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_rsi_series() {
+        let prices = prices();
+        let series = rsi(&prices, 14).unwrap();
+
+        assert_eq!(series.len(), prices.len());
+        assert!(series[0].is_nan());
+        assert!(series.last().unwrap().is_finite());
+        assert!(rsi(&prices, 0).is_err());
+    }
+
+    #[test]
+    fn test_macd_series() {
+        let prices = prices();
+        let series = macd(&prices, 3, 6, 3).unwrap();
+
+        assert_eq!(series.macd_line().len(), prices.len());
+        assert_eq!(series.signal_line().len(), prices.len());
+        assert!(series.histogram().last().unwrap().is_finite());
+        assert!(macd(&prices, 0, 6, 3).is_err());
+    }
+
+    #[test]
+    fn test_bollinger_series() {
+        let prices = prices();
+        let series = bollinger(&prices, 5, 2.0).unwrap();
+
+        assert_eq!(series.middle().len(), prices.len());
+        assert!(series.upper()[0].is_nan());
+        let (upper, lower) = (series.upper(), series.lower());
+        assert!(upper.last().unwrap() > lower.last().unwrap());
+        assert!(bollinger(&prices, 5, -1.0).is_err());
+    }
+}
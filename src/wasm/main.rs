@@ -0,0 +1,149 @@
+use wasm_bindgen::prelude::*;
+
+use crate::v1::bollinger::main::BollingerBands;
+use crate::v1::macd::main::MACD;
+use crate::v1::rsi::main::RSI;
+use crate::v1::types::V1Error;
+
+/// Converts per-bar results into a JS-friendly series, with `NaN` for bars without a value.
+fn to_series<T>(results: impl Iterator<Item = Option<T>>, value: impl Fn(&T) -> f64) -> Vec<f64> {
+    results
+        .map(|result| result.as_ref().map_or(f64::NAN, &value))
+        .collect()
+}
+
+/// Bollinger Bands series returned to JS, one `Float64Array` per band.
+#[wasm_bindgen]
+pub struct BollingerSeries {
+    upper: Vec<f64>,
+    middle: Vec<f64>,
+    lower: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl BollingerSeries {
+    /// The upper band per bar.
+    #[wasm_bindgen(getter)]
+    pub fn upper(&self) -> Vec<f64> {
+        self.upper.clone()
+    }
+
+    /// The middle band (SMA) per bar.
+    #[wasm_bindgen(getter)]
+    pub fn middle(&self) -> Vec<f64> {
+        self.middle.clone()
+    }
+
+    /// The lower band per bar.
+    #[wasm_bindgen(getter)]
+    pub fn lower(&self) -> Vec<f64> {
+        self.lower.clone()
+    }
+}
+
+/// MACD series returned to JS, one `Float64Array` per line.
+#[wasm_bindgen]
+pub struct MacdSeries {
+    macd_line: Vec<f64>,
+    signal_line: Vec<f64>,
+    histogram: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl MacdSeries {
+    /// The MACD line per bar.
+    #[wasm_bindgen(getter, js_name = macdLine)]
+    pub fn macd_line(&self) -> Vec<f64> {
+        self.macd_line.clone()
+    }
+
+    /// The signal line per bar.
+    #[wasm_bindgen(getter, js_name = signalLine)]
+    pub fn signal_line(&self) -> Vec<f64> {
+        self.signal_line.clone()
+    }
+
+    /// The histogram per bar.
+    #[wasm_bindgen(getter)]
+    pub fn histogram(&self) -> Vec<f64> {
+        self.histogram.clone()
+    }
+}
+
+/// Calculates the RSI of a price series.
+///
+/// # Arguments
+///
+/// * `prices` - Closing prices, oldest first.
+/// * `period` - The RSI period.
+///
+/// # Returns
+///
+/// * A `Float64Array` with one value per price; warmup bars are `NaN`.
+/// * Throws if `period` is zero.
+#[wasm_bindgen]
+pub fn rsi(prices: &[f64], period: usize) -> Result<Vec<f64>, String> {
+    let mut rsi = RSI::try_new(period, None, None).map_err(|e| e.to_string())?;
+    Ok(to_series(
+        prices.iter().map(|&price| rsi.calculate(price)),
+        |r| r.value,
+    ))
+}
+
+/// Calculates the MACD of a price series.
+///
+/// # Arguments
+///
+/// * `prices` - Closing prices, oldest first.
+/// * `fast_period` - The period for the fast EMA.
+/// * `slow_period` - The period for the slow EMA.
+/// * `signal_period` - The period for the signal EMA.
+///
+/// # Returns
+///
+/// * A `MacdSeries` whose lines have one value per price; warmup bars are `NaN`.
+/// * Throws if any period is zero.
+#[wasm_bindgen]
+pub fn macd(
+    prices: &[f64],
+    fast_period: usize,
+    slow_period: usize,
+    signal_period: usize,
+) -> Result<MacdSeries, String> {
+    let mut macd =
+        MACD::try_new(fast_period, slow_period, signal_period).map_err(|e| e.to_string())?;
+    let results: Vec<_> = prices.iter().map(|&price| macd.calculate(price)).collect();
+    Ok(MacdSeries {
+        macd_line: to_series(results.iter().map(Option::as_ref), |r| r.macd_line),
+        signal_line: to_series(results.iter().map(Option::as_ref), |r| r.signal_line),
+        histogram: to_series(results.iter().map(Option::as_ref), |r| r.histogram),
+    })
+}
+
+/// Calculates Bollinger Bands of a price series.
+///
+/// # Arguments
+///
+/// * `prices` - Closing prices, oldest first.
+/// * `period` - The moving average period.
+/// * `multiplier` - The number of standard deviations for the bands.
+///
+/// # Returns
+///
+/// * A `BollingerSeries` whose bands have one value per price; warmup bars are `NaN`.
+/// * Throws if the period or multiplier is invalid.
+#[wasm_bindgen]
+pub fn bollinger(
+    prices: &[f64],
+    period: usize,
+    multiplier: f64,
+) -> Result<BollingerSeries, String> {
+    let mut bb =
+        BollingerBands::new(period, multiplier).map_err(|e| V1Error::from(e).to_string())?;
+    let results: Vec<_> = prices.iter().map(|&price| bb.calculate(price)).collect();
+    Ok(BollingerSeries {
+        upper: to_series(results.iter().map(Option::as_ref), |r| r.upper),
+        middle: to_series(results.iter().map(Option::as_ref), |r| r.middle),
+        lower: to_series(results.iter().map(Option::as_ref), |r| r.lower),
+    })
+}